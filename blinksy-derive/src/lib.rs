@@ -0,0 +1,284 @@
+//! # Blinksy Derive Macros
+//!
+//! Derive macros for [Blinksy](https://github.com/ahdinosaur/blinksy). Use them through the
+//! `derive` feature of `blinksy`, rather than this crate directly.
+//!
+//! - [`PatternParams`](macro@PatternParams): Reflection, defaults, and serde for pattern
+//!   parameters
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Error, Expr, ExprLit,
+    ExprUnary, Field, Fields, Ident, Lit, LitStr, Path, UnOp,
+};
+
+/// Derives `PatternParams` for a struct of pattern parameters, with a `Default` impl.
+///
+/// Each field marked with `#[param(min = .., max = .., default = ..)]` is a parameter, in
+/// field order. Parameters must be `f32`. Their default is clamped to the range, and is the
+/// closest value to 0.0 if not given. Other fields aren't parameters, and default to
+/// `Default::default()`.
+///
+/// If `blinksy` is re-exported under another name, e.g. by `blinksy-pattern-api`, give its
+/// path with `#[pattern_params(crate = "..")]` on the struct.
+///
+/// With the `serde` feature of `blinksy`, a struct without generics also implements `Serialize`
+/// and `Deserialize` for its parameters: missing parameters take their default, and out of
+/// range values are clamped, so partial updates from a network are always valid.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::pattern::{ParamError, PatternParams};
+///
+/// #[derive(Debug, PatternParams)]
+/// pub struct SparkleParams {
+///     /// Sparkles per second
+///     #[param(min = 0., max = 100., default = 10.)]
+///     pub rate: f32,
+///     /// Duration of each sparkle, in seconds
+///     #[param(min = 0.01, max = 5.)]
+///     pub duration: f32,
+///     /// Not a parameter, so not reflected
+///     pub seed: u32,
+/// }
+///
+/// let mut params = SparkleParams::default();
+/// assert_eq!(params.rate, 10.);
+/// assert_eq!(params.duration, 0.01);
+///
+/// assert_eq!(SparkleParams::PARAMS[0].name, "rate");
+/// assert_eq!(SparkleParams::param_index("duration"), Some(1));
+///
+/// params.set_param(1, 2.).unwrap();
+/// assert_eq!(params.get_param(1), Some(2.));
+/// assert_eq!(params.set_param(0, 200.), Err(ParamError::OutOfRange));
+/// ```
+#[proc_macro_derive(PatternParams, attributes(param, pattern_params))]
+pub fn derive_pattern_params(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_pattern_params(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// A parameter field, with its range.
+struct Param {
+    ident: Ident,
+    min: f32,
+    max: f32,
+    default: f32,
+}
+
+fn expand_pattern_params(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let krate = parse_crate_path(input)?;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "PatternParams can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "PatternParams can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut params = Vec::new();
+    let mut defaults = Vec::new();
+    for field in fields {
+        let ident = field.ident.clone().expect("named field");
+        match parse_param(field)? {
+            Some(param) => {
+                let default = Literal::f32_suffixed(param.default);
+                defaults.push(quote! { #ident: #default });
+                params.push(param);
+            }
+            None => defaults.push(quote! { #ident: ::core::default::Default::default() }),
+        }
+    }
+
+    let infos = params.iter().map(|param| {
+        let param_name = param.ident.to_string();
+        let min = Literal::f32_suffixed(param.min);
+        let max = Literal::f32_suffixed(param.max);
+        quote! {
+            #krate::pattern::ParamInfo {
+                name: #param_name,
+                min: #min,
+                max: #max,
+            }
+        }
+    });
+    let getters = params.iter().enumerate().map(|(index, param)| {
+        let ident = &param.ident;
+        quote! { #index => ::core::option::Option::Some(self.#ident) }
+    });
+    let setters = params.iter().enumerate().map(|(index, param)| {
+        let ident = &param.ident;
+        let min = Literal::f32_suffixed(param.min);
+        let max = Literal::f32_suffixed(param.max);
+        quote! {
+            #index => {
+                if !(#min..=#max).contains(&value) {
+                    return ::core::result::Result::Err(
+                        #krate::pattern::ParamError::OutOfRange,
+                    );
+                }
+                self.#ident = value;
+            }
+        }
+    });
+    // The serde impls are only for structs without generics.
+    let serde = input.generics.params.is_empty().then(|| {
+        let fields = params.iter().map(|param| {
+            let ident = &param.ident;
+            let min = Literal::f32_suffixed(param.min);
+            let max = Literal::f32_suffixed(param.max);
+            quote! { #ident: #min..=#max }
+        });
+        let serde_crate = format!("{}::__private::serde", quote!(#krate)).replace(' ', "");
+        quote! { #krate::__pattern_params_serde!(#serde_crate; #name { #(#fields),* }); }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #krate::pattern::PatternParams for #name #type_generics
+            #where_clause
+        {
+            const PARAMS: &'static [#krate::pattern::ParamInfo] = &[#(#infos),*];
+
+            fn get_param(&self, index: usize) -> ::core::option::Option<f32> {
+                match index {
+                    #(#getters,)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            fn set_param(
+                &mut self,
+                index: usize,
+                value: f32,
+            ) -> ::core::result::Result<(), #krate::pattern::ParamError> {
+                match index {
+                    #(#setters)*
+                    _ => {
+                        return ::core::result::Result::Err(
+                            #krate::pattern::ParamError::UnknownIndex,
+                        )
+                    }
+                }
+                ::core::result::Result::Ok(())
+            }
+        }
+
+        impl #impl_generics ::core::default::Default for #name #type_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#defaults,)*
+                }
+            }
+        }
+
+        #serde
+    })
+}
+
+/// Parses the path of `blinksy` from the `#[pattern_params(crate = "..")]` attribute, if any.
+fn parse_crate_path(input: &DeriveInput) -> syn::Result<Path> {
+    let mut krate = parse_quote!(::blinksy);
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("pattern_params"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                krate = meta.value()?.parse::<LitStr>()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("expected `crate`"))
+            }
+        })?;
+    }
+    Ok(krate)
+}
+
+/// Parses the `#[param(..)]` attribute of a field, if any.
+fn parse_param(field: &Field) -> syn::Result<Option<Param>> {
+    let Some(attr) = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("param"))
+    else {
+        return Ok(None);
+    };
+    let ident = field.ident.clone().expect("named field");
+    if !matches!(&field.ty, syn::Type::Path(path) if path.path.is_ident("f32")) {
+        return Err(Error::new(field.ty.span(), "parameters must be `f32`"));
+    }
+
+    let mut min = None;
+    let mut max = None;
+    let mut default = None;
+    attr.parse_nested_meta(|meta| {
+        let value = parse_number(&meta.value()?.parse()?)?;
+        if meta.path.is_ident("min") {
+            min = Some(value);
+        } else if meta.path.is_ident("max") {
+            max = Some(value);
+        } else if meta.path.is_ident("default") {
+            default = Some(value);
+        } else {
+            return Err(meta.error("expected `min`, `max`, or `default`"));
+        }
+        Ok(())
+    })?;
+
+    let (Some(min), Some(max)) = (min, max) else {
+        return Err(Error::new(
+            attr.span(),
+            "parameters need a `min` and a `max`",
+        ));
+    };
+    if min > max {
+        return Err(Error::new(attr.span(), "`min` is greater than `max`"));
+    }
+    let default = default.unwrap_or(0.).clamp(min, max);
+    Ok(Some(Param {
+        ident,
+        min,
+        max,
+        default,
+    }))
+}
+
+/// Parses a number literal, optionally negative.
+fn parse_number(expr: &Expr) -> syn::Result<f32> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(lit),
+            ..
+        }) => lit.base10_parse(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse::<i64>().map(|value| value as f32),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => parse_number(expr).map(|value| -value),
+        _ => Err(Error::new(expr.span(), "expected a number literal")),
+    }
+}
@@ -0,0 +1,60 @@
+use blinksy::{
+    builtin_patterns,
+    input::{ControlEvent, DeviceControls},
+    layout::Layout1d,
+    layout1d, ControlBuilder,
+};
+use blinksy_desktop::{
+    driver::{Desktop, DesktopError},
+    time::elapsed_in_ms,
+};
+use std::{thread::sleep, time::Duration};
+
+layout1d!(StripLayout, 30);
+
+builtin_patterns!(enum Patterns: Dim1d);
+
+fn main() {
+    Desktop::new_1d::<StripLayout>().start(|driver| {
+        // Press Space, D, or T to click, double-click, or triple-click the device button.
+        let buttons = driver.buttons();
+
+        let registry = Patterns::registry::<StripLayout>();
+        let mut controls = DeviceControls::new(registry.len());
+
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<StripLayout, { StripLayout::PIXEL_COUNT }>()
+            .with_pattern::<Patterns>(registry.create_index(0).unwrap())
+            .with_driver(driver)
+            .with_frame_buffer_size::<{ StripLayout::PIXEL_COUNT }>()
+            .build();
+
+        control.set_brightness(controls.brightness());
+
+        loop {
+            while let Some(event) = buttons.poll() {
+                match controls.handle(event) {
+                    Some(ControlEvent::PatternChanged(index)) => {
+                        println!("Pattern: {}", Patterns::NAMES[index]);
+                        control.set_pattern(registry.create_index(index).unwrap());
+                    }
+                    Some(ControlEvent::BrightnessChanged(brightness)) => {
+                        println!("Brightness: {}", brightness);
+                        control.set_brightness(brightness);
+                    }
+                    Some(ControlEvent::PowerChanged(is_on)) => {
+                        println!("Power: {}", if is_on { "on" } else { "off" });
+                        control.set_on(is_on);
+                    }
+                    None => {}
+                }
+            }
+
+            if let Err(DesktopError::WindowClosed) = control.tick(elapsed_in_ms()) {
+                break;
+            }
+
+            sleep(Duration::from_millis(16));
+        }
+    });
+}
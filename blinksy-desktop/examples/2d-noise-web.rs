@@ -0,0 +1,41 @@
+//! Runs in a browser, as well as on desktop.
+//!
+//! Build for the web with `just desktop-2d-noise-web`, then serve `blinksy-desktop/web`.
+
+use blinksy::{
+    layout::{Layout2d, Shape2d, Vec2},
+    layout2d,
+    patterns::noise::{noise_fns, Noise2d, NoiseParams},
+    ControlBuilder,
+};
+use blinksy_desktop::{driver::Desktop, time::elapsed_in_ms};
+
+layout2d!(
+    PanelLayout,
+    [Shape2d::Grid {
+        start: Vec2::new(-1., -1.),
+        horizontal_end: Vec2::new(1., -1.),
+        vertical_end: Vec2::new(-1., 1.),
+        horizontal_pixel_count: 16,
+        vertical_pixel_count: 16,
+        serpentine: true,
+    }]
+);
+
+fn main() {
+    Desktop::new_2d::<PanelLayout>().run(|driver| {
+        let mut control = ControlBuilder::new_2d()
+            .with_layout::<PanelLayout, { PanelLayout::PIXEL_COUNT }>()
+            .with_pattern::<Noise2d<noise_fns::Perlin>>(NoiseParams {
+                ..Default::default()
+            })
+            .with_driver(driver)
+            .with_frame_buffer_size::<{ PanelLayout::PIXEL_COUNT }>()
+            .build();
+
+        // Called once per frame, so no loop or sleep
+        move || {
+            let _ = control.tick(elapsed_in_ms());
+        }
+    });
+}
@@ -24,7 +24,7 @@ layout3d!(
             end_angle_in_radians: PI,
             pixel_count: 60,
         },
-        Shape3d::Arc {
+        Shape3d::UniformArc {
             center: Vec3::new(0., -1., -0.5),
             axis_u: Vec3::new(1., 0., 0.),
             axis_v: Vec3::new(0., 1.25, 0.), // v_radius = 1.25
@@ -32,7 +32,7 @@ layout3d!(
             end_angle_in_radians: PI,
             pixel_count: 68,
         },
-        Shape3d::Arc {
+        Shape3d::UniformArc {
             center: Vec3::new(0., -1., 0.),
             axis_u: Vec3::new(1., 0., 0.),
             axis_v: Vec3::new(0., 1.5, 0.), // v_radius = 1.5
@@ -40,7 +40,7 @@ layout3d!(
             end_angle_in_radians: PI,
             pixel_count: 76,
         },
-        Shape3d::Arc {
+        Shape3d::UniformArc {
             center: Vec3::new(0., -1., 0.5),
             axis_u: Vec3::new(1., 0., 0.),
             axis_v: Vec3::new(0., 1.75, 0.), // v_radius = 1.75
@@ -49,7 +49,7 @@ layout3d!(
             pixel_count: 84,
         },
         // Tall elliptical arch, apex reaches y = +1
-        Shape3d::Arc {
+        Shape3d::UniformArc {
             center: Vec3::new(0., -1., 1.),
             axis_u: Vec3::new(1., 0., 0.),
             axis_v: Vec3::new(0., 2., 0.), // v_radius = 2.0 (ellipse)
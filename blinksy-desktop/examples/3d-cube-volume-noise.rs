@@ -45,6 +45,7 @@ fn main() {
             .with_pattern::<Noise3d<noise_fns::Perlin>>(NoiseParams {
                 time_scalar: 0.25 / 1e3,
                 position_scalar: 0.25,
+                ..Default::default()
             })
             .with_driver(driver)
             .with_frame_buffer_size::<{ CubeVolumeLayout::PIXEL_COUNT }>()
@@ -0,0 +1,52 @@
+//! Streams the edges of an X11 screen to an ambilight, over DDP or Adalight.
+//!
+//! ```shell
+//! cargo run -p blinksy-desktop --features x11 --example ambilight -- ddp 192.168.1.50
+//! cargo run -p blinksy-desktop --features x11 --example ambilight -- adalight /dev/ttyUSB0
+//! ```
+//!
+//! For Adalight, set the baud rate of the serial port first, e.g.
+//! `stty -F /dev/ttyUSB0 115200 raw`.
+
+use std::{env, error::Error, fs::OpenOptions, time::Duration};
+
+use blinksy_desktop::ambilight::{
+    AdalightSender, Ambilight, DdpSender, ScreenEdges, X11Screen, DDP_PORT,
+};
+
+/// LEDs along each of the top and bottom edges.
+const HORIZONTAL_PIXEL_COUNT: usize = 30;
+
+/// LEDs along each of the left and right edges.
+const VERTICAL_PIXEL_COUNT: usize = 17;
+
+/// Time between frames, for 30 frames per second.
+const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1);
+    let (protocol, target) = match (args.next(), args.next()) {
+        (Some(protocol), Some(target)) => (protocol, target),
+        _ => return Err("usage: ambilight <ddp HOST | adalight SERIAL_PORT>".into()),
+    };
+
+    let screen = X11Screen::connect()?;
+    let edges = ScreenEdges::new(HORIZONTAL_PIXEL_COUNT, VERTICAL_PIXEL_COUNT);
+
+    match protocol.as_str() {
+        "ddp" => {
+            let sender = DdpSender::connect((target.as_str(), DDP_PORT))?;
+            Ambilight::new(screen, edges, sender)
+                .with_smoothing(0.5)
+                .run(FRAME_INTERVAL)?;
+        }
+        "adalight" => {
+            let port = OpenOptions::new().write(true).open(&target)?;
+            Ambilight::new(screen, edges, AdalightSender::new(port))
+                .with_smoothing(0.5)
+                .run(FRAME_INTERVAL)?;
+        }
+        _ => return Err(format!("unknown protocol: {protocol}").into()),
+    }
+    Ok(())
+}
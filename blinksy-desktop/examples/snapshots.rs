@@ -0,0 +1,62 @@
+//! Renders each built-in pattern to a PNG, for the docs.
+//!
+//! ```sh
+//! cargo run -p blinksy-desktop --example snapshots -- <output directory>
+//! ```
+
+use blinksy::{
+    layout::{Shape2d, Vec2},
+    layout1d, layout2d,
+    patterns::{
+        noise::{noise_fns, Noise1d, Noise2d, NoiseParams},
+        rainbow::{Rainbow, RainbowParams},
+    },
+};
+use blinksy_desktop::snapshot::Snapshot;
+use std::{env, fs, io, path::PathBuf};
+
+layout1d!(StripLayout, 30);
+
+layout2d!(
+    PanelLayout,
+    [Shape2d::Grid {
+        start: Vec2::new(-1., -1.),
+        horizontal_end: Vec2::new(1., -1.),
+        vertical_end: Vec2::new(-1., 1.),
+        horizontal_pixel_count: 16,
+        vertical_pixel_count: 16,
+        serpentine: true,
+    }]
+);
+
+const TIME_IN_MS: u64 = 1000;
+
+fn main() -> io::Result<()> {
+    let directory = PathBuf::from(env::args().nth(1).unwrap_or("snapshots".to_string()));
+    fs::create_dir_all(&directory)?;
+
+    let strip = Snapshot::new_1d::<StripLayout>();
+    strip
+        .render::<Rainbow>(RainbowParams::default(), TIME_IN_MS)
+        .save_png(directory.join("1d-rainbow.png"))?;
+    strip
+        .render::<Noise1d<noise_fns::Perlin>>(NoiseParams::default(), TIME_IN_MS)
+        .save_png(directory.join("1d-noise-perlin.png"))?;
+
+    let panel = Snapshot::new_2d::<PanelLayout>();
+    panel
+        .render::<Rainbow>(RainbowParams::default(), TIME_IN_MS)
+        .save_png(directory.join("2d-rainbow.png"))?;
+    panel
+        .render::<Noise2d<noise_fns::Perlin>>(NoiseParams::default(), TIME_IN_MS)
+        .save_png(directory.join("2d-noise-perlin.png"))?;
+    panel
+        .render::<Noise2d<noise_fns::Simplex>>(NoiseParams::default(), TIME_IN_MS)
+        .save_png(directory.join("2d-noise-simplex.png"))?;
+    panel
+        .render::<Noise2d<noise_fns::OpenSimplex2>>(NoiseParams::default(), TIME_IN_MS)
+        .save_png(directory.join("2d-noise-open-simplex.png"))?;
+
+    println!("Saved snapshots to {}", directory.display());
+    Ok(())
+}
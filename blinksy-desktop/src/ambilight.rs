@@ -0,0 +1,548 @@
+//! # Ambilight Sender
+//!
+//! An ambilight lights the wall behind a screen with the colors at the edges of the screen.
+//! [`Ambilight`](crate::ambilight::Ambilight) is the sender half, on the computer: it captures
+//! the screen, samples the color of each LED along its edges, and streams them to a device,
+//! over the network with [DDP], or over a serial port with the [Adalight] protocol.
+//!
+//! 1. Stick an LED strip around the back of your screen, clockwise from the bottom left corner:
+//!    up the left edge, right along the top, down the right edge, and left along the bottom.
+//!    Describe it with [`ScreenEdges`](crate::ambilight::ScreenEdges).
+//! 2. Capture the screen with a [`FrameSource`]. Any closure returning an [`Image`] is a
+//!    frame source. On Linux with X11, use `X11Screen`, with the `x11` feature.
+//! 3. Send to your device with [`DdpSender`](crate::ambilight::DdpSender) or
+//!    [`AdalightSender`](crate::ambilight::AdalightSender).
+//!
+//! [DDP]: http://www.3waylabs.com/ddp/
+//! [Adalight]: https://github.com/adafruit/Adalight
+//! [`FrameSource`]: crate::mapping::FrameSource
+//! [`Image`]: crate::snapshot::Image
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use std::{convert::Infallible, io};
+//!
+//! use blinksy::color::LinearSrgb;
+//! use blinksy_desktop::{
+//!     ambilight::{Ambilight, FrameSender, ScreenEdges},
+//!     snapshot::Image,
+//! };
+//!
+//! // A red screen, as a stand-in for a screen capture
+//! let screen = || Ok::<_, Infallible>(Image::from_rgba(16, 9, [255, 0, 0, 255].repeat(16 * 9)));
+//!
+//! // Collect the frames, as a stand-in for a device
+//! struct Frames(Vec<Vec<LinearSrgb>>);
+//!
+//! impl FrameSender for Frames {
+//!     fn send(&mut self, pixels: &[LinearSrgb]) -> io::Result<()> {
+//!         self.0.push(pixels.to_vec());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let edges = ScreenEdges::new(8, 4);
+//! let mut ambilight = Ambilight::new(screen, edges, Frames(Vec::new()));
+//! ambilight.step().unwrap();
+//!
+//! let frames = ambilight.into_sender().0;
+//! assert_eq!(frames[0].len(), 24);
+//! assert!(frames[0].iter().all(|pixel| pixel.red > 0.99 && pixel.green < 0.01));
+//! ```
+//!
+//! Or, to stream your screen to a device at `192.168.1.50`:
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//!
+//! use blinksy_desktop::ambilight::{Ambilight, DdpSender, ScreenEdges, X11Screen, DDP_PORT};
+//!
+//! let sender = DdpSender::connect(("192.168.1.50", DDP_PORT))?;
+//! let mut ambilight = Ambilight::new(X11Screen::connect()?, ScreenEdges::new(30, 20), sender);
+//! ambilight.run(Duration::from_millis(33))?;
+//! ```
+
+use core::fmt;
+use std::{
+    io::{self, Write},
+    net::{ToSocketAddrs, UdpSocket},
+    sync::OnceLock,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use blinksy::color::{LinearSrgb, Srgb};
+
+use crate::{mapping::FrameSource, snapshot::Image};
+
+/// The default depth of the sampled edges, as a fraction of the screen size.
+pub const DEFAULT_EDGE_DEPTH: f32 = 0.1;
+
+/// The UDP port of DDP devices.
+pub const DDP_PORT: u16 = 4048;
+
+/// The most pixel data in one DDP packet, in bytes (480 RGB pixels).
+const DDP_MAX_DATA_LEN: usize = 1440;
+
+/// The most samples per side of the area of each LED, to bound the cost of sampling.
+const MAX_SAMPLES_PER_SIDE: u32 = 16;
+
+/// The LEDs around a screen, clockwise from the bottom left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenEdges {
+    /// LEDs up the left edge
+    pub left: usize,
+    /// LEDs right along the top edge
+    pub top: usize,
+    /// LEDs down the right edge
+    pub right: usize,
+    /// LEDs left along the bottom edge
+    pub bottom: usize,
+    /// Depth of the sampled area of the screen, from each edge inwards, as a fraction of the
+    /// screen width (left and right) or height (top and bottom)
+    pub depth: f32,
+}
+
+impl ScreenEdges {
+    /// Creates LEDs around all four edges, with the default depth.
+    ///
+    /// # Arguments
+    ///
+    /// - `horizontal` - LEDs along each of the top and bottom edges
+    /// - `vertical` - LEDs along each of the left and right edges
+    pub fn new(horizontal: usize, vertical: usize) -> Self {
+        Self {
+            left: vertical,
+            top: horizontal,
+            right: vertical,
+            bottom: horizontal,
+            depth: DEFAULT_EDGE_DEPTH,
+        }
+    }
+
+    /// Returns the number of LEDs around the screen.
+    pub fn pixel_count(&self) -> usize {
+        self.left + self.top + self.right + self.bottom
+    }
+
+    /// Samples the color of each LED from a frame of the screen, in LED order.
+    ///
+    /// Each color is the average of the area of the screen nearest to the LED, in linear
+    /// light.
+    ///
+    /// # Arguments
+    ///
+    /// - `image` - A frame of the screen
+    pub fn sample(&self, image: &Image) -> Vec<LinearSrgb> {
+        let (width, height) = (image.width() as f32, image.height() as f32);
+        let depth = self.depth.clamp(0., 1.);
+        let (depth_x, depth_y) = (width * depth, height * depth);
+
+        let mut pixels = Vec::with_capacity(self.pixel_count());
+        for index in 0..self.left {
+            let (y0, y1) = step(height, self.left, index);
+            pixels.push(average(image, 0., height - y1, depth_x, height - y0));
+        }
+        for index in 0..self.top {
+            let (x0, x1) = step(width, self.top, index);
+            pixels.push(average(image, x0, 0., x1, depth_y));
+        }
+        for index in 0..self.right {
+            let (y0, y1) = step(height, self.right, index);
+            pixels.push(average(image, width - depth_x, y0, width, y1));
+        }
+        for index in 0..self.bottom {
+            let (x0, x1) = step(width, self.bottom, index);
+            pixels.push(average(
+                image,
+                width - x1,
+                height - depth_y,
+                width - x0,
+                height,
+            ));
+        }
+        pixels
+    }
+}
+
+/// Returns the start and end of a step along an edge.
+fn step(length: f32, count: usize, index: usize) -> (f32, f32) {
+    let step = length / count as f32;
+    (index as f32 * step, (index + 1) as f32 * step)
+}
+
+/// Returns the average color of an area of the image, in linear light.
+fn average(image: &Image, x0: f32, y0: f32, x1: f32, y1: f32) -> LinearSrgb {
+    let (width, height) = (image.width(), image.height());
+    let (x0, x1) = pixel_range(x0, x1, width);
+    let (y0, y1) = pixel_range(y0, y1, height);
+    if x0 >= x1 || y0 >= y1 {
+        return LinearSrgb::new(0., 0., 0.);
+    }
+    let x_step = ((x1 - x0) / MAX_SAMPLES_PER_SIDE).max(1);
+    let y_step = ((y1 - y0) / MAX_SAMPLES_PER_SIDE).max(1);
+
+    let decode = srgb_decode_table();
+    let mut sum = [0.; 3];
+    let mut count = 0;
+    for y in (y0..y1).step_by(y_step as usize) {
+        for x in (x0..x1).step_by(x_step as usize) {
+            let pixel = image.pixel(x, y);
+            for (sum, channel) in sum.iter_mut().zip(pixel) {
+                *sum += decode[channel as usize];
+            }
+            count += 1;
+        }
+    }
+    let [red, green, blue] = sum.map(|sum| sum / count as f32);
+    LinearSrgb::new(red, green, blue)
+}
+
+/// Returns the pixels covered by a range, with at least one pixel.
+fn pixel_range(start: f32, end: f32, size: u32) -> (u32, u32) {
+    if size == 0 {
+        return (0, 0);
+    }
+    let start = (start.max(0.) as u32).min(size.saturating_sub(1));
+    let end = (end.ceil() as u32).clamp(start + 1, size);
+    (start, end)
+}
+
+/// Returns the linear value of each 8-bit sRGB value.
+fn srgb_decode_table() -> &'static [f32; 256] {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        core::array::from_fn(|value| {
+            let value = value as u8;
+            Srgb::from_u8(value, value, value).to_linear_srgb().red
+        })
+    })
+}
+
+/// Returns the 8-bit sRGB values of a pixel.
+fn srgb_bytes(pixel: LinearSrgb) -> [u8; 3] {
+    Srgb::from_linear_srgb(pixel).to_u8()
+}
+
+/// A sender of frames to a device.
+pub trait FrameSender {
+    /// Sends a frame of pixels, in LED order.
+    fn send(&mut self, pixels: &[LinearSrgb]) -> io::Result<()>;
+}
+
+/// Sends frames over UDP with the [DDP](http://www.3waylabs.com/ddp/) protocol, as supported
+/// by e.g. WLED.
+#[derive(Debug)]
+pub struct DdpSender {
+    socket: UdpSocket,
+    sequence: u8,
+}
+
+impl DdpSender {
+    /// Connects to a DDP device.
+    ///
+    /// # Arguments
+    ///
+    /// - `address` - The address of the device, usually on [`DDP_PORT`]
+    pub fn connect<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+        socket.connect(address)?;
+        Ok(Self {
+            socket,
+            sequence: 0,
+        })
+    }
+}
+
+impl FrameSender for DdpSender {
+    fn send(&mut self, pixels: &[LinearSrgb]) -> io::Result<()> {
+        // Sequence numbers are 1 to 15, as 0 means unused.
+        self.sequence = self.sequence % 15 + 1;
+        for packet in ddp_packets(pixels, self.sequence) {
+            self.socket.send(&packet)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the DDP packets of a frame, pushing the frame on the last packet.
+fn ddp_packets(pixels: &[LinearSrgb], sequence: u8) -> Vec<Vec<u8>> {
+    const VERSION_1: u8 = 0x40;
+    const PUSH: u8 = 0x01;
+    const RGB_8: u8 = 0x0b;
+    const DISPLAY_ID: u8 = 1;
+
+    let data: Vec<u8> = pixels.iter().copied().flat_map(srgb_bytes).collect();
+    let chunk_count = data.len().div_ceil(DDP_MAX_DATA_LEN).max(1);
+    (0..chunk_count)
+        .map(|index| {
+            let offset = index * DDP_MAX_DATA_LEN;
+            let chunk = &data[offset..(offset + DDP_MAX_DATA_LEN).min(data.len())];
+            let flags = if index == chunk_count - 1 {
+                VERSION_1 | PUSH
+            } else {
+                VERSION_1
+            };
+            let mut packet = vec![flags, sequence, RGB_8, DISPLAY_ID];
+            packet.extend_from_slice(&(offset as u32).to_be_bytes());
+            packet.extend_from_slice(&(chunk.len() as u16).to_be_bytes());
+            packet.extend_from_slice(chunk);
+            packet
+        })
+        .collect()
+}
+
+/// Sends frames with the [Adalight](https://github.com/adafruit/Adalight) protocol, usually
+/// over a serial port.
+///
+/// The writer is usually a serial port, e.g. from the `serialport` crate, or on Linux the
+/// device file (e.g. `/dev/ttyUSB0`), after setting its baud rate with `stty`.
+#[derive(Debug)]
+pub struct AdalightSender<Writer> {
+    writer: Writer,
+}
+
+impl<Writer> AdalightSender<Writer>
+where
+    Writer: Write,
+{
+    /// Wraps a writer to the device.
+    ///
+    /// # Arguments
+    ///
+    /// - `writer` - The serial port to the device
+    pub fn new(writer: Writer) -> Self {
+        Self { writer }
+    }
+
+    /// Unwraps the writer.
+    pub fn into_inner(self) -> Writer {
+        self.writer
+    }
+}
+
+impl<Writer> FrameSender for AdalightSender<Writer>
+where
+    Writer: Write,
+{
+    fn send(&mut self, pixels: &[LinearSrgb]) -> io::Result<()> {
+        self.writer.write_all(&adalight_frame(pixels))?;
+        self.writer.flush()
+    }
+}
+
+/// Returns the Adalight frame of pixels: a header, then the RGB data.
+fn adalight_frame(pixels: &[LinearSrgb]) -> Vec<u8> {
+    let [high, low] = (pixels.len().saturating_sub(1) as u16).to_be_bytes();
+    let mut frame = vec![b'A', b'd', b'a', high, low, high ^ low ^ 0x55];
+    frame.extend(pixels.iter().copied().flat_map(srgb_bytes));
+    frame
+}
+
+/// All types of errors that can happen while streaming.
+#[derive(Debug)]
+pub enum AmbilightError<CaptureError> {
+    /// Failed to capture the screen
+    Capture(CaptureError),
+    /// Failed to send a frame
+    Send(io::Error),
+}
+
+impl<CaptureError> fmt::Display for AmbilightError<CaptureError>
+where
+    CaptureError: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmbilightError::Capture(error) => write!(f, "failed to capture screen: {error}"),
+            AmbilightError::Send(error) => write!(f, "failed to send frame: {error}"),
+        }
+    }
+}
+
+impl<CaptureError> core::error::Error for AmbilightError<CaptureError> where
+    CaptureError: fmt::Debug + fmt::Display
+{
+}
+
+/// Streams the edges of a screen to a device.
+///
+/// # Type Parameters
+///
+/// - `Source` - The screen capture
+/// - `Sender` - The sender to the device
+#[derive(Debug)]
+pub struct Ambilight<Source, Sender> {
+    source: Source,
+    edges: ScreenEdges,
+    sender: Sender,
+    smoothing: f32,
+    pixels: Vec<LinearSrgb>,
+}
+
+impl<Source, Sender> Ambilight<Source, Sender>
+where
+    Source: FrameSource,
+    Sender: FrameSender,
+{
+    /// Creates an ambilight, without smoothing.
+    ///
+    /// # Arguments
+    ///
+    /// - `source` - The screen capture
+    /// - `edges` - The LEDs around the screen
+    /// - `sender` - The sender to the device
+    pub fn new(source: Source, edges: ScreenEdges, sender: Sender) -> Self {
+        Self {
+            source,
+            edges,
+            sender,
+            smoothing: 0.,
+            pixels: Vec::new(),
+        }
+    }
+
+    /// Smooths the colors over frames, so fast cuts don't flash the wall.
+    ///
+    /// # Arguments
+    ///
+    /// - `smoothing` - How much of the previous frame is kept, from 0.0 (none) to 1.0
+    pub fn with_smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = smoothing.clamp(0., 0.99);
+        self
+    }
+
+    /// Captures the screen, and sends the colors of the edges.
+    pub fn step(&mut self) -> Result<(), AmbilightError<Source::Error>> {
+        let image = self.source.capture().map_err(AmbilightError::Capture)?;
+        let pixels = self.edges.sample(&image);
+        if self.pixels.len() == pixels.len() {
+            let keep = self.smoothing;
+            for (previous, pixel) in self.pixels.iter_mut().zip(pixels) {
+                previous.red = previous.red * keep + pixel.red * (1. - keep);
+                previous.green = previous.green * keep + pixel.green * (1. - keep);
+                previous.blue = previous.blue * keep + pixel.blue * (1. - keep);
+            }
+        } else {
+            self.pixels = pixels;
+        }
+        self.sender.send(&self.pixels).map_err(AmbilightError::Send)
+    }
+
+    /// Streams the screen forever, at most once each interval, until an error.
+    ///
+    /// # Arguments
+    ///
+    /// - `interval` - The time between frames, e.g. 33 milliseconds for 30 frames per second
+    pub fn run(&mut self, interval: Duration) -> Result<(), AmbilightError<Source::Error>> {
+        loop {
+            let start = Instant::now();
+            self.step()?;
+            if let Some(remaining) = interval.checked_sub(start.elapsed()) {
+                sleep(remaining);
+            }
+        }
+    }
+
+    /// Unwraps the sender.
+    pub fn into_sender(self) -> Sender {
+        self.sender
+    }
+}
+
+#[cfg(feature = "x11")]
+pub use self::x11::X11Screen;
+
+#[cfg(feature = "x11")]
+mod x11 {
+    use std::io;
+
+    use x11rb::{
+        connection::Connection,
+        protocol::xproto::{ConnectionExt, ImageFormat, Window},
+        rust_connection::RustConnection,
+    };
+
+    use crate::{mapping::FrameSource, snapshot::Image};
+
+    /// Captures the screen of an X11 display.
+    pub struct X11Screen {
+        connection: RustConnection,
+        root: Window,
+        width: u16,
+        height: u16,
+        bits_per_pixel: u8,
+    }
+
+    impl X11Screen {
+        /// Connects to the X11 display from the `DISPLAY` environment variable.
+        pub fn connect() -> io::Result<Self> {
+            let (connection, screen_index) = x11rb::connect(None).map_err(io::Error::other)?;
+            let setup = connection.setup();
+            let screen = &setup.roots[screen_index];
+            let bits_per_pixel = setup
+                .pixmap_formats
+                .iter()
+                .find(|format| format.depth == screen.root_depth)
+                .map_or(0, |format| format.bits_per_pixel);
+            if bits_per_pixel != 32 {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("unsupported screen format: {bits_per_pixel} bits per pixel"),
+                ));
+            }
+            let (root, width, height) =
+                (screen.root, screen.width_in_pixels, screen.height_in_pixels);
+            Ok(Self {
+                connection,
+                root,
+                width,
+                height,
+                bits_per_pixel,
+            })
+        }
+    }
+
+    impl core::fmt::Debug for X11Screen {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("X11Screen")
+                .field("width", &self.width)
+                .field("height", &self.height)
+                .field("bits_per_pixel", &self.bits_per_pixel)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl FrameSource for X11Screen {
+        type Error = io::Error;
+
+        fn capture(&mut self) -> Result<Image, Self::Error> {
+            let reply = self
+                .connection
+                .get_image(
+                    ImageFormat::Z_PIXMAP,
+                    self.root,
+                    0,
+                    0,
+                    self.width,
+                    self.height,
+                    !0,
+                )
+                .map_err(io::Error::other)?
+                .reply()
+                .map_err(io::Error::other)?;
+            // 32-bit pixels are BGRX, in the byte order of a little-endian server.
+            let data = reply
+                .data
+                .chunks_exact(4)
+                .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], 255])
+                .collect();
+            Ok(Image::from_rgba(
+                self.width as u32,
+                self.height as u32,
+                data,
+            ))
+        }
+    }
+}
@@ -16,6 +16,16 @@
 //! - Mouse wheel: Zoom in/out
 //! - R key: Reset camera to default position
 //! - O key: Toggle between orthographic and perspective projection
+//! - 1, 2, 3 keys: Move camera to the front, top, or isometric view
+//! - A key: Toggle turntable animation, slowly orbiting the camera around the LEDs
+//! - G key: Toggle display gamma (see
+//!   [`DesktopConfig::display_gamma`](crate::driver::DesktopConfig::display_gamma))
+//! - L key: Toggle LED index labels and wiring order overlay
+//! - P key: Toggle the estimated power draw (see
+//!   [`DesktopConfig::power_model`](crate::driver::DesktopConfig::power_model))
+//! - Space, D, T keys: Click, double-click, or triple-click the device button (see
+//!   [`DesktopConfig::key_bindings`](crate::driver::DesktopConfig::key_bindings) and
+//!   [`DesktopButtons`](crate::driver::DesktopButtons))
 //!
 //! ## Usage
 //!
@@ -60,22 +70,31 @@
 //!
 //! [`Driver`]: blinksy::driver::Driver
 
+#[cfg(feature = "async")]
+use blinksy::driver::DriverAsync;
 use blinksy::{
-    color::{ColorCorrection, FromColor, LinearSrgb, Srgb},
+    color::{apply_output_pipeline, ColorCorrection, FromColor, LinearSrgb, Srgb},
     driver::Driver,
+    input::ButtonEvent,
     layout::{Layout1d, Layout2d, Layout3d, LayoutForDim},
     markers::{Dim1d, Dim2d, Dim3d},
+    power::{PowerEstimate, PowerModel},
 };
 use core::{fmt, marker::PhantomData};
 use egui_miniquad as egui_mq;
 use glam::{vec3, Mat4, Vec3, Vec4, Vec4Swizzles};
 use miniquad::*;
-use std::sync::mpsc::{channel, Receiver, SendError, Sender};
+use std::sync::{
+    mpsc::{channel, Receiver, SendError, Sender},
+    Arc, Mutex,
+};
 
 /// Configuration options for the desktop simulator.
 ///
 /// Allows customizing the appearance and behavior of the LED simulator window.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct DesktopConfig {
     /// Window title
     pub window_title: String,
@@ -97,6 +116,30 @@ pub struct DesktopConfig {
 
     /// Background color (R, G, B, A) where each component is 0.0 - 1.0
     pub background_color: (f32, f32, f32, f32),
+
+    /// How LED colors are encoded for your monitor
+    pub display_gamma: DisplayGamma,
+
+    /// Initially orbit the camera around the LEDs
+    pub turntable: bool,
+
+    /// Speed of the turntable animation, in radians per second
+    pub turntable_speed: f32,
+
+    /// Initially show each LED's index, and a line through the LEDs in wiring order
+    pub show_wiring: bool,
+
+    /// Initially show the estimated power draw of each frame
+    pub show_power: bool,
+
+    /// Model of the current drawn by the LEDs, to estimate power draw
+    ///
+    /// To model your chipset, use its current draw, e.g.
+    /// `PowerModel::new(Ws2815::CURRENT_DRAW, 12.)`.
+    pub power_model: PowerModel,
+
+    /// Keyboard shortcuts which emulate the device button
+    pub key_bindings: Vec<KeyBinding>,
 }
 
 impl Default for DesktopConfig {
@@ -109,10 +152,96 @@ impl Default for DesktopConfig {
             high_dpi: true,
             orthographic_view: true,
             background_color: (0.1, 0.1, 0.1, 1.0),
+            display_gamma: DisplayGamma::default(),
+            turntable: false,
+            turntable_speed: 0.3,
+            show_wiring: false,
+            show_power: true,
+            power_model: PowerModel::default(),
+            key_bindings: KeyBinding::defaults(),
         }
     }
 }
 
+/// How LED colors are encoded for display on a monitor.
+///
+/// LEDs are linear emitters: twice the PWM duty is twice the light. Monitors are not: they
+/// expect gamma-encoded values. To look like the real LEDs, the simulator encodes each LED's
+/// linear output with the monitor's gamma. If your LEDs look different to the simulator,
+/// calibrate this to your monitor.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplayGamma {
+    /// The sRGB transfer function, as expected by most monitors
+    #[default]
+    Srgb,
+
+    /// A pure power-law gamma, e.g. 2.2 or 2.4
+    Power(f32),
+
+    /// No encoding: show the linear output levels as is (too dark on most monitors)
+    Linear,
+}
+
+impl DisplayGamma {
+    /// Encode a linear color for display
+    pub(crate) fn encode(self, color: LinearSrgb) -> (f32, f32, f32) {
+        let LinearSrgb { red, green, blue } = color;
+        match self {
+            DisplayGamma::Srgb => {
+                let Srgb { red, green, blue } = color.to_srgb();
+                (red, green, blue)
+            }
+            DisplayGamma::Power(gamma) => {
+                let encode = |value: f32| value.clamp(0.0, 1.0).powf(1.0 / gamma);
+                (encode(red), encode(green), encode(blue))
+            }
+            DisplayGamma::Linear => (
+                red.clamp(0.0, 1.0),
+                green.clamp(0.0, 1.0),
+                blue.clamp(0.0, 1.0),
+            ),
+        }
+    }
+}
+
+/// A keyboard shortcut which emulates a gesture on the device button.
+///
+/// Keys are matched on the character typed, ignoring case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyBinding {
+    /// The key character
+    pub key: char,
+
+    /// The button event to emulate
+    pub event: ButtonEvent,
+}
+
+impl KeyBinding {
+    /// The default key bindings:
+    ///
+    /// - Space: Click
+    /// - D: Double click
+    /// - T: Triple click
+    pub fn defaults() -> Vec<KeyBinding> {
+        vec![
+            KeyBinding {
+                key: ' ',
+                event: ButtonEvent::Click,
+            },
+            KeyBinding {
+                key: 'd',
+                event: ButtonEvent::DoubleClick,
+            },
+            KeyBinding {
+                key: 't',
+                event: ButtonEvent::TripleClick,
+            },
+        ]
+    }
+}
+
 /// Desktop simulator for LED layouts in a desktop window.
 ///
 /// Provides a visual representation of your LED layout using miniquad,
@@ -169,6 +298,7 @@ impl Desktop<Dim1d, ()> {
         }
 
         let (sender, receiver) = channel();
+        let (button_sender, button_receiver) = channel();
         let is_window_closed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
         let is_window_closed_2 = is_window_closed.clone();
 
@@ -177,12 +307,18 @@ impl Desktop<Dim1d, ()> {
             layout: PhantomData,
             brightness: 1.0,
             correction: ColorCorrection::default(),
+            #[cfg(feature = "async")]
+            encoded: (1.0, ColorCorrection::default()),
             sender,
+            buttons: DesktopButtons {
+                receiver: Arc::new(Mutex::new(button_receiver)),
+            },
             is_window_closed,
         };
         let stage = DesktopStageOptions {
             positions,
             receiver,
+            button_sender,
             config,
             is_window_closed: is_window_closed_2,
         };
@@ -234,6 +370,7 @@ impl Desktop<Dim2d, ()> {
         }
 
         let (sender, receiver) = channel();
+        let (button_sender, button_receiver) = channel();
         let is_window_closed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
         let is_window_closed_2 = is_window_closed.clone();
 
@@ -242,12 +379,18 @@ impl Desktop<Dim2d, ()> {
             layout: PhantomData,
             brightness: 1.0,
             correction: ColorCorrection::default(),
+            #[cfg(feature = "async")]
+            encoded: (1.0, ColorCorrection::default()),
             sender,
+            buttons: DesktopButtons {
+                receiver: Arc::new(Mutex::new(button_receiver)),
+            },
             is_window_closed,
         };
         let stage = DesktopStageOptions {
             positions,
             receiver,
+            button_sender,
             config,
             is_window_closed: is_window_closed_2,
         };
@@ -299,6 +442,7 @@ impl Desktop<Dim3d, ()> {
         }
 
         let (sender, receiver) = channel();
+        let (button_sender, button_receiver) = channel();
         let is_window_closed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
         let is_window_closed_2 = is_window_closed.clone();
 
@@ -307,12 +451,18 @@ impl Desktop<Dim3d, ()> {
             layout: PhantomData,
             brightness: 1.0,
             correction: ColorCorrection::default(),
+            #[cfg(feature = "async")]
+            encoded: (1.0, ColorCorrection::default()),
             sender,
+            buttons: DesktopButtons {
+                receiver: Arc::new(Mutex::new(button_receiver)),
+            },
             is_window_closed,
         };
         let stage = DesktopStageOptions {
             positions,
             receiver,
+            button_sender,
             config,
             is_window_closed: is_window_closed_2,
         };
@@ -321,11 +471,21 @@ impl Desktop<Dim3d, ()> {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl<Dim, Layout> Desktop<Dim, Layout>
 where
     Dim: 'static + Send,
     Layout: 'static + Send,
 {
+    /// Starts the simulator, running your code on its own thread.
+    ///
+    /// Your code is free to loop forever, as on a device.
+    ///
+    /// Not available on the web, as browsers have no threads: use [`Desktop::run`] instead.
+    ///
+    /// # Parameters
+    ///
+    /// - `f` - Your code, given the driver
     pub fn start<F>(self, f: F)
     where
         F: 'static + FnOnce(DesktopDriver<Dim, Layout>) + Send,
@@ -338,6 +498,60 @@ where
     }
 }
 
+impl<Dim, Layout> Desktop<Dim, Layout>
+where
+    Dim: 'static,
+    Layout: 'static,
+{
+    /// Starts the simulator, calling your code once per frame on the render thread.
+    ///
+    /// Unlike [`Desktop::start`], this also works on the web (`wasm32-unknown-unknown`),
+    /// where the simulator runs in a browser canvas.
+    ///
+    /// ```rust,no_run
+    /// use blinksy::{
+    ///     layout::Layout1d,
+    ///     layout1d,
+    ///     patterns::rainbow::{Rainbow, RainbowParams},
+    ///     ControlBuilder,
+    /// };
+    /// use blinksy_desktop::{driver::Desktop, time::elapsed_in_ms};
+    ///
+    /// layout1d!(Layout, 30);
+    ///
+    /// Desktop::new_1d::<Layout>().run(|driver| {
+    ///     let mut control = ControlBuilder::new_1d()
+    ///         .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+    ///         .with_pattern::<Rainbow>(RainbowParams::default())
+    ///         .with_driver(driver)
+    ///         .with_frame_buffer_size::<{ Layout::PIXEL_COUNT }>()
+    ///         .build();
+    ///
+    ///     // Called once per frame
+    ///     move || {
+    ///         let _ = control.tick(elapsed_in_ms());
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// - `setup` - Your setup code, given the driver, returning your per-frame code
+    pub fn run<F, U>(self, setup: F)
+    where
+        F: 'static + FnOnce(DesktopDriver<Dim, Layout>) -> U,
+        U: 'static + FnMut(),
+    {
+        let Self { driver, stage } = self;
+
+        DesktopStage::start(move || {
+            let mut stage = DesktopStage::new(stage);
+            stage.on_update = Box::new(setup(driver));
+            stage
+        });
+    }
+}
+
 /// Desktop driver for simulating LED layouts in a desktop window.
 ///
 /// This struct implements the `Driver` trait.
@@ -351,11 +565,44 @@ pub struct DesktopDriver<Dim, Layout> {
     layout: PhantomData<Layout>,
     brightness: f32,
     correction: ColorCorrection,
+    /// The brightness and color correction of the last encoded frame, for async writes
+    #[cfg(feature = "async")]
+    encoded: (f32, ColorCorrection),
     sender: Sender<LedMessage>,
+    buttons: DesktopButtons,
     is_window_closed: std::sync::Arc<std::sync::atomic::AtomicBool>,
 }
 
 impl<Dim, Layout> DesktopDriver<Dim, Layout> {
+    /// Returns a handle to the emulated device button.
+    ///
+    /// Get this before giving the driver to your control system.
+    pub fn buttons(&self) -> DesktopButtons {
+        self.buttons.clone()
+    }
+
+    /// Sends a frame, and any changes to brightness or color correction, to the rendering thread.
+    fn send_frame<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: heapless::Vec<LinearSrgb, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), DesktopError> {
+        if self.brightness != brightness {
+            self.brightness = brightness;
+            self.send(LedMessage::UpdateBrightness(brightness))?;
+        }
+
+        if self.correction != correction {
+            self.correction = correction;
+            self.send(LedMessage::UpdateColorCorrection(correction))?;
+        }
+
+        let colors: Vec<LinearSrgb> = frame.into_iter().collect();
+
+        self.send(LedMessage::UpdateColors(colors))
+    }
+
     fn send(&self, message: LedMessage) -> Result<(), DesktopError> {
         if self
             .is_window_closed
@@ -368,6 +615,44 @@ impl<Dim, Layout> DesktopDriver<Dim, Layout> {
     }
 }
 
+/// The emulated device button, pressed with keyboard shortcuts in the simulator window.
+///
+/// Feed its events into the same [`DeviceControls`] as on your device, to develop and test
+/// the on-device UX on desktop.
+///
+/// ```rust,no_run
+/// use blinksy::input::DeviceControls;
+/// use blinksy_desktop::driver::Desktop;
+/// # blinksy::layout1d!(Layout, 30);
+///
+/// Desktop::new_1d::<Layout>().start(|driver| {
+///     let buttons = driver.buttons();
+///     let mut controls = DeviceControls::new(3);
+///
+///     loop {
+///         while let Some(event) = buttons.poll() {
+///             if let Some(change) = controls.handle(event) {
+///                 println!("{:?}", change);
+///             }
+///         }
+///         # break;
+///     }
+/// });
+/// ```
+///
+/// [`DeviceControls`]: blinksy::input::DeviceControls
+#[derive(Debug, Clone)]
+pub struct DesktopButtons {
+    receiver: Arc<Mutex<Receiver<ButtonEvent>>>,
+}
+
+impl DesktopButtons {
+    /// Returns the next button event, if any, without blocking.
+    pub fn poll(&self) -> Option<ButtonEvent> {
+        self.receiver.lock().ok()?.try_recv().ok()
+    }
+}
+
 /// Errors that can occur when using the Desktop driver.
 #[derive(Debug)]
 pub enum DesktopError {
@@ -440,20 +725,45 @@ where
         brightness: f32,
         correction: ColorCorrection,
     ) -> Result<(), Self::Error> {
-        if self.brightness != brightness {
-            self.brightness = brightness;
-            self.send(LedMessage::UpdateBrightness(brightness))?;
-        }
+        self.send_frame(frame, brightness, correction)
+    }
+}
 
-        if self.correction != correction {
-            self.correction = correction;
-            self.send(LedMessage::UpdateColorCorrection(correction))?;
-        }
+/// The async driver sends to the rendering thread without blocking, so it can be used from any
+/// async executor, to test async firmware code against the simulator.
+#[cfg(feature = "async")]
+impl<Dim, Layout> DriverAsync for DesktopDriver<Dim, Layout>
+where
+    Layout: LayoutForDim<Dim>,
+{
+    type Error = DesktopError;
+    type Color = LinearSrgb;
+    type Word = LinearSrgb;
 
-        let colors: Vec<LinearSrgb> = frame.into_iter().collect();
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        // Async writes don't get the brightness and color correction, so keep them for the write
+        self.encoded = (brightness, correction);
+        pixels
+            .into_iter()
+            .map(|color| LinearSrgb::from_color(color))
+            .collect()
+    }
 
-        self.send(LedMessage::UpdateColors(colors))?;
-        Ok(())
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        let (brightness, correction) = self.encoded;
+        self.send_frame(frame, brightness, correction)
     }
 }
 
@@ -466,7 +776,7 @@ impl<Dim, Layout> Drop for DesktopDriver<Dim, Layout> {
 /// Camera controller for the 3D LED visualization.
 ///
 /// Handles camera movement, rotation, and projection calculations.
-struct Camera {
+pub(crate) struct Camera {
     /// Distance from camera to target
     distance: f32,
 
@@ -500,7 +810,7 @@ impl Camera {
     const MIN_PITCH: f32 = -core::f32::consts::PI / 2.0 + 0.1;
 
     /// Create a new camera with default settings
-    fn new(aspect_ratio: f32, use_orthographic: bool) -> Self {
+    pub(crate) fn new(aspect_ratio: f32, use_orthographic: bool) -> Self {
         let default_fov = 2.0 * ((1.0 / Self::DEFAULT_DISTANCE).atan());
         Self {
             distance: Self::DEFAULT_DISTANCE,
@@ -521,6 +831,27 @@ impl Camera {
         self.pitch = Self::DEFAULT_PITCH;
     }
 
+    /// Move camera to a preset orientation, keeping the current zoom
+    fn set_preset(&mut self, preset: CameraPreset) {
+        let (yaw, pitch) = match preset {
+            CameraPreset::Front => (Self::DEFAULT_YAW, Self::DEFAULT_PITCH),
+            CameraPreset::Top => (Self::DEFAULT_YAW, Self::MAX_PITCH),
+            // Looking down the diagonal of a cube
+            CameraPreset::Isometric => (
+                core::f32::consts::PI * 0.25,
+                (1.0 / core::f32::consts::SQRT_2).atan(),
+            ),
+        };
+        self.target = Self::DEFAULT_TARGET;
+        self.yaw = yaw;
+        self.pitch = pitch;
+    }
+
+    /// Orbit camera horizontally around the target
+    fn orbit(&mut self, delta_yaw: f32) {
+        self.yaw = (self.yaw + delta_yaw) % core::f32::consts::TAU;
+    }
+
     /// Update camera aspect ratio when window is resized
     fn set_aspect_ratio(&mut self, aspect_ratio: f32) {
         self.aspect_ratio = aspect_ratio;
@@ -581,11 +912,22 @@ impl Camera {
     }
 
     /// Get the combined view-projection matrix
-    fn view_projection_matrix(&self) -> Mat4 {
+    pub(crate) fn view_projection_matrix(&self) -> Mat4 {
         self.projection_matrix() * self.view_matrix()
     }
 }
 
+/// Preset camera orientations
+#[derive(Debug, Clone, Copy)]
+enum CameraPreset {
+    /// Looking at the front (along -Z)
+    Front,
+    /// Looking down from the top (along -Y)
+    Top,
+    /// Looking down from the front right
+    Isometric,
+}
+
 /// Manages LED selection and interaction
 struct LedPicker {
     positions: Vec<Vec3>,
@@ -718,9 +1060,9 @@ impl UiManager {
         self.egui_mq.char_event(character);
     }
 
-    /// Render the LED information UI
+    /// Render the UI: the wiring overlay and power estimate (if enabled), and LED information
     #[allow(clippy::too_many_arguments)]
-    fn render_led_info(
+    fn render(
         &mut self,
         ctx: &mut dyn RenderingBackend,
         led_picker: &mut LedPicker,
@@ -728,10 +1070,34 @@ impl UiManager {
         colors: &[LinearSrgb],
         brightness: f32,
         correction: ColorCorrection,
+        wiring_view_proj: Option<Mat4>,
+        power: Option<PowerEstimate>,
     ) {
         self.egui_mq.run(ctx, |_mq_ctx, egui_ctx| {
             self.want_mouse_capture = egui_ctx.wants_pointer_input();
 
+            if let Some(view_proj) = wiring_view_proj {
+                Self::render_wiring_overlay(egui_ctx, positions, view_proj);
+            }
+
+            if let Some(power) = power {
+                egui::Area::new(egui::Id::new("power"))
+                    .anchor(egui::Align2::LEFT_TOP, egui::vec2(8.0, 8.0))
+                    .interactable(false)
+                    .show(egui_ctx, |ui| {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "Power: {:.2} A, {:.1} W at {:.1} V",
+                                power.amps(),
+                                power.watts(),
+                                power.voltage,
+                            ))
+                            .monospace()
+                            .color(egui::Color32::from_gray(220)),
+                        );
+                    });
+            }
+
             // Only show LED info window if an LED is selected
             if let Some(led_idx) = led_picker.selected_led {
                 let pos = positions[led_idx];
@@ -739,23 +1105,17 @@ impl UiManager {
 
                 let (red, green, blue) = (color.red, color.green, color.blue);
 
-                // Apply brightness
-                let (bright_red, bright_green, bright_blue) =
-                    (red * brightness, green * brightness, blue * brightness);
-
-                // Apply color correction
-                let (correct_red, correct_green, correct_blue) = (
-                    bright_red * correction.red,
-                    bright_green * correction.green,
-                    bright_blue * correction.blue,
-                );
+                // Apply color correction and brightness
+                let output = apply_output_pipeline(color, brightness, correction);
+                let (output_red, output_green, output_blue) =
+                    (output.red, output.green, output.blue);
 
                 // Convert to sRGB
                 let Srgb {
                     red: srgb_red,
                     green: srgb_green,
                     blue: srgb_blue,
-                } = LinearSrgb::new(correct_red, correct_green, correct_blue).to_srgb();
+                } = output.to_srgb();
 
                 egui::Window::new("LED Information")
                     .collapsible(false)
@@ -773,25 +1133,19 @@ impl UiManager {
                             red, green, blue,
                         ));
 
-                        // Display global brightness
-                        ui.label(format!("Global Brightness: {:.3}", brightness));
-
-                        // Display brightness-adjusted RGB values
-                        ui.label(format!(
-                            "Brightness-adjusted RGB: R={:.3}, G={:.3}, B={:.3}",
-                            bright_red, bright_green, bright_blue
-                        ));
-
                         // Display global color correction
                         ui.label(format!(
                             "Global Color Correction: R={:.3}, G={:.3}, B={:.3}",
                             correction.red, correction.green, correction.blue
                         ));
 
-                        // Display brightness-adjusted RGB values
+                        // Display global brightness
+                        ui.label(format!("Global Brightness: {:.3}", brightness));
+
+                        // Display output RGB values, as emitted by the LEDs
                         ui.label(format!(
-                            "Correction-adjusted RGB: R={:.3}, G={:.3}, B={:.3}",
-                            correct_red, correct_green, correct_blue
+                            "Output RGB: R={:.3}, G={:.3}, B={:.3}",
+                            output_red, output_green, output_blue
                         ));
 
                         // Display sRGB values
@@ -820,6 +1174,60 @@ impl UiManager {
         });
     }
 
+    /// Render each LED's index, and a line through the LEDs in wiring order
+    fn render_wiring_overlay(egui_ctx: &egui::Context, positions: &[Vec3], view_proj: Mat4) {
+        let screen_rect = egui_ctx.screen_rect();
+        let painter = egui_ctx.layer_painter(egui::LayerId::background());
+
+        // Project to screen coordinates, or None if behind the camera
+        let to_screen = |position: Vec3| {
+            let clip = view_proj * position.extend(1.0);
+            if clip.w <= 0.0 {
+                return None;
+            }
+            let ndc = clip.xyz() / clip.w;
+            Some(egui::pos2(
+                screen_rect.left() + (ndc.x + 1.0) * 0.5 * screen_rect.width(),
+                screen_rect.top() + (1.0 - ndc.y) * 0.5 * screen_rect.height(),
+            ))
+        };
+
+        let line_stroke = egui::Stroke::new(1.0, egui::Color32::from_white_alpha(128));
+        let label_color = egui::Color32::from_rgb(255, 220, 80);
+        let label_font = egui::FontId::monospace(10.0);
+
+        // Draw the wiring line, broken wherever an LED is behind the camera
+        let mut line: Vec<egui::Pos2> = Vec::new();
+        for &position in positions {
+            match to_screen(position) {
+                Some(point) => line.push(point),
+                None => {
+                    if line.len() > 1 {
+                        painter.add(egui::Shape::line(std::mem::take(&mut line), line_stroke));
+                    } else {
+                        line.clear();
+                    }
+                }
+            }
+        }
+        if line.len() > 1 {
+            painter.add(egui::Shape::line(line, line_stroke));
+        }
+
+        // Draw the index labels
+        for (index, &position) in positions.iter().enumerate() {
+            if let Some(point) = to_screen(position) {
+                painter.text(
+                    point + egui::vec2(4.0, -4.0),
+                    egui::Align2::LEFT_BOTTOM,
+                    index.to_string(),
+                    label_font.clone(),
+                    label_color,
+                );
+            }
+        }
+    }
+
     /// Draw egui content
     fn draw(&mut self, ctx: &mut dyn RenderingBackend) {
         self.egui_mq.draw(ctx);
@@ -968,6 +1376,7 @@ impl Renderer {
 struct DesktopStageOptions {
     pub positions: Vec<Vec3>,
     pub receiver: Receiver<LedMessage>,
+    pub button_sender: Sender<ButtonEvent>,
     pub config: DesktopConfig,
     pub is_window_closed: std::sync::Arc<std::sync::atomic::AtomicBool>,
 }
@@ -981,8 +1390,14 @@ struct DesktopStage {
     brightness: f32,
     correction: ColorCorrection,
     receiver: Receiver<LedMessage>,
+    button_sender: Sender<ButtonEvent>,
     camera: Camera,
     config: DesktopConfig,
+    display_gamma_enabled: bool,
+    turntable: bool,
+    last_update_time: f64,
+    show_wiring: bool,
+    show_power: bool,
     is_window_closed: std::sync::Arc<std::sync::atomic::AtomicBool>,
     mouse_down: bool,
     last_mouse_x: f32,
@@ -990,6 +1405,7 @@ struct DesktopStage {
     ui_manager: UiManager,
     led_picker: LedPicker,
     renderer: Renderer,
+    on_update: Box<dyn FnMut()>,
 }
 
 impl DesktopStage {
@@ -1014,6 +1430,7 @@ impl DesktopStage {
         let DesktopStageOptions {
             positions,
             receiver,
+            button_sender,
             config,
             is_window_closed,
         } = options;
@@ -1033,6 +1450,10 @@ impl DesktopStage {
         let (width, height) = window::screen_size();
         let camera = Camera::new(width / height, config.orthographic_view);
 
+        let turntable = config.turntable;
+        let show_wiring = config.show_wiring;
+        let show_power = config.show_power;
+
         // Initialize colors buffer
         let colors_buffer = (0..positions.len())
             .map(|_| Vec4::new(0.0, 0.0, 0.0, 1.0))
@@ -1047,8 +1468,14 @@ impl DesktopStage {
             brightness: 1.0,
             correction: ColorCorrection::default(),
             receiver,
+            button_sender,
             camera,
             config,
+            display_gamma_enabled: true,
+            turntable,
+            last_update_time: date::now(),
+            show_wiring,
+            show_power,
             is_window_closed,
             mouse_down: false,
             last_mouse_x: 0.0,
@@ -1056,6 +1483,7 @@ impl DesktopStage {
             ui_manager,
             led_picker,
             renderer,
+            on_update: Box::new(|| {}),
         };
 
         // Setup buffers
@@ -1098,6 +1526,27 @@ impl DesktopStage {
             KeyCode::O => {
                 self.camera.toggle_projection_mode();
             }
+            KeyCode::Key1 => {
+                self.camera.set_preset(CameraPreset::Front);
+            }
+            KeyCode::Key2 => {
+                self.camera.set_preset(CameraPreset::Top);
+            }
+            KeyCode::Key3 => {
+                self.camera.set_preset(CameraPreset::Isometric);
+            }
+            KeyCode::A => {
+                self.turntable = !self.turntable;
+            }
+            KeyCode::G => {
+                self.display_gamma_enabled = !self.display_gamma_enabled;
+            }
+            KeyCode::L => {
+                self.show_wiring = !self.show_wiring;
+            }
+            KeyCode::P => {
+                self.show_power = !self.show_power;
+            }
             KeyCode::Escape => {
                 // Clear selection when Escape is pressed
                 self.led_picker.clear_selection();
@@ -1105,36 +1554,48 @@ impl DesktopStage {
             _ => {}
         }
     }
+
+    /// Handles input for the emulated device button
+    fn handle_button_input(&mut self, character: char) {
+        for binding in &self.config.key_bindings {
+            if binding.key.eq_ignore_ascii_case(&character) {
+                // The user thread may have dropped the buttons, which is fine
+                let _ = self.button_sender.send(binding.event);
+            }
+        }
+    }
 }
 
 impl EventHandler for DesktopStage {
     fn update(&mut self) {
+        (self.on_update)();
         self.process_messages();
+
+        let now = date::now();
+        let delta_time = (now - self.last_update_time) as f32;
+        self.last_update_time = now;
+
+        if self.turntable && !self.mouse_down {
+            self.camera.orbit(delta_time * self.config.turntable_speed);
+        }
     }
 
     fn draw(&mut self) {
+        let display_gamma = if self.display_gamma_enabled {
+            self.config.display_gamma
+        } else {
+            DisplayGamma::Linear
+        };
+
         let colors_buffer: Vec<Vec4> = self
             .colors
             .iter()
-            .map(|color| {
-                let (red, green, blue) = (color.red, color.green, color.blue);
-
-                // Apply brightness
-                let (red, green, blue) = (
-                    red * self.brightness,
-                    green * self.brightness,
-                    blue * self.brightness,
-                );
-
-                // Apply color correction
-                let (red, green, blue) = (
-                    red * self.correction.red,
-                    green * self.correction.green,
-                    blue * self.correction.blue,
-                );
+            .map(|&color| {
+                // Apply the same color correction and brightness as the LEDs
+                let light = apply_output_pipeline(color, self.brightness, self.correction);
 
-                // Convert to sRGB
-                let Srgb { red, green, blue } = LinearSrgb::new(red, green, blue).to_srgb();
+                // Encode for display
+                let (red, green, blue) = display_gamma.encode(light);
 
                 Vec4::new(red, green, blue, 1.)
             })
@@ -1156,14 +1617,25 @@ impl EventHandler for DesktopStage {
             self.config.background_color,
         );
 
-        // Render UI with LED info if needed
-        self.ui_manager.render_led_info(
+        // Estimate power draw of the frame
+        let power = self.show_power.then(|| {
+            self.config.power_model.estimate(
+                self.colors.iter().copied(),
+                self.brightness,
+                self.correction,
+            )
+        });
+
+        // Render UI with wiring overlay, power estimate, and LED info if needed
+        self.ui_manager.render(
             &mut *self.ctx,
             &mut self.led_picker,
             &self.positions,
             &self.colors,
             self.brightness,
             self.correction,
+            self.show_wiring.then_some(view_proj),
+            power,
         );
 
         // Draw egui
@@ -1232,8 +1704,12 @@ impl EventHandler for DesktopStage {
         self.ui_manager.key_up_event(keycode, keymods);
     }
 
-    fn char_event(&mut self, character: char, _keymods: KeyMods, _repeat: bool) {
+    fn char_event(&mut self, character: char, _keymods: KeyMods, repeat: bool) {
         self.ui_manager.char_event(character);
+
+        if !repeat && !self.ui_manager.want_mouse_capture {
+            self.handle_button_input(character);
+        }
     }
 
     fn quit_requested_event(&mut self) {
@@ -47,9 +47,50 @@
 //!     }
 //! });
 //! ```
+//!
+//! ## Snapshots
+//!
+//! To render a pattern to an image, without a window (e.g. for docs, or in tests), see
+//! [`snapshot`].
+//!
+//! ## Mapping
+//!
+//! To find the position of each LED of an installation with a camera, and write them as a
+//! layout, see [`mapping`].
+//!
+//! ## Serial
+//!
+//! To control a device over USB serial, or stream frames to it, see [`serial`].
+//!
+//! ## Web
+//!
+//! The simulator also runs in a browser, compiled to WebAssembly (`wasm32-unknown-unknown`),
+//! so you can share interactive previews of your patterns as web pages.
+//!
+//! Browsers have no threads, so instead of [`Desktop::start`](driver::Desktop::start), use
+//! [`Desktop::run`](driver::Desktop::run), which calls your code once per frame. Then load
+//! the built `.wasm` file with [miniquad's JavaScript loader], as in
+//! [`web/index.html`](https://github.com/ahdinosaur/blinksy/blob/main/blinksy-desktop/web/index.html).
+//!
+//! [miniquad's JavaScript loader]: https://github.com/not-fl3/miniquad#wasm
+
+/// Streaming the edges of a screen to LEDs behind it
+pub mod ambilight;
 
 /// Desktop LED simulation
 pub mod driver;
 
+/// Mapping LED positions with a camera
+pub mod mapping;
+
+/// Controlling a device over USB serial
+pub mod serial;
+
+/// Offscreen rendering to images
+pub mod snapshot;
+
 /// Time utilities
 pub mod time;
+
+/// Verification of the simulator against LED hardware
+pub mod verify;
@@ -0,0 +1,559 @@
+//! # LED Position Mapping
+//!
+//! Measuring the position of each LED of an irregular installation (e.g. LEDs strung on a
+//! tree) by hand is slow. [`Mapper`](crate::mapping::Mapper) finds them with a camera: it
+//! lights each LED in turn, captures a frame, and finds the bright blob of the lit LED. The
+//! positions are then written as a layout with a [`Shape2d::Points`] or [`Shape3d::Points`]
+//! shape, to include in your firmware.
+//!
+//! 1. Point a camera at the installation, in a dark room, and keep both still.
+//! 2. Implement [`FrameSource`](crate::mapping::FrameSource) for your camera, usually with a
+//!    webcam crate. Any closure returning an [`Image`] is a frame source.
+//! 3. Map the LEDs with the driver of the installation, with
+//!    [`Mapper::map_driver`](crate::mapping::Mapper::map_driver), or with a closure which
+//!    lights one LED, with [`Mapper::map`](crate::mapping::Mapper::map).
+//! 4. Write the layout with [`layout2d_source`](crate::mapping::layout2d_source). Or for 3D,
+//!    map again with the camera looking from the right side of the installation, and write
+//!    the layout with [`layout3d_source`](crate::mapping::layout3d_source).
+//!
+//! LEDs which aren't found (e.g. hidden from the camera) are placed between their neighbors in
+//! the chain.
+//!
+//! [`Shape2d::Points`]: blinksy::layout::Shape2d::Points
+//! [`Shape3d::Points`]: blinksy::layout::Shape3d::Points
+//! [`Image`]: crate::snapshot::Image
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use std::{cell::Cell, convert::Infallible, rc::Rc, time::Duration};
+//!
+//! use blinksy::{
+//!     color::LinearSrgb,
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     layout2d,
+//! };
+//! use blinksy_desktop::{
+//!     mapping::{layout2d_source, Mapper},
+//!     snapshot::Snapshot,
+//! };
+//!
+//! layout2d!(
+//!     Installation,
+//!     [Shape2d::Line {
+//!         start: Vec2::new(-1., -0.5),
+//!         end: Vec2::new(1., 0.5),
+//!         pixel_count: 5,
+//!     }]
+//! );
+//!
+//! // Simulate the camera with a snapshot of the installation
+//! let lit = Rc::new(Cell::new(None));
+//! let camera = {
+//!     let lit = lit.clone();
+//!     let snapshot = Snapshot::new_2d::<Installation>();
+//!     move || {
+//!         let pixels = (0..Installation::PIXEL_COUNT).map(|index| {
+//!             let value = if lit.get() == Some(index) { 1. } else { 0. };
+//!             LinearSrgb::new(value, value, value)
+//!         });
+//!         Ok::<_, Infallible>(snapshot.render_pixels(pixels))
+//!     }
+//! };
+//!
+//! let mut mapper = Mapper::new(camera).with_settle_time(Duration::ZERO);
+//! let mapping = mapper
+//!     .map(Installation::PIXEL_COUNT, |index| {
+//!         lit.set(index);
+//!         Ok::<_, Infallible>(())
+//!     })
+//!     .unwrap();
+//! assert_eq!(mapping.missing().count(), 0);
+//!
+//! // The LEDs are found from the left bottom to the right top
+//! let points = mapping.to_points_2d();
+//! assert!(points
+//!     .windows(2)
+//!     .all(|pair| pair[0].x < pair[1].x && pair[0].y < pair[1].y));
+//!
+//! // Write the layout, e.g. to include in your firmware with `include!`
+//! let source = layout2d_source("InstallationLayout", &points);
+//! assert!(source.contains("layout2d!(pub InstallationLayout"));
+//! ```
+
+use core::fmt::{self, Write};
+use std::{thread::sleep, time::Duration};
+
+use blinksy::{
+    color::{ColorCorrection, FromColor, LinearSrgb},
+    driver::Driver,
+};
+use glam::{Vec2, Vec3};
+
+use crate::snapshot::Image;
+
+/// The default brightness increase of a lit LED over the background, from 0 to 255.
+pub const DEFAULT_THRESHOLD: u8 = 64;
+
+/// The default time to wait after lighting an LED, for the camera to capture a new frame.
+pub const DEFAULT_SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// The radius of a blob, as a fraction of the larger side of a frame.
+const BLOB_RADIUS: f32 = 0.05;
+
+/// A source of camera frames.
+///
+/// Any closure returning a frame, or an error, is a frame source.
+pub trait FrameSource {
+    /// The error type that may be returned when capturing.
+    type Error;
+
+    /// Captures a new frame.
+    ///
+    /// The frame must be captured after this is called, not buffered from before.
+    fn capture(&mut self) -> Result<Image, Self::Error>;
+}
+
+impl<F, E> FrameSource for F
+where
+    F: FnMut() -> Result<Image, E>,
+{
+    type Error = E;
+
+    fn capture(&mut self) -> Result<Image, Self::Error> {
+        self()
+    }
+}
+
+/// All types of errors that can happen while mapping.
+#[derive(Debug)]
+pub enum MappingError<LightError, CaptureError> {
+    /// Lighting the LEDs failed.
+    Light(LightError),
+    /// Capturing a frame failed.
+    Capture(CaptureError),
+    /// A frame had a different size than the first frame.
+    FrameSize,
+}
+
+impl<LightError, CaptureError> fmt::Display for MappingError<LightError, CaptureError>
+where
+    LightError: fmt::Display,
+    CaptureError: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MappingError::Light(error) => write!(f, "failed to light LEDs: {error}"),
+            MappingError::Capture(error) => write!(f, "failed to capture frame: {error}"),
+            MappingError::FrameSize => write!(f, "frame size changed while mapping"),
+        }
+    }
+}
+
+impl<LightError, CaptureError> core::error::Error for MappingError<LightError, CaptureError>
+where
+    LightError: fmt::Debug + fmt::Display,
+    CaptureError: fmt::Debug + fmt::Display,
+{
+}
+
+/// Maps the position of each LED with a camera.
+///
+/// # Type Parameters
+///
+/// - `Source` - The source of camera frames
+pub struct Mapper<Source> {
+    source: Source,
+    threshold: u8,
+    settle_time: Duration,
+    color: LinearSrgb,
+}
+
+impl<Source: FrameSource> Mapper<Source> {
+    /// Creates a mapper with the default threshold and settle time.
+    ///
+    /// # Parameters
+    ///
+    /// - `source` - The source of camera frames
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            threshold: DEFAULT_THRESHOLD,
+            settle_time: DEFAULT_SETTLE_TIME,
+            color: LinearSrgb::new(1., 1., 1.),
+        }
+    }
+
+    /// Sets the brightness increase of a lit LED over the background. Raise it if reflections
+    /// are found as LEDs, lower it if dim LEDs aren't found.
+    ///
+    /// # Parameters
+    ///
+    /// - `threshold` - The brightness increase, from 0 to 255, defaults to
+    ///   [`DEFAULT_THRESHOLD`]
+    pub fn with_threshold(mut self, threshold: u8) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the time to wait after lighting an LED, before capturing a frame.
+    ///
+    /// # Parameters
+    ///
+    /// - `settle_time` - The time, defaults to [`DEFAULT_SETTLE_TIME`]
+    pub fn with_settle_time(mut self, settle_time: Duration) -> Self {
+        self.settle_time = settle_time;
+        self
+    }
+
+    /// Sets the color of the lit LED, with [`Mapper::map_driver`].
+    ///
+    /// # Parameters
+    ///
+    /// - `color` - The color, defaults to white
+    pub fn with_color(mut self, color: LinearSrgb) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Maps the LEDs, lighting each with a closure.
+    ///
+    /// # Parameters
+    ///
+    /// - `pixel_count` - The number of LEDs
+    /// - `light` - Lights only the LED at an index, or none
+    ///
+    /// # Returns
+    ///
+    /// The position of each LED in the camera frames
+    pub fn map<Light, LightError>(
+        &mut self,
+        pixel_count: usize,
+        mut light: Light,
+    ) -> Result<Mapping, MappingError<LightError, Source::Error>>
+    where
+        Light: FnMut(Option<usize>) -> Result<(), LightError>,
+    {
+        let background = self.capture_lit(&mut light, None)?;
+        let (width, height) = (background.width(), background.height());
+
+        let mut positions = Vec::with_capacity(pixel_count);
+        for index in 0..pixel_count {
+            let frame = self.capture_lit(&mut light, Some(index))?;
+            if frame.width() != width || frame.height() != height {
+                return Err(MappingError::FrameSize);
+            }
+            positions.push(detect(&background, &frame, self.threshold));
+        }
+
+        light(None).map_err(MappingError::Light)?;
+
+        Ok(Mapping {
+            width,
+            height,
+            positions,
+        })
+    }
+
+    /// Maps the LEDs, lighting each with a driver.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `PIXEL_COUNT` - The number of LEDs
+    /// - `FRAME_BUFFER_SIZE` - The frame buffer size of the driver
+    ///
+    /// # Parameters
+    ///
+    /// - `driver` - The driver of the LEDs
+    ///
+    /// # Returns
+    ///
+    /// The position of each LED in the camera frames
+    pub fn map_driver<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, D>(
+        &mut self,
+        driver: &mut D,
+    ) -> Result<Mapping, MappingError<D::Error, Source::Error>>
+    where
+        D: Driver,
+        D::Color: FromColor<LinearSrgb>,
+    {
+        let color = self.color;
+        self.map(PIXEL_COUNT, |lit| {
+            let pixels = (0..PIXEL_COUNT).map(|index| {
+                if lit == Some(index) {
+                    color
+                } else {
+                    LinearSrgb::new(0., 0., 0.)
+                }
+            });
+            driver.show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+                pixels,
+                1.,
+                ColorCorrection::default(),
+            )
+        })
+    }
+
+    /// Lights only the LED at an index, or none, then captures a frame.
+    fn capture_lit<Light, LightError>(
+        &mut self,
+        light: &mut Light,
+        index: Option<usize>,
+    ) -> Result<Image, MappingError<LightError, Source::Error>>
+    where
+        Light: FnMut(Option<usize>) -> Result<(), LightError>,
+    {
+        light(index).map_err(MappingError::Light)?;
+        sleep(self.settle_time);
+        self.source.capture().map_err(MappingError::Capture)
+    }
+}
+
+/// The positions of LEDs in camera frames.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mapping {
+    width: u32,
+    height: u32,
+    positions: Vec<Option<Vec2>>,
+}
+
+impl Mapping {
+    /// Returns the width of the camera frames in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the camera frames in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the position of each LED in pixels from the top left of the camera frames, if
+    /// found.
+    pub fn positions(&self) -> &[Option<Vec2>] {
+        &self.positions
+    }
+
+    /// Returns the indices of the LEDs which weren't found.
+    pub fn missing(&self) -> impl Iterator<Item = usize> + '_ {
+        self.positions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, position)| position.is_none().then_some(index))
+    }
+
+    /// Returns the 2D layout points of the LEDs, as seen by the camera.
+    ///
+    /// The points are scaled to fit between -1.0 and 1.0, keeping their aspect ratio, with
+    /// the top of the camera frames up.
+    pub fn to_points_2d(&self) -> Vec<Vec2> {
+        let points: Vec<Vec2> = fill_missing(&self.positions)
+            .into_iter()
+            .map(|position| Vec2::new(position.x, -position.y))
+            .collect();
+
+        let min = points
+            .iter()
+            .fold(Vec2::INFINITY, |min, &point| min.min(point));
+        let max = points
+            .iter()
+            .fold(Vec2::NEG_INFINITY, |max, &point| max.max(point));
+        let (center, scale) = fit(min.extend(0.), max.extend(0.));
+        points
+            .into_iter()
+            .map(|point| (point - center.truncate()) * scale)
+            .collect()
+    }
+
+    /// Returns the 3D layout points of the LEDs, from two mappings of the same LEDs: one with
+    /// the camera in front, and one with the camera on the right side.
+    ///
+    /// The front view gives x and y, and the side view gives z. The points are scaled to fit
+    /// between -1.0 and 1.0, keeping their aspect ratio.
+    ///
+    /// # Parameters
+    ///
+    /// - `front` - The mapping with the camera in front
+    /// - `side` - The mapping with the camera on the right side, at the same distance
+    pub fn to_points_3d(front: &Mapping, side: &Mapping) -> Vec<Vec3> {
+        let points: Vec<Vec3> = fill_missing(&front.positions)
+            .into_iter()
+            .zip(fill_missing(&side.positions))
+            // From the right side, the left of the frame is the front
+            .map(|(front, side)| Vec3::new(front.x, -front.y, -side.x))
+            .collect();
+
+        let min = points
+            .iter()
+            .fold(Vec3::INFINITY, |min, &point| min.min(point));
+        let max = points
+            .iter()
+            .fold(Vec3::NEG_INFINITY, |max, &point| max.max(point));
+        let (center, scale) = fit(min, max);
+        points
+            .into_iter()
+            .map(|point| (point - center) * scale)
+            .collect()
+    }
+}
+
+/// Writes the source code of a 2D layout of points, for [`layout2d!`](blinksy::layout2d).
+///
+/// # Parameters
+///
+/// - `name` - The name of the layout type
+/// - `points` - The point of each LED
+///
+/// # Returns
+///
+/// The source code, defining a public layout type and a constant of its points
+pub fn layout2d_source(name: &str, points: &[Vec2]) -> String {
+    let points = points
+        .iter()
+        .map(|point| format!("Vec2::new({:.4}, {:.4})", point.x, point.y));
+    layout_source(name, "2d", "Vec2", points)
+}
+
+/// Writes the source code of a 3D layout of points, for [`layout3d!`](blinksy::layout3d).
+///
+/// # Parameters
+///
+/// - `name` - The name of the layout type
+/// - `points` - The point of each LED
+///
+/// # Returns
+///
+/// The source code, defining a public layout type and a constant of its points
+pub fn layout3d_source(name: &str, points: &[Vec3]) -> String {
+    let points = points
+        .iter()
+        .map(|point| format!("Vec3::new({:.4}, {:.4}, {:.4})", point.x, point.y, point.z));
+    layout_source(name, "3d", "Vec3", points)
+}
+
+/// Writes the source code of a layout of points.
+fn layout_source(
+    name: &str,
+    dim: &str,
+    vector: &str,
+    points: impl ExactSizeIterator<Item = String>,
+) -> String {
+    let points_name = format!("{}_POINTS", screaming_snake_case(name));
+
+    let mut source = String::new();
+    // Writing to a string can't fail
+    let _ = writeln!(source, "// LED positions, mapped with a camera");
+    let _ = writeln!(
+        source,
+        "use blinksy::{{layout::{{Shape{dim}, {vector}}}, layout{dim}}};\n"
+    );
+    let _ = writeln!(source, "/// The point of each LED in [`{name}`].");
+    let _ = writeln!(
+        source,
+        "pub const {points_name}: [{vector}; {}] = [",
+        points.len()
+    );
+    for point in points {
+        let _ = writeln!(source, "    {point},");
+    }
+    let _ = writeln!(source, "];\n");
+    let _ = writeln!(
+        source,
+        "layout{dim}!(pub {name}, [Shape{dim}::Points(&{points_name})]);"
+    );
+    source
+}
+
+/// Converts a type name to the name of a constant, e.g. `TreeLayout` to `TREE_LAYOUT`.
+fn screaming_snake_case(name: &str) -> String {
+    let mut output = String::with_capacity(name.len() + 4);
+    let mut previous: Option<char> = None;
+    for character in name.chars() {
+        if character.is_uppercase()
+            && previous.is_some_and(|previous| previous.is_lowercase() || previous.is_numeric())
+        {
+            output.push('_');
+        }
+        output.extend(character.to_uppercase());
+        previous = Some(character);
+    }
+    output
+}
+
+/// Returns the center and scale to fit points within a bounding box between -1.0 and 1.0,
+/// keeping their aspect ratio.
+fn fit(min: Vec3, max: Vec3) -> (Vec3, f32) {
+    if !min.is_finite() || !max.is_finite() {
+        return (Vec3::ZERO, 1.);
+    }
+    let extent = (max - min).max_element();
+    let scale = if extent > 0. { 2. / extent } else { 1. };
+    ((min + max) / 2., scale)
+}
+
+/// Places LEDs which weren't found between their nearest found neighbors in the chain.
+fn fill_missing(positions: &[Option<Vec2>]) -> Vec<Vec2> {
+    let found: Vec<(usize, Vec2)> = positions
+        .iter()
+        .enumerate()
+        .filter_map(|(index, position)| position.map(|position| (index, position)))
+        .collect();
+
+    (0..positions.len())
+        .map(|index| {
+            if let Some(position) = positions[index] {
+                return position;
+            }
+            let before = found.iter().rev().find(|(found, _)| *found < index);
+            let after = found.iter().find(|(found, _)| *found > index);
+            match (before, after) {
+                (Some(&(start, from)), Some(&(end, to))) => {
+                    from.lerp(to, (index - start) as f32 / (end - start) as f32)
+                }
+                (Some(&(_, position)), None) | (None, Some(&(_, position))) => position,
+                (None, None) => Vec2::ZERO,
+            }
+        })
+        .collect()
+}
+
+/// Finds the blob of a lit LED: the centroid of the pixels around the brightest pixel, which
+/// are brighter than the background by at least the threshold.
+fn detect(background: &Image, frame: &Image, threshold: u8) -> Option<Vec2> {
+    let (width, height) = (frame.width() as usize, frame.height() as usize);
+    let increases: Vec<u8> = frame
+        .data()
+        .chunks_exact(4)
+        .zip(background.data().chunks_exact(4))
+        .map(|(pixel, background)| luma(pixel).saturating_sub(luma(background)))
+        .collect();
+
+    let (peak, &peak_increase) = increases
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, increase)| **increase)?;
+    if peak_increase < threshold {
+        return None;
+    }
+
+    let radius = (width.max(height) as f32 * BLOB_RADIUS).ceil() as usize;
+    let (peak_x, peak_y) = (peak % width, peak / width);
+    let (mut sum, mut total) = (Vec2::ZERO, 0.);
+    for y in peak_y.saturating_sub(radius)..(peak_y + radius + 1).min(height) {
+        for x in peak_x.saturating_sub(radius)..(peak_x + radius + 1).min(width) {
+            let increase = increases[y * width + x];
+            if increase >= threshold {
+                let weight = increase as f32;
+                sum += Vec2::new(x as f32 + 0.5, y as f32 + 0.5) * weight;
+                total += weight;
+            }
+        }
+    }
+    Some(sum / total)
+}
+
+/// Returns the brightness of an RGBA pixel, from 0 to 255.
+fn luma(pixel: &[u8]) -> u8 {
+    let [red, green, blue] = [pixel[0], pixel[1], pixel[2]].map(u32::from);
+    ((red * 54 + green * 183 + blue * 19) >> 8) as u8
+}
@@ -0,0 +1,136 @@
+//! # Serial Control Client
+//!
+//! A host-side client for the [serial control protocol](blinksy::serial), to control a device
+//! over USB serial, without any radio: set its brightness, select its pattern, or stream
+//! frames of pixels to it.
+//!
+//! The writer is usually a serial port, e.g. from the `serialport` crate, or on Linux the
+//! device file (e.g. `/dev/ttyACM0`). As USB serial (CDC ACM) ignores the baud rate, there is
+//! nothing to set up.
+//!
+//! A [`SerialClient`](crate::serial::SerialClient) is also a
+//! [`FrameSender`](crate::ambilight::FrameSender), so it can stream an
+//! [`Ambilight`](crate::ambilight::Ambilight).
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use std::fs::OpenOptions;
+//!
+//! use blinksy::color::LinearSrgb;
+//! use blinksy_desktop::serial::SerialClient;
+//!
+//! let port = OpenOptions::new().write(true).open("/dev/ttyACM0")?;
+//! let mut client = SerialClient::new(port);
+//!
+//! client.set_brightness(0.5)?;
+//! client.set_pattern("rainbow")?;
+//! client.send_pixels(&[LinearSrgb::new(1., 0., 0.); 60])?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! On the device, decode the messages with a [`Decoder`](blinksy::serial::Decoder):
+//!
+//! ```rust
+//! use blinksy::serial::{Command, Decoder};
+//! use blinksy_desktop::serial::SerialClient;
+//!
+//! let mut client = SerialClient::new(Vec::new());
+//! client.set_pattern("noise").unwrap();
+//!
+//! let mut decoder = Decoder::<64>::new();
+//! let mut pattern = None;
+//! for byte in client.into_inner() {
+//!     if let Some(Ok(Command::SetPattern(name))) = decoder.push(byte) {
+//!         pattern = Some(name.to_owned());
+//!     }
+//! }
+//! assert_eq!(pattern.as_deref(), Some("noise"));
+//! ```
+
+use std::io::{self, Write};
+
+use blinksy::{
+    color::{LinearSrgb, Srgb},
+    serial::{header, CommandCode},
+};
+
+use crate::ambilight::FrameSender;
+
+/// A client for the serial control protocol.
+#[derive(Debug)]
+pub struct SerialClient<Writer> {
+    writer: Writer,
+}
+
+impl<Writer> SerialClient<Writer>
+where
+    Writer: Write,
+{
+    /// Wraps a writer to the device.
+    ///
+    /// # Arguments
+    ///
+    /// - `writer` - The serial port to the device
+    pub fn new(writer: Writer) -> Self {
+        Self { writer }
+    }
+
+    /// Unwraps the writer.
+    pub fn into_inner(self) -> Writer {
+        self.writer
+    }
+
+    /// Sets the brightness of the device.
+    ///
+    /// # Arguments
+    ///
+    /// - `brightness` - Brightness level from 0.0 (off) to 1.0 (full)
+    pub fn set_brightness(&mut self, brightness: f32) -> io::Result<()> {
+        self.write_message(CommandCode::SetBrightness, &brightness.to_le_bytes())
+    }
+
+    /// Selects a pattern of the device by name.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The name of the pattern, as registered on the device
+    pub fn set_pattern(&mut self, name: &str) -> io::Result<()> {
+        self.write_message(CommandCode::SetPattern, name.as_bytes())
+    }
+
+    /// Shows a frame of pixels on the device.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels` - The color of each pixel, in LED order
+    pub fn send_pixels(&mut self, pixels: &[LinearSrgb]) -> io::Result<()> {
+        let payload: Vec<u8> = pixels
+            .iter()
+            .flat_map(|&pixel| Srgb::from_linear_srgb(pixel).to_u8())
+            .collect();
+        self.write_message(CommandCode::Pixels, &payload)
+    }
+
+    /// Writes a message, then flushes the writer.
+    fn write_message(&mut self, code: CommandCode, payload: &[u8]) -> io::Result<()> {
+        let len = u16::try_from(payload.len()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "payload longer than the serial protocol allows",
+            )
+        })?;
+        self.writer.write_all(&header(code, len))?;
+        self.writer.write_all(payload)?;
+        self.writer.flush()
+    }
+}
+
+impl<Writer> FrameSender for SerialClient<Writer>
+where
+    Writer: Write,
+{
+    fn send(&mut self, pixels: &[LinearSrgb]) -> io::Result<()> {
+        self.send_pixels(pixels)
+    }
+}
@@ -0,0 +1,434 @@
+//! # Offscreen Snapshots
+//!
+//! [`Snapshot`](crate::snapshot::Snapshot) renders a layout and pattern at a given time to an
+//! [`Image`](crate::snapshot::Image), offscreen: without a window or a GPU, so it runs in tests
+//! and examples, e.g. to generate the images of each pattern in the docs and README from code.
+//!
+//! The LEDs are drawn as in the simulator window when it opens: the same shapes, camera view,
+//! and display gamma, all from the [`DesktopConfig`](crate::driver::DesktopConfig). The image
+//! is the size of the window.
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use blinksy::{
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     layout2d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//! };
+//! use blinksy_desktop::snapshot::Snapshot;
+//!
+//! layout2d!(
+//!     PanelLayout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 16,
+//!         vertical_pixel_count: 16,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! // Render the rainbow, one second in
+//! let image = Snapshot::new_2d::<PanelLayout>().render::<Rainbow>(RainbowParams::default(), 1000);
+//!
+//! // Encode as a PNG, or save with `image.save_png("rainbow.png")`
+//! let mut png = Vec::new();
+//! image.write_png(&mut png).unwrap();
+//! assert_eq!(&png[1..4], b"PNG");
+//! ```
+
+use std::{fs::File, io, io::BufWriter, io::Write, marker::PhantomData, path::Path};
+
+use blinksy::{
+    color::{FromColor, LinearSrgb},
+    layout::{Layout1d, Layout2d, Layout3d, LayoutForDim},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern,
+};
+use glam::{vec3, Mat4, Vec3, Vec4Swizzles};
+
+use crate::driver::{Camera, DesktopConfig};
+
+/// The faces of each LED, as in the simulator window: an octahedron.
+const LED_FACES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [0, 2, 3],
+    [0, 3, 4],
+    [0, 4, 1],
+    [5, 1, 2],
+    [5, 2, 3],
+    [5, 3, 4],
+    [5, 4, 1],
+];
+
+/// The corners of an LED, around its position.
+fn led_vertices(position: Vec3, radius: f32) -> [Vec3; 6] {
+    [
+        vec3(0.0, -radius, 0.0),
+        vec3(radius, 0.0, radius),
+        vec3(radius, 0.0, -radius),
+        vec3(-radius, 0.0, -radius),
+        vec3(-radius, 0.0, radius),
+        vec3(0.0, radius, 0.0),
+    ]
+    .map(|vertex| position + vertex)
+}
+
+/// Offscreen renderer of LED layouts to images.
+///
+/// # Type Parameters
+///
+/// - `Dim` - The dimension marker (Dim1d or Dim2d or Dim3d)
+/// - `Layout` - The specific layout type
+pub struct Snapshot<Dim, Layout> {
+    dim: PhantomData<Dim>,
+    layout: PhantomData<Layout>,
+    positions: Vec<Vec3>,
+    config: DesktopConfig,
+}
+
+impl Snapshot<Dim1d, ()> {
+    /// Creates a snapshot renderer for 1D layouts.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Layout` - The layout type implementing Layout1d
+    pub fn new_1d<Layout>() -> Snapshot<Dim1d, Layout>
+    where
+        Layout: Layout1d,
+    {
+        Self::new_1d_with_config::<Layout>(DesktopConfig::default())
+    }
+
+    /// Creates a snapshot renderer for 1D layouts with custom configuration.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Layout` - The layout type implementing Layout1d
+    ///
+    /// # Parameters
+    ///
+    /// - `config` - Configuration options, as for the simulator window
+    pub fn new_1d_with_config<Layout>(config: DesktopConfig) -> Snapshot<Dim1d, Layout>
+    where
+        Layout: Layout1d,
+    {
+        let positions = Layout::points().map(|x| vec3(x, 0.0, 0.0)).collect();
+        Snapshot::with_positions(positions, config)
+    }
+}
+
+impl Snapshot<Dim2d, ()> {
+    /// Creates a snapshot renderer for 2D layouts.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Layout` - The layout type implementing Layout2d
+    pub fn new_2d<Layout>() -> Snapshot<Dim2d, Layout>
+    where
+        Layout: Layout2d,
+    {
+        Self::new_2d_with_config::<Layout>(DesktopConfig::default())
+    }
+
+    /// Creates a snapshot renderer for 2D layouts with custom configuration.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Layout` - The layout type implementing Layout2d
+    ///
+    /// # Parameters
+    ///
+    /// - `config` - Configuration options, as for the simulator window
+    pub fn new_2d_with_config<Layout>(config: DesktopConfig) -> Snapshot<Dim2d, Layout>
+    where
+        Layout: Layout2d,
+    {
+        let positions = Layout::points()
+            .map(|point| vec3(point.x, point.y, 0.0))
+            .collect();
+        Snapshot::with_positions(positions, config)
+    }
+}
+
+impl Snapshot<Dim3d, ()> {
+    /// Creates a snapshot renderer for 3D layouts.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Layout` - The layout type implementing Layout3d
+    pub fn new_3d<Layout>() -> Snapshot<Dim3d, Layout>
+    where
+        Layout: Layout3d,
+    {
+        Self::new_3d_with_config::<Layout>(DesktopConfig::default())
+    }
+
+    /// Creates a snapshot renderer for 3D layouts with custom configuration.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Layout` - The layout type implementing Layout3d
+    ///
+    /// # Parameters
+    ///
+    /// - `config` - Configuration options, as for the simulator window
+    pub fn new_3d_with_config<Layout>(config: DesktopConfig) -> Snapshot<Dim3d, Layout>
+    where
+        Layout: Layout3d,
+    {
+        let positions = Layout::points()
+            .map(|point| vec3(point.x, point.y, point.z))
+            .collect();
+        Snapshot::with_positions(positions, config)
+    }
+}
+
+impl<Dim, Layout> Snapshot<Dim, Layout>
+where
+    Layout: LayoutForDim<Dim>,
+{
+    fn with_positions(positions: Vec<Vec3>, config: DesktopConfig) -> Self {
+        Self {
+            dim: PhantomData,
+            layout: PhantomData,
+            positions,
+            config,
+        }
+    }
+
+    /// Renders a pattern at a time.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `P` - The pattern type
+    ///
+    /// # Parameters
+    ///
+    /// - `params` - The pattern parameters
+    /// - `time_in_ms` - The pattern time in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// The image of the LEDs
+    pub fn render<P>(&self, params: P::Params, time_in_ms: u64) -> Image
+    where
+        P: Pattern<Dim, Layout>,
+        LinearSrgb: FromColor<P::Color>,
+    {
+        self.render_pixels(P::new(params).tick(time_in_ms))
+    }
+
+    /// Renders explicit colors for each LED, e.g. from a pattern already running.
+    ///
+    /// LEDs without a color are drawn off.
+    ///
+    /// # Parameters
+    ///
+    /// - `pixels` - Iterator of colors for each LED
+    ///
+    /// # Returns
+    ///
+    /// The image of the LEDs
+    pub fn render_pixels<I, C>(&self, pixels: I) -> Image
+    where
+        I: IntoIterator<Item = C>,
+        LinearSrgb: FromColor<C>,
+    {
+        let width = self.config.window_width.max(1) as u32;
+        let height = self.config.window_height.max(1) as u32;
+
+        let (red, green, blue, alpha) = self.config.background_color;
+        let background = [red, green, blue, alpha].map(to_u8);
+        let mut image = Image::new(width, height, background);
+
+        let camera = Camera::new(width as f32 / height as f32, self.config.orthographic_view);
+        let view_proj = camera.view_projection_matrix();
+
+        let mut depths = vec![f32::INFINITY; (width * height) as usize];
+        let mut pixels = pixels.into_iter().map(LinearSrgb::from_color);
+        for &position in &self.positions {
+            let color = pixels.next().unwrap_or(LinearSrgb::new(0., 0., 0.));
+            let (red, green, blue) = self.config.display_gamma.encode(color);
+            let color = [to_u8(red), to_u8(green), to_u8(blue), 255];
+
+            let vertices = led_vertices(position, self.config.led_radius);
+            for face in LED_FACES {
+                let triangle = face.map(|index| vertices[index]);
+                draw_triangle(&mut image, &mut depths, view_proj, triangle, color);
+            }
+        }
+
+        image
+    }
+}
+
+/// Draws a flat triangle into an image, with a depth test as in the simulator window.
+fn draw_triangle(
+    image: &mut Image,
+    depths: &mut [f32],
+    view_proj: Mat4,
+    triangle: [Vec3; 3],
+    color: [u8; 4],
+) {
+    let (width, height) = (image.width as f32, image.height as f32);
+
+    // Project each corner to pixel coordinates, with its depth
+    let mut corners = [Vec3::ZERO; 3];
+    for (corner, vertex) in corners.iter_mut().zip(triangle) {
+        let clip = view_proj * vertex.extend(1.0);
+        if clip.w <= 0.0 {
+            return;
+        }
+        let ndc = clip.xyz() / clip.w;
+        *corner = vec3(
+            (ndc.x + 1.0) * 0.5 * width,
+            (1.0 - ndc.y) * 0.5 * height,
+            ndc.z,
+        );
+    }
+    let [a, b, c] = corners;
+
+    let area = edge(a, b, c);
+    if area == 0.0 {
+        return;
+    }
+
+    let min_x = a.x.min(b.x).min(c.x).floor().max(0.0) as u32;
+    let max_x = a.x.max(b.x).max(c.x).ceil().min(width) as u32;
+    let min_y = a.y.min(b.y).min(c.y).floor().max(0.0) as u32;
+    let max_y = a.y.max(b.y).max(c.y).ceil().min(height) as u32;
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            // Sample at the center of each pixel, for either winding
+            let point = vec3(x as f32 + 0.5, y as f32 + 0.5, 0.0);
+            let weights = [edge(b, c, point), edge(c, a, point), edge(a, b, point)]
+                .map(|weight| weight / area);
+            if weights.iter().any(|&weight| weight < 0.0) {
+                continue;
+            }
+
+            let depth = weights[0] * a.z + weights[1] * b.z + weights[2] * c.z;
+            let index = (y * image.width + x) as usize;
+            if !(-1.0..=1.0).contains(&depth) || depth > depths[index] {
+                continue;
+            }
+            depths[index] = depth;
+            image.set_pixel(x, y, color);
+        }
+    }
+}
+
+/// Returns twice the signed area of a triangle, in 2D.
+fn edge(a: Vec3, b: Vec3, point: Vec3) -> f32 {
+    (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x)
+}
+
+/// Converts a color component from 0.0 - 1.0 to 0 - 255.
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// An image rendered by a [`Snapshot`], in 8-bit RGBA.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Image {
+    fn new(width: u32, height: u32, background: [u8; 4]) -> Self {
+        Self {
+            width,
+            height,
+            data: background.repeat((width * height) as usize),
+        }
+    }
+
+    /// Creates an image from RGBA data, e.g. a frame captured from a camera.
+    ///
+    /// # Parameters
+    ///
+    /// - `width` - The width in pixels
+    /// - `height` - The height in pixels
+    /// - `data` - The RGBA data of all pixels, row by row from the top left
+    ///
+    /// # Panics
+    ///
+    /// If the data is not 4 bytes for each pixel
+    pub fn from_rgba(width: u32, height: u32, data: Vec<u8>) -> Self {
+        assert_eq!(
+            data.len(),
+            (width * height * 4) as usize,
+            "RGBA data doesn't match the image size"
+        );
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Returns the width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the RGBA color of a pixel, from the top left.
+    ///
+    /// # Panics
+    ///
+    /// If the pixel is outside the image
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let index = ((y * self.width + x) * 4) as usize;
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.data[index..index + 4]);
+        pixel
+    }
+
+    /// Returns the RGBA data of all pixels, row by row from the top left.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let index = ((y * self.width + x) * 4) as usize;
+        self.data[index..index + 4].copy_from_slice(&color);
+    }
+
+    /// Encodes the image as a PNG.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer` - Where to write the PNG
+    pub fn write_png<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_source_srgb(png::SrgbRenderingIntent::Perceptual);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.data)
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+
+    /// Saves the image as a PNG file.
+    ///
+    /// # Parameters
+    ///
+    /// - `path` - The path of the file
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        self.write_png(file)
+    }
+}
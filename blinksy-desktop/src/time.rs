@@ -20,9 +20,13 @@
 //! ```
 
 use std::sync::OnceLock;
-use std::time::Instant;
 
-static START_TIME: OnceLock<Instant> = OnceLock::new();
+#[cfg(not(target_arch = "wasm32"))]
+static START_TIME: OnceLock<std::time::Instant> = OnceLock::new();
+
+// On the web, `std::time::Instant` is unsupported, so use the browser's clock.
+#[cfg(target_arch = "wasm32")]
+static START_TIME: OnceLock<f64> = OnceLock::new();
 
 /// Returns the number of milliseconds elapsed since the program started. This is useful to pass
 /// into `control.tick`.
@@ -33,7 +37,23 @@ static START_TIME: OnceLock<Instant> = OnceLock::new();
 /// # Returns
 ///
 /// The number of milliseconds since program start or first call to this function
+#[cfg(not(target_arch = "wasm32"))]
 pub fn elapsed_in_ms() -> u64 {
-    let start = START_TIME.get_or_init(Instant::now);
+    let start = START_TIME.get_or_init(std::time::Instant::now);
     start.elapsed().as_millis() as u64
 }
+
+/// Returns the number of milliseconds elapsed since the program started. This is useful to pass
+/// into `control.tick`.
+///
+/// It initializes a static timer on first call and then measures elapsed time
+/// from that point.
+///
+/// # Returns
+///
+/// The number of milliseconds since program start or first call to this function
+#[cfg(target_arch = "wasm32")]
+pub fn elapsed_in_ms() -> u64 {
+    let start = START_TIME.get_or_init(miniquad::date::now);
+    ((miniquad::date::now() - start) * 1000.) as u64
+}
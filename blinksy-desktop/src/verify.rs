@@ -0,0 +1,231 @@
+//! # Simulator Verification
+//!
+//! What you preview in the simulator should be what your LEDs show. [`compare`] runs the same
+//! colors through the simulator's output and through a chipset's encoding, decodes the
+//! encoded frame back into the light each LED emits, and reports the difference of each pixel.
+//!
+//! This catches discrepancies such as:
+//!
+//! - Channel order: decode with the channel order of your physical LEDs, to check the
+//!   [`ClocklessLed::LED_CHANNELS`] of your chipset.
+//! - Gamma: both sides are compared in linear light, as LEDs emit it.
+//! - White extraction: RGBW LEDs mix their white channel with the RGB channels.
+//! - Quantization: the encoded words have a limited [color depth].
+//!
+//! Only [clockless](blinksy::driver::clockless) chipsets are supported.
+//!
+//! The decoding assumes each channel emits linear light at full scale, and a white channel
+//! emits the same light as full red, green, and blue together.
+//!
+//! [`compare`]: crate::verify::compare
+//! [`ClocklessLed::LED_CHANNELS`]: blinksy::driver::ClocklessLed::LED_CHANNELS
+//! [color depth]: blinksy::driver::ClocklessLed::COLOR_CAPABILITY
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::{ColorCorrection, LedChannels, RgbChannels},
+//!     driver::ClocklessLed,
+//!     layout1d,
+//!     leds::Ws2812,
+//!     markers::Dim1d,
+//!     pattern::Pattern,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//! };
+//! use blinksy_desktop::verify::compare;
+//!
+//! layout1d!(StripLayout, 30);
+//!
+//! let pattern = <Rainbow as Pattern<Dim1d, StripLayout>>::new(RainbowParams::default());
+//! let pixels = || <Rainbow as Pattern<Dim1d, StripLayout>>::tick(&pattern, 1000);
+//!
+//! // The same LEDs, only rounded to 8 bits
+//! let comparison = compare::<Ws2812, _, _>(
+//!     pixels(),
+//!     0.5,
+//!     ColorCorrection::default(),
+//!     &Ws2812::LED_CHANNELS,
+//! );
+//! assert!(comparison.is_match());
+//!
+//! // The physical LEDs are RGB, not GRB: red and green are swapped
+//! let comparison = compare::<Ws2812, _, _>(
+//!     pixels(),
+//!     0.5,
+//!     ColorCorrection::default(),
+//!     &LedChannels::Rgb(RgbChannels::RGB),
+//! );
+//! assert!(!comparison.is_match());
+//! println!("{comparison}");
+//! ```
+
+use core::fmt;
+use std::iter::once;
+
+use blinksy::{
+    color::{apply_output_pipeline, ColorCorrection, FromColor, LedChannels, LinearSrgb},
+    driver::ClocklessLed,
+    util::component::Component,
+};
+
+/// The difference between the simulator and the hardware, for one pixel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PixelDelta {
+    /// The index of the pixel
+    pub index: usize,
+    /// The light shown by the simulator, before it is encoded for display
+    pub simulated: LinearSrgb,
+    /// The light emitted by the LED, decoded from the encoded frame
+    pub hardware: LinearSrgb,
+    /// The largest difference of any channel, from 0.0 to 1.0
+    pub delta: f32,
+}
+
+/// The differences between the simulator and the hardware, for a frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    /// The difference of each pixel
+    pub pixels: Vec<PixelDelta>,
+    /// The largest difference expected from quantization alone
+    pub tolerance: f32,
+}
+
+impl Comparison {
+    /// Returns the pixel with the largest difference, if any pixels.
+    pub fn max_delta(&self) -> Option<&PixelDelta> {
+        self.pixels
+            .iter()
+            .max_by(|a, b| a.delta.total_cmp(&b.delta))
+    }
+
+    /// Returns the average difference over all pixels.
+    pub fn mean_delta(&self) -> f32 {
+        if self.pixels.is_empty() {
+            return 0.;
+        }
+        self.pixels.iter().map(|pixel| pixel.delta).sum::<f32>() / self.pixels.len() as f32
+    }
+
+    /// Returns the pixels which differ by more than the tolerance.
+    pub fn mismatches(&self) -> impl Iterator<Item = &PixelDelta> {
+        self.pixels
+            .iter()
+            .filter(|pixel| pixel.delta > self.tolerance)
+    }
+
+    /// Returns whether every pixel is within the tolerance.
+    pub fn is_match(&self) -> bool {
+        self.mismatches().next().is_none()
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mismatches = self.mismatches().count();
+        write!(
+            f,
+            "{} of {} pixels differ by more than {:.4} (mean delta {:.4})",
+            mismatches,
+            self.pixels.len(),
+            self.tolerance,
+            self.mean_delta(),
+        )?;
+        if let Some(pixel) = self.max_delta().filter(|_| mismatches > 0) {
+            let LinearSrgb { red, green, blue } = pixel.simulated;
+            write!(
+                f,
+                "\nlargest at pixel {}: simulated ({red:.3}, {green:.3}, {blue:.3})",
+                pixel.index,
+            )?;
+            let LinearSrgb { red, green, blue } = pixel.hardware;
+            write!(
+                f,
+                ", hardware ({red:.3}, {green:.3}, {blue:.3}), delta {:.4}",
+                pixel.delta,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the simulator with a chipset, for a frame of colors.
+///
+/// # Type Parameters
+///
+/// - `Led` - The chipset to encode with
+///
+/// # Arguments
+///
+/// - `pixels` - Iterator of colors for each pixel, e.g. from a pattern's tick
+/// - `brightness` - Global brightness scaling factor (0.0 to 1.0)
+/// - `correction` - Color correction factors
+/// - `hardware_channels` - The channels of the physical LEDs, usually `&Led::LED_CHANNELS`
+///
+/// # Returns
+///
+/// The difference of each pixel
+pub fn compare<Led, I, C>(
+    pixels: I,
+    brightness: f32,
+    correction: ColorCorrection,
+    hardware_channels: &LedChannels,
+) -> Comparison
+where
+    Led: ClocklessLed,
+    I: IntoIterator<Item = C>,
+    LinearSrgb: FromColor<C>,
+{
+    let pixels = pixels
+        .into_iter()
+        .map(LinearSrgb::from_color)
+        .enumerate()
+        .map(|(index, color)| {
+            let simulated = apply_output_pipeline(color, brightness, correction);
+            let words = Led::encode::<1, 4, _, LinearSrgb>(once(color), brightness, correction);
+            let hardware = decode(&words, hardware_channels);
+            let delta = (simulated.red - hardware.red)
+                .abs()
+                .max((simulated.green - hardware.green).abs())
+                .max((simulated.blue - hardware.blue).abs());
+            PixelDelta {
+                index,
+                simulated,
+                hardware,
+                delta,
+            }
+        })
+        .collect();
+
+    // Rounding to the nearest level is off by up to half a level, in each channel the light
+    // is mixed from (up to the RGB and white channels)
+    let levels = Led::COLOR_CAPABILITY.levels() as f32;
+    let tolerance = 1. / (levels - 1.) + f32::EPSILON;
+
+    Comparison { pixels, tolerance }
+}
+
+/// Decodes the encoded words of one pixel into the light it emits.
+fn decode<Word: Component>(words: &[Word], channels: &LedChannels) -> LinearSrgb {
+    match channels {
+        LedChannels::Rgb(rgb_channels) => {
+            let [red, green, blue] = unorder(rgb_channels.reorder([0, 1, 2]), words);
+            LinearSrgb::new(red, green, blue)
+        }
+        LedChannels::Rgbw(rgbw_channels) => {
+            let [red, green, blue, white] = unorder(rgbw_channels.reorder([0, 1, 2, 3]), words);
+            LinearSrgb::new(red + white, green + white, blue + white)
+        }
+    }
+}
+
+/// Puts the words of each channel back in canonical order, as normalized values.
+///
+/// The order has, for each word, the canonical index of its channel.
+fn unorder<Word: Component, const N: usize>(order: [usize; N], words: &[Word]) -> [f32; N] {
+    let mut values = [0.; N];
+    for (word, index) in words.iter().zip(order) {
+        values[index] = word.to_normalized_f32();
+    }
+    values
+}
@@ -0,0 +1,135 @@
+#![no_std]
+
+//! # Blinksy Pattern API
+//!
+//! The parts of [Blinksy](https://github.com/ahdinosaur/blinksy) needed to write a pattern
+//! outside of the `blinksy` crate.
+//!
+//! A pattern only needs a small part of `blinksy`: the [`Pattern`](pattern::Pattern) trait,
+//! the layout traits, and the color types. This crate re-exports just that part, as a
+//! convenience, so a pattern crate's imports show what it depends on.
+//!
+//! The items are re-exported (not copied), so a pattern written against this crate is a
+//! pattern for the application's `blinksy`, as long as both use the same version of `blinksy`.
+//! This crate isn't a stable interface: a breaking change in `blinksy` to any item here is a
+//! breaking change of this crate too.
+//!
+//! ## Writing a pattern crate
+//!
+//! Start from the [template crate], or:
+//!
+//! 1. Create a `no_std` library, depending on `blinksy-pattern-api` (with the `derive`
+//!    feature, to derive your parameters).
+//! 2. Define your parameters, deriving [`PatternParams`](pattern::PatternParams) with
+//!    `#[pattern_params(crate = "blinksy_pattern_api")]`, so user interfaces can list and
+//!    change them.
+//! 3. Implement [`Pattern`](pattern::Pattern) for each dimension you support, with a color
+//!    type from [`color`].
+//! 4. Only import from this crate. If you need something from `blinksy` which isn't here,
+//!    [make an issue](https://github.com/ahdinosaur/blinksy/issues).
+//!
+//! ```rust
+//! use blinksy_pattern_api::prelude::*;
+//!
+//! #[derive(Debug, PatternParams)]
+//! #[pattern_params(crate = "blinksy_pattern_api")]
+//! pub struct SolidParams {
+//!     /// Hue, from 0.0 to 1.0
+//!     #[param(min = 0., max = 1.)]
+//!     pub hue: f32,
+//! }
+//!
+//! pub struct Solid {
+//!     params: SolidParams,
+//! }
+//!
+//! impl<Layout> Pattern<Dim1d, Layout> for Solid
+//! where
+//!     Layout: Layout1d,
+//! {
+//!     type Params = SolidParams;
+//!     type Color = Okhsv;
+//!
+//!     fn new(params: Self::Params) -> Self {
+//!         Self { params }
+//!     }
+//!
+//!     fn params(&self) -> Option<&Self::Params> {
+//!         Some(&self.params)
+//!     }
+//!
+//!     fn params_mut(&mut self) -> Option<&mut Self::Params> {
+//!         Some(&mut self.params)
+//!     }
+//!
+//!     fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+//!         let color = Okhsv::new(self.params.hue, 1., 1.);
+//!         Layout::points().map(move |_| color)
+//!     }
+//! }
+//! ```
+//!
+//! Then, in an application:
+//!
+//! ```rust,ignore
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Solid>(SolidParams::default())
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+//!
+//! ## Features
+//!
+//! - `derive`: `#[derive(PatternParams)]`
+//! - `serde`: Serialize and deserialize parameters and colors
+//!
+//! [template crate]: https://github.com/ahdinosaur/blinksy/tree/main/blinksy-pattern-api/template
+
+pub mod color {
+    //! Color types for pattern output.
+    //!
+    //! A pattern may output any color which converts into [`LinearSrgb`].
+
+    pub use blinksy::color::{
+        FromColor, GammaSrgb, Hsv, HsvHue, HsvHueMap, HsvHueRainbow, HsvHueSpectrum, IntoColor,
+        LinearSrgb, Okhsl, Okhsv, Oklab, Srgb,
+    };
+}
+
+pub mod layout {
+    //! Layout traits, for the positions of the LEDs.
+    //!
+    //! The layout macros are here too, to test patterns.
+
+    pub use blinksy::layout::{
+        Layout1d, Layout2d, Layout3d, LayoutForDim, Shape2d, Shape3d, Vec2, Vec3,
+    };
+    pub use blinksy::{layout1d, layout2d, layout3d};
+}
+
+pub mod markers {
+    //! Dimension markers, to implement a pattern for each dimension.
+
+    pub use blinksy::markers::{Dim1d, Dim2d, Dim3d};
+}
+
+pub mod pattern {
+    //! The pattern trait, and reflection of pattern parameters.
+
+    pub use blinksy::driver::ColorCapability;
+    pub use blinksy::pattern::{ParamError, ParamInfo, Pattern, PatternParams};
+}
+
+pub mod prelude {
+    //! Everything needed to write a pattern.
+
+    pub use crate::color::*;
+    pub use crate::layout::{Layout1d, Layout2d, Layout3d, Vec2, Vec3};
+    pub use crate::markers::*;
+    pub use crate::pattern::*;
+}
+
+#[doc(hidden)]
+pub use blinksy::{__pattern_params_serde, __private};
@@ -0,0 +1,151 @@
+#![no_std]
+
+//! # Pulse Pattern
+//!
+//! A template for a third-party Blinksy pattern crate: copy this crate, rename it, and replace
+//! the pattern with your own.
+//!
+//! The pulse pattern sends rings of light outwards from the center of the layout.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{layout::Layout2d, layout2d, ControlBuilder};
+//! use blinksy_pattern_template::{Pulse, PulseParams};
+//!
+//! let control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Pulse>(PulseParams::default())
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use blinksy_pattern_api::prelude::*;
+#[allow(unused_imports)]
+use num_traits::Float;
+
+/// Configuration parameters for the Pulse pattern.
+#[derive(Debug, PatternParams)]
+#[pattern_params(crate = "blinksy_pattern_api")]
+pub struct PulseParams {
+    /// Pulses per minute
+    #[param(min = 1., max = 240., default = 60.)]
+    pub bpm: f32,
+    /// Hue of the pulses, from 0.0 to 1.0
+    #[param(min = 0., max = 1., default = 0.6)]
+    pub hue: f32,
+    /// Rings across the layout, from the center to the edge
+    #[param(min = 0., max = 8., default = 1.)]
+    pub rings: f32,
+}
+
+/// Pulse pattern implementation.
+#[derive(Debug)]
+pub struct Pulse {
+    /// Configuration parameters
+    params: PulseParams,
+}
+
+impl Pulse {
+    /// Returns the color at a distance from the center of the layout.
+    fn color(&self, distance: f32, time_in_ms: u64) -> Okhsv {
+        let beats = time_in_ms as f32 * self.params.bpm / 60_000.;
+        let phase = (beats - distance * self.params.rings) * core::f32::consts::TAU;
+        let value = 0.5 + 0.5 * phase.cos();
+        Okhsv::new(self.params.hue, 1., value * value)
+    }
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for Pulse
+where
+    Layout: Layout1d,
+{
+    type Params = PulseParams;
+    type Color = Okhsv;
+
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Layout::points().map(move |x| self.color(x.abs(), time_in_ms))
+    }
+}
+
+impl<Layout> Pattern<Dim2d, Layout> for Pulse
+where
+    Layout: Layout2d,
+{
+    type Params = PulseParams;
+    type Color = Okhsv;
+
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Layout::points().map(move |point| self.color(point.length(), time_in_ms))
+    }
+}
+
+impl<Layout> Pattern<Dim3d, Layout> for Pulse
+where
+    Layout: Layout3d,
+{
+    type Params = PulseParams;
+    type Color = Okhsv;
+
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Layout::points().map(move |point| self.color(point.length(), time_in_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blinksy_pattern_api::layout::layout1d;
+
+    use super::*;
+
+    layout1d!(Layout, 5);
+
+    #[test]
+    fn rings_start_from_the_center() {
+        let pulse = <Pulse as Pattern<Dim1d, Layout>>::new(PulseParams::default());
+        let mut colors = Pattern::<Dim1d, Layout>::tick(&pulse, 0);
+
+        // The points are at -1.0, -0.5, 0.0, 0.5, and 1.0
+        let between = colors.nth(1).unwrap();
+        let center = colors.next().unwrap();
+        assert!(between.v < 1e-3);
+        assert_eq!(center.v, 1.);
+    }
+}
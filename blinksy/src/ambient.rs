@@ -0,0 +1,258 @@
+//! # Ambient Light
+//!
+//! [`AutoBrightness`] scales the global brightness with the ambient light, read from an
+//! [`AmbientLightSensor`]: dim in a dark room, bright in daylight. Optionally, it also turns
+//! the LEDs off (sleeps) when the room is dark, and back on (wakes) when the lights come on.
+//!
+//! The sensor is anything implementing [`AmbientLightSensor`], including any closure returning
+//! a light level from 0.0 (dark) to 1.0 (bright). (On ESP32 chips, `blinksy-esp` provides one
+//! for a light-dependent resistor on an ADC pin.)
+//!
+//! To not flicker at the edges, the brightness only follows the light once it has changed by
+//! more than the hysteresis.
+//!
+//! Like [`DeviceControls`](crate::input::DeviceControls), it doesn't change the control system
+//! itself, but returns a [`ControlEvent`] to apply.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use core::cell::Cell;
+//! use blinksy::{
+//!     ambient::{AutoBrightness, AutoBrightnessLimits},
+//!     input::ControlEvent,
+//! };
+//!
+//! // Read your light sensor, from 0.0 (dark) to 1.0 (bright).
+//! let light_level = Cell::new(1.);
+//! let mut auto_brightness = AutoBrightness::new(
+//!     || light_level.get(),
+//!     AutoBrightnessLimits {
+//!         sleep_below: Some(0.05),
+//!         ..Default::default()
+//!     },
+//! );
+//!
+//! assert_eq!(
+//!     auto_brightness.update(),
+//!     Some(ControlEvent::BrightnessChanged(1.))
+//! );
+//! assert_eq!(auto_brightness.update(), None);
+//!
+//! // The room goes dark
+//! light_level.set(0.);
+//! assert_eq!(
+//!     auto_brightness.update(),
+//!     Some(ControlEvent::PowerChanged(false))
+//! );
+//!
+//! // The room lights come on
+//! light_level.set(0.5);
+//! assert_eq!(
+//!     auto_brightness.update(),
+//!     Some(ControlEvent::PowerChanged(true))
+//! );
+//! assert!(matches!(
+//!     auto_brightness.update(),
+//!     Some(ControlEvent::BrightnessChanged(brightness)) if brightness < 1.
+//! ));
+//! ```
+//!
+//! Then, in your control loop:
+//!
+//! ```rust,ignore
+//! match auto_brightness.update() {
+//!     Some(ControlEvent::BrightnessChanged(brightness)) => control.set_brightness(brightness),
+//!     Some(ControlEvent::PowerChanged(is_on)) => control.set_on(is_on),
+//!     _ => {}
+//! }
+//! ```
+
+use crate::input::ControlEvent;
+
+/// An ambient light sensor, read on each [`AutoBrightness::update`].
+pub trait AmbientLightSensor {
+    /// Returns the current light level, from 0.0 (dark) to 1.0 (bright).
+    fn light_level(&mut self) -> f32;
+}
+
+impl<F> AmbientLightSensor for F
+where
+    F: FnMut() -> f32,
+{
+    fn light_level(&mut self) -> f32 {
+        self()
+    }
+}
+
+/// How the brightness follows the ambient light level.
+///
+/// From `dark_level` up to `bright_level`, brightness is scaled linearly from `min_brightness`
+/// up to `max_brightness`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AutoBrightnessLimits {
+    /// Light level at or below which brightness is at its minimum (default: 0.1)
+    pub dark_level: f32,
+    /// Light level at or above which brightness is at its maximum (default: 0.9)
+    pub bright_level: f32,
+    /// Brightness in the dark, from 0.0 to 1.0 (default: 0.1)
+    pub min_brightness: f32,
+    /// Brightness in bright light, from 0.0 to 1.0 (default: 1.0)
+    pub max_brightness: f32,
+    /// How much the light level must change before the brightness follows (default: 0.05)
+    pub hysteresis: f32,
+    /// Light level below which the LEDs sleep, if any (default: never sleep)
+    pub sleep_below: Option<f32>,
+}
+
+impl Default for AutoBrightnessLimits {
+    fn default() -> Self {
+        Self {
+            dark_level: 0.1,
+            bright_level: 0.9,
+            min_brightness: 0.1,
+            max_brightness: 1.,
+            hysteresis: 0.05,
+            sleep_below: None,
+        }
+    }
+}
+
+impl AutoBrightnessLimits {
+    /// Returns the brightness for a light level.
+    ///
+    /// # Arguments
+    ///
+    /// - `light_level` - The current light level
+    ///
+    /// # Returns
+    ///
+    /// A brightness from `min_brightness` to `max_brightness`
+    pub fn brightness(&self, light_level: f32) -> f32 {
+        let range = self.bright_level - self.dark_level;
+        let progress = if range > 0. {
+            ((light_level - self.dark_level) / range).clamp(0., 1.)
+        } else if light_level >= self.bright_level {
+            1.
+        } else {
+            0.
+        };
+        self.min_brightness + progress * (self.max_brightness - self.min_brightness)
+    }
+}
+
+/// Automatic brightness from an ambient light sensor.
+///
+/// # Type Parameters
+///
+/// - `Sensor` - The ambient light sensor
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AutoBrightness<Sensor> {
+    sensor: Sensor,
+    limits: AutoBrightnessLimits,
+    /// The light level the brightness was last set from
+    light_level: Option<f32>,
+    brightness: f32,
+    is_awake: bool,
+    is_brightness_changed: bool,
+}
+
+impl<Sensor> AutoBrightness<Sensor>
+where
+    Sensor: AmbientLightSensor,
+{
+    /// Creates automatic brightness from a sensor.
+    ///
+    /// # Arguments
+    ///
+    /// - `sensor` - The ambient light sensor
+    /// - `limits` - How the brightness follows the light level
+    pub fn new(sensor: Sensor, limits: AutoBrightnessLimits) -> Self {
+        Self {
+            sensor,
+            limits,
+            light_level: None,
+            brightness: limits.max_brightness,
+            is_awake: true,
+            is_brightness_changed: false,
+        }
+    }
+
+    /// Reads the sensor, and returns any change to apply to the control system.
+    ///
+    /// After waking, the brightness is returned on the next update.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(ControlEvent::PowerChanged(_))` when the LEDs should sleep or wake
+    /// - `Some(ControlEvent::BrightnessChanged(_))` when the brightness should change
+    /// - `None` otherwise
+    pub fn update(&mut self) -> Option<ControlEvent> {
+        let light_level = self.sensor.light_level();
+        let AutoBrightnessLimits {
+            hysteresis,
+            sleep_below,
+            ..
+        } = self.limits;
+
+        if let Some(sleep_below) = sleep_below {
+            if self.is_awake && light_level < sleep_below {
+                self.is_awake = false;
+                return Some(ControlEvent::PowerChanged(false));
+            }
+            if !self.is_awake && light_level >= sleep_below + hysteresis {
+                self.is_awake = true;
+                self.set_light_level(light_level);
+                self.is_brightness_changed = true;
+                return Some(ControlEvent::PowerChanged(true));
+            }
+        }
+        if !self.is_awake {
+            return None;
+        }
+
+        let is_changed = match self.light_level {
+            Some(previous) => (light_level - previous).abs() > hysteresis,
+            None => true,
+        };
+        if is_changed {
+            self.set_light_level(light_level);
+            self.is_brightness_changed = true;
+        }
+        if core::mem::take(&mut self.is_brightness_changed) {
+            return Some(ControlEvent::BrightnessChanged(self.brightness));
+        }
+        None
+    }
+
+    fn set_light_level(&mut self, light_level: f32) {
+        self.light_level = Some(light_level);
+        self.brightness = self.limits.brightness(light_level);
+    }
+
+    /// Returns the brightness for the last light level.
+    pub fn brightness(&self) -> f32 {
+        self.brightness
+    }
+
+    /// Returns whether the LEDs are awake, i.e. not asleep because of darkness.
+    pub fn is_awake(&self) -> bool {
+        self.is_awake
+    }
+
+    /// Sets how the brightness follows the light level.
+    ///
+    /// The new brightness is returned on the next update.
+    pub fn set_limits(&mut self, limits: AutoBrightnessLimits) {
+        self.limits = limits;
+        self.light_level = None;
+    }
+
+    /// Unwraps the sensor.
+    pub fn into_inner(self) -> Sensor {
+        self.sensor
+    }
+}
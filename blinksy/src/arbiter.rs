@@ -0,0 +1,184 @@
+//! # Source Arbiter
+//!
+//! A device may show one of several sources: a realtime stream from the network (e.g. from a
+//! PC running an ambilight or music visualizer), a scheduled pattern (e.g. from a timer), or
+//! its default pattern. [`SourceArbiter`] picks the source with the highest priority:
+//!
+//! 1. [`Source::Realtime`], while frames arrive, and until a timeout after the last frame
+//! 2. [`Source::Scheduled`], while a schedule is running
+//! 3. [`Source::Default`], otherwise
+//!
+//! So when the streaming PC goes away, the device falls back to a local pattern, instead of
+//! freezing on the last frame.
+//!
+//! The arbiter doesn't show anything itself: show the pixels of the realtime stream with
+//! [`Control::show_pixels`](crate::control::Control::show_pixels), and the patterns with
+//! [`Control::tick`](crate::control::Control::tick).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::arbiter::{Source, SourceArbiter};
+//!
+//! let mut arbiter = SourceArbiter::new();
+//! assert_eq!(arbiter.update(0), None);
+//! assert_eq!(arbiter.source(), Source::Default);
+//!
+//! // A schedule starts, for a minute
+//! arbiter.start_scheduled(0, Some(60_000));
+//! assert_eq!(arbiter.update(0), Some(Source::Scheduled));
+//!
+//! // A realtime stream starts, then stops
+//! arbiter.realtime_frame(1000);
+//! assert_eq!(arbiter.update(1000), Some(Source::Realtime));
+//! assert_eq!(arbiter.update(3000), None);
+//! assert_eq!(arbiter.update(4000), Some(Source::Scheduled));
+//!
+//! // The schedule ends
+//! assert_eq!(arbiter.update(60_000), Some(Source::Default));
+//! ```
+//!
+//! Then, in your control loop:
+//!
+//! ```rust,ignore
+//! if let Some(frame) = receiver.poll() {
+//!     arbiter.realtime_frame(time_in_ms);
+//!     pixels = frame;
+//! }
+//! if let Some(source) = arbiter.update(time_in_ms) {
+//!     // Change the pattern for the new source, e.g. the scheduled pattern
+//! }
+//! match arbiter.source() {
+//!     Source::Realtime => control.show_pixels(pixels.iter().copied(), time_in_ms)?,
+//!     Source::Scheduled | Source::Default => control.tick(time_in_ms)?,
+//! }
+//! ```
+
+/// The default time after the last realtime frame until falling back, in milliseconds.
+pub const DEFAULT_REALTIME_TIMEOUT_IN_MS: u64 = 2500;
+
+/// A source of what the LEDs show, from the highest priority to the lowest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Source {
+    /// A realtime stream of frames, e.g. from the network
+    Realtime,
+    /// A scheduled pattern
+    Scheduled,
+    /// The default pattern
+    Default,
+}
+
+/// Picks between the realtime, scheduled, and default sources, with timeouts.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SourceArbiter {
+    realtime_timeout_in_ms: u64,
+    last_realtime_in_ms: Option<u64>,
+    is_scheduled: bool,
+    scheduled_until_in_ms: Option<u64>,
+    source: Source,
+}
+
+impl Default for SourceArbiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SourceArbiter {
+    /// Creates an arbiter on the default source, with the default realtime timeout.
+    pub fn new() -> Self {
+        Self {
+            realtime_timeout_in_ms: DEFAULT_REALTIME_TIMEOUT_IN_MS,
+            last_realtime_in_ms: None,
+            is_scheduled: false,
+            scheduled_until_in_ms: None,
+            source: Source::Default,
+        }
+    }
+
+    /// Sets the time after the last realtime frame until falling back.
+    ///
+    /// # Arguments
+    ///
+    /// - `timeout_in_ms` - Timeout in milliseconds (default: [`DEFAULT_REALTIME_TIMEOUT_IN_MS`])
+    pub fn with_realtime_timeout(mut self, timeout_in_ms: u64) -> Self {
+        self.realtime_timeout_in_ms = timeout_in_ms;
+        self
+    }
+
+    /// Records that a realtime frame arrived.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The current time in milliseconds
+    pub fn realtime_frame(&mut self, time_in_ms: u64) {
+        self.last_realtime_in_ms = Some(time_in_ms);
+    }
+
+    /// Stops the realtime source now, e.g. when the stream says it's done, without waiting for
+    /// the timeout.
+    pub fn end_realtime(&mut self) {
+        self.last_realtime_in_ms = None;
+    }
+
+    /// Starts the scheduled source.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The current time in milliseconds
+    /// - `duration_in_ms` - How long the schedule runs, or `None` until
+    ///   [`end_scheduled`](Self::end_scheduled)
+    pub fn start_scheduled(&mut self, time_in_ms: u64, duration_in_ms: Option<u64>) {
+        self.is_scheduled = true;
+        self.scheduled_until_in_ms = duration_in_ms.map(|duration| time_in_ms + duration);
+    }
+
+    /// Ends the scheduled source.
+    pub fn end_scheduled(&mut self) {
+        self.is_scheduled = false;
+        self.scheduled_until_in_ms = None;
+    }
+
+    /// Picks the source for the current time.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The current time in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// - `Some(source)` when the source changed
+    /// - `None` otherwise
+    pub fn update(&mut self, time_in_ms: u64) -> Option<Source> {
+        if self
+            .scheduled_until_in_ms
+            .is_some_and(|until_in_ms| time_in_ms >= until_in_ms)
+        {
+            self.end_scheduled();
+        }
+        let is_realtime = self.last_realtime_in_ms.is_some_and(|last_in_ms| {
+            time_in_ms.saturating_sub(last_in_ms) < self.realtime_timeout_in_ms
+        });
+
+        let source = if is_realtime {
+            Source::Realtime
+        } else if self.is_scheduled {
+            Source::Scheduled
+        } else {
+            Source::Default
+        };
+        if source == self.source {
+            return None;
+        }
+        self.source = source;
+        Some(source)
+    }
+
+    /// Returns the source picked on the last update.
+    pub fn source(&self) -> Source {
+        self.source
+    }
+}
@@ -0,0 +1,203 @@
+//! # Audio Beat Detection
+//!
+//! [`BeatDetector`] finds the beats in music, for audio-reactive effects which pulse with the
+//! music rather than just follow its amplitude.
+//!
+//! It doesn't read a microphone itself: feed it the energy of each block of audio samples,
+//! e.g. the RMS level of the samples, or the magnitude of the bass bins of an FFT (where the
+//! kick drum is). An onset is a sudden rise of the energy above its recent average. From the
+//! time between onsets, the detector estimates the tempo, with a confidence of how steady it is.
+//!
+//! Each [`Beat`] is then recorded into a [`BpmClock`] (usually the global [`BPM`]), so
+//! patterns follow the music with the [beat oscillators](crate::util::beat), and pulse on each
+//! beat with [`BpmClock::since_beat_in_ms`].
+//!
+//! [`BPM`]: crate::util::beat::BPM
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{audio::BeatDetector, util::beat::BPM};
+//!
+//! let mut detector = BeatDetector::new();
+//!
+//! // A kick drum at 120 BPM, every 500ms, in blocks of 10ms of audio
+//! for time_in_ms in (0..10_000).step_by(10) {
+//!     let energy = if time_in_ms % 500 == 0 { 1. } else { 0.1 };
+//!     if let Some(beat) = detector.update(energy, time_in_ms) {
+//!         beat.record(&BPM);
+//!     }
+//! }
+//!
+//! assert!((BPM.bpm() - 120.).abs() < 1.);
+//! assert!(BPM.confidence() > 0.9);
+//! ```
+
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::util::beat::BpmClock;
+
+/// The default sensitivity of a [`BeatDetector`]: how many standard deviations above the
+/// average energy is an onset.
+pub const DEFAULT_SENSITIVITY: f32 = 2.;
+
+/// The default shortest time between beats, in milliseconds (240 BPM).
+pub const DEFAULT_MIN_INTERVAL_IN_MS: u64 = 250;
+
+/// The longest time between beats to estimate a tempo from, in milliseconds (30 BPM).
+const MAX_INTERVAL_IN_MS: u64 = 2000;
+
+/// How much each energy sample moves its average and variance.
+const ENERGY_WEIGHT: f32 = 0.05;
+
+/// How much each interval between beats moves the tempo and its confidence.
+const TEMPO_WEIGHT: f32 = 0.2;
+
+/// A detected beat.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Beat {
+    /// The time of the beat in milliseconds
+    pub time_in_ms: u64,
+    /// How far the energy rose above its average, in standard deviations
+    pub strength: f32,
+    /// The estimated tempo in beats per minute, if enough beats were detected
+    pub bpm: Option<f32>,
+    /// How steady the tempo is, from 0.0 to 1.0
+    pub confidence: f32,
+}
+
+impl Beat {
+    /// Records the beat into a clock: syncs its phase, and sets its tempo and confidence.
+    ///
+    /// # Arguments
+    ///
+    /// - `clock` - The clock, usually the global [`BPM`](crate::util::beat::BPM)
+    pub fn record(&self, clock: &BpmClock) {
+        if let Some(bpm) = self.bpm {
+            clock.set_bpm(bpm);
+        }
+        clock.record_beat(self.time_in_ms, self.confidence);
+    }
+}
+
+/// Detects beats from the energy of audio.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BeatDetector {
+    sensitivity: f32,
+    min_interval_in_ms: u64,
+    average: f32,
+    variance: f32,
+    last_beat_in_ms: Option<u64>,
+    interval_in_ms: Option<f32>,
+    confidence: f32,
+}
+
+impl Default for BeatDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BeatDetector {
+    /// Creates a detector with the default sensitivity and shortest time between beats.
+    pub fn new() -> Self {
+        Self {
+            sensitivity: DEFAULT_SENSITIVITY,
+            min_interval_in_ms: DEFAULT_MIN_INTERVAL_IN_MS,
+            average: 0.,
+            variance: 0.,
+            last_beat_in_ms: None,
+            interval_in_ms: None,
+            confidence: 0.,
+        }
+    }
+
+    /// Sets the sensitivity: how many standard deviations above the average energy is an
+    /// onset. Lower values detect more beats.
+    ///
+    /// # Arguments
+    ///
+    /// - `sensitivity` - The sensitivity, defaults to [`DEFAULT_SENSITIVITY`]
+    pub fn with_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    /// Sets the shortest time between beats, so one beat isn't detected twice.
+    ///
+    /// # Arguments
+    ///
+    /// - `min_interval_in_ms` - The time in milliseconds, defaults to
+    ///   [`DEFAULT_MIN_INTERVAL_IN_MS`]
+    pub fn with_min_interval(mut self, min_interval_in_ms: u64) -> Self {
+        self.min_interval_in_ms = min_interval_in_ms;
+        self
+    }
+
+    /// Returns the estimated tempo in beats per minute, if enough beats were detected.
+    pub fn bpm(&self) -> Option<f32> {
+        self.interval_in_ms.map(|interval| 60_000. / interval)
+    }
+
+    /// Returns how steady the tempo is, from 0.0 to 1.0.
+    pub fn confidence(&self) -> f32 {
+        self.confidence
+    }
+
+    /// Updates the detector with the energy of a block of audio.
+    ///
+    /// # Arguments
+    ///
+    /// - `energy` - The energy of the block, e.g. its RMS level
+    /// - `time_in_ms` - The time of the block in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// The beat, if the block is one
+    pub fn update(&mut self, energy: f32, time_in_ms: u64) -> Option<Beat> {
+        let deviation = energy - self.average;
+        let std_dev = self.variance.sqrt();
+        let strength = if std_dev > 0. {
+            deviation / std_dev
+        } else {
+            0.
+        };
+
+        self.average += ENERGY_WEIGHT * deviation;
+        self.variance += ENERGY_WEIGHT * (deviation * deviation - self.variance);
+
+        let is_onset = strength > self.sensitivity;
+        let is_too_soon = self.last_beat_in_ms.is_some_and(|last_beat_in_ms| {
+            time_in_ms.saturating_sub(last_beat_in_ms) < self.min_interval_in_ms
+        });
+        if !is_onset || is_too_soon {
+            return None;
+        }
+
+        if let Some(last_beat_in_ms) = self.last_beat_in_ms {
+            let interval_in_ms = time_in_ms.saturating_sub(last_beat_in_ms);
+            if (1..=MAX_INTERVAL_IN_MS).contains(&interval_in_ms) {
+                self.update_tempo(interval_in_ms as f32);
+            }
+        }
+        self.last_beat_in_ms = Some(time_in_ms);
+
+        Some(Beat {
+            time_in_ms,
+            strength,
+            bpm: self.bpm(),
+            confidence: self.confidence,
+        })
+    }
+
+    /// Updates the tempo and its confidence with the time between two beats.
+    fn update_tempo(&mut self, interval_in_ms: f32) {
+        let average = *self.interval_in_ms.get_or_insert(interval_in_ms);
+        let error = ((interval_in_ms - average).abs() / average).min(1.);
+        self.confidence += TEMPO_WEIGHT * ((1. - error) - self.confidence);
+        self.interval_in_ms = Some(average + TEMPO_WEIGHT * (interval_in_ms - average));
+    }
+}
@@ -0,0 +1,478 @@
+//! # Blur
+//!
+//! A blur softens a pattern, by mixing the color of each pixel with its neighbors: the
+//! backbone of many soft, diffused looks.
+//!
+//! [`Blurred`] wraps a pattern to blur its colors, with a blur that depends on the layout:
+//!
+//! - For 1d layouts, and 2d layouts made of one [`Shape2d::Grid`], a small separable kernel
+//!   over the pixel indices, with [`blur_line`] and [`blur_grid`]. Serpentine grids are
+//!   handled.
+//! - For any other layout, each pixel is mixed with the pixels within a radius, weighted by
+//!   distance.
+//!
+//! ## Buffer size
+//!
+//! The colors are buffered, so until [the `generic_const_exprs` feature] is stable, you must
+//! explicitly provide the pixel count, as `Layout::PIXEL_COUNT`.
+//!
+//! [the `generic_const_exprs` feature]: https://doc.rust-lang.org/beta/unstable-book/language-features/generic-const-exprs.html
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     blur::{BlurParams, Blurred},
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     layout2d,
+//!     patterns::noise::{noise_fns, Noise2d, NoiseParams},
+//!     ControlBuilder,
+//! };
+//! # use blinksy::driver::NullDriver;
+//!
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 16,
+//!         vertical_pixel_count: 16,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! let params = BlurParams {
+//!     inner: NoiseParams::default(),
+//!     ..BlurParams::default()
+//! };
+//!
+//! let mut control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Blurred<Noise2d<noise_fns::Perlin>, { Layout::PIXEL_COUNT }>>(params)
+//! #   .with_driver(NullDriver)
+//! #   .with_frame_buffer_size::<0>()
+//!     // ...
+//!     .build();
+//! ```
+
+use heapless::Vec;
+
+use crate::{
+    color::{FromColor, LinearSrgb},
+    driver::ColorCapability,
+    layout::{Layout1d, Layout2d, Layout3d, LayoutForDim, Shape2d, Vec3},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern,
+};
+
+/// Blurs a line of pixels, in place.
+///
+/// Each pixel keeps `1 - amount` of its color, and takes `amount / 2` from each neighbor. At
+/// the ends, the missing neighbor is the pixel itself.
+///
+/// # Arguments
+///
+/// - `pixels` - The colors, in order along the line
+/// - `amount` - How much to blur, from 0.0 (none) to 1.0
+pub fn blur_line(pixels: &mut [LinearSrgb], amount: f32) {
+    blur_run(pixels, 0..pixels.len(), amount);
+}
+
+/// Blurs a grid of pixels, in place, with the kernel of [`blur_line`] along each row, then
+/// along each column.
+///
+/// The pixels are in rows, as from a [`Shape2d::Grid`]. Any pixels after the last full row are
+/// left as is.
+///
+/// # Arguments
+///
+/// - `pixels` - The colors, in rows
+/// - `width` - The number of pixels in each row
+/// - `serpentine` - Whether every other row is reversed
+/// - `amount` - How much to blur, from 0.0 (none) to 1.0
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{blur::blur_grid, color::LinearSrgb};
+///
+/// let black = LinearSrgb::new(0., 0., 0.);
+/// let mut pixels = [black; 9];
+/// pixels[4] = LinearSrgb::new(1., 1., 1.);
+///
+/// blur_grid(&mut pixels, 3, false, 0.5);
+///
+/// assert_eq!(pixels[4].red, 0.25);
+/// assert_eq!([pixels[1], pixels[3], pixels[5], pixels[7]].map(|color| color.red), [0.125; 4]);
+/// assert_eq!([pixels[0], pixels[2], pixels[6], pixels[8]].map(|color| color.red), [0.0625; 4]);
+/// ```
+pub fn blur_grid(pixels: &mut [LinearSrgb], width: usize, serpentine: bool, amount: f32) {
+    if width == 0 {
+        return;
+    }
+    let height = pixels.len() / width;
+    let index = |row: usize, column: usize| {
+        if serpentine && row % 2 == 1 {
+            row * width + width - 1 - column
+        } else {
+            row * width + column
+        }
+    };
+    for row in 0..height {
+        blur_run(pixels, (0..width).map(|column| index(row, column)), amount);
+    }
+    for column in 0..width {
+        blur_run(pixels, (0..height).map(|row| index(row, column)), amount);
+    }
+}
+
+/// Blurs the pixels at some indices, in order, in place.
+fn blur_run(pixels: &mut [LinearSrgb], mut indices: impl Iterator<Item = usize>, amount: f32) {
+    let amount = amount.clamp(0., 1.);
+    let Some(mut index) = indices.next() else {
+        return;
+    };
+    let mut previous = None;
+    loop {
+        let next = indices.next();
+        let color = pixels[index];
+        let left = previous.unwrap_or(color);
+        let right = next.map_or(color, |next| pixels[next]);
+        let channel = |color: f32, left: f32, right: f32| {
+            color * (1. - amount) + (left + right) * amount / 2.
+        };
+        pixels[index] = LinearSrgb {
+            red: channel(color.red, left.red, right.red),
+            green: channel(color.green, left.green, right.green),
+            blue: channel(color.blue, left.blue, right.blue),
+        };
+        previous = Some(color);
+        match next {
+            Some(next) => index = next,
+            None => break,
+        }
+    }
+}
+
+/// Blurs pixels at any points, mixing each pixel with the pixels within a radius, weighted by
+/// distance.
+fn blur_by_distance<const PIXEL_COUNT: usize>(
+    pixels: &[LinearSrgb],
+    points: &[Vec3],
+    radius: f32,
+    amount: f32,
+) -> Vec<LinearSrgb, PIXEL_COUNT> {
+    let amount = amount.clamp(0., 1.);
+    pixels
+        .iter()
+        .zip(points)
+        .map(|(&color, &point)| {
+            let mut total_weight = 0.;
+            let mut total = LinearSrgb {
+                red: 0.,
+                green: 0.,
+                blue: 0.,
+            };
+            for (&neighbor, &neighbor_point) in pixels.iter().zip(points) {
+                let distance = point.distance(neighbor_point);
+                if distance <= 0. || distance >= radius {
+                    continue;
+                }
+                let weight = 1. - distance / radius;
+                total_weight += weight;
+                total.red += neighbor.red * weight;
+                total.green += neighbor.green * weight;
+                total.blue += neighbor.blue * weight;
+            }
+            if total_weight <= 0. {
+                return color;
+            }
+            let channel =
+                |color: f32, total: f32| color * (1. - amount) + total / total_weight * amount;
+            LinearSrgb {
+                red: channel(color.red, total.red),
+                green: channel(color.green, total.green),
+                blue: channel(color.blue, total.blue),
+            }
+        })
+        .collect()
+}
+
+/// Parameters for a [`Blurred`] pattern.
+///
+/// # Type Parameters
+///
+/// - `InnerParams` - The parameters of the inner pattern
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlurParams<InnerParams> {
+    /// The parameters of the inner pattern
+    pub inner: InnerParams,
+    /// How much to blur, from 0.0 (none) to 1.0
+    pub amount: f32,
+    /// The distance to blur within, in layout units, for layouts which aren't a line or grid
+    pub radius: f32,
+}
+
+impl<InnerParams: Default> Default for BlurParams<InnerParams> {
+    fn default() -> Self {
+        Self {
+            inner: InnerParams::default(),
+            amount: 0.5,
+            radius: 0.25,
+        }
+    }
+}
+
+/// A pattern with its colors blurred.
+///
+/// # Type Parameters
+///
+/// - `Inner` - The pattern type
+/// - `PIXEL_COUNT` - The number of pixels in the layout
+#[derive(Debug)]
+pub struct Blurred<Inner, const PIXEL_COUNT: usize> {
+    pattern: Inner,
+    amount: f32,
+    radius: f32,
+}
+
+impl<Inner, const PIXEL_COUNT: usize> Blurred<Inner, PIXEL_COUNT> {
+    /// Returns the inner pattern.
+    pub fn inner(&self) -> &Inner {
+        &self.pattern
+    }
+
+    /// Returns the inner pattern mutably.
+    pub fn inner_mut(&mut self) -> &mut Inner {
+        &mut self.pattern
+    }
+
+    /// Returns the parameters of the inner pattern, if it exposes them.
+    ///
+    /// The blur itself is set with [`set_amount`](Self::set_amount) and
+    /// [`set_radius`](Self::set_radius).
+    pub fn inner_params<Dim, Layout>(&self) -> Option<&Inner::Params>
+    where
+        Inner: Pattern<Dim, Layout>,
+        Layout: LayoutForDim<Dim>,
+    {
+        self.pattern.params()
+    }
+
+    /// Returns the parameters of the inner pattern mutably, if it exposes them.
+    pub fn inner_params_mut<Dim, Layout>(&mut self) -> Option<&mut Inner::Params>
+    where
+        Inner: Pattern<Dim, Layout>,
+        Layout: LayoutForDim<Dim>,
+    {
+        self.pattern.params_mut()
+    }
+
+    /// Returns how much to blur.
+    pub fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    /// Sets how much to blur.
+    ///
+    /// # Arguments
+    ///
+    /// - `amount` - How much to blur, from 0.0 (none) to 1.0
+    pub fn set_amount(&mut self, amount: f32) {
+        self.amount = amount;
+    }
+
+    /// Returns the distance to blur within.
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Sets the distance to blur within.
+    ///
+    /// # Arguments
+    ///
+    /// - `radius` - The distance, in layout units, for layouts which aren't a line or grid
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius;
+    }
+
+    /// Returns the colors of the inner pattern, buffered.
+    fn inner_colors<Dim, Layout>(&self, time_in_ms: u64) -> Vec<LinearSrgb, PIXEL_COUNT>
+    where
+        Inner: Pattern<Dim, Layout>,
+        Layout: LayoutForDim<Dim>,
+        LinearSrgb: FromColor<Inner::Color>,
+    {
+        self.pattern
+            .tick(time_in_ms)
+            .take(PIXEL_COUNT)
+            .map(LinearSrgb::from_color)
+            .collect()
+    }
+}
+
+impl<Layout, Inner, const PIXEL_COUNT: usize> Pattern<Dim1d, Layout> for Blurred<Inner, PIXEL_COUNT>
+where
+    Layout: Layout1d,
+    Inner: Pattern<Dim1d, Layout>,
+    LinearSrgb: FromColor<Inner::Color>,
+{
+    type Params = BlurParams<Inner::Params>;
+    type Color = LinearSrgb;
+
+    fn new(params: Self::Params) -> Self {
+        Self {
+            pattern: Inner::new(params.inner),
+            amount: params.amount,
+            radius: params.radius,
+        }
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let mut colors = self.inner_colors::<Dim1d, Layout>(time_in_ms);
+        blur_line(&mut colors, self.amount);
+        colors.into_iter()
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.pattern.set_color_capability(capability);
+    }
+}
+
+impl<Layout, Inner, const PIXEL_COUNT: usize> Pattern<Dim2d, Layout> for Blurred<Inner, PIXEL_COUNT>
+where
+    Layout: Layout2d,
+    Inner: Pattern<Dim2d, Layout>,
+    LinearSrgb: FromColor<Inner::Color>,
+{
+    type Params = BlurParams<Inner::Params>;
+    type Color = LinearSrgb;
+
+    fn new(params: Self::Params) -> Self {
+        Self {
+            pattern: Inner::new(params.inner),
+            amount: params.amount,
+            radius: params.radius,
+        }
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let mut colors = self.inner_colors::<Dim2d, Layout>(time_in_ms);
+        let mut shapes = Layout::shapes();
+        match (shapes.next(), shapes.next()) {
+            (
+                Some(Shape2d::Grid {
+                    horizontal_pixel_count,
+                    serpentine,
+                    ..
+                }),
+                None,
+            ) => {
+                blur_grid(&mut colors, horizontal_pixel_count, serpentine, self.amount);
+            }
+            _ => {
+                let points: Vec<Vec3, PIXEL_COUNT> =
+                    Layout::points().map(|point| point.extend(0.)).collect();
+                colors = blur_by_distance(&colors, &points, self.radius, self.amount);
+            }
+        }
+        colors.into_iter()
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.pattern.set_color_capability(capability);
+    }
+}
+
+impl<Layout, Inner, const PIXEL_COUNT: usize> Pattern<Dim3d, Layout> for Blurred<Inner, PIXEL_COUNT>
+where
+    Layout: Layout3d,
+    Inner: Pattern<Dim3d, Layout>,
+    LinearSrgb: FromColor<Inner::Color>,
+{
+    type Params = BlurParams<Inner::Params>;
+    type Color = LinearSrgb;
+
+    fn new(params: Self::Params) -> Self {
+        Self {
+            pattern: Inner::new(params.inner),
+            amount: params.amount,
+            radius: params.radius,
+        }
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let colors = self.inner_colors::<Dim3d, Layout>(time_in_ms);
+        let points: Vec<Vec3, PIXEL_COUNT> = Layout::points().collect();
+        blur_by_distance::<PIXEL_COUNT>(&colors, &points, self.radius, self.amount).into_iter()
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.pattern.set_color_capability(capability);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serpentine_grid_matches_rows() {
+        let colors: [LinearSrgb; 6] =
+            core::array::from_fn(|index| LinearSrgb::new(index as f32 / 5., 0., 0.));
+        let mut rows = colors;
+        blur_grid(&mut rows, 3, false, 0.5);
+
+        // The same grid, with the second row reversed
+        let mut serpentine = [
+            colors[0], colors[1], colors[2], colors[5], colors[4], colors[3],
+        ];
+        blur_grid(&mut serpentine, 3, true, 0.5);
+        assert_eq!(
+            serpentine,
+            [rows[0], rows[1], rows[2], rows[5], rows[4], rows[3]]
+        );
+    }
+
+    #[test]
+    fn test_params_and_radius_can_be_changed() {
+        use crate::{
+            patterns::rainbow::{Rainbow, RainbowParams},
+            testing::{Cube2, PatternHarness},
+        };
+
+        let mut harness = PatternHarness::<Dim3d, Cube2, Blurred<Rainbow, 8>>::new(BlurParams {
+            inner: RainbowParams {
+                position_scalar: 0.25,
+                ..Default::default()
+            },
+            amount: 1.,
+            radius: 0.,
+        });
+        let unblurred: [LinearSrgb; 8] = harness.frame(0);
+
+        harness.pattern_mut().set_radius(3.);
+        assert_eq!(harness.pattern().radius(), 3.);
+        let blurred: [LinearSrgb; 8] = harness.frame(0);
+        assert_ne!(blurred, unblurred);
+
+        let before: [LinearSrgb; 8] = harness.frame(1000);
+        let params = harness.pattern_mut().inner_params_mut::<Dim3d, Cube2>();
+        params.unwrap().time_scalar = 0.25 / 1e3;
+        let params = harness.pattern().inner_params::<Dim3d, Cube2>();
+        assert_eq!(params.map(|params| params.time_scalar), Some(0.25 / 1e3));
+        assert_ne!(harness.frame::<8>(1000), before);
+    }
+
+    #[test]
+    fn test_blur_by_distance() {
+        let red = LinearSrgb::new(1., 0., 0.);
+        let blue = LinearSrgb::new(0., 0., 1.);
+        let points = [Vec3::ZERO, Vec3::X, Vec3::new(10., 0., 0.)];
+
+        let blurred = blur_by_distance::<3>(&[red, blue, red], &points, 2., 1.);
+        assert_eq!(blurred, [blue, red, red]);
+    }
+}
@@ -0,0 +1,239 @@
+//! # Smart Home Bridge
+//!
+//! A layer to plug a device into a smart home ecosystem, such as Matter or HomeKit, with
+//! minimal glue. Blinksy doesn't implement these protocols: an external crate does, and uses
+//! this layer to get and set the state of the lights.
+//!
+//! - A [`Light`] is something the ecosystem controls: on or off, brightness, hue and
+//!   saturation. It's implemented for [`Control`].
+//! - A [`StateReporter`] finds changes to report back to the ecosystem, such as from a button
+//!   on the device, so home apps stay in sync.
+//!
+//! As a [`Control`] shows patterns rather than a single color, the hue and saturation tint
+//! the pattern (see [`Control::set_tint`]): the hue rotates the hue of every color, and the
+//! saturation scales its saturation. So a hue of 0 and a saturation of 1 show the pattern as
+//! it is, and a saturation of 0 shows it in grayscale.
+//!
+//! The [`LightState`] uses ranges from 0.0 to 1.0, which map to the ecosystems as:
+//!
+//! | State        | Matter                                      | HomeKit             |
+//! |--------------|---------------------------------------------|---------------------|
+//! | `is_on`      | `OnOff`                                     | `On`                |
+//! | `brightness` | `CurrentLevel` / 254                        | `Brightness` / 100  |
+//! | `hue`        | `CurrentHue` / 254                          | `Hue` / 360         |
+//! | `saturation` | `CurrentSaturation` / 254                   | `Saturation` / 100  |
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     bridge::StateReporter,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     ControlBuilder,
+//! };
+//! # use blinksy::driver::NullDriver;
+//!
+//! layout1d!(Layout, 60);
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Rainbow>(RainbowParams::default())
+//! #   .with_driver(NullDriver)
+//! #   .with_frame_buffer_size::<0>()
+//!     // ...
+//!     .build();
+//! let mut reporter = StateReporter::new();
+//! // The first poll reports the initial state
+//! assert!(reporter.poll(&control).is_some());
+//!
+//! // From the ecosystem, e.g. a Matter `MoveToLevel` command
+//! let level: u8 = 127;
+//! control.set_brightness(level as f32 / 254.);
+//! // Changes from the ecosystem are already known, so aren't reported back
+//! reporter.mark_reported(&control);
+//! assert_eq!(reporter.poll(&control), None);
+//!
+//! // From the device, e.g. a button
+//! control.set_on(false);
+//! let state = reporter.poll(&control).unwrap();
+//! assert!(!state.is_on);
+//! // Then report the state to the ecosystem, e.g. as Matter attributes
+//! ```
+
+use crate::{
+    control::{Control, FrameHooks},
+    layout::LayoutForDim,
+    pattern::Pattern,
+};
+
+/// The state of a light, as exposed to a smart home ecosystem.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LightState {
+    /// Whether the light is on
+    pub is_on: bool,
+    /// Brightness level from 0.0 (off) to 1.0 (full)
+    pub brightness: f32,
+    /// Hue, in turns (0.0 to 1.0)
+    pub hue: f32,
+    /// Saturation, from 0.0 (grayscale) to 1.0 (full)
+    pub saturation: f32,
+}
+
+impl Default for LightState {
+    fn default() -> Self {
+        Self {
+            is_on: true,
+            brightness: 1.,
+            hue: 0.,
+            saturation: 1.,
+        }
+    }
+}
+
+/// A light which a smart home ecosystem can control.
+///
+/// Each getter and setter maps to an attribute of the ecosystem, see the
+/// [module docs](crate::bridge).
+pub trait Light {
+    /// Returns whether the light is on.
+    fn is_on(&self) -> bool;
+
+    /// Turns the light on or off.
+    ///
+    /// # Arguments
+    ///
+    /// - `is_on` - Whether the light is on
+    fn set_on(&mut self, is_on: bool);
+
+    /// Returns the brightness level, from 0.0 (off) to 1.0 (full).
+    fn brightness(&self) -> f32;
+
+    /// Sets the brightness level.
+    ///
+    /// # Arguments
+    ///
+    /// - `brightness` - Brightness level from 0.0 (off) to 1.0 (full)
+    fn set_brightness(&mut self, brightness: f32);
+
+    /// Returns the hue, in turns (0.0 to 1.0), and the saturation, from 0.0 to 1.0.
+    fn hue_saturation(&self) -> (f32, f32);
+
+    /// Sets the hue and saturation.
+    ///
+    /// # Arguments
+    ///
+    /// - `hue` - Hue, in turns (0.0 to 1.0)
+    /// - `saturation` - Saturation, from 0.0 (grayscale) to 1.0 (full)
+    fn set_hue_saturation(&mut self, hue: f32, saturation: f32);
+
+    /// Returns the whole state of the light.
+    fn state(&self) -> LightState {
+        let (hue, saturation) = self.hue_saturation();
+        LightState {
+            is_on: self.is_on(),
+            brightness: self.brightness(),
+            hue,
+            saturation,
+        }
+    }
+
+    /// Sets the whole state of the light, e.g. when restoring a scene.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` - The new state
+    fn set_state(&mut self, state: LightState) {
+        self.set_brightness(state.brightness);
+        self.set_hue_saturation(state.hue, state.saturation);
+        self.set_on(state.is_on);
+    }
+}
+
+impl<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Exec,
+        Layout,
+        P,
+        Driver,
+        Hooks,
+    > Light for Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, P, Driver, Hooks>
+where
+    Layout: LayoutForDim<Dim>,
+    P: Pattern<Dim, Layout>,
+    Hooks: FrameHooks,
+{
+    fn is_on(&self) -> bool {
+        Control::is_on(self)
+    }
+
+    fn set_on(&mut self, is_on: bool) {
+        Control::set_on(self, is_on);
+    }
+
+    fn brightness(&self) -> f32 {
+        Control::brightness(self)
+    }
+
+    fn set_brightness(&mut self, brightness: f32) {
+        Control::set_brightness(self, brightness.clamp(0., 1.));
+    }
+
+    fn hue_saturation(&self) -> (f32, f32) {
+        self.tint()
+    }
+
+    fn set_hue_saturation(&mut self, hue: f32, saturation: f32) {
+        self.set_tint(hue, saturation.clamp(0., 1.));
+    }
+}
+
+/// Finds changes to the state of a light, to report back to a smart home ecosystem.
+///
+/// Poll it regularly, e.g. after each [`tick`](Control::tick): it returns the state whenever
+/// it has changed since the last report.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StateReporter {
+    reported: Option<LightState>,
+}
+
+impl StateReporter {
+    /// Creates a reporter, which reports the state on the first poll.
+    pub const fn new() -> Self {
+        Self { reported: None }
+    }
+
+    /// Returns the state of the light, if it changed since the last report.
+    ///
+    /// # Arguments
+    ///
+    /// - `light` - The light
+    pub fn poll<L: Light>(&mut self, light: &L) -> Option<LightState> {
+        let state = light.state();
+        if self.reported == Some(state) {
+            return None;
+        }
+        self.reported = Some(state);
+        Some(state)
+    }
+
+    /// Marks the state of the light as reported, e.g. after a change from the ecosystem.
+    ///
+    /// # Arguments
+    ///
+    /// - `light` - The light
+    pub fn mark_reported<L: Light>(&mut self, light: &L) {
+        self.reported = Some(light.state());
+    }
+
+    /// Returns the last state reported, if any.
+    pub fn reported(&self) -> Option<LightState> {
+        self.reported
+    }
+}
@@ -0,0 +1,53 @@
+//! # Capacities
+//!
+//! Blinksy allocates nothing on a heap: every buffer has a capacity fixed at compile time. This
+//! module collects the default capacities in one place, so memory use can be reviewed, and
+//! tuned per target without forking.
+//!
+//! Each buffer whose capacity is up to you takes it as a const generic parameter, which
+//! defaults to the constant here where there is one:
+//!
+//! | Buffer | Capacity | Default |
+//! |--------|----------|---------|
+//! | [`DeviceConfig`](crate::config::DeviceConfig) segments | `SEGMENT_COUNT` | [`MAX_SEGMENTS`] |
+//! | [`StairsParams`](crate::patterns::stairs::StairsParams) steps | `SEGMENT_COUNT` | [`MAX_SEGMENTS`] |
+//! | [`PatternRegistry`](crate::registry::PatternRegistry) entries | `CAPACITY` | The number of patterns |
+//! | [`FrameDiffer`](crate::driver::FrameDiffer), [`SpanDiffer`](crate::driver::SpanDiffer) previous frame | `CAPACITY` | |
+//! | [`Decay`](crate::driver::Decay) last frame | `CAPACITY` | |
+//! | [`Control`](crate::control::Control) frame buffer | `FRAME_BUFFER_SIZE` | |
+//! | `FrameChannel` frames (feature `embassy`) | `DEPTH` | |
+//!
+//! Some capacities stay fixed, as they're part of a type which must be serializable, or of a
+//! type which is named often:
+//!
+//! | Buffer | Capacity |
+//! |--------|----------|
+//! | [`Palette`](crate::color::Palette) colors (in [`NoiseParams`](crate::patterns::noise::NoiseParams)) | [`MAX_PALETTE_COLORS`] |
+//! | `FrameQueue` frames (in `blinksy-esp`) | [`FRAME_QUEUE_SIZE`] |
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     capacities::MAX_SEGMENTS,
+//!     config::{Chipset, DeviceConfig},
+//! };
+//!
+//! // The default capacity
+//! let config = DeviceConfig::new(Chipset::Ws2812, 120);
+//! assert_eq!(config.segments.capacity(), MAX_SEGMENTS);
+//!
+//! // A staircase with more steps than the default
+//! let config = DeviceConfig::<16>::with_segment_capacity(Chipset::Ws2812, 16 * 30);
+//! assert_eq!(config.segments.capacity(), 16);
+//! ```
+
+/// The default maximum number of segments, in a [`DeviceConfig`](crate::config::DeviceConfig)
+/// or [`StairsParams`](crate::patterns::stairs::StairsParams).
+pub const MAX_SEGMENTS: usize = 8;
+
+/// The most colors a [`Palette`](crate::color::Palette) can hold.
+pub const MAX_PALETTE_COLORS: usize = 16;
+
+/// How many rendered frames can wait in a `FrameQueue` of `blinksy-esp` to be transmitted.
+pub const FRAME_QUEUE_SIZE: usize = 2;
@@ -16,6 +16,7 @@ use num_traits::Float;
 /// - Compensating for RGB LED intensity differences
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColorCorrection {
     /// Scaling factor for red channel
     pub red: f32,
@@ -18,6 +18,7 @@ use num_traits::Float;
 /// GammaSrgb uses a simple power function: C_gamma = C_linear^(1/gamma)
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GammaSrgb {
     /// Red component (0.0 to 1.0)
     pub red: f32,
@@ -63,6 +63,13 @@ impl<M: HsvHueMap> Hsv<M> {
     }
 }
 
+impl<M: HsvHueMap> Default for Hsv<M> {
+    /// Black.
+    fn default() -> Self {
+        Self::new(0., 0., 0.)
+    }
+}
+
 impl<M: HsvHueMap> FromColor<Hsv<M>> for LinearSrgb {
     fn from_color(color: Hsv<M>) -> Self {
         // Special case for zero saturation (grayscale)
@@ -154,6 +161,7 @@ impl<M: HsvHueMap> HsvHue<M> {
 ///
 /// - [`HsvHueRainbow`]: Visually balanced rainbow
 /// - [`HsvHueSpectrum`]: Mathematically straight spectrum
+/// - [`GradientHueMap`]: A hue wheel from your own gradient stops, like a WLED palette
 ///
 pub trait HsvHueMap: Sized {
     /// Convert a hue value to RGB
@@ -287,3 +295,185 @@ impl HsvHueMap for HsvHueRainbow {
         }
     }
 }
+
+/// A stop of a hue gradient: the color at a position of the hue wheel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HueStop {
+    /// Position on the hue wheel (0.0 to 1.0)
+    pub position: f32,
+    /// Color at this position
+    pub color: LinearSrgb,
+}
+
+impl HueStop {
+    /// Creates a new hue stop
+    ///
+    /// # Arguments
+    ///
+    /// - `position` - Position on the hue wheel (0.0 to 1.0)
+    /// - `color` - Color at this position
+    pub const fn new(position: f32, color: LinearSrgb) -> Self {
+        Self { position, color }
+    }
+}
+
+/// Trait for the gradient stops of a [`GradientHueMap`].
+///
+/// Use [`hue_gradient!`](crate::hue_gradient) to implement it from a list of stops.
+///
+/// The stops are lent to a closure, so they may be built at runtime: e.g. in a
+/// [`heapless::Vec`] behind a mutex, loaded from the device configuration.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Mutex;
+///
+/// use blinksy::color::{FromColor, GradientHueMap, Hsv, HueGradient, HueStop, LinearSrgb};
+/// use heapless::Vec;
+///
+/// static STOPS: Mutex<Vec<HueStop, 8>> = Mutex::new(Vec::new());
+///
+/// /// The gradient of the device configuration
+/// struct Configured;
+///
+/// impl HueGradient for Configured {
+///     fn with_stops<R>(f: impl FnOnce(&[HueStop]) -> R) -> R {
+///         f(&STOPS.lock().unwrap())
+///     }
+/// }
+///
+/// let purple = LinearSrgb::new(0.5, 0., 1.);
+/// STOPS.lock().unwrap().push(HueStop::new(0., purple)).unwrap();
+///
+/// let color = Hsv::<GradientHueMap<Configured>>::new(0.3, 1., 1.);
+/// assert_eq!(LinearSrgb::from_color(color), purple);
+/// ```
+pub trait HueGradient {
+    /// Calls a closure with the stops of the gradient, sorted by position.
+    fn with_stops<R>(f: impl FnOnce(&[HueStop]) -> R) -> R;
+}
+
+/// Hue mapping from the gradient stops of a [`HueGradient`], like a WLED palette
+///
+/// The colors between two stops are blended in linear sRGB. The hue wheel wraps around, so
+/// after the last stop, the colors blend back to the first stop.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{
+///     color::{FromColor, GradientHueMap, Hsv, HueStop, LinearSrgb},
+///     hue_gradient,
+/// };
+///
+/// hue_gradient!(
+///     /// Red, to yellow, to white
+///     Fire,
+///     [
+///         HueStop::new(0., LinearSrgb::new(1., 0., 0.)),
+///         HueStop::new(0.5, LinearSrgb::new(1., 1., 0.)),
+///         HueStop::new(0.75, LinearSrgb::new(1., 1., 1.)),
+///     ]
+/// );
+///
+/// let yellow = Hsv::<GradientHueMap<Fire>>::new(0.5, 1., 1.);
+/// assert_eq!(LinearSrgb::from_color(yellow), LinearSrgb::new(1., 1., 0.));
+///
+/// // Between the last stop and the first stop
+/// let pink = Hsv::<GradientHueMap<Fire>>::new(0.875, 1., 1.);
+/// assert_eq!(LinearSrgb::from_color(pink), LinearSrgb::new(1., 0.5, 0.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GradientHueMap<G: HueGradient> {
+    gradient: PhantomData<G>,
+}
+
+impl<G: HueGradient> HsvHueMap for GradientHueMap<G> {
+    fn hue_to_rgb(hue: f32) -> LinearSrgb {
+        G::with_stops(|stops| gradient_to_rgb(stops, hue))
+    }
+}
+
+/// Returns the color of a hue, blended between the stops around it.
+fn gradient_to_rgb(stops: &[HueStop], hue: f32) -> LinearSrgb {
+    let (first, last) = match (stops.first(), stops.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return LinearSrgb::new(0., 0., 0.),
+    };
+
+    // The stops around the hue, wrapping from the last stop to the first stop
+    let (from, to, to_position) = match stops.iter().position(|stop| stop.position > hue) {
+        Some(0) => (last, first, first.position),
+        Some(index) => (&stops[index - 1], &stops[index], stops[index].position),
+        None => (last, first, first.position + 1.),
+    };
+    let from_position = if hue < from.position {
+        from.position - 1.
+    } else {
+        from.position
+    };
+
+    let span = to_position - from_position;
+    let t = if span > 0. {
+        (hue - from_position) / span
+    } else {
+        0.
+    };
+    LinearSrgb::new(
+        from.color.red + (to.color.red - from.color.red) * t,
+        from.color.green + (to.color.green - from.color.green) * t,
+        from.color.blue + (to.color.blue - from.color.blue) * t,
+    )
+}
+
+/// Define a [`HueGradient`] type from a list of stops, for a [`GradientHueMap`].
+///
+/// # Arguments
+///
+/// - `$name` - The name of the type
+/// - `[$($stop:expr),*]` - A list of [`HueStop`]s, sorted by position
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{
+///     color::{FromColor, GradientHueMap, Hsv, HueStop, LinearSrgb},
+///     hue_gradient,
+/// };
+///
+/// hue_gradient!(
+///     pub Ocean,
+///     [
+///         HueStop::new(0., LinearSrgb::new(0., 0., 0.5)),
+///         HueStop::new(0.5, LinearSrgb::new(0., 0.5, 1.)),
+///     ]
+/// );
+///
+/// type OceanHsv = Hsv<GradientHueMap<Ocean>>;
+///
+/// let deep = LinearSrgb::from_color(OceanHsv::new(0., 1., 1.));
+/// assert_eq!(deep, LinearSrgb::new(0., 0., 0.5));
+/// ```
+#[macro_export]
+macro_rules! hue_gradient {
+    ($(#[$attr:meta])* $vis:vis $name:ident, [$($stop:expr),* $(,)?]) => {
+        $(#[$attr])*
+        #[derive(
+            ::core::fmt::Debug,
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::cmp::PartialEq,
+        )]
+        $vis struct $name;
+
+        impl $crate::color::HueGradient for $name {
+            fn with_stops<R>(f: impl FnOnce(&[$crate::color::HueStop]) -> R) -> R {
+                static STOPS: &[$crate::color::HueStop] = &[$($stop),*];
+                f(STOPS)
+            }
+        }
+    };
+}
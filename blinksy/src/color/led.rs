@@ -2,7 +2,7 @@ use core::{array::IntoIter, iter::Iterator, ops::Index};
 
 use crate::util::component::Component;
 
-use super::{ColorCorrection, LinearSrgb};
+use super::{apply_output_pipeline, ColorCorrection, LinearSrgb};
 
 /// Color data ready for output to LED hardware
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -109,22 +109,8 @@ impl<C: Component> LedRgb<C> {
         brightness: f32,
         correction: ColorCorrection,
     ) -> Self {
-        let LinearSrgb { red, green, blue } = linear_srgb;
-
-        // Apply color correction
-        let red = red * correction.red;
-        let green = green * correction.green;
-        let blue = blue * correction.blue;
-
-        // Apply brightness
-        let red = red * brightness;
-        let green = green * brightness;
-        let blue = blue * brightness;
-
-        // Clamp values
-        let red = red.clamp(0., 1.);
-        let green = green.clamp(0., 1.);
-        let blue = blue.clamp(0., 1.);
+        let LinearSrgb { red, green, blue } =
+            apply_output_pipeline(linear_srgb, brightness, correction);
 
         Self([
             C::from_normalized_f32(red),
@@ -197,26 +183,10 @@ impl<C: Component> LedRgbw<C> {
         let white = red.min(green).min(blue);
 
         // Subtract white from RGB to get true RGB components
-        let red = red - white;
-        let green = green - white;
-        let blue = blue - white;
-
-        // Apply color correction
-        let red = red * correction.red;
-        let green = green * correction.green;
-        let blue = blue * correction.blue;
-
-        // Apply brightness
-        let red = red * brightness;
-        let green = green * brightness;
-        let blue = blue * brightness;
-        let white = white * brightness;
-
-        // Clamp values
-        let red = red.clamp(0., 1.);
-        let green = green.clamp(0., 1.);
-        let blue = blue.clamp(0., 1.);
-        let white = white.clamp(0., 1.);
+        let rgb = LinearSrgb::new(red - white, green - white, blue - white);
+
+        let LinearSrgb { red, green, blue } = apply_output_pipeline(rgb, brightness, correction);
+        let white = (white * brightness).clamp(0., 1.);
 
         Self([
             C::from_normalized_f32(red),
@@ -284,8 +254,9 @@ impl LedChannels {
 /// Enumeration of RGB channel orders.
 ///
 /// Different RGB LED chipsets may use different ordering of the R, G, and B channels.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RgbChannels {
     /// Red, Green, Blue
     RGB,
@@ -18,8 +18,9 @@ use super::{
 ///
 /// Mathematical operations on linear RGB values (like averaging or interpolation) will
 /// produce physically correct results, unlike operations on gamma-encoded sRGB values.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinearSrgb {
     /// Red component (0.0 to 1.0)
     pub red: f32,
@@ -45,7 +46,7 @@ impl LinearSrgb {
     ///
     /// let red = LinearSrgb::new(1.0, 0.0, 0.0);
     /// ```
-    pub fn new(red: f32, green: f32, blue: f32) -> Self {
+    pub const fn new(red: f32, green: f32, blue: f32) -> Self {
         LinearSrgb {
             red: red.clamp(0.0, 1.0),
             green: green.clamp(0.0, 1.0),
@@ -16,8 +16,9 @@ use super::LinearSrgb;
 ///
 /// LMS is primarily used as an intermediate space for color processing algorithms,
 /// particularly those that simulate or account for human color vision characteristics.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Lms {
     /// Long cone response component
     pub long: f32,
@@ -20,9 +20,14 @@
 //! - [`FromColor`] - Convert from a color type
 //! - [`IntoColor`] - Convert to a color type
 //!
+//! ## Palettes
+//!
+//! - [`Palette`] - Gradient of evenly spaced colors
+//!
 //! ## LED Output Modifiers
 //!
 //! - [`ColorCorrection`] - Correction factors for LED output
+//! - [`apply_output_pipeline`] - Color correction and brightness, as applied to LED output
 //!
 //! ## LED Output
 //!
@@ -43,6 +48,8 @@ mod lms;
 mod okhsl;
 mod okhsv;
 mod oklab;
+mod output;
+mod palette;
 mod srgb;
 mod xyz;
 
@@ -56,5 +63,7 @@ pub use self::lms::*;
 pub use self::okhsl::*;
 pub use self::okhsv::*;
 pub use self::oklab::*;
+pub use self::output::*;
+pub use self::palette::*;
 pub use self::srgb::*;
 pub use self::xyz::*;
@@ -13,8 +13,9 @@ use num_traits::Float;
 /// - `h`: Hue component (0.0 to 1.0) representing the color's position on the color wheel
 /// - `s`: Saturation component (0.0 to 1.0) representing the color's intensity/purity
 /// - `l`: Lightness component (0.0 to 1.0) representing the color's brightness
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Okhsl {
     /// Hue component [0.0, 1.0) where 0 and 1 both represent red
     pub h: f32,
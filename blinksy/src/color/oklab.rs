@@ -24,8 +24,9 @@ use num_traits::Float;
 /// - **White Point**: D65 (6500K), same as sRGB
 ///
 /// Reference: <https://bottosson.github.io/posts/oklab/>
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Oklab {
     /// Lightness component [0.0, 1.0]
     pub l: f32,
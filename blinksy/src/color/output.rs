@@ -0,0 +1,47 @@
+use super::{ColorCorrection, LinearSrgb};
+
+/// Applies the output pipeline shared by every LED output: color correction, then global
+/// brightness, then clamping to the range the LEDs can show.
+///
+/// Chipset encodes use this before converting to their words, and the desktop simulator uses
+/// this before encoding for display, so both show the same light.
+///
+/// # Arguments
+///
+/// - `linear_srgb` - Linear RGB color to output
+/// - `brightness` - Global brightness scaling factor (0.0 to 1.0)
+/// - `correction` - Color correction factors for the LEDs
+///
+/// # Returns
+///
+/// The linear light emitted by the LED, from 0.0 to 1.0 in each channel
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::color::{apply_output_pipeline, ColorCorrection, LinearSrgb};
+///
+/// let correction = ColorCorrection::new(1.0, 0.5, 2.0);
+/// let light = apply_output_pipeline(LinearSrgb::new(0.8, 0.8, 0.8), 0.5, correction);
+/// assert_eq!(light, LinearSrgb::new(0.4, 0.2, 0.8));
+/// ```
+pub fn apply_output_pipeline(
+    linear_srgb: LinearSrgb,
+    brightness: f32,
+    correction: ColorCorrection,
+) -> LinearSrgb {
+    let LinearSrgb { red, green, blue } = linear_srgb;
+
+    // Apply color correction
+    let red = red * correction.red;
+    let green = green * correction.green;
+    let blue = blue * correction.blue;
+
+    // Apply brightness
+    let red = red * brightness;
+    let green = green * brightness;
+    let blue = blue * brightness;
+
+    // Clamp values
+    LinearSrgb::new(red.clamp(0., 1.), green.clamp(0., 1.), blue.clamp(0., 1.))
+}
@@ -0,0 +1,84 @@
+use super::{FromColor, LinearSrgb, Oklab};
+pub use crate::capacities::MAX_PALETTE_COLORS;
+
+/// A gradient palette of evenly spaced colors, most similar to a FastLED palette.
+///
+/// Colors are stored and blended in [`Oklab`], so the gradient between two colors is
+/// perceptually smooth.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{
+///     assert_color_near,
+///     color::{LinearSrgb, Palette},
+/// };
+///
+/// let palette = Palette::new([LinearSrgb::new(1., 0., 0.), LinearSrgb::new(0., 0., 1.)]);
+///
+/// assert_color_near!(palette.sample(0.), LinearSrgb::new(1., 0., 0.));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Palette {
+    colors: [Oklab; MAX_PALETTE_COLORS],
+    len: usize,
+}
+
+impl Palette {
+    /// Creates a palette from colors, evenly spaced from the start to the end.
+    ///
+    /// Only the first [`MAX_PALETTE_COLORS`] colors are used.
+    ///
+    /// # Arguments
+    ///
+    /// - `colors` - The colors of the gradient, in order
+    pub fn new<C>(colors: impl IntoIterator<Item = C>) -> Self
+    where
+        LinearSrgb: FromColor<C>,
+    {
+        let mut palette = Self {
+            colors: [Oklab::new(0., 0., 0.); MAX_PALETTE_COLORS],
+            len: 0,
+        };
+        for (slot, color) in palette.colors.iter_mut().zip(colors) {
+            *slot = Oklab::from_linear_srgb(LinearSrgb::from_color(color));
+            palette.len += 1;
+        }
+        palette
+    }
+
+    /// Returns the colors of the palette, in order.
+    pub fn colors(&self) -> &[Oklab] {
+        &self.colors[..self.len.min(MAX_PALETTE_COLORS)]
+    }
+
+    /// Returns the color at a position of the gradient.
+    ///
+    /// # Arguments
+    ///
+    /// - `position` - From 0.0 (the first color) to 1.0 (the last color), clamped
+    ///
+    /// # Returns
+    ///
+    /// The blend of the two nearest colors, or black for an empty palette
+    pub fn sample(&self, position: f32) -> Oklab {
+        let colors = self.colors();
+        match colors.len() {
+            0 => Oklab::new(0., 0., 0.),
+            1 => colors[0],
+            len => {
+                let scaled = position.clamp(0., 1.) * (len - 1) as f32;
+                let index = (scaled as usize).min(len - 2);
+                let t = scaled - index as f32;
+                let (from, to) = (colors[index], colors[index + 1]);
+                Oklab::new(
+                    from.l + (to.l - from.l) * t,
+                    from.a + (to.a - from.a) * t,
+                    from.b + (to.b - from.b) * t,
+                )
+            }
+        }
+    }
+}
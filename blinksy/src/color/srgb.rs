@@ -17,8 +17,9 @@ use num_traits::Float;
 /// sRGB values are non-linear (gamma-encoded) to account for human perception. This means
 /// that arithmetic operations on sRGB values (like averaging or interpolation) will not
 /// produce perceptually correct results. For such operations, convert to `LinearSrgb` first.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Srgb {
     /// Red component (0.0 to 1.0)
     pub red: f32,
@@ -54,6 +55,68 @@ impl Srgb {
         }
     }
 
+    /// Creates a new sRGB color from 8-bit components
+    ///
+    /// # Arguments
+    ///
+    /// - `red` - Red component (0 to 255)
+    /// - `green` - Green component (0 to 255)
+    /// - `blue` - Blue component (0 to 255)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blinksy::color::Srgb;
+    ///
+    /// let orange = Srgb::from_u8(255, 128, 0);
+    /// assert_eq!(orange, Srgb::new(1.0, 128.0 / 255.0, 0.0));
+    /// ```
+    pub const fn from_u8(red: u8, green: u8, blue: u8) -> Self {
+        Srgb {
+            red: red as f32 / 255.0,
+            green: green as f32 / 255.0,
+            blue: blue as f32 / 255.0,
+        }
+    }
+
+    /// Creates a new sRGB color from a hex code, as in CSS
+    ///
+    /// # Arguments
+    ///
+    /// - `hex` - The color as `0xRRGGBB`, the highest byte is ignored
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blinksy::color::Srgb;
+    ///
+    /// const ORANGE: Srgb = Srgb::from_hex(0xFF8000);
+    /// assert_eq!(ORANGE, Srgb::from_u8(255, 128, 0));
+    /// assert_eq!(ORANGE.to_hex(), 0xFF8000);
+    /// ```
+    pub const fn from_hex(hex: u32) -> Self {
+        Self::from_u8((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+    }
+
+    /// Converts to 8-bit components, rounded to the nearest
+    ///
+    /// # Returns
+    ///
+    /// The red, green, and blue components (0 to 255)
+    pub fn to_u8(self) -> [u8; 3] {
+        [self.red, self.green, self.blue].map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Converts to a hex code, as in CSS
+    ///
+    /// # Returns
+    ///
+    /// The color as `0xRRGGBB`
+    pub fn to_hex(self) -> u32 {
+        let [red, green, blue] = self.to_u8();
+        (red as u32) << 16 | (green as u32) << 8 | blue as u32
+    }
+
     /// Converts from sRGB to linear RGB color space
     ///
     /// This removes the gamma encoding, making the color values proportional to light intensity.
@@ -78,6 +141,18 @@ impl Srgb {
     }
 }
 
+impl From<[u8; 3]> for Srgb {
+    fn from([red, green, blue]: [u8; 3]) -> Self {
+        Self::from_u8(red, green, blue)
+    }
+}
+
+impl From<Srgb> for [u8; 3] {
+    fn from(srgb: Srgb) -> Self {
+        srgb.to_u8()
+    }
+}
+
 /// Convert sRGB gamma-encoded component to linear RGB component
 ///
 /// The sRGB standard uses a piece-wise function that's approximately
@@ -11,8 +11,9 @@ use super::LinearSrgb;
 /// - **White Point**: D65 (6500K)
 /// - **Device-Independent**: Based on human perception
 /// - **Linear**: Values are proportional to light intensity
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Xyz {
     /// X component (mix of cone responses, roughly corresponds to red)
     pub x: f32,
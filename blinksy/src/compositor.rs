@@ -0,0 +1,588 @@
+//! # Compositor
+//!
+//! The compositor merges overlapping layers of pixels, such as an overlay segment (a
+//! notification, a clock, a cursor, ...) on top of an ambient background.
+//!
+//! A [`Segment`] is a range of pixels of the layout, with a [`BlendMode`] and an opacity for
+//! how its colors merge into the colors below:
+//!
+//! - [`BlendMode::Add`]: Adds colors, saturating at full. Good for light effects on top, like
+//!   sparkles.
+//! - [`BlendMode::Max`]: Takes the brighter of each channel, so neither layer darkens the
+//!   other.
+//! - [`BlendMode::AlphaOver`]: Paints colors on top, mixed by opacity. Good for overlays.
+//! - [`BlendMode::Multiply`]: Multiplies colors, so the top layer masks or tints the layer
+//!   below.
+//!
+//! Blending happens in linear sRGB, so mixes are physically correct.
+//!
+//! Layers may also be partially transparent: a [`Composite`] works in [`LinearSrgba`], linear
+//! sRGB with an alpha channel, so a top pattern with transparent pixels shows the pattern below
+//! through them. The colors become opaque (over black) only when shown by a driver.
+//!
+//! To composite two patterns, use [`Composite`]. To blend colors yourself, use
+//! [`BlendMode::blend`].
+//!
+//! For temporary feedback on top of any pattern (e.g. "WiFi connected"), use a
+//! [`Notification`], through [`Control::notify`](crate::control::Control::notify).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     compositor::{BlendMode, Composite, CompositeParams, Segment},
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::{
+//!         noise::{noise_fns, Noise1d, NoiseParams},
+//!         rainbow::{Rainbow, RainbowParams},
+//!     },
+//!     ControlBuilder,
+//! };
+//! # use blinksy::driver::NullDriver;
+//!
+//! layout1d!(Layout, 60);
+//!
+//! // A rainbow over the first 10 pixels, on top of a noise background
+//! let params = CompositeParams {
+//!     base: NoiseParams::default(),
+//!     top: RainbowParams::default(),
+//!     segment: Segment::new(0..10, BlendMode::AlphaOver, 0.8),
+//! };
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Composite<Noise1d<noise_fns::Perlin>, Rainbow>>(params)
+//! #   .with_driver(NullDriver)
+//! #   .with_frame_buffer_size::<0>()
+//!     // ...
+//!     .build();
+//! ```
+
+use core::{f32::consts::PI, ops::Range};
+
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{
+    color::{
+        FromColor, GammaSrgb, Hsv, HsvHueMap, LinearSrgb, Lms, Okhsl, Okhsv, Oklab, Srgb, Xyz,
+    },
+    driver::ColorCapability,
+    layout::LayoutForDim,
+    pattern::Pattern,
+};
+
+/// A linear sRGB color with an alpha channel, the working color of the compositor.
+///
+/// The color channels are straight (not premultiplied by alpha). Converting to an opaque
+/// [`LinearSrgb`], e.g. for a driver, composites the color over black.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{
+///     color::{FromColor, LinearSrgb},
+///     compositor::LinearSrgba,
+/// };
+///
+/// let half_red = LinearSrgba::new(1., 0., 0., 0.5);
+/// assert_eq!(LinearSrgb::from_color(half_red), LinearSrgb::new(0.5, 0., 0.));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinearSrgba {
+    /// Red component (0.0 to 1.0)
+    pub red: f32,
+    /// Green component (0.0 to 1.0)
+    pub green: f32,
+    /// Blue component (0.0 to 1.0)
+    pub blue: f32,
+    /// Alpha component, from 0.0 (transparent) to 1.0 (opaque)
+    pub alpha: f32,
+}
+
+impl LinearSrgba {
+    /// A fully transparent color.
+    pub const TRANSPARENT: Self = Self::new(0., 0., 0., 0.);
+
+    /// Creates a new color with alpha
+    ///
+    /// # Arguments
+    ///
+    /// - `red` - Red component (0.0 to 1.0)
+    /// - `green` - Green component (0.0 to 1.0)
+    /// - `blue` - Blue component (0.0 to 1.0)
+    /// - `alpha` - Alpha component, from 0.0 (transparent) to 1.0 (opaque)
+    pub const fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            red: red.clamp(0., 1.),
+            green: green.clamp(0., 1.),
+            blue: blue.clamp(0., 1.),
+            alpha: alpha.clamp(0., 1.),
+        }
+    }
+
+    /// Creates a color from a linear sRGB color and an alpha
+    ///
+    /// # Arguments
+    ///
+    /// - `color` - The color
+    /// - `alpha` - Alpha component, from 0.0 (transparent) to 1.0 (opaque)
+    pub const fn from_color_alpha(color: LinearSrgb, alpha: f32) -> Self {
+        Self::new(color.red, color.green, color.blue, alpha)
+    }
+
+    /// Returns the color channels, without the alpha.
+    pub const fn color(self) -> LinearSrgb {
+        LinearSrgb::new(self.red, self.green, self.blue)
+    }
+
+    /// Returns the same color, with its alpha scaled, e.g. to fade it out.
+    ///
+    /// # Arguments
+    ///
+    /// - `opacity` - The scale of the alpha, from 0.0 to 1.0
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        Self::from_color_alpha(self.color(), self.alpha * opacity.clamp(0., 1.))
+    }
+}
+
+impl FromColor<LinearSrgba> for LinearSrgb {
+    fn from_color(color: LinearSrgba) -> Self {
+        LinearSrgb::new(
+            color.red * color.alpha,
+            color.green * color.alpha,
+            color.blue * color.alpha,
+        )
+    }
+}
+
+macro_rules! impl_opaque_linear_srgba {
+    ($($color:ty),*) => {
+        $(
+            impl FromColor<$color> for LinearSrgba {
+                fn from_color(color: $color) -> Self {
+                    LinearSrgba::from_color_alpha(LinearSrgb::from_color(color), 1.)
+                }
+            }
+        )*
+    };
+}
+
+impl_opaque_linear_srgba!(LinearSrgb, GammaSrgb, Lms, Okhsl, Okhsv, Oklab, Srgb, Xyz);
+
+impl<M: HsvHueMap> FromColor<Hsv<M>> for LinearSrgba {
+    fn from_color(color: Hsv<M>) -> Self {
+        LinearSrgba::from_color_alpha(LinearSrgb::from_color(color), 1.)
+    }
+}
+
+/// How the colors of a layer merge into the colors below.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlendMode {
+    /// Adds the colors, saturating at full
+    Add,
+    /// Takes the brighter of each channel
+    Max,
+    /// Paints the top color over the bottom color
+    #[default]
+    AlphaOver,
+    /// Multiplies the colors
+    Multiply,
+}
+
+impl BlendMode {
+    /// Blends a top color into a bottom color.
+    ///
+    /// # Arguments
+    ///
+    /// - `bottom` - The color below
+    /// - `top` - The color of the layer on top
+    /// - `opacity` - How much of the blend to apply, from 0.0 (only the bottom color) to 1.0
+    ///
+    /// # Returns
+    ///
+    /// The blended color
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blinksy::{color::LinearSrgb, compositor::BlendMode};
+    ///
+    /// let bottom = LinearSrgb::new(0.5, 0.5, 0.);
+    /// let top = LinearSrgb::new(0., 1., 1.);
+    ///
+    /// assert_eq!(BlendMode::Max.blend(bottom, top, 1.), LinearSrgb::new(0.5, 1., 1.));
+    /// assert_eq!(BlendMode::Multiply.blend(bottom, top, 1.), LinearSrgb::new(0., 0.5, 0.));
+    /// assert_eq!(
+    ///     BlendMode::AlphaOver.blend(bottom, top, 0.5),
+    ///     LinearSrgb::new(0.25, 0.75, 0.5)
+    /// );
+    /// ```
+    pub fn blend(self, bottom: LinearSrgb, top: LinearSrgb, opacity: f32) -> LinearSrgb {
+        let opacity = opacity.clamp(0., 1.);
+        let channel = |bottom: f32, top: f32| {
+            let blended = match self {
+                BlendMode::Add => (bottom + top).min(1.),
+                BlendMode::Max => bottom.max(top),
+                BlendMode::AlphaOver => top,
+                BlendMode::Multiply => bottom * top,
+            };
+            bottom + (blended - bottom) * opacity
+        };
+        LinearSrgb {
+            red: channel(bottom.red, top.red),
+            green: channel(bottom.green, top.green),
+            blue: channel(bottom.blue, top.blue),
+        }
+    }
+
+    /// Blends a top color into a bottom color, both with alpha.
+    ///
+    /// The top color is blended where both colors are, and painted over the bottom color
+    /// where only the top color is, as in the [W3C compositing model].
+    ///
+    /// [W3C compositing model]: https://www.w3.org/TR/compositing-1/#blending
+    ///
+    /// # Arguments
+    ///
+    /// - `bottom` - The color below
+    /// - `top` - The color of the layer on top
+    /// - `opacity` - How much of the blend to apply, scaling the alpha of the top color
+    ///
+    /// # Returns
+    ///
+    /// The blended color
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blinksy::compositor::{BlendMode, LinearSrgba};
+    ///
+    /// let bottom = LinearSrgba::new(0., 0., 1., 1.);
+    ///
+    /// // A transparent top shows the bottom
+    /// let blended = BlendMode::AlphaOver.blend_rgba(bottom, LinearSrgba::TRANSPARENT, 1.);
+    /// assert_eq!(blended, bottom);
+    ///
+    /// // A half-transparent top, at half opacity, is a quarter over the bottom
+    /// let top = LinearSrgba::new(1., 0., 0., 0.5);
+    /// let blended = BlendMode::AlphaOver.blend_rgba(bottom, top, 0.5);
+    /// assert_eq!(blended, LinearSrgba::new(0.25, 0., 0.75, 1.));
+    /// ```
+    pub fn blend_rgba(self, bottom: LinearSrgba, top: LinearSrgba, opacity: f32) -> LinearSrgba {
+        let top_alpha = top.alpha * opacity.clamp(0., 1.);
+        let alpha = top_alpha + bottom.alpha * (1. - top_alpha);
+        if alpha <= 0. {
+            return LinearSrgba::TRANSPARENT;
+        }
+        let channel = |bottom_channel: f32, top_channel: f32| {
+            let blended = match self {
+                BlendMode::Add => (bottom_channel + top_channel).min(1.),
+                BlendMode::Max => bottom_channel.max(top_channel),
+                BlendMode::AlphaOver => top_channel,
+                BlendMode::Multiply => bottom_channel * top_channel,
+            };
+            // Where the bottom is transparent, the top is painted as is
+            let top_channel = top_channel + (blended - top_channel) * bottom.alpha;
+            let premultiplied =
+                top_channel * top_alpha + bottom_channel * bottom.alpha * (1. - top_alpha);
+            premultiplied / alpha
+        };
+        LinearSrgba::new(
+            channel(bottom.red, top.red),
+            channel(bottom.green, top.green),
+            channel(bottom.blue, top.blue),
+            alpha,
+        )
+    }
+}
+
+/// A range of pixels of a layer, and how its colors merge into the colors below.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    /// The range of pixel indices covered
+    pub range: Range<usize>,
+    /// How the colors merge into the colors below
+    pub blend_mode: BlendMode,
+    /// How much of the blend to apply, from 0.0 (invisible) to 1.0
+    pub opacity: f32,
+}
+
+impl Segment {
+    /// Creates a segment.
+    ///
+    /// # Arguments
+    ///
+    /// - `range` - The range of pixel indices covered
+    /// - `blend_mode` - How the colors merge into the colors below
+    /// - `opacity` - How much of the blend to apply, from 0.0 (invisible) to 1.0
+    pub const fn new(range: Range<usize>, blend_mode: BlendMode, opacity: f32) -> Self {
+        Self {
+            range,
+            blend_mode,
+            opacity,
+        }
+    }
+
+    /// Creates a segment covering the whole layout.
+    ///
+    /// # Arguments
+    ///
+    /// - `blend_mode` - How the colors merge into the colors below
+    /// - `opacity` - How much of the blend to apply, from 0.0 (invisible) to 1.0
+    pub const fn full(blend_mode: BlendMode, opacity: f32) -> Self {
+        Self::new(0..usize::MAX, blend_mode, opacity)
+    }
+
+    /// Blends a top color into a bottom color, if the pixel is in the segment.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the pixel
+    /// - `bottom` - The color below
+    /// - `top` - The color of the segment
+    ///
+    /// # Returns
+    ///
+    /// The blended color, or the bottom color if the pixel isn't in the segment
+    pub fn blend(&self, index: usize, bottom: LinearSrgb, top: LinearSrgb) -> LinearSrgb {
+        if self.range.contains(&index) {
+            self.blend_mode.blend(bottom, top, self.opacity)
+        } else {
+            bottom
+        }
+    }
+
+    /// Blends a top color into a bottom color, both with alpha, if the pixel is in the segment.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the pixel
+    /// - `bottom` - The color below
+    /// - `top` - The color of the segment
+    ///
+    /// # Returns
+    ///
+    /// The blended color, or the bottom color if the pixel isn't in the segment
+    pub fn blend_rgba(&self, index: usize, bottom: LinearSrgba, top: LinearSrgba) -> LinearSrgba {
+        if self.range.contains(&index) {
+            self.blend_mode.blend_rgba(bottom, top, self.opacity)
+        } else {
+            bottom
+        }
+    }
+}
+
+impl Default for Segment {
+    /// A segment covering the whole layout, painted fully on top.
+    fn default() -> Self {
+        Self::full(BlendMode::default(), 1.)
+    }
+}
+
+/// Parameters for a [`Composite`] pattern.
+///
+/// # Type Parameters
+///
+/// - `BaseParams` - The parameters of the base pattern
+/// - `TopParams` - The parameters of the top pattern
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompositeParams<BaseParams, TopParams> {
+    /// The parameters of the base pattern
+    pub base: BaseParams,
+    /// The parameters of the top pattern
+    pub top: TopParams,
+    /// Where and how the top pattern merges into the base pattern
+    pub segment: Segment,
+}
+
+/// A pattern made of a top pattern merged into a base pattern, within a [`Segment`].
+///
+/// Both patterns are computed on the whole layout. Either pattern may output colors with
+/// alpha, as [`LinearSrgba`], so the composite may be partially transparent too, e.g. to
+/// nest it in another composite.
+///
+/// # Type Parameters
+///
+/// - `Base` - The base (background) pattern type
+/// - `Top` - The top (overlay) pattern type
+#[derive(Debug)]
+pub struct Composite<Base, Top> {
+    base: Base,
+    top: Top,
+    segment: Segment,
+}
+
+impl<Base, Top> Composite<Base, Top> {
+    /// Returns the segment of the top pattern.
+    pub fn segment(&self) -> &Segment {
+        &self.segment
+    }
+
+    /// Returns the segment of the top pattern mutably, e.g. to fade the top pattern in or out.
+    pub fn segment_mut(&mut self) -> &mut Segment {
+        &mut self.segment
+    }
+}
+
+impl<Dim, Layout, Base, Top> Pattern<Dim, Layout> for Composite<Base, Top>
+where
+    Layout: LayoutForDim<Dim>,
+    Base: Pattern<Dim, Layout>,
+    Top: Pattern<Dim, Layout>,
+    LinearSrgba: FromColor<Base::Color> + FromColor<Top::Color>,
+{
+    type Params = CompositeParams<Base::Params, Top::Params>;
+    type Color = LinearSrgba;
+
+    fn new(params: Self::Params) -> Self {
+        Self {
+            base: Base::new(params.base),
+            top: Top::new(params.top),
+            segment: params.segment,
+        }
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.base
+            .tick(time_in_ms)
+            .zip(self.top.tick(time_in_ms))
+            .enumerate()
+            .map(|(index, (bottom, top))| {
+                self.segment.blend_rgba(
+                    index,
+                    LinearSrgba::from_color(bottom),
+                    LinearSrgba::from_color(top),
+                )
+            })
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.base.set_color_capability(capability);
+        self.top.set_color_capability(capability);
+    }
+}
+
+/// How a [`Notification`] is shown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NotificationStyle {
+    /// The whole layout flashes the color, then fades back to the pattern.
+    Flash,
+    /// The whole layout pulses the color in and out.
+    Pulse {
+        /// Duration of each pulse in milliseconds
+        period_in_ms: u64,
+    },
+    /// A bar of the color fills the layout, from the first pixel.
+    Progress {
+        /// How much of the bar is filled, from 0.0 to 1.0
+        progress: f32,
+    },
+}
+
+/// A temporary overlay on top of the pattern, for device feedback.
+///
+/// The notification's time starts at the first update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Notification {
+    color: LinearSrgba,
+    duration_in_ms: u64,
+    style: NotificationStyle,
+    start_time_in_ms: Option<u64>,
+}
+
+impl Notification {
+    /// Creates a notification.
+    ///
+    /// # Arguments
+    ///
+    /// - `color` - The color of the overlay, which may be partially transparent
+    /// - `duration_in_ms` - How long to show the overlay, in milliseconds
+    /// - `style` - How the overlay is shown
+    pub fn new<C>(color: C, duration_in_ms: u64, style: NotificationStyle) -> Self
+    where
+        LinearSrgba: FromColor<C>,
+    {
+        Self {
+            color: LinearSrgba::from_color(color),
+            duration_in_ms,
+            style,
+            start_time_in_ms: None,
+        }
+    }
+
+    /// Returns the color of the overlay.
+    pub fn color(&self) -> LinearSrgba {
+        self.color
+    }
+
+    /// Returns how the overlay is shown.
+    pub fn style(&self) -> NotificationStyle {
+        self.style
+    }
+
+    /// Advances the notification to the current time.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - Current time in milliseconds
+    /// - `pixel_count` - The number of pixels in the layout
+    ///
+    /// # Returns
+    ///
+    /// The segment to blend the overlay color with, or `None` if the notification is over
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blinksy::{
+    ///     color::LinearSrgb,
+    ///     compositor::{Notification, NotificationStyle},
+    /// };
+    ///
+    /// let style = NotificationStyle::Progress { progress: 0.5 };
+    /// let mut notification = Notification::new(LinearSrgb::new(0., 0., 1.), 1000, style);
+    ///
+    /// let segment = notification.update(5000, 10).unwrap();
+    /// assert_eq!(segment.range, 0..5);
+    ///
+    /// assert!(notification.update(6000, 10).is_none());
+    /// ```
+    pub fn update(&mut self, time_in_ms: u64, pixel_count: usize) -> Option<Segment> {
+        let start_time_in_ms = *self.start_time_in_ms.get_or_insert(time_in_ms);
+        let elapsed_in_ms = time_in_ms.saturating_sub(start_time_in_ms);
+        if elapsed_in_ms >= self.duration_in_ms {
+            return None;
+        }
+
+        let segment = match self.style {
+            NotificationStyle::Flash => {
+                let fade = elapsed_in_ms as f32 / self.duration_in_ms as f32;
+                Segment::full(BlendMode::AlphaOver, 1. - fade)
+            }
+            NotificationStyle::Pulse { period_in_ms } => {
+                let phase = if period_in_ms == 0 {
+                    0.
+                } else {
+                    (elapsed_in_ms % period_in_ms) as f32 / period_in_ms as f32
+                };
+                Segment::full(BlendMode::AlphaOver, (1. - (2. * PI * phase).cos()) / 2.)
+            }
+            NotificationStyle::Progress { progress } => {
+                let filled = (progress.clamp(0., 1.) * pixel_count as f32).round() as usize;
+                Segment::new(0..filled, BlendMode::AlphaOver, 1.)
+            }
+        };
+        Some(segment)
+    }
+}
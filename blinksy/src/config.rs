@@ -0,0 +1,213 @@
+//! # Device Configuration
+//!
+//! A [`DeviceConfig`] describes an installation: how many LEDs, which chipset, which color
+//! order, its segments, how it connects to the network, and how many LEDs show its status.
+//! With the config read from flash at boot, one firmware binary can serve many installations.
+//!
+//! With the `postcard` feature, configs are encoded with [`postcard`], without a heap:
+//! [`DeviceConfig::to_bytes`] to write a config, [`DeviceConfig::from_bytes`] to read it back.
+//! Each encoded config starts with its [`CONFIG_VERSION`], so old configs are rejected rather
+//! than misread.
+//!
+//! As layouts and drivers are chosen at compile time, the firmware matches on the config to
+//! select between the drivers it's built with, and builds its layout for the most LEDs it
+//! supports, sending only [`pixel_count`](DeviceConfig::pixel_count) of them.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # #[cfg(feature = "postcard")]
+//! # {
+//! use blinksy::{
+//!     color::RgbChannels,
+//!     compositor::{BlendMode, Segment},
+//!     config::{Chipset, DeviceConfig, NetworkMode},
+//! };
+//!
+//! let mut config = DeviceConfig::new(Chipset::Ws2812, 120);
+//! config.color_order = RgbChannels::GRB;
+//! config.network_mode = NetworkMode::AccessPoint;
+//! config.status_pixel_count = 1;
+//! config
+//!     .segments
+//!     .push(Segment::new(0..60, BlendMode::AlphaOver, 1.))
+//!     .unwrap();
+//!
+//! let mut buffer = [0; 256];
+//! let bytes = config.to_bytes(&mut buffer).unwrap();
+//!
+//! assert_eq!(DeviceConfig::from_bytes(bytes).unwrap(), config);
+//! # }
+//! ```
+
+use heapless::Vec;
+
+pub use crate::capacities::MAX_SEGMENTS;
+use crate::{color::RgbChannels, compositor::Segment};
+
+/// The version of the encoded config format.
+pub const CONFIG_VERSION: u8 = 2;
+
+/// The LED chipsets of a config.
+///
+/// See [`leds`](crate::leds) for their details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Chipset {
+    /// [`Ws2812`](crate::leds::Ws2812)
+    Ws2812,
+    /// [`Ws2813`](crate::leds::Ws2813)
+    Ws2813,
+    /// [`Ws2815`](crate::leds::Ws2815)
+    Ws2815,
+    /// [`Ws2816`](crate::leds::Ws2816)
+    Ws2816,
+    /// [`Sk6812`](crate::leds::Sk6812)
+    Sk6812,
+    /// [`Apa102`](crate::leds::Apa102)
+    Apa102,
+    /// [`Lpd8806`](crate::leds::Lpd8806)
+    Lpd8806,
+    /// [`P9813`](crate::leds::P9813)
+    P9813,
+    /// [`Ws2801`](crate::leds::Ws2801)
+    Ws2801,
+}
+
+impl Chipset {
+    /// Returns whether the chipset is clocked (data and clock), rather than clockless (only
+    /// data).
+    pub const fn is_clocked(self) -> bool {
+        matches!(
+            self,
+            Chipset::Apa102 | Chipset::Lpd8806 | Chipset::P9813 | Chipset::Ws2801
+        )
+    }
+}
+
+/// How the device connects to the network.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NetworkMode {
+    /// No network
+    #[default]
+    None,
+    /// Join an existing WiFi network
+    Station,
+    /// Host a WiFi network, for setup
+    AccessPoint,
+    /// Wired Ethernet
+    Ethernet,
+}
+
+/// The configuration of an installation.
+///
+/// # Type Parameters
+///
+/// - `SEGMENT_COUNT` - The most segments (default: [`MAX_SEGMENTS`])
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceConfig<const SEGMENT_COUNT: usize = MAX_SEGMENTS> {
+    /// The number of LEDs
+    pub pixel_count: u16,
+    /// The LED chipset
+    pub chipset: Chipset,
+    /// The order of the color channels, for LEDs wired differently from their chipset
+    pub color_order: RgbChannels,
+    /// The segments of the LEDs
+    pub segments: Vec<Segment, SEGMENT_COUNT>,
+    /// How the device connects to the network
+    pub network_mode: NetworkMode,
+    /// The number of pixels at the start of the chain reserved to show the status of the
+    /// device, before the pattern: see [`StatusPixels`](crate::driver::StatusPixels)
+    pub status_pixel_count: u8,
+}
+
+impl DeviceConfig {
+    /// Creates a config, with RGB color order, no segments, no network, and no status pixels.
+    ///
+    /// Holds up to [`MAX_SEGMENTS`] segments, see
+    /// [`with_segment_capacity`](Self::with_segment_capacity) for more or less.
+    ///
+    /// # Arguments
+    ///
+    /// - `chipset` - The LED chipset
+    /// - `pixel_count` - The number of LEDs
+    pub const fn new(chipset: Chipset, pixel_count: u16) -> Self {
+        Self::with_segment_capacity(chipset, pixel_count)
+    }
+}
+
+impl<const SEGMENT_COUNT: usize> DeviceConfig<SEGMENT_COUNT> {
+    /// Creates a config holding up to `SEGMENT_COUNT` segments, with RGB color order, no
+    /// segments, no network, and no status pixels.
+    ///
+    /// # Arguments
+    ///
+    /// - `chipset` - The LED chipset
+    /// - `pixel_count` - The number of LEDs
+    pub const fn with_segment_capacity(chipset: Chipset, pixel_count: u16) -> Self {
+        Self {
+            pixel_count,
+            chipset,
+            color_order: RgbChannels::RGB,
+            segments: Vec::new(),
+            network_mode: NetworkMode::None,
+            status_pixel_count: 0,
+        }
+    }
+}
+
+/// All types of errors that can happen while encoding or decoding a config.
+#[cfg(feature = "postcard")]
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConfigError {
+    /// Raised if the config was encoded with a different version
+    Version(u8),
+    /// Raised if the config can't be encoded or decoded
+    Postcard(postcard::Error),
+}
+
+#[cfg(feature = "postcard")]
+impl From<postcard::Error> for ConfigError {
+    fn from(error: postcard::Error) -> Self {
+        ConfigError::Postcard(error)
+    }
+}
+
+#[cfg(feature = "postcard")]
+impl<const SEGMENT_COUNT: usize> DeviceConfig<SEGMENT_COUNT> {
+    /// Decodes a config.
+    ///
+    /// # Arguments
+    ///
+    /// - `bytes` - The encoded config, e.g. as read from flash (trailing bytes are ignored)
+    ///
+    /// # Returns
+    ///
+    /// The config, or an error if the bytes aren't a config of this version
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConfigError> {
+        let (version, rest) = postcard::take_from_bytes::<u8>(bytes)?;
+        if version != CONFIG_VERSION {
+            return Err(ConfigError::Version(version));
+        }
+        let (config, _) = postcard::take_from_bytes::<Self>(rest)?;
+        Ok(config)
+    }
+
+    /// Encodes the config.
+    ///
+    /// # Arguments
+    ///
+    /// - `buffer` - The buffer to encode into
+    ///
+    /// # Returns
+    ///
+    /// The encoded bytes, or an error if the buffer is too small
+    pub fn to_bytes<'a>(&self, buffer: &'a mut [u8]) -> Result<&'a mut [u8], ConfigError> {
+        Ok(postcard::to_slice(&(CONFIG_VERSION, self), buffer)?)
+    }
+}
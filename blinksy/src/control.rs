@@ -25,6 +25,19 @@
 //!
 //! // Use the control system
 //! control.set_brightness(0.5);
+//! // Dim perceptually, so low brightness values stay useful
+//! control.set_brightness_curve(BrightnessCurve::Cie1931);
+//! // Ramp up brightness over 2 seconds on boot
+//! control.set_soft_start(2000);
+//! // Run the pattern at half speed
+//! control.set_time_scale(0.5);
+//! // Flash green for a second, on top of the pattern
+//! control.notify(LinearSrgb::new(0., 1., 0.), 1000, NotificationStyle::Flash);
+//! // Turn the LEDs off, and back on with the same brightness and pattern
+//! control.turn_off();
+//! control.turn_on();
+//! // Try writing a frame again if the driver fails, instead of returning the error
+//! control.set_error_policy(ErrorPolicy::Retry { attempts: 2 });
 //!
 //! // Main control loop
 //! loop {
@@ -59,12 +72,20 @@
 //! ```
 use core::marker::PhantomData;
 
+#[allow(unused_imports)]
+use num_traits::Float;
+
 use crate::{
-    color::{ColorCorrection, FromColor},
-    driver::Driver as DriverTrait,
+    color::{ColorCorrection, FromColor, LinearSrgb, Oklab},
+    compositor::{LinearSrgba, Notification, NotificationStyle, Segment},
+    driver::{ColorCapability, Driver as DriverTrait, GlobalBrightness},
     layout::LayoutForDim,
     markers::{Blocking, Dim1d, Dim2d, Dim3d, Set, Unset},
     pattern::Pattern as PatternTrait,
+    presence::{PresenceFade, PresenceSensor, PresenceTimeout},
+    projection::Projected,
+    remap::{PixelMap, Remapped},
+    replicate::Replicated,
 };
 #[cfg(feature = "async")]
 use crate::{driver::DriverAsync as DriverAsyncTrait, markers::Async};
@@ -79,9 +100,22 @@ use crate::{driver::DriverAsync as DriverAsyncTrait, markers::Async};
 ///
 /// You can use [`Control`] to
 ///
-/// - Set a global brightness
+/// - Replace the pattern
+/// - Replace the driver, e.g. to change the chipset at runtime
+/// - Turn the LEDs off and on, keeping the brightness and pattern
+/// - Set a global brightness, with a [`BrightnessCurve`]
+/// - Step through brightness presets, e.g. on button presses
+/// - Ramp up brightness on boot (soft-start)
+/// - Only light up after motion, from a [presence sensor](crate::presence)
+/// - Slow down, speed up, or pause the pattern's time
+/// - Overlay a temporary notification on top of the pattern
+/// - Drop to an idle tick rate while frames are unchanged
+/// - Retry or skip frames the driver fails to write, with an [`ErrorPolicy`]
+/// - Shift the hue or scale the saturation of any pattern, with a tint
 /// - Set a global color correction.
+/// - Lock the global brightness field of LEDs like the APA102, e.g. for filming
 /// - Send a frame of colors from the pattern to the driver.
+/// - Send an explicit frame of colors to the driver, bypassing the pattern.
 ///
 /// Tip: Use [`ControlBuilder`] to build your [`Control`] struct.
 ///
@@ -94,6 +128,7 @@ use crate::{driver::DriverAsync as DriverAsyncTrait, markers::Async};
 // * `Layout` - The [`layout`](crate::layout) type
 // * `Pattern` - The [`pattern`](crate::pattern) type
 // * `Driver` - The LED [`driver`](crate::driver) type
+// * `Hooks` - The [frame hooks](Control::on_frame), or `()` for none
 pub struct Control<
     const PIXEL_COUNT: usize,
     const FRAME_BUFFER_SIZE: usize,
@@ -102,6 +137,7 @@ pub struct Control<
     Layout,
     Pattern,
     Driver,
+    Hooks = (),
 > where
     Layout: LayoutForDim<Dim>,
     Pattern: PatternTrait<Dim, Layout>,
@@ -112,7 +148,376 @@ pub struct Control<
     pattern: Pattern,
     driver: Driver,
     brightness: f32,
+    brightness_step: Option<usize>,
+    brightness_curve: BrightnessCurve,
     correction: ColorCorrection,
+    global_brightness: GlobalBrightness,
+    tint: Option<Tint>,
+    frame_hooks: Hooks,
+    soft_start: SoftStart,
+    presence: Option<PresenceFade>,
+    pattern_clock: PatternClock,
+    frame_rate: FrameRate,
+    error_policy: ErrorPolicy,
+    stats: ControlStats,
+    notification: Option<Notification>,
+    is_color_capability_set: bool,
+    is_global_brightness_set: bool,
+    is_on: bool,
+    is_blanked: bool,
+}
+
+/// How the global brightness value maps to the actual output level.
+///
+/// Perceived lightness is far from linear in output level: half the output looks much brighter
+/// than half as bright, so with a linear curve, the bottom half of a brightness slider covers
+/// most of the visible range. A perceptual curve spreads this range over the whole slider.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BrightnessCurve {
+    /// Output level is the brightness value
+    #[default]
+    Linear,
+    /// Brightness value is the perceived lightness (CIE 1931 L*, scaled to 0.0 to 1.0)
+    Cie1931,
+    /// Output level is the brightness value squared, a cheap approximation of
+    /// [`Cie1931`](Self::Cie1931)
+    Square,
+}
+
+impl BrightnessCurve {
+    /// Maps a brightness value to an output level.
+    ///
+    /// # Arguments
+    ///
+    /// - `brightness` - Brightness value from 0.0 (off) to 1.0 (full)
+    ///
+    /// # Returns
+    ///
+    /// Output level from 0.0 (off) to 1.0 (full)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blinksy::BrightnessCurve;
+    ///
+    /// assert_eq!(BrightnessCurve::Linear.apply(0.5), 0.5);
+    /// assert_eq!(BrightnessCurve::Square.apply(0.5), 0.25);
+    ///
+    /// // Half lightness is less than a fifth of the output
+    /// let level = BrightnessCurve::Cie1931.apply(0.5);
+    /// assert!((level - 0.184).abs() < 1e-3);
+    /// ```
+    pub fn apply(self, brightness: f32) -> f32 {
+        let brightness = brightness.clamp(0., 1.);
+        match self {
+            BrightnessCurve::Linear => brightness,
+            BrightnessCurve::Cie1931 => {
+                let lightness = brightness * 100.;
+                if lightness <= 8. {
+                    lightness / 903.3
+                } else {
+                    let cube_root = (lightness + 16.) / 116.;
+                    cube_root * cube_root * cube_root
+                }
+            }
+            BrightnessCurve::Square => brightness * brightness,
+        }
+    }
+}
+
+/// The default duration of the soft-start brightness ramp, in milliseconds.
+pub const DEFAULT_SOFT_START_IN_MS: u64 = 1000;
+
+/// Brightness ramp from the first frame, to limit inrush current at power-up.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct SoftStart {
+    duration_in_ms: u64,
+    start_time_in_ms: Option<u64>,
+    scale: f32,
+}
+
+impl SoftStart {
+    fn new(duration_in_ms: u64) -> Self {
+        Self {
+            duration_in_ms,
+            start_time_in_ms: None,
+            scale: 0.0,
+        }
+    }
+
+    /// Updates and returns the brightness scale for the current time.
+    fn update(&mut self, time_in_ms: u64) -> f32 {
+        if self.duration_in_ms == 0 {
+            self.scale = 1.0;
+            return self.scale;
+        }
+        let start_time_in_ms = *self.start_time_in_ms.get_or_insert(time_in_ms);
+        let elapsed_in_ms = time_in_ms.saturating_sub(start_time_in_ms);
+        self.scale = (elapsed_in_ms as f32 / self.duration_in_ms as f32).min(1.0);
+        self.scale
+    }
+}
+
+/// Hue shift and saturation scale, applied to the pattern's colors.
+///
+/// Rotates and scales the chroma in Oklab, so the perceived lightness is kept.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct Tint {
+    hue_shift: f32,
+    cos: f32,
+    sin: f32,
+    saturation_scale: f32,
+}
+
+impl Tint {
+    fn new(hue_shift: f32, saturation_scale: f32) -> Self {
+        let (sin, cos) = (hue_shift * core::f32::consts::TAU).sin_cos();
+        Self {
+            hue_shift,
+            cos,
+            sin,
+            saturation_scale: saturation_scale.max(0.),
+        }
+    }
+
+    fn apply(&self, color: LinearSrgb) -> LinearSrgb {
+        let Oklab { l, a, b } = Oklab::from_linear_srgb(color);
+        let a_tinted = (a * self.cos - b * self.sin) * self.saturation_scale;
+        let b_tinted = (a * self.sin + b * self.cos) * self.saturation_scale;
+        let LinearSrgb { red, green, blue } = Oklab::new(l, a_tinted, b_tinted).to_linear_srgb();
+        LinearSrgb::new(red.max(0.), green.max(0.), blue.max(0.))
+    }
+}
+
+/// The hooks run on each frame, between the pattern and the driver: see [`Control::on_frame`].
+///
+/// Implemented for `()`, for no hooks, and for `(Hooks, Hook)`, for a hook added after other
+/// hooks. So each hook is owned by the [`Control`], as part of its type.
+pub trait FrameHooks {
+    /// Whether there are no hooks, so frames needn't be buffered.
+    const IS_EMPTY: bool;
+
+    /// Runs each hook on the colors of every pixel, in the order they were added.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels` - The colors of every pixel, which the hooks may change
+    fn run(&mut self, pixels: &mut [LinearSrgb]);
+}
+
+impl FrameHooks for () {
+    const IS_EMPTY: bool = true;
+
+    fn run(&mut self, _pixels: &mut [LinearSrgb]) {}
+}
+
+impl<Hooks, Hook> FrameHooks for (Hooks, Hook)
+where
+    Hooks: FrameHooks,
+    Hook: FnMut(&mut [LinearSrgb]),
+{
+    const IS_EMPTY: bool = false;
+
+    fn run(&mut self, pixels: &mut [LinearSrgb]) {
+        self.0.run(pixels);
+        (self.1)(pixels);
+    }
+}
+
+/// What [`Control::tick`] does when the driver fails to write a frame.
+///
+/// Transient failures (e.g. a busy RMT channel or SPI bus) usually pass by the next frame, so
+/// there's no need to stop the whole control loop.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorPolicy {
+    /// Write the encoded frame again, up to this many more times, then return the error
+    Retry {
+        /// The number of writes after the first
+        attempts: u8,
+    },
+    /// Drop the frame, without returning the error, and show the next frame on the next tick
+    SkipFrame,
+    /// Return the error
+    #[default]
+    Fatal,
+}
+
+/// Statistics of the frames written by [`Control::tick`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ControlStats {
+    /// The number of frames written
+    pub frames: u32,
+    /// The number of failed writes, including retries
+    pub errors: u32,
+    /// The number of frames dropped by [`ErrorPolicy::SkipFrame`]
+    pub skipped_frames: u32,
+}
+
+/// The default interval between ticks, in milliseconds (about 60 frames per second).
+pub const DEFAULT_FRAME_INTERVAL_IN_MS: u64 = 16;
+
+/// When to drop to an idle tick rate, while frames are unchanged.
+///
+/// See [`Control::set_idle_frame_rate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdleFrameRate {
+    /// Interval between ticks while idle, in milliseconds (default: 1000)
+    pub interval_in_ms: u64,
+    /// How long frames must be unchanged before idling, in milliseconds (default: 2000)
+    pub after_in_ms: u64,
+}
+
+impl Default for IdleFrameRate {
+    fn default() -> Self {
+        Self {
+            interval_in_ms: 1000,
+            after_in_ms: 2000,
+        }
+    }
+}
+
+/// Tick rate, which drops to an idle rate while frames are unchanged.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct FrameRate {
+    interval_in_ms: u64,
+    idle: Option<IdleFrameRate>,
+    /// Hash of the last frame, or `None` to treat the next frame as changed.
+    last_frame_hash: Option<u64>,
+    unchanged_since_in_ms: u64,
+    last_time_in_ms: u64,
+}
+
+impl FrameRate {
+    fn new() -> Self {
+        Self {
+            interval_in_ms: DEFAULT_FRAME_INTERVAL_IN_MS,
+            idle: None,
+            last_frame_hash: None,
+            unchanged_since_in_ms: 0,
+            last_time_in_ms: 0,
+        }
+    }
+
+    /// Records the hash of the frame shown at the current time.
+    fn update(&mut self, frame_hash: u64, time_in_ms: u64) {
+        if self.last_frame_hash != Some(frame_hash) {
+            self.unchanged_since_in_ms = time_in_ms;
+        }
+        self.last_frame_hash = Some(frame_hash);
+        self.last_time_in_ms = time_in_ms;
+    }
+
+    /// Resumes the full rate, as the next frame may change.
+    fn wake(&mut self) {
+        self.last_frame_hash = None;
+    }
+
+    fn is_idle(&self) -> bool {
+        match self.idle {
+            Some(idle) => {
+                self.last_frame_hash.is_some()
+                    && self
+                        .last_time_in_ms
+                        .saturating_sub(self.unchanged_since_in_ms)
+                        >= idle.after_in_ms
+            }
+            None => false,
+        }
+    }
+
+    fn current_interval_in_ms(&self) -> u64 {
+        match self.idle {
+            Some(idle) if self.is_idle() => idle.interval_in_ms,
+            _ => self.interval_in_ms,
+        }
+    }
+}
+
+/// Hashes frames, with 64-bit FNV-1a, to find unchanged frames.
+struct FrameHasher(u64);
+
+impl FrameHasher {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, value: f32) {
+        for byte in value.to_bits().to_le_bytes() {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_color(&mut self, color: &LinearSrgb) {
+        self.write(color.red);
+        self.write(color.green);
+        self.write(color.blue);
+    }
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// The frame hash while the LEDs are off.
+const OFF_FRAME_HASH: u64 = 0;
+
+/// Pattern time, warped from real time by a time scale and pausing.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct PatternClock {
+    time_scale: f32,
+    is_paused: bool,
+    last_time_in_ms: Option<u64>,
+    time_in_ms: u64,
+    /// Fraction of a millisecond of pattern time not yet added to `time_in_ms`.
+    remainder_in_ms: f32,
+}
+
+impl PatternClock {
+    fn new() -> Self {
+        Self {
+            time_scale: 1.0,
+            is_paused: false,
+            last_time_in_ms: None,
+            time_in_ms: 0,
+            remainder_in_ms: 0.,
+        }
+    }
+
+    /// Advances and returns the pattern time for the current real time.
+    ///
+    /// Pattern time starts at the real time of the first update, so with the default time
+    /// scale and no pausing, pattern time is real time.
+    fn update(&mut self, time_in_ms: u64) -> u64 {
+        match self.last_time_in_ms {
+            None => self.time_in_ms = time_in_ms,
+            Some(last_time_in_ms) if !self.is_paused => {
+                let elapsed_in_ms = time_in_ms.saturating_sub(last_time_in_ms);
+                let scaled_in_ms = elapsed_in_ms as f32 * self.time_scale + self.remainder_in_ms;
+                // Truncates toward zero, so a negative remainder carries over when reversed.
+                let whole_in_ms = scaled_in_ms.trunc();
+                self.remainder_in_ms = scaled_in_ms - whole_in_ms;
+                // Saturates at 0 when running backwards, instead of wrapping around.
+                self.time_in_ms = if whole_in_ms < 0. {
+                    self.time_in_ms.saturating_sub(-whole_in_ms as u64)
+                } else {
+                    self.time_in_ms.saturating_add(whole_in_ms as u64)
+                };
+            }
+            Some(_) => {}
+        }
+        self.last_time_in_ms = Some(time_in_ms);
+        self.time_in_ms
+    }
 }
 
 impl<
@@ -146,17 +551,545 @@ where
             pattern,
             driver,
             brightness: 1.0,
+            brightness_step: None,
+            brightness_curve: BrightnessCurve::default(),
             correction: ColorCorrection::default(),
+            global_brightness: GlobalBrightness::default(),
+            tint: None,
+            frame_hooks: (),
+            soft_start: SoftStart::new(DEFAULT_SOFT_START_IN_MS),
+            presence: None,
+            pattern_clock: PatternClock::new(),
+            frame_rate: FrameRate::new(),
+            error_policy: ErrorPolicy::default(),
+            stats: ControlStats::default(),
+            notification: None,
+            is_color_capability_set: false,
+            is_global_brightness_set: false,
+            is_on: true,
+            is_blanked: false,
+        }
+    }
+}
+
+impl<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Exec,
+        Layout,
+        Pattern,
+        Driver,
+        Hooks,
+    > Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, Driver, Hooks>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+    Hooks: FrameHooks,
+{
+    /// Sets the duration of the soft-start brightness ramp.
+    ///
+    /// From the first [`tick`](Self::tick), brightness ramps up linearly from 0.0 to the set
+    /// brightness over this duration, so full-brightness patterns don't draw a sudden inrush
+    /// of current at power-up (which browns out many 5V supplies).
+    ///
+    /// Defaults to [`DEFAULT_SOFT_START_IN_MS`]. The ramp starts with the first frame, from a
+    /// tick or `show_pixels`.
+    ///
+    /// # Arguments
+    ///
+    /// - `duration_in_ms` - Duration of the ramp in milliseconds, or 0 to disable
+    pub fn set_soft_start(&mut self, duration_in_ms: u64) {
+        self.frame_rate.wake();
+        self.soft_start = SoftStart::new(duration_in_ms);
+    }
+
+    /// Sets a presence timeout, so the LEDs only light up after presence (e.g. motion on the
+    /// stairs), then fade out once the timeout passes.
+    ///
+    /// Until presence is detected, with [`update_presence`](Self::update_presence) or
+    /// [`presence_detected`](Self::presence_detected), the LEDs stay dark.
+    ///
+    /// # Arguments
+    ///
+    /// - `timeout` - How long to stay on after presence, or `None` to always be on (default)
+    pub fn set_presence_timeout(&mut self, timeout: Option<PresenceTimeout>) {
+        self.frame_rate.wake();
+        self.presence = timeout.map(PresenceFade::new);
+    }
+
+    /// Reads a presence sensor, restarting the presence timeout if someone is present.
+    ///
+    /// # Arguments
+    ///
+    /// - `sensor` - The presence sensor
+    /// - `time_in_ms` - Current time in milliseconds
+    pub fn update_presence<Sensor>(&mut self, sensor: &mut Sensor, time_in_ms: u64)
+    where
+        Sensor: PresenceSensor,
+    {
+        if sensor.is_present() {
+            self.presence_detected(time_in_ms);
+        }
+    }
+
+    /// Restarts the presence timeout, as someone is present.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - Current time in milliseconds
+    pub fn presence_detected(&mut self, time_in_ms: u64) {
+        if let Some(presence) = &mut self.presence {
+            if !presence.is_held(time_in_ms) {
+                self.frame_rate.wake();
+            }
+            presence.detect(time_in_ms);
+        }
+    }
+
+    /// Returns the brightness scale from the presence timeout, for the current time.
+    fn update_presence_scale(&mut self, time_in_ms: u64) -> f32 {
+        match &mut self.presence {
+            Some(presence) => presence.update(time_in_ms),
+            None => 1.,
         }
     }
 
+    /// Returns the brightness scale from the presence timeout, as of the last tick.
+    fn presence_scale(&self) -> f32 {
+        self.presence.as_ref().map_or(1., |presence| presence.scale)
+    }
+
+    /// Sets what [`tick`](Self::tick) does when the driver fails to write a frame, including
+    /// the all-off frame while the LEDs are [off](Self::turn_off).
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` - The error policy (default: [`ErrorPolicy::Fatal`])
+    pub fn set_error_policy(&mut self, policy: ErrorPolicy) {
+        self.error_policy = policy;
+    }
+
+    /// Returns the statistics of the frames written by [`tick`](Self::tick), e.g. to report
+    /// errors skipped by the [error policy](Self::set_error_policy).
+    pub fn stats(&self) -> ControlStats {
+        self.stats
+    }
+
+    /// Clears the statistics.
+    pub fn reset_stats(&mut self) {
+        self.stats = ControlStats::default();
+    }
+
+    /// Returns the number of writes to try for a frame, from the error policy.
+    fn write_attempts(&self) -> u32 {
+        match self.error_policy {
+            ErrorPolicy::Retry { attempts } => attempts as u32 + 1,
+            ErrorPolicy::SkipFrame | ErrorPolicy::Fatal => 1,
+        }
+    }
+
+    /// Records the result of writing a frame, and applies the error policy to a failure.
+    ///
+    /// # Returns
+    ///
+    /// Whether the frame was written, or the error to return
+    fn record_write<Error>(&mut self, result: Result<(), Error>) -> Result<bool, Error> {
+        match result {
+            Ok(()) => {
+                self.stats.frames = self.stats.frames.saturating_add(1);
+                Ok(true)
+            }
+            Err(_) if self.error_policy == ErrorPolicy::SkipFrame => {
+                self.stats.skipped_frames = self.stats.skipped_frames.saturating_add(1);
+                Ok(false)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Sets the interval between ticks at the full frame rate.
+    ///
+    /// The control doesn't wait itself: wait for [`frame_interval_in_ms`](Self::frame_interval_in_ms)
+    /// between ticks in your loop.
+    ///
+    /// # Arguments
+    ///
+    /// - `interval_in_ms` - Interval in milliseconds (default: [`DEFAULT_FRAME_INTERVAL_IN_MS`])
+    pub fn set_frame_interval(&mut self, interval_in_ms: u64) {
+        self.frame_rate.interval_in_ms = interval_in_ms;
+    }
+
+    /// Sets when to drop to an idle tick rate, while frames are unchanged (a static pattern,
+    /// or the LEDs turned off), to save CPU time and power, e.g. for signage.
+    ///
+    /// The full rate resumes when a frame changes, or when the control is changed (pattern,
+    /// brightness, notification, ...). Call [`wake`](Self::wake) to resume it for any other
+    /// external event.
+    ///
+    /// # Arguments
+    ///
+    /// - `idle` - When to idle, or `None` to always tick at the full rate (default)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// control.set_idle_frame_rate(Some(IdleFrameRate::default()));
+    ///
+    /// loop {
+    ///     control.tick(/* current time in milliseconds */).unwrap();
+    ///     sleep_ms(control.frame_interval_in_ms());
+    /// }
+    /// ```
+    pub fn set_idle_frame_rate(&mut self, idle: Option<IdleFrameRate>) {
+        self.frame_rate.wake();
+        self.frame_rate.idle = idle;
+    }
+
+    /// Returns how long to wait before the next tick, in milliseconds: the idle interval while
+    /// idle, otherwise the full rate interval.
+    pub fn frame_interval_in_ms(&self) -> u64 {
+        self.frame_rate.current_interval_in_ms()
+    }
+
+    /// Returns whether ticks are at the idle rate.
+    pub fn is_idle(&self) -> bool {
+        self.frame_rate.is_idle()
+    }
+
+    /// Resumes the full frame rate, e.g. for an external event which may change the next
+    /// frame.
+    pub fn wake(&mut self) {
+        self.frame_rate.wake();
+    }
+
+    /// Turns the LEDs off, keeping the brightness, pattern, and all other state.
+    ///
+    /// The next [`tick`](Self::tick) shows an all-off frame, then ticks show nothing until
+    /// [`turn_on`](Self::turn_on). Check [`is_on`](Self::is_on) to also switch a power relay.
+    pub fn turn_off(&mut self) {
+        self.frame_rate.wake();
+        self.is_on = false;
+        self.is_blanked = false;
+    }
+
+    /// Turns the LEDs back on, with the brightness and pattern from before
+    /// [`turn_off`](Self::turn_off).
+    ///
+    /// Brightness ramps up again with the [soft-start](Self::set_soft_start), as the LEDs may
+    /// have just been powered on.
+    pub fn turn_on(&mut self) {
+        self.frame_rate.wake();
+        if !self.is_on {
+            self.is_on = true;
+            self.is_blanked = false;
+            self.soft_start = SoftStart::new(self.soft_start.duration_in_ms);
+        }
+    }
+
+    /// Turns the LEDs on or off, e.g. from a remote power state.
+    ///
+    /// # Arguments
+    ///
+    /// - `is_on` - Whether the LEDs are on
+    pub fn set_on(&mut self, is_on: bool) {
+        if is_on {
+            self.turn_on();
+        } else {
+            self.turn_off();
+        }
+    }
+
+    /// Returns whether the LEDs are on.
+    pub fn is_on(&self) -> bool {
+        self.is_on
+    }
+
     /// Sets the overall brightness level.
     ///
     /// # Arguments
     ///
     /// - `brightness` - Brightness level from 0.0 (off) to 1.0 (full)
     pub fn set_brightness(&mut self, brightness: f32) {
+        self.frame_rate.wake();
         self.brightness = brightness;
+        self.brightness_step = None;
+    }
+
+    /// Returns the overall brightness level, before the brightness curve.
+    pub fn brightness(&self) -> f32 {
+        self.brightness
+    }
+
+    /// Steps to the next brightness preset, wrapping around after the last.
+    ///
+    /// The selected step is kept across calls. If no step is selected yet (or the brightness was
+    /// since set with [`set_brightness`](Self::set_brightness)), steps to the first preset
+    /// brighter than the current brightness.
+    ///
+    /// To restore the step after a power cycle, save [`brightness_step`](Self::brightness_step)
+    /// and load it with [`set_brightness_step`](Self::set_brightness_step).
+    ///
+    /// # Arguments
+    ///
+    /// - `steps` - The brightness presets, each from 0.0 (off) to 1.0 (full)
+    ///
+    /// # Returns
+    ///
+    /// The new brightness level
+    pub fn cycle_brightness(&mut self, steps: &[f32]) -> f32 {
+        if steps.is_empty() {
+            return self.brightness;
+        }
+        let index = match self.brightness_step {
+            Some(index) => (index + 1) % steps.len(),
+            None => steps
+                .iter()
+                .position(|&step| step > self.brightness)
+                .unwrap_or(0),
+        };
+        self.set_brightness_step(steps, index);
+        self.brightness
+    }
+
+    /// Selects a brightness preset, e.g. when restoring saved settings.
+    ///
+    /// Out of range indices are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// - `steps` - The brightness presets, as given to [`cycle_brightness`](Self::cycle_brightness)
+    /// - `index` - Index of the preset to select
+    pub fn set_brightness_step(&mut self, steps: &[f32], index: usize) {
+        if let Some(&brightness) = steps.get(index) {
+            self.frame_rate.wake();
+            self.brightness = brightness;
+            self.brightness_step = Some(index);
+        }
+    }
+
+    /// Returns the index of the selected brightness preset, if one is selected.
+    pub fn brightness_step(&self) -> Option<usize> {
+        self.brightness_step
+    }
+
+    /// Sets how the brightness level maps to the output level.
+    ///
+    /// Defaults to [`BrightnessCurve::Linear`].
+    ///
+    /// # Arguments
+    ///
+    /// - `curve` - Brightness response curve
+    pub fn set_brightness_curve(&mut self, curve: BrightnessCurve) {
+        self.frame_rate.wake();
+        self.brightness_curve = curve;
+    }
+
+    /// Sets a global tint, applied to the pattern's colors before any notification.
+    ///
+    /// Use this to warm up, cool down, or desaturate any pattern, e.g. from a remote or UI,
+    /// without changing the pattern. The perceived lightness of each color is kept.
+    ///
+    /// # Arguments
+    ///
+    /// - `hue_shift` - Rotation of the hue, in turns (0.0 to 1.0, default: 0.0)
+    /// - `saturation_scale` - Scale of the saturation, e.g. 0.0 for grayscale (default: 1.0,
+    ///   negative values are treated as 0.0)
+    pub fn set_tint(&mut self, hue_shift: f32, saturation_scale: f32) {
+        self.frame_rate.wake();
+        self.tint = if hue_shift == 0. && saturation_scale == 1. {
+            None
+        } else {
+            Some(Tint::new(hue_shift, saturation_scale))
+        };
+    }
+
+    /// Returns the global tint, as the hue shift and the saturation scale.
+    ///
+    /// Without a tint, returns `(0.0, 1.0)`.
+    pub fn tint(&self) -> (f32, f32) {
+        self.tint
+            .map_or((0., 1.), |tint| (tint.hue_shift, tint.saturation_scale))
+    }
+
+    /// Adds a hook, run on each frame between the pattern and the driver.
+    ///
+    /// Use this for small customizations of any pattern, without writing a wrapper pattern or
+    /// driver: clamping a region, or showing a status pixel. Each hook is given the colors of
+    /// every pixel, after the [tint](Self::set_tint) and any notification, and may change
+    /// them. Hooks run in the order they were added, also on frames shown with `show_pixels`.
+    /// With any hooks, each frame is buffered in full before being encoded.
+    ///
+    /// The hook is owned by the control system, as part of its type, so this returns a new
+    /// control.
+    ///
+    /// # Arguments
+    ///
+    /// - `hook` - The hook, e.g. a closure
+    ///
+    /// # Returns
+    ///
+    /// The control with the hook added
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blinksy::{
+    ///     color::LinearSrgb,
+    ///     layout::Layout1d,
+    ///     layout1d,
+    ///     patterns::rainbow::{Rainbow, RainbowParams},
+    ///     ControlBuilder,
+    /// };
+    /// # use blinksy::driver::NullDriver;
+    ///
+    /// layout1d!(Layout, 60);
+    ///
+    /// let is_connected = true;
+    ///
+    /// let mut control = ControlBuilder::new_1d()
+    ///     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+    ///     .with_pattern::<Rainbow>(RainbowParams::default())
+    /// #   .with_driver(NullDriver)
+    /// #   .with_frame_buffer_size::<0>()
+    ///     // ...
+    ///     .build()
+    ///     // Keep the last 10 LEDs dim, e.g. as they face a window
+    ///     .on_frame(|pixels: &mut [LinearSrgb]| {
+    ///         for pixel in pixels.iter_mut().skip(50) {
+    ///             pixel.red = pixel.red.min(0.1);
+    ///             pixel.green = pixel.green.min(0.1);
+    ///             pixel.blue = pixel.blue.min(0.1);
+    ///         }
+    ///     })
+    ///     // Show the first LED in green once connected, e.g. as a status pixel
+    ///     .on_frame(move |pixels: &mut [LinearSrgb]| {
+    ///         if is_connected {
+    ///             pixels[0] = LinearSrgb::new(0., 1., 0.);
+    ///         }
+    ///     });
+    ///
+    /// control.tick(0).unwrap();
+    /// ```
+    #[must_use]
+    pub fn on_frame<Hook>(
+        self,
+        hook: Hook,
+    ) -> Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, Driver, (Hooks, Hook)>
+    where
+        Hook: FnMut(&mut [LinearSrgb]),
+    {
+        let (mut control, driver, hooks) = self.into_parts();
+        control.frame_rate.wake();
+        control.with_parts(driver, (hooks, hook))
+    }
+
+    /// Removes all hooks added with [`on_frame`](Self::on_frame).
+    ///
+    /// # Returns
+    ///
+    /// The control without hooks, and the hooks
+    pub fn clear_frame_hooks(
+        self,
+    ) -> (
+        Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, Driver>,
+        Hooks,
+    ) {
+        let (mut control, driver, hooks) = self.into_parts();
+        control.frame_rate.wake();
+        (control.with_parts(driver, ()), hooks)
+    }
+
+    /// Returns the output level of the brightness, after the brightness curve.
+    fn curved_brightness(&self) -> f32 {
+        self.brightness_curve.apply(self.brightness)
+    }
+
+    /// Sets how fast time passes for the pattern, relative to real time.
+    ///
+    /// Every pattern is given a warped timestamp, so any pattern can be slowed down or sped up
+    /// without its own speed parameter. Changes apply from the next [`tick`](Self::tick), without
+    /// a jump in the pattern's time.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_scale` - Speed of pattern time, e.g. 0.5 for half speed or 2.0 for double speed
+    ///   (default: 1.0). Negative values run the pattern in reverse, e.g. -1.0 for backwards
+    ///   at normal speed, until pattern time reaches 0.
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.frame_rate.wake();
+        self.pattern_clock.time_scale = time_scale;
+    }
+
+    /// Freezes the pattern's time, until [`resume`](Self::resume).
+    ///
+    /// Ticks still show the pattern, so brightness changes are still applied.
+    pub fn pause(&mut self) {
+        self.pattern_clock.is_paused = true;
+    }
+
+    /// Resumes the pattern's time from where it was paused.
+    pub fn resume(&mut self) {
+        self.frame_rate.wake();
+        self.pattern_clock.is_paused = false;
+    }
+
+    /// Returns whether the pattern's time is paused.
+    pub fn is_paused(&self) -> bool {
+        self.pattern_clock.is_paused
+    }
+
+    /// Shows a notification on top of the pattern, for device feedback like "WiFi connected" or
+    /// "OTA in progress".
+    ///
+    /// The notification is blended over the pattern's colors, without disrupting the pattern,
+    /// and starts from the next [`tick`](Self::tick). It replaces any current notification, so
+    /// call this again to update the progress of a [`NotificationStyle::Progress`] bar.
+    ///
+    /// # Arguments
+    ///
+    /// - `color` - The color of the overlay, which may be partially transparent, as
+    ///   [`LinearSrgba`]
+    /// - `duration_in_ms` - How long to show the overlay, in milliseconds
+    /// - `style` - How the overlay is shown
+    pub fn notify<C>(&mut self, color: C, duration_in_ms: u64, style: NotificationStyle)
+    where
+        LinearSrgba: FromColor<C>,
+    {
+        self.frame_rate.wake();
+        self.notification = Some(Notification::new(color, duration_in_ms, style));
+    }
+
+    /// Removes the current notification, if any.
+    pub fn clear_notification(&mut self) {
+        self.frame_rate.wake();
+        self.notification = None;
+    }
+
+    /// Returns whether a notification is shown.
+    pub fn is_notifying(&self) -> bool {
+        self.notification.is_some()
+    }
+
+    /// Advances the notification, and returns its color and segment, if still shown.
+    fn update_notification(&mut self, time_in_ms: u64) -> Option<(LinearSrgba, Segment)> {
+        let notification = self.notification.as_mut()?;
+        match notification.update(time_in_ms, PIXEL_COUNT) {
+            Some(segment) => Some((notification.color(), segment)),
+            None => {
+                self.notification = None;
+                None
+            }
+        }
+    }
+
+    /// Whether a frame needs the pattern's colors as [`LinearSrgb`]: for a tint, a notification,
+    /// frame hooks, or the hash of the idle frame rate.
+    fn is_overlaid(&self, notification: &Option<(LinearSrgba, Segment)>) -> bool {
+        self.tint.is_some()
+            || notification.is_some()
+            || !Hooks::IS_EMPTY
+            || self.frame_rate.idle.is_some()
     }
 
     /// Sets a color correction.
@@ -165,23 +1098,230 @@ where
     ///
     /// - `correction` - Color correction factors
     pub fn set_color_correction(&mut self, correction: ColorCorrection) {
+        self.frame_rate.wake();
         self.correction = correction;
     }
+
+    /// Sets how the driver chooses the global brightness field of the LEDs, if they have one
+    /// (like the [`APA102`](crate::leds::Apa102)).
+    ///
+    /// Lock it for camera and film work, so its slower PWM doesn't flicker between frames. See
+    /// [`GlobalBrightness`].
+    ///
+    /// # Arguments
+    ///
+    /// - `global_brightness` - How to choose the global brightness field (default:
+    ///   [`GlobalBrightness::Adaptive`])
+    pub fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.frame_rate.wake();
+        self.global_brightness = global_brightness;
+        self.is_global_brightness_set = false;
+    }
+
+    /// Returns how the driver chooses the global brightness field of the LEDs.
+    pub fn global_brightness(&self) -> GlobalBrightness {
+        self.global_brightness
+    }
+
+    /// Replaces the pattern.
+    ///
+    /// To switch between different pattern types, combine them with
+    /// [`pattern_set!`](crate::pattern_set) and select one with a
+    /// [`PatternRegistry`](crate::registry::PatternRegistry).
+    ///
+    /// # Arguments
+    ///
+    /// - `pattern` - The new pattern
+    pub fn set_pattern(&mut self, pattern: Pattern) {
+        self.frame_rate.wake();
+        self.pattern = pattern;
+        self.is_color_capability_set = false;
+    }
+
+    /// Returns the driver.
+    pub fn driver(&self) -> &Driver {
+        &self.driver
+    }
+
+    /// Returns the driver mutably, e.g. to reconfigure it in place.
+    pub fn driver_mut(&mut self) -> &mut Driver {
+        &mut self.driver
+    }
+
+    /// Replaces the driver, keeping the pattern and all other state.
+    ///
+    /// Use this to re-initialize the output at runtime, without rebooting: the pattern carries
+    /// on from where it was.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The new driver
+    ///
+    /// # Returns
+    ///
+    /// The previous driver, e.g. to release its peripherals
+    pub fn replace_driver(&mut self, driver: Driver) -> Driver {
+        self.frame_rate.wake();
+        self.is_global_brightness_set = false;
+        core::mem::replace(&mut self.driver, driver)
+    }
+
+    /// Changes to a driver of a different type, keeping the pattern and all other state.
+    ///
+    /// Use this to change the chipset at runtime (e.g. from a config UI), without rebooting.
+    /// As the driver type is part of the control type, this returns a new control.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `NewDriver` - The new driver type
+    /// - `NEW_FRAME_BUFFER_SIZE` - The frame buffer size of the new driver
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The new driver
+    ///
+    /// # Returns
+    ///
+    /// The control with the new driver, and the previous driver
+    pub fn change_driver<NewDriver, const NEW_FRAME_BUFFER_SIZE: usize>(
+        self,
+        driver: NewDriver,
+    ) -> (
+        Control<PIXEL_COUNT, NEW_FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, NewDriver, Hooks>,
+        Driver,
+    ) {
+        let (mut control, previous, hooks) = self.into_parts();
+        control.is_color_capability_set = false;
+        control.is_global_brightness_set = false;
+        control.is_blanked = false;
+        control.frame_rate.last_frame_hash = None;
+        (control.with_parts(driver, hooks), previous)
+    }
+
+    /// Splits the control into its state, without the driver and the hooks, and them.
+    fn into_parts(
+        self,
+    ) -> (
+        Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, (), ()>,
+        Driver,
+        Hooks,
+    ) {
+        let control = Control {
+            dim: self.dim,
+            exec: self.exec,
+            layout: self.layout,
+            pattern: self.pattern,
+            driver: (),
+            brightness: self.brightness,
+            brightness_step: self.brightness_step,
+            brightness_curve: self.brightness_curve,
+            correction: self.correction,
+            global_brightness: self.global_brightness,
+            tint: self.tint,
+            frame_hooks: (),
+            soft_start: self.soft_start,
+            presence: self.presence,
+            pattern_clock: self.pattern_clock,
+            frame_rate: self.frame_rate,
+            error_policy: self.error_policy,
+            stats: self.stats,
+            notification: self.notification,
+            is_color_capability_set: self.is_color_capability_set,
+            is_global_brightness_set: self.is_global_brightness_set,
+            is_on: self.is_on,
+            is_blanked: self.is_blanked,
+        };
+        (control, self.driver, self.frame_hooks)
+    }
+
+    /// Returns the pattern parameters, if the pattern exposes them.
+    ///
+    /// See [`PatternParams`](crate::pattern::PatternParams) to list and modify parameters
+    /// generically.
+    pub fn pattern_params(&self) -> Option<&Pattern::Params> {
+        self.pattern.params()
+    }
+
+    /// Returns the pattern parameters mutably, if the pattern exposes them.
+    ///
+    /// See [`PatternParams`](crate::pattern::PatternParams) to list and modify parameters
+    /// generically.
+    pub fn pattern_params_mut(&mut self) -> Option<&mut Pattern::Params> {
+        self.frame_rate.wake();
+        self.pattern.params_mut()
+    }
 }
 
-impl<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Dim, Layout, Pattern, Driver>
-    Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Blocking, Layout, Pattern, Driver>
+impl<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Dim, Exec, Layout, Pattern>
+    Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, (), ()>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+{
+    /// Joins the state of a control with a driver and hooks.
+    fn with_parts<const NEW_FRAME_BUFFER_SIZE: usize, Driver, Hooks>(
+        self,
+        driver: Driver,
+        frame_hooks: Hooks,
+    ) -> Control<PIXEL_COUNT, NEW_FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, Driver, Hooks>
+    {
+        Control {
+            dim: self.dim,
+            exec: self.exec,
+            layout: self.layout,
+            pattern: self.pattern,
+            driver,
+            brightness: self.brightness,
+            brightness_step: self.brightness_step,
+            brightness_curve: self.brightness_curve,
+            correction: self.correction,
+            global_brightness: self.global_brightness,
+            tint: self.tint,
+            frame_hooks,
+            soft_start: self.soft_start,
+            presence: self.presence,
+            pattern_clock: self.pattern_clock,
+            frame_rate: self.frame_rate,
+            error_policy: self.error_policy,
+            stats: self.stats,
+            notification: self.notification,
+            is_color_capability_set: self.is_color_capability_set,
+            is_global_brightness_set: self.is_global_brightness_set,
+            is_on: self.is_on,
+            is_blanked: self.is_blanked,
+        }
+    }
+}
+
+impl<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Layout,
+        Pattern,
+        Driver,
+        Hooks,
+    > Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Blocking, Layout, Pattern, Driver, Hooks>
 where
     Layout: LayoutForDim<Dim>,
     Pattern: PatternTrait<Dim, Layout>,
+    Hooks: FrameHooks,
     Driver: DriverTrait,
-    Driver::Color: FromColor<Pattern::Color>,
+    Driver::Color: FromColor<Pattern::Color> + FromColor<LinearSrgb>,
+    LinearSrgb: FromColor<Pattern::Color>,
 {
     /// Updates the LED state based on the current time.
     ///
     /// This method:
-    /// 1. Calls the pattern to generate colors
-    /// 2. Passes the colors and brightness to the driver
+    /// 1. On the first frame, tells the pattern the [color capability](Self::color_capability)
+    ///    of the driver
+    /// 2. Calls the pattern to generate colors
+    /// 3. Applies any [tint](Self::set_tint), then blends any notification on top, then runs
+    ///    any [frame hooks](Self::on_frame). With none of these, the pattern's colors are
+    ///    converted straight to the driver's.
+    /// 4. Passes the colors and brightness to the driver, applying the
+    ///    [error policy](Self::set_error_policy) to a failed write
+    /// 5. Checks whether the frame changed, for the [idle frame rate](Self::set_idle_frame_rate)
     ///
     /// # Arguments
     ///
@@ -189,31 +1329,219 @@ where
     ///
     /// # Returns
     ///
+    /// Result indicating success or an error from the driver, unless skipped by the error
+    /// policy
+    pub fn tick(&mut self, time_in_ms: u64) -> Result<(), Driver::Error>
+    where
+        Driver::Word: Clone,
+    {
+        if !self.is_on {
+            self.blank()?;
+            self.frame_rate.update(OFF_FRAME_HASH, time_in_ms);
+            return Ok(());
+        }
+        self.update_color_capability();
+        self.update_global_brightness();
+        let brightness = self.curved_brightness()
+            * self.soft_start.update(time_in_ms)
+            * self.update_presence_scale(time_in_ms);
+        let pattern_time_in_ms = self.pattern_clock.update(time_in_ms);
+        let notification = self.update_notification(time_in_ms);
+        let mut hasher = FrameHasher::new();
+        hasher.write(brightness);
+        let frame = self.encode_frame(pattern_time_in_ms, brightness, &notification, &mut hasher);
+        if self.write_frame(frame, brightness)? {
+            self.frame_rate.update(hasher.finish(), time_in_ms);
+        }
+        Ok(())
+    }
+
+    /// Encodes the frame of the pattern at a time, with any tint, notification, and frame hooks.
+    fn encode_frame(
+        &mut self,
+        pattern_time_in_ms: u64,
+        brightness: f32,
+        notification: &Option<(LinearSrgba, Segment)>,
+        hasher: &mut FrameHasher,
+    ) -> heapless::Vec<Driver::Word, FRAME_BUFFER_SIZE> {
+        let is_overlaid = self.is_overlaid(notification);
+        let colors = self.pattern.tick(pattern_time_in_ms);
+        if is_overlaid {
+            let pixels = run_frame_hooks::<PIXEL_COUNT, _>(
+                overlay(colors, self.tint, notification.clone()),
+                &mut self.frame_hooks,
+            )
+            .inspect(|color| hasher.write_color(color));
+            self.driver.encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+                pixels,
+                brightness,
+                self.correction,
+            )
+        } else {
+            // Nothing to overlay, so convert the pattern's colors straight to the driver's
+            self.driver.encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+                colors,
+                brightness,
+                self.correction,
+            )
+        }
+    }
+
+    /// Writes a frame, writing the same encoded frame again for each retry set by the error
+    /// policy.
+    ///
+    /// The frame isn't encoded again, as that would run the pattern, the frame hooks, and any
+    /// stateful driver adapters (like a [`FrameDiffer`](crate::driver::FrameDiffer)) twice.
+    fn write_frame(
+        &mut self,
+        frame: heapless::Vec<Driver::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+    ) -> Result<bool, Driver::Error>
+    where
+        Driver::Word: Clone,
+    {
+        let mut attempts = self.write_attempts();
+        let result = loop {
+            attempts -= 1;
+            if attempts == 0 {
+                break self.driver.write(frame, brightness, self.correction);
+            }
+            match self
+                .driver
+                .write(frame.clone(), brightness, self.correction)
+            {
+                Err(_) => self.stats.errors = self.stats.errors.saturating_add(1),
+                result => break result,
+            }
+        };
+        if result.is_err() {
+            self.stats.errors = self.stats.errors.saturating_add(1);
+        }
+        self.record_write(result)
+    }
+
+    /// Shows an explicit frame of pixels, bypassing the pattern.
+    ///
+    /// Use this for pixels computed outside of a pattern (network streams, games, ...),
+    /// while still applying the brightness and color correction of the control system. While
+    /// the LEDs are [off](Self::turn_off), the pixels aren't shown.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels` - Iterator of colors for each pixel
+    /// - `time_in_ms` - Current time in milliseconds, for the [soft-start](Self::set_soft_start)
+    ///
+    /// # Returns
+    ///
     /// Result indicating success or an error from the driver
-    pub fn tick(&mut self, time_in_ms: u64) -> Result<(), Driver::Error> {
-        let pixels = self.pattern.tick(time_in_ms);
+    pub fn show_pixels<I, C>(&mut self, pixels: I, time_in_ms: u64) -> Result<(), Driver::Error>
+    where
+        I: IntoIterator<Item = C>,
+        Driver::Color: FromColor<C>,
+        LinearSrgb: FromColor<C>,
+    {
+        if !self.is_on {
+            return self.blank();
+        }
+        self.frame_rate.wake();
+        self.update_global_brightness();
+        let brightness =
+            self.curved_brightness() * self.soft_start.update(time_in_ms) * self.presence_scale();
+        if Hooks::IS_EMPTY {
+            return self.driver.show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+                pixels,
+                brightness,
+                self.correction,
+            );
+        }
+        let pixels = run_frame_hooks::<PIXEL_COUNT, _>(
+            pixels.into_iter().map(LinearSrgb::from_color),
+            &mut self.frame_hooks,
+        );
         self.driver.show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
             pixels,
-            self.brightness,
+            brightness,
             self.correction,
         )
     }
+
+    /// Shows an all-off frame, if not yet shown since the LEDs were turned off, applying the
+    /// error policy to a failed write.
+    fn blank(&mut self) -> Result<(), Driver::Error> {
+        if self.is_blanked {
+            return Ok(());
+        }
+        let mut attempts = self.write_attempts();
+        let result = loop {
+            attempts -= 1;
+            match self.driver.blank::<PIXEL_COUNT, FRAME_BUFFER_SIZE>() {
+                Err(_) if attempts > 0 => {
+                    self.stats.errors = self.stats.errors.saturating_add(1);
+                }
+                result => break result,
+            }
+        };
+        if result.is_err() {
+            self.stats.errors = self.stats.errors.saturating_add(1);
+        }
+        // A skipped blank frame is tried again on the next tick
+        self.is_blanked = self.record_write(result)?;
+        Ok(())
+    }
+
+    /// Returns the [color capability](crate::driver::capability) of the driver, which the
+    /// pattern is told before the first frame.
+    pub fn color_capability(&self) -> ColorCapability {
+        Driver::COLOR_CAPABILITY
+    }
+
+    /// Tells the pattern the color capability of the driver, if not yet told.
+    fn update_color_capability(&mut self) {
+        if !self.is_color_capability_set {
+            self.pattern.set_color_capability(Driver::COLOR_CAPABILITY);
+            self.is_color_capability_set = true;
+        }
+    }
+
+    /// Tells the driver how to choose the global brightness field, if not yet told.
+    fn update_global_brightness(&mut self) {
+        if !self.is_global_brightness_set {
+            self.driver.set_global_brightness(self.global_brightness);
+            self.is_global_brightness_set = true;
+        }
+    }
 }
 
 #[cfg(feature = "async")]
-impl<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Dim, Layout, Pattern, Driver>
-    Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Async, Layout, Pattern, Driver>
+impl<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Layout,
+        Pattern,
+        Driver,
+        Hooks,
+    > Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Async, Layout, Pattern, Driver, Hooks>
 where
     Layout: LayoutForDim<Dim>,
     Pattern: PatternTrait<Dim, Layout>,
+    Hooks: FrameHooks,
     Driver: DriverAsyncTrait,
-    Driver::Color: FromColor<Pattern::Color>,
+    Driver::Color: FromColor<Pattern::Color> + FromColor<LinearSrgb>,
+    LinearSrgb: FromColor<Pattern::Color>,
 {
     /// Updates the LED state based on the current time, asynchronously.
     ///
     /// This method:
-    /// 1. Calls the pattern to generate colors
-    /// 2. Passes the colors and brightness to the driver
+    /// 1. On the first frame, tells the pattern the [color capability](Self::color_capability)
+    ///    of the driver
+    /// 2. Calls the pattern to generate colors
+    /// 3. Applies any [tint](Self::set_tint), then blends any notification on top, then runs
+    ///    any [frame hooks](Self::on_frame). With none of these, the pattern's colors are
+    ///    converted straight to the driver's.
+    /// 4. Passes the colors and brightness to the driver, applying the
+    ///    [error policy](Self::set_error_policy) to a failed write
+    /// 5. Checks whether the frame changed, for the [idle frame rate](Self::set_idle_frame_rate)
     ///
     /// # Arguments
     ///
@@ -221,13 +1549,246 @@ where
     ///
     /// # Returns
     ///
+    /// Result indicating success or an error from the driver, unless skipped by the error
+    /// policy
+    pub async fn tick(&mut self, time_in_ms: u64) -> Result<(), Driver::Error>
+    where
+        Driver::Word: Clone,
+    {
+        if !self.is_on {
+            self.blank().await?;
+            self.frame_rate.update(OFF_FRAME_HASH, time_in_ms);
+            return Ok(());
+        }
+        self.update_color_capability();
+        self.update_global_brightness();
+        let brightness = self.curved_brightness()
+            * self.soft_start.update(time_in_ms)
+            * self.update_presence_scale(time_in_ms);
+        let pattern_time_in_ms = self.pattern_clock.update(time_in_ms);
+        let notification = self.update_notification(time_in_ms);
+        let mut hasher = FrameHasher::new();
+        hasher.write(brightness);
+        let frame = self.encode_frame(pattern_time_in_ms, brightness, &notification, &mut hasher);
+        if self.write_frame(frame).await? {
+            self.frame_rate.update(hasher.finish(), time_in_ms);
+        }
+        Ok(())
+    }
+
+    /// Encodes the frame of the pattern at a time, with any tint, notification, and frame hooks.
+    fn encode_frame(
+        &mut self,
+        pattern_time_in_ms: u64,
+        brightness: f32,
+        notification: &Option<(LinearSrgba, Segment)>,
+        hasher: &mut FrameHasher,
+    ) -> heapless::Vec<Driver::Word, FRAME_BUFFER_SIZE> {
+        let is_overlaid = self.is_overlaid(notification);
+        let colors = self.pattern.tick(pattern_time_in_ms);
+        if is_overlaid {
+            let pixels = run_frame_hooks::<PIXEL_COUNT, _>(
+                overlay(colors, self.tint, notification.clone()),
+                &mut self.frame_hooks,
+            )
+            .inspect(|color| hasher.write_color(color));
+            self.driver.encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+                pixels,
+                brightness,
+                self.correction,
+            )
+        } else {
+            // Nothing to overlay, so convert the pattern's colors straight to the driver's
+            self.driver.encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+                colors,
+                brightness,
+                self.correction,
+            )
+        }
+    }
+
+    /// Writes a frame, writing the same encoded frame again for each retry set by the error
+    /// policy.
+    ///
+    /// The frame isn't encoded again, as that would run the pattern, the frame hooks, and any
+    /// stateful driver adapters (like a [`FrameDiffer`](crate::driver::FrameDiffer)) twice.
+    async fn write_frame(
+        &mut self,
+        frame: heapless::Vec<Driver::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<bool, Driver::Error>
+    where
+        Driver::Word: Clone,
+    {
+        let mut attempts = self.write_attempts();
+        let result = loop {
+            attempts -= 1;
+            if attempts == 0 {
+                break self.driver.write(frame).await;
+            }
+            match self.driver.write(frame.clone()).await {
+                Err(_) => self.stats.errors = self.stats.errors.saturating_add(1),
+                result => break result,
+            }
+        };
+        if result.is_err() {
+            self.stats.errors = self.stats.errors.saturating_add(1);
+        }
+        self.record_write(result)
+    }
+
+    /// Shows an explicit frame of pixels, bypassing the pattern, asynchronously.
+    ///
+    /// Use this for pixels computed outside of a pattern (network streams, games, ...),
+    /// while still applying the brightness and color correction of the control system. While
+    /// the LEDs are [off](Self::turn_off), the pixels aren't shown.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels` - Iterator of colors for each pixel
+    /// - `time_in_ms` - Current time in milliseconds, for the [soft-start](Self::set_soft_start)
+    ///
+    /// # Returns
+    ///
     /// Result indicating success or an error from the driver
-    pub async fn tick(&mut self, time_in_ms: u64) -> Result<(), Driver::Error> {
-        let pixels = self.pattern.tick(time_in_ms);
+    pub async fn show_pixels<I, C>(
+        &mut self,
+        pixels: I,
+        time_in_ms: u64,
+    ) -> Result<(), Driver::Error>
+    where
+        I: IntoIterator<Item = C>,
+        Driver::Color: FromColor<C>,
+        LinearSrgb: FromColor<C>,
+    {
+        if !self.is_on {
+            return self.blank().await;
+        }
+        self.frame_rate.wake();
+        self.update_global_brightness();
+        let brightness =
+            self.curved_brightness() * self.soft_start.update(time_in_ms) * self.presence_scale();
+        if Hooks::IS_EMPTY {
+            return self
+                .driver
+                .show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, self.correction)
+                .await;
+        }
+        let pixels = run_frame_hooks::<PIXEL_COUNT, _>(
+            pixels.into_iter().map(LinearSrgb::from_color),
+            &mut self.frame_hooks,
+        );
         self.driver
-            .show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, self.brightness, self.correction)
+            .show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, self.correction)
             .await
     }
+
+    /// Shows an all-off frame, if not yet shown since the LEDs were turned off, applying the
+    /// error policy to a failed write.
+    async fn blank(&mut self) -> Result<(), Driver::Error> {
+        if self.is_blanked {
+            return Ok(());
+        }
+        let mut attempts = self.write_attempts();
+        let result = loop {
+            attempts -= 1;
+            match self.driver.blank::<PIXEL_COUNT, FRAME_BUFFER_SIZE>().await {
+                Err(_) if attempts > 0 => {
+                    self.stats.errors = self.stats.errors.saturating_add(1);
+                }
+                result => break result,
+            }
+        };
+        if result.is_err() {
+            self.stats.errors = self.stats.errors.saturating_add(1);
+        }
+        // A skipped blank frame is tried again on the next tick
+        self.is_blanked = self.record_write(result)?;
+        Ok(())
+    }
+
+    /// Returns the [color capability](crate::driver::capability) of the driver, which the
+    /// pattern is told before the first frame.
+    pub fn color_capability(&self) -> ColorCapability {
+        Driver::COLOR_CAPABILITY
+    }
+
+    /// Tells the pattern the color capability of the driver, if not yet told.
+    fn update_color_capability(&mut self) {
+        if !self.is_color_capability_set {
+            self.pattern.set_color_capability(Driver::COLOR_CAPABILITY);
+            self.is_color_capability_set = true;
+        }
+    }
+
+    /// Tells the driver how to choose the global brightness field, if not yet told.
+    fn update_global_brightness(&mut self) {
+        if !self.is_global_brightness_set {
+            self.driver.set_global_brightness(self.global_brightness);
+            self.is_global_brightness_set = true;
+        }
+    }
+}
+
+/// Blends a notification, if any, on top of a pattern's colors.
+fn overlay<Color>(
+    pixels: impl Iterator<Item = Color>,
+    tint: Option<Tint>,
+    notification: Option<(LinearSrgba, Segment)>,
+) -> impl Iterator<Item = LinearSrgb>
+where
+    LinearSrgb: FromColor<Color>,
+{
+    pixels.enumerate().map(move |(index, color)| {
+        let color = LinearSrgb::from_color(color);
+        let color = match &tint {
+            Some(tint) => tint.apply(color),
+            None => color,
+        };
+        match &notification {
+            Some((top, segment)) => {
+                // Over the opaque pattern, the blend is opaque too
+                let bottom = LinearSrgba::from_color_alpha(color, 1.);
+                segment.blend_rgba(index, bottom, *top).color()
+            }
+            None => color,
+        }
+    })
+}
+
+/// Runs any frame hooks on the pixels, buffering the frame only if there are any.
+fn run_frame_hooks<const PIXEL_COUNT: usize, Hooks>(
+    pixels: impl Iterator<Item = LinearSrgb>,
+    hooks: &mut Hooks,
+) -> impl Iterator<Item = LinearSrgb>
+where
+    Hooks: FrameHooks,
+{
+    if Hooks::IS_EMPTY {
+        return HookedPixels::Direct(pixels);
+    }
+    let mut frame: heapless::Vec<LinearSrgb, PIXEL_COUNT> = pixels.take(PIXEL_COUNT).collect();
+    hooks.run(&mut frame);
+    HookedPixels::Buffered(frame.into_iter())
+}
+
+/// The pixels of a frame, either straight from the pattern, or buffered for frame hooks.
+enum HookedPixels<Pixels, const PIXEL_COUNT: usize> {
+    Direct(Pixels),
+    Buffered(heapless::vec::IntoIter<LinearSrgb, PIXEL_COUNT, usize>),
+}
+
+impl<Pixels, const PIXEL_COUNT: usize> Iterator for HookedPixels<Pixels, PIXEL_COUNT>
+where
+    Pixels: Iterator<Item = LinearSrgb>,
+{
+    type Item = LinearSrgb;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            HookedPixels::Direct(pixels) => pixels.next(),
+            HookedPixels::Buffered(pixels) => pixels.next(),
+        }
+    }
 }
 
 /// The builder allows your to build up your [`Control`] system one-by-one
@@ -456,6 +2017,131 @@ where
             is_frame_buffer_set: self.is_frame_buffer_set,
         }
     }
+
+    /// Specifies a 1D pattern, run through a projection, and its parameters.
+    ///
+    /// See [`projection`](crate::projection) to run 1D patterns on 2D or 3D layouts.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Pattern` - The 1D pattern type
+    /// - `Proj` - The projection type, e.g. [`Radial`](crate::projection::Radial)
+    ///
+    /// # Arguments
+    ///
+    /// - `params` - The pattern parameters
+    ///
+    /// # Returns
+    ///
+    /// Builder with the projected pattern specified
+    pub fn with_projected_pattern<Pattern, Proj>(
+        self,
+        params: <Projected<Pattern, Proj> as PatternTrait<Dim, Layout>>::Params,
+    ) -> ControlBuilder<
+        PIXEL_COUNT,
+        FRAME_BUFFER_SIZE,
+        Dim,
+        Exec,
+        Layout,
+        Projected<Pattern, Proj>,
+        Driver,
+        IsFrameBufferSet,
+    >
+    where
+        Projected<Pattern, Proj>: PatternTrait<Dim, Layout>,
+    {
+        self.with_pattern::<Projected<Pattern, Proj>>(params)
+    }
+
+    /// Specifies a pattern for a logical layout, replicated across the layout, and its
+    /// parameters.
+    ///
+    /// See [`replicate`](crate::replicate) to mirror or tile a pattern.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Pattern` - The pattern type, for the logical layout
+    /// - `Logical` - The logical layout type, e.g. [`Mirrored<Layout>`](crate::replicate::Mirrored)
+    /// - `LOGICAL_PIXEL_COUNT` - A constant for the number of pixels in the logical layout
+    ///   (`Logical::PIXEL_COUNT`)
+    ///
+    /// # Arguments
+    ///
+    /// - `params` - The pattern parameters
+    ///
+    /// # Returns
+    ///
+    /// Builder with the replicated pattern specified
+    pub fn with_replicated_pattern<Pattern, Logical, const LOGICAL_PIXEL_COUNT: usize>(
+        self,
+        params: <Replicated<Pattern, Logical, LOGICAL_PIXEL_COUNT> as PatternTrait<
+            Dim,
+            Layout,
+        >>::Params,
+    ) -> ControlBuilder<
+        PIXEL_COUNT,
+        FRAME_BUFFER_SIZE,
+        Dim,
+        Exec,
+        Layout,
+        Replicated<Pattern, Logical, LOGICAL_PIXEL_COUNT>,
+        Driver,
+        IsFrameBufferSet,
+    >
+    where
+        Replicated<Pattern, Logical, LOGICAL_PIXEL_COUNT>: PatternTrait<Dim, Layout>,
+    {
+        self.with_pattern::<Replicated<Pattern, Logical, LOGICAL_PIXEL_COUNT>>(params)
+    }
+
+    /// Specifies a pattern for a logical layout, shown in the order of a pixel map, and its
+    /// parameters.
+    ///
+    /// See [`remap`](crate::remap) to decouple the layout of a pattern from the wiring order.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Pattern` - The pattern type, for the logical layout
+    /// - `Logical` - The logical layout type
+    /// - `LOGICAL_PIXEL_COUNT` - A constant for the number of pixels in the logical layout
+    ///   (`Logical::PIXEL_COUNT`)
+    ///
+    /// # Arguments
+    ///
+    /// - `params` - The pattern parameters
+    /// - `map` - The logical pixel of each LED
+    ///
+    /// # Returns
+    ///
+    /// Builder with the remapped pattern specified
+    pub fn with_remapped_pattern<Pattern, Logical, const LOGICAL_PIXEL_COUNT: usize>(
+        self,
+        params: Pattern::Params,
+        map: PixelMap<PIXEL_COUNT>,
+    ) -> ControlBuilder<
+        PIXEL_COUNT,
+        FRAME_BUFFER_SIZE,
+        Dim,
+        Exec,
+        Layout,
+        Remapped<Pattern, Logical, LOGICAL_PIXEL_COUNT, PIXEL_COUNT>,
+        Driver,
+        IsFrameBufferSet,
+    >
+    where
+        Logical: LayoutForDim<Dim>,
+        Pattern: PatternTrait<Dim, Logical>,
+        Remapped<Pattern, Logical, LOGICAL_PIXEL_COUNT, PIXEL_COUNT>: PatternTrait<Dim, Layout>,
+    {
+        ControlBuilder {
+            dim: self.dim,
+            exec: self.exec,
+            layout: self.layout,
+            pattern: Remapped::with_map(Pattern::new(params), map),
+            driver: self.driver,
+            is_frame_buffer_set: self.is_frame_buffer_set,
+        }
+    }
 }
 
 impl<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Dim, Layout, Pattern>
@@ -596,7 +2282,9 @@ where
     Layout: LayoutForDim<Dim>,
     Pattern: PatternTrait<Dim, Layout>,
     Driver: DriverTrait,
-    Driver::Color: FromColor<Pattern::Color>,
+    Driver::Color: FromColor<Pattern::Color> + FromColor<LinearSrgb>,
+    Driver::Word: Clone,
+    LinearSrgb: FromColor<Pattern::Color>,
 {
     /// Builds the final [`Control`] struct.
     ///
@@ -617,7 +2305,9 @@ where
     Layout: LayoutForDim<Dim>,
     Pattern: PatternTrait<Dim, Layout>,
     Driver: DriverAsyncTrait,
-    Driver::Color: FromColor<Pattern::Color>,
+    Driver::Color: FromColor<Pattern::Color> + FromColor<LinearSrgb>,
+    Driver::Word: Clone,
+    LinearSrgb: FromColor<Pattern::Color>,
 {
     /// Builds the final [`Control`] struct.
     ///
@@ -630,3 +2320,495 @@ where
         Control::new(self.pattern, self.driver)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_clock_warps_time() {
+        let mut clock = PatternClock::new();
+        assert_eq!(clock.update(1000), 1000);
+        assert_eq!(clock.update(1010), 1010);
+
+        clock.time_scale = 0.5;
+        assert_eq!(clock.update(1013), 1011);
+        assert_eq!(clock.update(1014), 1012);
+
+        clock.is_paused = true;
+        assert_eq!(clock.update(2000), 1012);
+
+        clock.is_paused = false;
+        clock.time_scale = 2.0;
+        assert_eq!(clock.update(2005), 1022);
+    }
+
+    #[test]
+    fn pattern_clock_runs_backwards() {
+        let mut clock = PatternClock::new();
+        assert_eq!(clock.update(100), 100);
+
+        clock.time_scale = -1.0;
+        assert_eq!(clock.update(110), 90);
+
+        clock.time_scale = -0.5;
+        assert_eq!(clock.update(113), 89);
+        assert_eq!(clock.update(114), 88);
+
+        // Stops at 0, instead of wrapping around.
+        clock.time_scale = -2.0;
+        assert_eq!(clock.update(200), 0);
+        assert_eq!(clock.update(300), 0);
+
+        clock.time_scale = 1.0;
+        assert_eq!(clock.update(310), 10);
+    }
+
+    /// Counts frames, and whether the last frame was all off, failing the next `failures`
+    /// writes.
+    #[derive(Default)]
+    struct CountingDriver {
+        frame_count: usize,
+        is_last_frame_off: bool,
+        failures: u32,
+    }
+
+    impl DriverTrait for CountingDriver {
+        type Error = ();
+        type Color = LinearSrgb;
+        type Word = ();
+
+        fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+            &mut self,
+            pixels: Pixels,
+            brightness: f32,
+            _correction: ColorCorrection,
+        ) -> heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>
+        where
+            Pixels: IntoIterator<Item = Color>,
+            Self::Color: FromColor<Color>,
+        {
+            self.is_last_frame_off = brightness == 0.
+                || pixels.into_iter().all(|color| {
+                    let color = LinearSrgb::from_color(color);
+                    color.red == 0. && color.green == 0. && color.blue == 0.
+                });
+            heapless::Vec::new()
+        }
+
+        fn write<const FRAME_BUFFER_SIZE: usize>(
+            &mut self,
+            _frame: heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> Result<(), Self::Error> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(());
+            }
+            self.frame_count += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn soft_start_ramps_explicit_frames() {
+        use crate::{layout::Layout1d, layout1d, patterns::rainbow::Rainbow};
+
+        layout1d!(Layout, 4);
+
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(Default::default())
+            .with_driver(CountingDriver::default())
+            .with_frame_buffer_size::<0>()
+            .build();
+        assert_eq!(control.soft_start.scale, 0.0);
+
+        // Before any tick, the first frame starts the ramp
+        let white = [LinearSrgb::new(1., 1., 1.); 4];
+        control.show_pixels(white, 100).unwrap();
+        assert!(control.driver().is_last_frame_off);
+
+        control.show_pixels(white, 600).unwrap();
+        assert!(!control.driver().is_last_frame_off);
+        assert_eq!(control.soft_start.scale, 0.5);
+
+        control.tick(1100).unwrap();
+        assert_eq!(control.soft_start.scale, 1.0);
+    }
+
+    #[test]
+    fn turn_off_blanks_once_and_turn_on_restores() {
+        use crate::{layout::Layout1d, layout1d, patterns::rainbow::Rainbow};
+
+        layout1d!(Layout, 4);
+
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(Default::default())
+            .with_driver(CountingDriver::default())
+            .with_frame_buffer_size::<0>()
+            .build();
+        control.set_soft_start(0);
+        control.set_brightness(0.5);
+
+        control.tick(0).unwrap();
+        assert_eq!(control.driver().frame_count, 1);
+        assert!(!control.driver().is_last_frame_off);
+
+        control.turn_off();
+        assert!(!control.is_on());
+        control.tick(10).unwrap();
+        control.tick(20).unwrap();
+        control
+            .show_pixels([LinearSrgb::new(1., 1., 1.); 4], 25)
+            .unwrap();
+        assert_eq!(control.driver().frame_count, 2);
+        assert!(control.driver().is_last_frame_off);
+
+        control.turn_on();
+        control.tick(30).unwrap();
+        assert_eq!(control.driver().frame_count, 3);
+        assert!(!control.driver().is_last_frame_off);
+        assert_eq!(control.brightness, 0.5);
+    }
+
+    /// A driver color, which remembers whether it was converted straight from the pattern's.
+    struct DirectColor(bool);
+
+    impl FromColor<crate::color::Hsv<crate::color::HsvHueRainbow>> for DirectColor {
+        fn from_color(_color: crate::color::Hsv<crate::color::HsvHueRainbow>) -> Self {
+            Self(true)
+        }
+    }
+
+    impl FromColor<LinearSrgb> for DirectColor {
+        fn from_color(_color: LinearSrgb) -> Self {
+            Self(false)
+        }
+    }
+
+    /// Records whether every pixel of the last frame was converted straight from the pattern.
+    #[derive(Default)]
+    struct DirectDriver {
+        is_direct: bool,
+    }
+
+    impl DriverTrait for DirectDriver {
+        type Error = ();
+        type Color = DirectColor;
+        type Word = ();
+
+        fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+            &mut self,
+            pixels: Pixels,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>
+        where
+            Pixels: IntoIterator<Item = Color>,
+            Self::Color: FromColor<Color>,
+        {
+            self.is_direct = pixels
+                .into_iter()
+                .all(|color| DirectColor::from_color(color).0);
+            heapless::Vec::new()
+        }
+
+        fn write<const FRAME_BUFFER_SIZE: usize>(
+            &mut self,
+            _frame: heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn colors_are_converted_directly_unless_overlaid() {
+        use crate::{layout::Layout1d, layout1d, patterns::rainbow::Rainbow};
+
+        layout1d!(Layout, 4);
+
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(Default::default())
+            .with_driver(DirectDriver::default())
+            .with_frame_buffer_size::<0>()
+            .build();
+
+        control.tick(0).unwrap();
+        assert!(control.driver().is_direct);
+
+        control.notify(LinearSrgb::new(1., 0., 0.), 100, NotificationStyle::Flash);
+        control.tick(10).unwrap();
+        assert!(!control.driver().is_direct);
+
+        control.tick(200).unwrap();
+        assert!(control.driver().is_direct);
+
+        control.set_tint(0.5, 1.);
+        control.tick(210).unwrap();
+        assert!(!control.driver().is_direct);
+    }
+
+    #[test]
+    fn notifications_may_be_partially_transparent() {
+        use crate::compositor::BlendMode;
+
+        let blue = LinearSrgb::new(0., 0., 1.);
+        let half_red = LinearSrgba::new(1., 0., 0., 0.5);
+        let segment = Segment::new(1..2, BlendMode::AlphaOver, 1.);
+
+        let mut pixels = overlay([blue, blue].into_iter(), None, Some((half_red, segment)));
+        assert_eq!(pixels.next(), Some(blue));
+        assert_eq!(pixels.next(), Some(LinearSrgb::new(0.5, 0., 0.5)));
+    }
+
+    #[test]
+    fn cycle_brightness_steps_and_wraps() {
+        use crate::{layout::Layout1d, layout1d, patterns::rainbow::Rainbow};
+
+        layout1d!(Layout, 4);
+
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(Default::default())
+            .with_driver(CountingDriver::default())
+            .with_frame_buffer_size::<0>()
+            .build();
+        let steps = [0.25, 0.5, 1.0];
+
+        control.set_brightness(0.3);
+        assert_eq!(control.brightness_step(), None);
+        assert_eq!(control.cycle_brightness(&steps), 0.5);
+        assert_eq!(control.cycle_brightness(&steps), 1.0);
+        assert_eq!(control.cycle_brightness(&steps), 0.25);
+        assert_eq!(control.brightness_step(), Some(0));
+
+        control.set_brightness_step(&steps, 5);
+        assert_eq!(control.brightness_step(), Some(0));
+        control.set_brightness_step(&steps, 2);
+        assert_eq!(control.brightness, 1.0);
+        assert_eq!(control.cycle_brightness(&[]), 1.0);
+    }
+
+    #[test]
+    fn frame_hooks_change_pixels_in_order() {
+        use core::cell::Cell;
+
+        use crate::{layout::Layout1d, layout1d, patterns::rainbow::Rainbow};
+
+        layout1d!(Layout, 4);
+
+        let clear = |pixels: &mut [LinearSrgb]| pixels.fill(LinearSrgb::new(0., 0., 0.));
+        let status = |pixels: &mut [LinearSrgb]| pixels[3] = LinearSrgb::new(0., 1., 0.);
+        let frame_count = Cell::new(0);
+        let count = |_: &mut [LinearSrgb]| frame_count.set(frame_count.get() + 1);
+
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(Default::default())
+            .with_driver(CountingDriver::default())
+            .with_frame_buffer_size::<0>()
+            .build()
+            .on_frame(clear);
+        control.set_soft_start(0);
+        control.tick(0).unwrap();
+        assert!(control.driver().is_last_frame_off);
+
+        let mut control = control.on_frame(status);
+        control.tick(10).unwrap();
+        assert!(!control.driver().is_last_frame_off);
+
+        let (control, _) = control.clear_frame_hooks();
+        let mut control = control.on_frame(clear).on_frame(count);
+        control.tick(20).unwrap();
+        assert!(control.driver().is_last_frame_off);
+
+        // Hooks run on explicit frames too
+        control
+            .show_pixels([LinearSrgb::new(1., 1., 1.); 4], 30)
+            .unwrap();
+        assert!(control.driver().is_last_frame_off);
+        assert_eq!(frame_count.get(), 2);
+    }
+
+    #[test]
+    fn tint_shifts_hue_and_keeps_lightness() {
+        let red = LinearSrgb::new(1., 0., 0.);
+
+        let gray = Tint::new(0., 0.).apply(red);
+        crate::assert_color_near!(gray, LinearSrgb::new(gray.green, gray.green, gray.green));
+
+        let full_turn = Tint::new(1., 1.).apply(red);
+        crate::assert_color_near!(full_turn, red);
+
+        let pink = LinearSrgb::new(0.5, 0.3, 0.3);
+        let shifted = Tint::new(0.5, 1.).apply(pink);
+        assert!(shifted.red < shifted.green && shifted.red < shifted.blue);
+        let lightness = |color| Oklab::from_linear_srgb(color).l;
+        assert!((lightness(shifted) - lightness(pink)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn presence_fades_in_holds_and_fades_out() {
+        let mut fade = PresenceFade::new(PresenceTimeout {
+            hold_in_ms: 1000,
+            fade_in_in_ms: 100,
+            fade_out_in_ms: 200,
+        });
+        assert_eq!(fade.update(0), 0.);
+
+        fade.detect(0);
+        assert_eq!(fade.update(50), 0.5);
+        assert_eq!(fade.update(100), 1.);
+        assert_eq!(fade.update(1000), 1.);
+        assert_eq!(fade.update(1100), 0.5);
+        assert_eq!(fade.update(1200), 0.);
+
+        fade.detect(1200);
+        assert_eq!(fade.update(1250), 0.5);
+    }
+
+    #[test]
+    fn frame_rate_idles_while_unchanged() {
+        let mut frame_rate = FrameRate::new();
+        frame_rate.idle = Some(IdleFrameRate {
+            interval_in_ms: 500,
+            after_in_ms: 100,
+        });
+
+        frame_rate.update(1, 0);
+        frame_rate.update(1, 50);
+        assert_eq!(
+            frame_rate.current_interval_in_ms(),
+            DEFAULT_FRAME_INTERVAL_IN_MS
+        );
+        frame_rate.update(1, 100);
+        assert_eq!(frame_rate.current_interval_in_ms(), 500);
+
+        // A changed frame resumes the full rate
+        frame_rate.update(2, 600);
+        assert!(!frame_rate.is_idle());
+        frame_rate.update(2, 700);
+        assert!(frame_rate.is_idle());
+
+        // As does waking, until frames are unchanged again
+        frame_rate.wake();
+        assert!(!frame_rate.is_idle());
+        frame_rate.update(2, 1200);
+        assert!(!frame_rate.is_idle());
+        frame_rate.update(2, 1300);
+        assert!(frame_rate.is_idle());
+    }
+
+    #[test]
+    fn error_policy_applies_to_blank_frames() {
+        use crate::{layout::Layout1d, layout1d, patterns::rainbow::Rainbow};
+
+        layout1d!(Layout, 4);
+
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(Default::default())
+            .with_driver(CountingDriver::default())
+            .with_frame_buffer_size::<0>()
+            .build();
+        control.turn_off();
+
+        control.set_error_policy(ErrorPolicy::SkipFrame);
+        control.driver_mut().failures = 1;
+        control.tick(0).unwrap();
+        assert_eq!(control.driver().frame_count, 0);
+
+        // The skipped blank frame is tried again
+        control.tick(10).unwrap();
+        control.tick(20).unwrap();
+        assert_eq!(control.driver().frame_count, 1);
+        assert!(control.driver().is_last_frame_off);
+
+        control.turn_on();
+        control.turn_off();
+        control.set_error_policy(ErrorPolicy::Retry { attempts: 1 });
+        control.driver_mut().failures = 1;
+        control.tick(30).unwrap();
+        assert_eq!(control.driver().frame_count, 2);
+
+        assert_eq!(
+            control.stats(),
+            ControlStats {
+                frames: 2,
+                errors: 2,
+                skipped_frames: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn error_policy_retries_the_encoded_frame() {
+        use crate::{driver::FrameDiffer, layout::Layout1d, layout1d, patterns::rainbow::Rainbow};
+
+        layout1d!(Layout, 4);
+
+        // Fails the first write
+        let driver = CountingDriver {
+            failures: 1,
+            ..Default::default()
+        };
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(Default::default())
+            .with_driver(FrameDiffer::<_, 4>::new(driver))
+            .with_frame_buffer_size::<0>()
+            .build();
+        control.set_error_policy(ErrorPolicy::Retry { attempts: 1 });
+
+        // Encoding the frame again would find it unchanged, and skip the retry
+        control.tick(0).unwrap();
+        let (_, differ) =
+            control.change_driver::<_, 0>(FrameDiffer::<_, 4>::new(CountingDriver::default()));
+        assert_eq!(differ.into_inner().frame_count, 1);
+    }
+
+    #[test]
+    fn error_policy_retries_and_skips_frames() {
+        use crate::{layout::Layout1d, layout1d, patterns::rainbow::Rainbow};
+
+        layout1d!(Layout, 4);
+
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(Default::default())
+            .with_driver(CountingDriver::default())
+            .with_frame_buffer_size::<0>()
+            .build();
+
+        // Fatal by default
+        control.driver_mut().failures = 1;
+        assert_eq!(control.tick(0), Err(()));
+
+        control.set_error_policy(ErrorPolicy::Retry { attempts: 1 });
+        control.driver_mut().failures = 1;
+        control.tick(10).unwrap();
+        control.driver_mut().failures = 2;
+        assert_eq!(control.tick(20), Err(()));
+        assert_eq!(control.driver().frame_count, 1);
+
+        control.set_error_policy(ErrorPolicy::SkipFrame);
+        control.driver_mut().failures = 1;
+        control.tick(30).unwrap();
+        control.tick(40).unwrap();
+
+        assert_eq!(
+            control.stats(),
+            ControlStats {
+                frames: 2,
+                errors: 5,
+                skipped_frames: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! # Compute Budget
+//!
+//! [`ComputeBudget`] wraps any driver and measures how long each frame takes to compute,
+//! against a budget (usually the frame period), to diagnose why an animation stutters: if
+//! computing a frame takes longer than the frame period, the pattern can't keep up.
+//!
+//! Patterns compute their colors lazily, while the driver encodes them, so the compute time
+//! of a frame is the time to encode it: the pattern and the encoding together. Writing the
+//! frame to the LEDs isn't counted.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::{ColorCorrection, LinearSrgb},
+//!     driver::{ComputeBudget, Driver, NullDriver},
+//! };
+//!
+//! // A clock in microseconds, e.g. from your HAL
+//! let clock = || 0;
+//!
+//! // Budget for 60 frames per second
+//! let mut driver = ComputeBudget::new(NullDriver::default(), clock, 1_000_000 / 60);
+//!
+//! let pixels = [LinearSrgb::new(1., 1., 1.); 4];
+//! driver
+//!     .show::<4, 4, _, _>(pixels, 1., ColorCorrection::default())
+//!     .unwrap();
+//!
+//! if driver.is_overrun() {
+//!     // Warn: the pattern can't keep up, see `driver.stats()`
+//! }
+//! assert_eq!(driver.stats().frames, 1);
+//! ```
+
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use super::{ColorCapability, Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor};
+
+/// Statistics of frame compute times, against a budget.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BudgetStats {
+    /// The compute time of the last frame, in microseconds
+    pub last_in_us: u64,
+    /// The longest compute time of a frame, in microseconds
+    pub max_in_us: u64,
+    /// The number of frames measured
+    pub frames: u32,
+    /// The number of frames which took longer than the budget
+    pub overruns: u32,
+}
+
+impl BudgetStats {
+    /// Records the compute time of a frame.
+    fn record(&mut self, compute_in_us: u64, budget_in_us: u64) {
+        self.last_in_us = compute_in_us;
+        self.max_in_us = self.max_in_us.max(compute_in_us);
+        self.frames = self.frames.saturating_add(1);
+        if compute_in_us > budget_in_us {
+            self.overruns = self.overruns.saturating_add(1);
+        }
+    }
+}
+
+/// A driver wrapper which measures the compute time of each frame, against a budget.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver
+/// - `Clock` - A clock, returning the current time in microseconds
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ComputeBudget<Driver, Clock> {
+    driver: Driver,
+    clock: Clock,
+    budget_in_us: u64,
+    stats: BudgetStats,
+}
+
+impl<Driver, Clock> ComputeBudget<Driver, Clock>
+where
+    Clock: Fn() -> u64,
+{
+    /// Wraps a driver with a compute budget.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The inner driver
+    /// - `clock` - Returns the current time in microseconds
+    /// - `budget_in_us` - The budget for each frame in microseconds, usually the frame period
+    pub fn new(driver: Driver, clock: Clock, budget_in_us: u64) -> Self {
+        Self {
+            driver,
+            clock,
+            budget_in_us,
+            stats: BudgetStats::default(),
+        }
+    }
+
+    /// Sets the budget for each frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `budget_in_us` - The budget in microseconds
+    pub fn set_budget(&mut self, budget_in_us: u64) {
+        self.budget_in_us = budget_in_us;
+    }
+
+    /// Returns the budget for each frame, in microseconds.
+    pub fn budget(&self) -> u64 {
+        self.budget_in_us
+    }
+
+    /// Returns the statistics of frame compute times.
+    pub fn stats(&self) -> BudgetStats {
+        self.stats
+    }
+
+    /// Returns whether the last frame took longer than the budget.
+    pub fn is_overrun(&self) -> bool {
+        self.stats.last_in_us > self.budget_in_us
+    }
+
+    /// Clears the statistics, e.g. after changing pattern.
+    pub fn reset_stats(&mut self) {
+        self.stats = BudgetStats::default();
+    }
+
+    /// Unwraps the inner driver and clock.
+    pub fn into_inner(self) -> (Driver, Clock) {
+        (self.driver, self.clock)
+    }
+
+    /// Runs `encode`, recording how long it took.
+    fn measure<T>(&mut self, encode: impl FnOnce(&mut Driver) -> T) -> T {
+        let start_in_us = (self.clock)();
+        let frame = encode(&mut self.driver);
+        let compute_in_us = (self.clock)().saturating_sub(start_in_us);
+        self.stats.record(compute_in_us, self.budget_in_us);
+        frame
+    }
+}
+
+impl<D, Clock> Driver for ComputeBudget<D, Clock>
+where
+    D: Driver,
+    Clock: Fn() -> u64,
+{
+    type Error = D::Error;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        self.measure(|driver| {
+            driver.encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction)
+        })
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        self.driver.write(frame, brightness, correction)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D, Clock> DriverAsync for ComputeBudget<D, Clock>
+where
+    D: DriverAsync,
+    Clock: Fn() -> u64,
+{
+    type Error = D::Error;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        self.measure(|driver| {
+            driver.encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction)
+        })
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.driver.write(frame).await
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
@@ -0,0 +1,149 @@
+//! # Color Capability
+//!
+//! LEDs differ in how many colors they can show: most have 8 bits per channel, some (like the
+//! [`WS2816`](crate::leds::Ws2816)) have 16, and some (like the [`SK6812`](crate::leds::Sk6812))
+//! have a white channel. Each driver advertises its [`ColorCapability`], so patterns can choose
+//! their color space and precision to match: e.g. a pattern can render smooth gradients on
+//! 16-bit LEDs, while dithering or quantizing on 8-bit LEDs.
+//!
+//! [`Control`](crate::control::Control) passes the capability of its driver to its pattern,
+//! with [`Pattern::set_color_capability`](crate::pattern::Pattern::set_color_capability),
+//! before the first frame.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     driver::{ClocklessLed, ColorCapability},
+//!     leds::{Sk6812, Ws2812, Ws2816},
+//! };
+//!
+//! assert_eq!(Ws2812::COLOR_CAPABILITY, ColorCapability::RGB8);
+//! assert_eq!(Ws2816::COLOR_CAPABILITY, ColorCapability::RGB16);
+//! assert_eq!(Sk6812::COLOR_CAPABILITY, ColorCapability::RGBW8);
+//!
+//! // Quantize a channel to what the LEDs can show
+//! assert_eq!(ColorCapability::RGB8.levels(), 256);
+//! assert_eq!(ColorCapability::RGB8.quantize(0.5), 128. / 255.);
+//! ```
+//!
+//! ## Example (Pattern)
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::LinearSrgb,
+//!     driver::ColorCapability,
+//!     layout::Layout1d,
+//!     markers::Dim1d,
+//!     pattern::Pattern,
+//! };
+//!
+//! /// A slow fade, stepped to the levels the LEDs can show.
+//! struct Fade {
+//!     capability: ColorCapability,
+//! }
+//!
+//! impl<Layout: Layout1d> Pattern<Dim1d, Layout> for Fade {
+//!     type Params = ();
+//!     type Color = LinearSrgb;
+//!
+//!     fn new(_params: ()) -> Self {
+//!         Self {
+//!             capability: ColorCapability::default(),
+//!         }
+//!     }
+//!
+//!     fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+//!         let level = self.capability.quantize((time_in_ms % 60_000) as f32 / 60_000.);
+//!         Layout::points().map(move |_| LinearSrgb::new(level, level, level))
+//!     }
+//!
+//!     fn set_color_capability(&mut self, capability: ColorCapability) {
+//!         self.capability = capability;
+//!     }
+//! }
+//! ```
+
+use crate::color::LedChannels;
+
+/// The native color depth and space of a driver's LEDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ColorCapability {
+    /// The number of bits of each color channel
+    pub bits_per_channel: u8,
+    /// Whether the LEDs have a white channel
+    pub has_white: bool,
+}
+
+impl ColorCapability {
+    /// RGB, with 8 bits per channel.
+    pub const RGB8: Self = Self::new(8, false);
+
+    /// RGB, with 16 bits per channel.
+    pub const RGB16: Self = Self::new(16, false);
+
+    /// RGBW, with 8 bits per channel.
+    pub const RGBW8: Self = Self::new(8, true);
+
+    /// Creates a color capability.
+    ///
+    /// # Arguments
+    ///
+    /// - `bits_per_channel` - The number of bits of each color channel
+    /// - `has_white` - Whether the LEDs have a white channel
+    pub const fn new(bits_per_channel: u8, has_white: bool) -> Self {
+        Self {
+            bits_per_channel,
+            has_white,
+        }
+    }
+
+    /// Creates the color capability of LEDs with the given channels.
+    ///
+    /// # Arguments
+    ///
+    /// - `channels` - The color channels of the LEDs
+    /// - `bits_per_channel` - The number of bits of each color channel
+    pub const fn from_channels(channels: LedChannels, bits_per_channel: u8) -> Self {
+        let has_white = matches!(channels, LedChannels::Rgbw(_));
+        Self::new(bits_per_channel, has_white)
+    }
+
+    /// Returns the capability both drivers support, e.g. for a driver writing to both: the
+    /// lower depth, with white only if both have it.
+    pub const fn common(self, other: Self) -> Self {
+        let bits_per_channel = if self.bits_per_channel < other.bits_per_channel {
+            self.bits_per_channel
+        } else {
+            other.bits_per_channel
+        };
+        Self::new(bits_per_channel, self.has_white && other.has_white)
+    }
+
+    /// Returns whether each channel has more than 8 bits.
+    pub const fn is_high_depth(self) -> bool {
+        self.bits_per_channel > 8
+    }
+
+    /// Returns the number of levels of each channel.
+    pub const fn levels(self) -> u32 {
+        1 << self.bits_per_channel
+    }
+
+    /// Rounds a channel value to the nearest level the LEDs can show.
+    ///
+    /// # Arguments
+    ///
+    /// - `value` - The channel value, from 0.0 to 1.0
+    pub fn quantize(self, value: f32) -> f32 {
+        let max = (self.levels() - 1) as f32;
+        (value.clamp(0., 1.) * max + 0.5) as u32 as f32 / max
+    }
+}
+
+impl Default for ColorCapability {
+    fn default() -> Self {
+        Self::RGB8
+    }
+}
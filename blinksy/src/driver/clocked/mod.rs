@@ -17,6 +17,7 @@
 //! ## Traits
 //!
 //! - [`ClockedLed`]: Trait defining protocol specifics for a clocked LED chipset
+//! - [`SimpleClockedLed`]: Trait defining a clocked LED chipset with only a few constants
 //! - [`ClockedWriter`]: Trait for how to write data for a clocked protocol
 //! - [`ClockedWriterAsync`]: Trait for how to write data for a clocked protocol, asynchronously
 //!
@@ -83,14 +84,17 @@ use core::marker::PhantomData;
 use heapless::Vec;
 
 use crate::color::{ColorCorrection, FromColor};
-use crate::driver::Driver;
+use crate::power::CurrentDraw;
+use crate::driver::{ColorCapability, Driver, GlobalBrightness};
 #[cfg(feature = "async")]
 use crate::driver::DriverAsync;
 
 mod delay;
+mod simple;
 mod spi;
 
 pub use self::delay::*;
+pub use self::simple::*;
 
 /// Trait that defines the protocol specifics for a clocked LED chipset.
 ///
@@ -108,6 +112,16 @@ pub trait ClockedLed {
     /// The color representation type.
     type Color;
 
+    /// Current drawn by one LED, for [power estimates](crate::power).
+    ///
+    /// Defaults to [`CurrentDraw::DEFAULT`].
+    const CURRENT_DRAW: CurrentDraw = CurrentDraw::DEFAULT;
+
+    /// The native color depth and space of the LEDs.
+    ///
+    /// Defaults to [`ColorCapability::RGB8`].
+    const COLOR_CAPABILITY: ColorCapability = ColorCapability::RGB8;
+
     /// A start frame to begin a transmission.
     ///
     /// # Returns
@@ -132,6 +146,30 @@ pub trait ClockedLed {
         correction: ColorCorrection,
     ) -> impl IntoIterator<Item = Self::Word>;
 
+    /// A color frame for a single LED, with the global brightness field chosen as hinted.
+    ///
+    /// Defaults to [`led`](Self::led), for LEDs without a global brightness field.
+    ///
+    /// # Arguments
+    ///
+    /// - `color` - The color to write
+    /// - `brightness` - Global brightness scaling factor (0.0 to 1.0)
+    /// - `correction` - Color correction factors
+    /// - `global_brightness` - How to choose the global brightness field
+    ///
+    /// # Returns
+    ///
+    /// An iterator of words to write
+    fn led_with_global_brightness(
+        color: Self::Color,
+        brightness: f32,
+        correction: ColorCorrection,
+        global_brightness: GlobalBrightness,
+    ) -> impl IntoIterator<Item = Self::Word> {
+        let _ = global_brightness;
+        Self::led(color, brightness, correction)
+    }
+
     /// An end frame to conclude a transmission.
     ///
     /// # Arguments
@@ -154,6 +192,7 @@ pub trait ClockedLed {
     /// - `pixel` - The pixel color to write
     /// - `brightness` - Global brightness scaling factor (0.0 to 1.0)
     /// - `correction` - Color correction factors
+    /// - `global_brightness` - How to choose the global brightness field
     /// - `pixel_count` - The number of LEDs that were written
     ///
     /// # Returns
@@ -163,6 +202,7 @@ pub trait ClockedLed {
         pixels: I,
         brightness: f32,
         correction: ColorCorrection,
+        global_brightness: GlobalBrightness,
         pixel_count: usize,
     ) -> impl IntoIterator<Item = Self::Word>
     where
@@ -170,11 +210,10 @@ pub trait ClockedLed {
     {
         Self::start()
             .into_iter()
-            .chain(
-                pixels
+            .chain(pixels.into_iter().flat_map(move |color| {
+                Self::led_with_global_brightness(color, brightness, correction, global_brightness)
                     .into_iter()
-                    .flat_map(move |color| Self::led(color, brightness, correction).into_iter()),
-            )
+            }))
             .chain(Self::end(pixel_count))
     }
 }
@@ -242,6 +281,8 @@ pub struct ClockedDriver<Led, Writer> {
     led: PhantomData<Led>,
     /// Writer implementation for the clocked protocol
     writer: Writer,
+    /// How to choose the global brightness field of the LEDs
+    global_brightness: GlobalBrightness,
 }
 
 impl Default for ClockedDriver<(), ()> {
@@ -249,6 +290,7 @@ impl Default for ClockedDriver<(), ()> {
         ClockedDriver {
             led: PhantomData,
             writer: (),
+            global_brightness: GlobalBrightness::default(),
         }
     }
 }
@@ -258,6 +300,7 @@ impl<Writer> ClockedDriver<(), Writer> {
         ClockedDriver {
             led: PhantomData,
             writer: self.writer,
+            global_brightness: self.global_brightness,
         }
     }
 }
@@ -267,6 +310,7 @@ impl<Led> ClockedDriver<Led, ()> {
         ClockedDriver {
             led: self.led,
             writer,
+            global_brightness: self.global_brightness,
         }
     }
 }
@@ -280,6 +324,8 @@ where
     type Color = Led::Color;
     type Word = Led::Word;
 
+    const COLOR_CAPABILITY: ColorCapability = Led::COLOR_CAPABILITY;
+
     fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, I, C>(
         &mut self,
         pixels: I,
@@ -291,11 +337,20 @@ where
         Led::Color: FromColor<C>,
     {
         let pixels = pixels.into_iter().map(Led::Color::from_color);
-        let frame: Vec<_, FRAME_BUFFER_SIZE> =
-            Vec::from_iter(Led::update(pixels, brightness, correction, PIXEL_COUNT));
+        let frame: Vec<_, FRAME_BUFFER_SIZE> = Vec::from_iter(Led::update(
+            pixels,
+            brightness,
+            correction,
+            self.global_brightness,
+            PIXEL_COUNT,
+        ));
         frame
     }
 
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.global_brightness = global_brightness;
+    }
+
     fn write<const FRAME_BUFFER_SIZE: usize>(
         &mut self,
         frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
@@ -316,6 +371,8 @@ where
     type Color = Led::Color;
     type Word = Led::Word;
 
+    const COLOR_CAPABILITY: ColorCapability = Led::COLOR_CAPABILITY;
+
     fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, I, C>(
         &mut self,
         pixels: I,
@@ -327,11 +384,20 @@ where
         Led::Color: FromColor<C>,
     {
         let pixels = pixels.into_iter().map(Led::Color::from_color);
-        let frame: Vec<_, FRAME_BUFFER_SIZE> =
-            Vec::from_iter(Led::update(pixels, brightness, correction, PIXEL_COUNT));
+        let frame: Vec<_, FRAME_BUFFER_SIZE> = Vec::from_iter(Led::update(
+            pixels,
+            brightness,
+            correction,
+            self.global_brightness,
+            PIXEL_COUNT,
+        ));
         frame
     }
 
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.global_brightness = global_brightness;
+    }
+
     async fn write<const FRAME_BUFFER_SIZE: usize>(
         &mut self,
         frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
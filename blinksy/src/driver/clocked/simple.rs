@@ -0,0 +1,234 @@
+use heapless::Vec;
+
+use crate::{
+    color::{apply_output_pipeline, ColorCorrection, LinearSrgb, RgbChannels},
+    driver::ColorCapability,
+    power::CurrentDraw,
+};
+
+use super::ClockedLed;
+
+/// The maximum number of bytes for a single LED of a [`SimpleClockedLed`].
+const MAX_LED_BYTES: usize = 16;
+
+/// A field of bits, written most significant bit first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Bits {
+    /// The value, in the lowest `count` bits
+    pub value: u32,
+    /// The number of bits
+    pub count: u32,
+}
+
+impl Bits {
+    /// An empty field.
+    pub const NONE: Bits = Bits::new(0, 0);
+
+    /// Creates a field of bits.
+    ///
+    /// # Arguments
+    ///
+    /// - `value` - The value, in the lowest `count` bits
+    /// - `count` - The number of bits
+    pub const fn new(value: u32, count: u32) -> Self {
+        Self { value, count }
+    }
+}
+
+/// Trait for describing a clocked LED chipset with only a few constants.
+///
+/// Most clocked chipsets frame each LED the same way: an optional header, an optional global
+/// brightness field, then each color channel (with an optional header), in some channel order.
+/// Implement this trait to get a [`ClockedLed`] implementation for free.
+///
+/// Each LED is written as these fields, most significant bit first:
+///
+/// 1. [`led_header`](Self::led_header) (default: [`LED_HEADER`](Self::LED_HEADER))
+/// 2. Global brightness, if [`BRIGHTNESS_BITS`](Self::BRIGHTNESS_BITS) is not 0
+/// 3. For each channel in [`RGB_CHANNELS`](Self::RGB_CHANNELS) order:
+///    1. [`CHANNEL_HEADER`](Self::CHANNEL_HEADER)
+///    2. The channel value, [`CHANNEL_BITS`](Self::CHANNEL_BITS) wide
+///
+/// The fields of an LED must add up to a whole number of bytes, and at most 16 bytes.
+///
+/// Without a brightness field, global brightness scales the channel values.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{
+///     color::RgbChannels,
+///     driver::{Bits, SimpleClockedLed},
+/// };
+///
+/// // A chipset with 16 bits per LED: a '1' start bit, then 5 bits per channel
+/// struct MyLed;
+///
+/// impl SimpleClockedLed for MyLed {
+///     const START_FRAME: &'static [u8] = &[0x00, 0x00, 0x00, 0x00];
+///     const END_FRAME_PIXELS_PER_BYTE: usize = 8;
+///     const RGB_CHANNELS: RgbChannels = RgbChannels::RGB;
+///     const LED_HEADER: Bits = Bits::new(0b1, 1);
+///     const CHANNEL_BITS: u32 = 5;
+/// }
+/// ```
+pub trait SimpleClockedLed {
+    /// The start frame, written before the LEDs.
+    const START_FRAME: &'static [u8];
+
+    /// The fixed part of the end frame, written after the LEDs.
+    const END_FRAME: &'static [u8] = &[];
+
+    /// For the variable part of the end frame: one zero byte per this many LEDs, to clock the
+    /// data through the whole chain, or 0 for none.
+    ///
+    /// Written after the fixed part of the end frame.
+    const END_FRAME_PIXELS_PER_BYTE: usize = 0;
+
+    /// The order of the color channels.
+    const RGB_CHANNELS: RgbChannels;
+
+    /// The header of each LED.
+    const LED_HEADER: Bits = Bits::NONE;
+
+    /// The width of the global brightness field of each LED, or 0 for none.
+    const BRIGHTNESS_BITS: u32 = 0;
+
+    /// The header of each color channel.
+    const CHANNEL_HEADER: Bits = Bits::NONE;
+
+    /// The width of each color channel.
+    const CHANNEL_BITS: u32 = 8;
+
+    /// Current drawn by one LED, for [power estimates](crate::power).
+    const CURRENT_DRAW: CurrentDraw = CurrentDraw::DEFAULT;
+
+    /// The header of an LED, given its channel values.
+    ///
+    /// Override this for chipsets with a checksum in the header, returning as many bits as
+    /// [`LED_HEADER`](Self::LED_HEADER). By default, returns [`LED_HEADER`](Self::LED_HEADER).
+    ///
+    /// # Arguments
+    ///
+    /// - `rgb` - The [R, G, B] channel values, [`CHANNEL_BITS`](Self::CHANNEL_BITS) wide
+    fn led_header(rgb: [u32; 3]) -> Bits {
+        let _ = rgb;
+        Self::LED_HEADER
+    }
+}
+
+/// The number of bytes for a single LED.
+pub(crate) const fn led_byte_count<Led: SimpleClockedLed>() -> usize {
+    let led_bits = Led::LED_HEADER.count
+        + Led::BRIGHTNESS_BITS
+        + 3 * (Led::CHANNEL_HEADER.count + Led::CHANNEL_BITS);
+    (led_bits as usize).div_ceil(8)
+}
+
+/// The number of zero bytes in the variable part of an end frame.
+pub(crate) const fn end_frame_padding(pixel_count: usize, pixels_per_byte: usize) -> usize {
+    if pixels_per_byte == 0 {
+        0
+    } else {
+        pixel_count.div_ceil(pixels_per_byte)
+    }
+}
+
+/// Converts a normalized value to an integer with the given number of bits.
+fn quantize(value: f32, bits: u32) -> u32 {
+    let max = ((1u64 << bits) - 1) as f32;
+    (value.clamp(0., 1.) * max) as u32
+}
+
+/// Writes fields of bits into bytes, most significant bit first.
+struct BitWriter {
+    bytes: Vec<u8, MAX_LED_BYTES>,
+    current: u8,
+    current_count: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            current: 0,
+            current_count: 0,
+        }
+    }
+
+    fn write(&mut self, bits: Bits) {
+        for index in (0..bits.count).rev() {
+            let bit = ((bits.value >> index) & 1) as u8;
+            self.current = (self.current << 1) | bit;
+            self.current_count += 1;
+            if self.current_count == 8 {
+                // LEDs longer than the maximum are truncated
+                let _ = self.bytes.push(self.current);
+                self.current = 0;
+                self.current_count = 0;
+            }
+        }
+    }
+
+    fn finish(self) -> Vec<u8, MAX_LED_BYTES> {
+        self.bytes
+    }
+}
+
+impl<Led> ClockedLed for Led
+where
+    Led: SimpleClockedLed,
+{
+    type Word = u8;
+    type Color = LinearSrgb;
+
+    const CURRENT_DRAW: CurrentDraw = <Led as SimpleClockedLed>::CURRENT_DRAW;
+    const COLOR_CAPABILITY: ColorCapability =
+        ColorCapability::new(<Led as SimpleClockedLed>::CHANNEL_BITS as u8, false);
+
+    fn start() -> impl IntoIterator<Item = Self::Word> {
+        Self::START_FRAME.iter().copied()
+    }
+
+    fn led(
+        linear_rgb: LinearSrgb,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> impl IntoIterator<Item = Self::Word> {
+        // Without a brightness field, brightness scales the channels
+        let scale = if Self::BRIGHTNESS_BITS == 0 {
+            brightness
+        } else {
+            1.
+        };
+
+        let LinearSrgb { red, green, blue } = apply_output_pipeline(linear_rgb, scale, correction);
+        let rgb = [
+            quantize(red, Self::CHANNEL_BITS),
+            quantize(green, Self::CHANNEL_BITS),
+            quantize(blue, Self::CHANNEL_BITS),
+        ];
+
+        let mut writer = BitWriter::new();
+        writer.write(Self::led_header(rgb));
+        if Self::BRIGHTNESS_BITS != 0 {
+            writer.write(Bits::new(
+                quantize(brightness, Self::BRIGHTNESS_BITS),
+                Self::BRIGHTNESS_BITS,
+            ));
+        }
+        for channel in Self::RGB_CHANNELS.reorder(rgb) {
+            writer.write(Self::CHANNEL_HEADER);
+            writer.write(Bits::new(channel, Self::CHANNEL_BITS));
+        }
+        writer.finish()
+    }
+
+    fn end(pixel_count: usize) -> impl IntoIterator<Item = Self::Word> {
+        Self::END_FRAME.iter().copied().chain(core::iter::repeat_n(
+            0u8,
+            end_frame_padding(pixel_count, Self::END_FRAME_PIXELS_PER_BYTE),
+        ))
+    }
+}
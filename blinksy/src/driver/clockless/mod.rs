@@ -67,7 +67,8 @@ use heapless::Vec;
 use crate::driver::DriverAsync;
 use crate::{
     color::{ColorCorrection, FromColor, LedChannels, LedColor, LinearSrgb},
-    driver::Driver,
+    driver::{ColorCapability, Driver},
+    power::CurrentDraw,
     time::Nanoseconds,
     util::component::Component,
 };
@@ -126,6 +127,19 @@ pub trait ClocklessLed {
     /// Different LED chipsets may expect data in different channel orders (e.g., RGB, GRB, RGBW).
     const LED_CHANNELS: LedChannels;
 
+    /// Current drawn by one LED, for [power estimates](crate::power).
+    ///
+    /// Defaults to [`CurrentDraw::DEFAULT`].
+    const CURRENT_DRAW: CurrentDraw = CurrentDraw::DEFAULT;
+
+    /// The native color depth and space of the LEDs.
+    ///
+    /// Defaults to the word size and channels of the LEDs.
+    const COLOR_CAPABILITY: ColorCapability = ColorCapability::from_channels(
+        Self::LED_CHANNELS,
+        (core::mem::size_of::<Self::Word>() * 8) as u8,
+    );
+
     /// Calculates the total cycle time for a bit transmission.
     ///
     /// Returns the maximum of (T_0H + T_0L) and (T_1H + T_1L) to ensure
@@ -251,6 +265,8 @@ where
     type Color = LinearSrgb;
     type Word = Led::Word;
 
+    const COLOR_CAPABILITY: ColorCapability = Led::COLOR_CAPABILITY;
+
     fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, I, C>(
         &mut self,
         pixels: I,
@@ -284,6 +300,8 @@ where
     type Color = LinearSrgb;
     type Word = Led::Word;
 
+    const COLOR_CAPABILITY: ColorCapability = Led::COLOR_CAPABILITY;
+
     fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, I, C>(
         &mut self,
         pixels: I,
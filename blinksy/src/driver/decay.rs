@@ -0,0 +1,340 @@
+//! # Decay
+//!
+//! [`Decay`] wraps any driver and blends each new frame with a fraction of the last frame it
+//! sent, giving trails to any pattern without changing the pattern:
+//!
+//! - [`DecayMode::Phosphor`]: Each pixel fades out from its last color, unless the new color is
+//!   brighter, like the phosphor of an old screen. Moving dots leave trails.
+//! - [`DecayMode::MotionBlur`]: Each pixel mixes its last color into the new color, so every
+//!   change is smoothed out over a few frames.
+//!
+//! The last frame is kept in a buffer, so the persistence applies per frame: at a higher frame
+//! rate, trails are shorter in time.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use embedded_hal::{delay::DelayNs, digital::OutputPin};
+//! use blinksy::{
+//!     driver::{ClocklessDelayBuilder, ClocklessDriver, Decay, DecayMode, Driver},
+//!     leds::Ws2812,
+//! };
+//!
+//! fn setup_leds<Data, Delay>(data: Data, delay: Delay) -> impl Driver
+//! where
+//!     Data: OutputPin,
+//!     Delay: DelayNs,
+//! {
+//!     let writer = ClocklessDelayBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_data(data)
+//!         .with_delay(delay)
+//!         .build();
+//!     let driver = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(writer);
+//!
+//!     // Keep 90% of the last frame, for up to 60 pixels
+//!     Decay::<_, 60>::new(driver)
+//!         .with_mode(DecayMode::Phosphor)
+//!         .with_persistence(0.9)
+//! }
+//! ```
+
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use super::{ColorCapability, Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor, LinearSrgb};
+
+/// How a [`Decay`] blends each new frame with the last frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecayMode {
+    /// Takes the brighter of the new color, and the last color faded by the persistence
+    #[default]
+    Phosphor,
+    /// Mixes the last color into the new color, by the persistence
+    MotionBlur,
+}
+
+impl DecayMode {
+    /// Blends a new color with the last color.
+    ///
+    /// # Arguments
+    ///
+    /// - `last` - The last color sent
+    /// - `next` - The new color
+    /// - `persistence` - How much of the last color to keep, from 0.0 (none) to 1.0
+    ///
+    /// # Returns
+    ///
+    /// The color to send
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blinksy::{color::LinearSrgb, driver::DecayMode};
+    ///
+    /// let last = LinearSrgb::new(1., 0., 0.);
+    /// let next = LinearSrgb::new(0., 0., 1.);
+    ///
+    /// assert_eq!(DecayMode::Phosphor.blend(last, next, 0.5), LinearSrgb::new(0.5, 0., 1.));
+    /// assert_eq!(DecayMode::MotionBlur.blend(last, next, 0.5), LinearSrgb::new(0.5, 0., 0.5));
+    /// ```
+    pub fn blend(self, last: LinearSrgb, next: LinearSrgb, persistence: f32) -> LinearSrgb {
+        let persistence = persistence.clamp(0., 1.);
+        let channel = |last: f32, next: f32| match self {
+            DecayMode::Phosphor => next.max(last * persistence),
+            DecayMode::MotionBlur => next + (last - next) * persistence,
+        };
+        LinearSrgb {
+            red: channel(last.red, next.red),
+            green: channel(last.green, next.green),
+            blue: channel(last.blue, next.blue),
+        }
+    }
+}
+
+/// A driver wrapper which blends each frame with a fraction of the last frame sent.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver
+/// - `CAPACITY` - The maximum number of pixels kept. With more pixels than this, frames are
+///   forwarded as they are.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Decay<Driver, const CAPACITY: usize> {
+    driver: Driver,
+    mode: DecayMode,
+    persistence: f32,
+    last: Vec<LinearSrgb, CAPACITY>,
+}
+
+impl<Driver, const CAPACITY: usize> Decay<Driver, CAPACITY> {
+    /// Wraps a driver, with the default mode, and a persistence of 0.8.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The inner driver
+    pub fn new(driver: Driver) -> Self {
+        Self {
+            driver,
+            mode: DecayMode::default(),
+            persistence: 0.8,
+            last: Vec::new(),
+        }
+    }
+
+    /// Sets how each new frame is blended with the last frame.
+    pub fn with_mode(mut self, mode: DecayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets how much of the last frame to keep, from 0.0 (none) to 1.0.
+    pub fn with_persistence(mut self, persistence: f32) -> Self {
+        self.persistence = persistence;
+        self
+    }
+
+    /// Returns how much of the last frame is kept.
+    pub fn persistence(&self) -> f32 {
+        self.persistence
+    }
+
+    /// Sets how much of the last frame to keep, e.g. to lengthen the trails at runtime.
+    ///
+    /// # Arguments
+    ///
+    /// - `persistence` - From 0.0 (none) to 1.0
+    pub fn set_persistence(&mut self, persistence: f32) {
+        self.persistence = persistence;
+    }
+
+    /// Forgets the last frame, so the next frame is sent as it is.
+    pub fn clear(&mut self) {
+        self.last.clear();
+    }
+
+    /// Unwraps the inner driver.
+    pub fn into_inner(self) -> Driver {
+        self.driver
+    }
+
+    /// Blends a new frame with the last frame, and keeps it as the last frame.
+    fn decay<const PIXEL_COUNT: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+    ) -> Vec<LinearSrgb, PIXEL_COUNT>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        LinearSrgb: FromColor<Color>,
+    {
+        let mut colors: Vec<LinearSrgb, PIXEL_COUNT> = pixels
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .map(LinearSrgb::from_color)
+            .collect();
+        if self.last.len() == colors.len() {
+            for (color, last) in colors.iter_mut().zip(&self.last) {
+                *color = self.mode.blend(*last, *color, self.persistence);
+            }
+        }
+        self.last.clear();
+        // Without enough capacity, every frame is forwarded as it is.
+        if self.last.extend_from_slice(&colors).is_err() {
+            self.last.clear();
+        }
+        colors
+    }
+}
+
+impl<D, const CAPACITY: usize> Driver for Decay<D, CAPACITY>
+where
+    D: Driver,
+    D::Color: FromColor<LinearSrgb>,
+{
+    type Error = D::Error;
+    type Color = LinearSrgb;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let colors = self.decay::<PIXEL_COUNT, _, _>(pixels);
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, LinearSrgb>(colors, brightness, correction)
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        self.driver.write(frame, brightness, correction)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+
+    /// Forgets the last frame, so the LEDs are off at once, without trails.
+    fn blank<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+    ) -> Result<(), Self::Error> {
+        self.clear();
+        self.driver.blank::<PIXEL_COUNT, FRAME_BUFFER_SIZE>()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D, const CAPACITY: usize> DriverAsync for Decay<D, CAPACITY>
+where
+    D: DriverAsync,
+    D::Color: FromColor<LinearSrgb>,
+{
+    type Error = D::Error;
+    type Color = LinearSrgb;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let colors = self.decay::<PIXEL_COUNT, _, _>(pixels);
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, LinearSrgb>(colors, brightness, correction)
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.driver.write(frame).await
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+
+    /// Forgets the last frame, so the LEDs are off at once, without trails.
+    async fn blank<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+    ) -> Result<(), Self::Error> {
+        self.clear();
+        self.driver.blank::<PIXEL_COUNT, FRAME_BUFFER_SIZE>().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driver::NullDriver;
+
+    const BLACK: LinearSrgb = LinearSrgb {
+        red: 0.,
+        green: 0.,
+        blue: 0.,
+    };
+    const WHITE: LinearSrgb = LinearSrgb {
+        red: 1.,
+        green: 1.,
+        blue: 1.,
+    };
+
+    #[test]
+    fn test_trail_fades_out() {
+        let mut decay = Decay::<NullDriver, 2>::new(NullDriver).with_persistence(0.5);
+
+        assert_eq!(decay.decay::<2, _, _>([WHITE, BLACK]), [WHITE, BLACK]);
+        let frame = decay.decay::<2, _, _>([BLACK, WHITE]);
+        assert_eq!(frame, [LinearSrgb::new(0.5, 0.5, 0.5), WHITE]);
+        let frame = decay.decay::<2, _, _>([BLACK, BLACK]);
+        assert_eq!(
+            frame,
+            [
+                LinearSrgb::new(0.25, 0.25, 0.25),
+                LinearSrgb::new(0.5, 0.5, 0.5)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blank_forgets_the_last_frame() {
+        let mut decay = Decay::<NullDriver, 2>::new(NullDriver).with_persistence(0.5);
+
+        decay.decay::<2, _, _>([WHITE, WHITE]);
+        Driver::blank::<2, 0>(&mut decay).unwrap();
+        assert_eq!(decay.decay::<2, _, _>([BLACK, WHITE]), [BLACK, WHITE]);
+    }
+
+    #[test]
+    fn test_over_capacity() {
+        let mut decay = Decay::<NullDriver, 1>::new(NullDriver);
+
+        assert_eq!(decay.decay::<2, _, _>([WHITE, WHITE]), [WHITE, WHITE]);
+        assert_eq!(decay.decay::<2, _, _>([BLACK, BLACK]), [BLACK, BLACK]);
+    }
+}
@@ -0,0 +1,711 @@
+//! # Frame Differ
+//!
+//! Many patterns are often static, or change only part of a frame. For network outputs, every
+//! unneeded frame costs bandwidth and radio airtime. These wrappers track the last frame sent,
+//! and skip what hasn't changed:
+//!
+//! - [`FrameDiffer`]: Forwards a frame only if any pixel changed beyond a threshold.
+//! - [`SpanDiffer`]: Forwards only the span of pixels which changed beyond a threshold, for
+//!   protocols which support partial updates (like DDP), implementing [`PartialUpdate`].
+//!
+//! A change in brightness or color correction always forwards the whole frame.
+//!
+//! Pixels are compared as [`LinearSrgb`], by their largest channel difference.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use embedded_hal::{delay::DelayNs, digital::OutputPin};
+//! use blinksy::{
+//!     driver::{ClocklessDelayBuilder, ClocklessDriver, Driver, FrameDiffer},
+//!     leds::Ws2812,
+//! };
+//!
+//! fn setup_leds<Data, Delay>(data: Data, delay: Delay) -> impl Driver
+//! where
+//!     Data: OutputPin,
+//!     Delay: DelayNs,
+//! {
+//!     let writer = ClocklessDelayBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_data(data)
+//!         .with_delay(delay)
+//!         .build();
+//!     let driver = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(writer);
+//!
+//!     // Track up to 60 pixels, ignoring changes below one 8-bit step
+//!     FrameDiffer::<_, 60>::new(driver).with_threshold(1. / 255.)
+//! }
+//! ```
+
+use heapless::Vec;
+#[allow(unused_imports)]
+use num_traits::Float;
+
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use super::{ColorCapability, Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor, LinearSrgb};
+
+/// A driver which can update a span of pixels, leaving the others as they are.
+pub trait PartialUpdate: Driver {
+    /// Writes a frame buffer for a span of pixels.
+    ///
+    /// The frame buffer is as encoded by [`Driver::encode`], for only the pixels in the span.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixel_offset` - Index of the first pixel in the span
+    /// - `frame` - Frame buffer for the span
+    /// - `brightness` - Global brightness scaling factor (0.0 to 1.0)
+    /// - `correction` - Color correction factors
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error
+    fn write_span<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        pixel_offset: usize,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error>;
+}
+
+/// An async driver which can update a span of pixels, leaving the others as they are.
+#[cfg(feature = "async")]
+pub trait PartialUpdateAsync: DriverAsync {
+    #[allow(async_fn_in_trait)]
+    /// Writes a frame buffer for a span of pixels, asynchronously.
+    ///
+    /// The frame buffer is as encoded by [`DriverAsync::encode`], for only the pixels in the
+    /// span.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixel_offset` - Index of the first pixel in the span
+    /// - `frame` - Frame buffer for the span
+    ///
+    /// # Returns
+    ///
+    /// Future that resolves to a Result indicating success or an error
+    async fn write_span<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        pixel_offset: usize,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error>;
+}
+
+/// The last frame sent, to compare new frames against.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct PreviousFrame<const CAPACITY: usize> {
+    colors: Vec<LinearSrgb, CAPACITY>,
+    brightness: f32,
+    correction: ColorCorrection,
+    is_valid: bool,
+    pending: Option<PendingFrame<CAPACITY>>,
+}
+
+/// A frame encoded but not yet written, to record as sent once written.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum PendingFrame<const CAPACITY: usize> {
+    /// The whole frame, or `None` colors without enough capacity
+    Whole {
+        colors: Option<Vec<LinearSrgb, CAPACITY>>,
+        brightness: f32,
+        correction: ColorCorrection,
+    },
+    /// The colors of the span of changed pixels, from `start`
+    Span {
+        start: usize,
+        colors: Vec<LinearSrgb, CAPACITY>,
+    },
+}
+
+impl<const CAPACITY: usize> PreviousFrame<CAPACITY> {
+    fn new() -> Self {
+        Self {
+            colors: Vec::new(),
+            brightness: 0.,
+            correction: ColorCorrection::default(),
+            is_valid: false,
+            pending: None,
+        }
+    }
+
+    /// Compares a new frame, returning the span of changed pixels (if any).
+    ///
+    /// The pixels in that span are recorded as sent only once [committed](Self::commit), so a
+    /// frame which failed to write is sent again.
+    fn diff(
+        &mut self,
+        colors: &[LinearSrgb],
+        threshold: f32,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Option<(usize, usize)> {
+        self.pending = None;
+
+        let is_comparable = self.is_valid
+            && self.colors.len() == colors.len()
+            && self.brightness == brightness
+            && self.correction == correction;
+
+        if !is_comparable {
+            // Without enough capacity, every frame is forwarded whole.
+            self.pending = Some(PendingFrame::Whole {
+                colors: Vec::from_slice(colors).ok(),
+                brightness,
+                correction,
+            });
+            return Some((0, colors.len()));
+        }
+
+        let is_changed = |(previous, next): (&LinearSrgb, &LinearSrgb)| {
+            (previous.red - next.red).abs() > threshold
+                || (previous.green - next.green).abs() > threshold
+                || (previous.blue - next.blue).abs() > threshold
+        };
+        let start = self.colors.iter().zip(colors).position(is_changed)?;
+        let end = colors.len()
+            - self
+                .colors
+                .iter()
+                .zip(colors)
+                .rev()
+                .position(is_changed)
+                .unwrap_or(0);
+
+        // The span fits, as the frame is the same length as the previous one.
+        self.pending = Vec::from_slice(&colors[start..end])
+            .ok()
+            .map(|colors| PendingFrame::Span { start, colors });
+        Some((start, end))
+    }
+
+    /// Records the frame from the last [`diff`](Self::diff) as sent, once written.
+    fn commit(&mut self) {
+        match self.pending.take() {
+            Some(PendingFrame::Whole {
+                colors,
+                brightness,
+                correction,
+            }) => {
+                self.brightness = brightness;
+                self.correction = correction;
+                self.is_valid = colors.is_some();
+                self.colors = colors.unwrap_or_default();
+            }
+            Some(PendingFrame::Span { start, colors }) => {
+                self.colors[start..start + colors.len()].copy_from_slice(&colors);
+            }
+            None => {}
+        }
+    }
+
+    /// Forgets the previous frame, so the next frame is forwarded whole.
+    fn invalidate(&mut self) {
+        self.is_valid = false;
+        self.pending = None;
+    }
+}
+
+/// A driver wrapper which forwards a frame only if it changed.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver
+/// - `CAPACITY` - The maximum number of pixels tracked. With more pixels than this, every frame
+///   is forwarded.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameDiffer<Driver, const CAPACITY: usize> {
+    driver: Driver,
+    threshold: f32,
+    previous: PreviousFrame<CAPACITY>,
+    is_skipped: bool,
+}
+
+impl<Driver, const CAPACITY: usize> FrameDiffer<Driver, CAPACITY> {
+    /// Wraps a driver, forwarding frames with any change.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The inner driver
+    pub fn new(driver: Driver) -> Self {
+        Self {
+            driver,
+            threshold: 0.,
+            previous: PreviousFrame::new(),
+            is_skipped: false,
+        }
+    }
+
+    /// Sets the largest channel difference (from 0.0 to 1.0) not counted as a change.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Returns whether the last frame was skipped, as unchanged.
+    pub fn is_skipped(&self) -> bool {
+        self.is_skipped
+    }
+
+    /// Unwraps the inner driver.
+    pub fn into_inner(self) -> Driver {
+        self.driver
+    }
+}
+
+impl<D, const CAPACITY: usize> Driver for FrameDiffer<D, CAPACITY>
+where
+    D: Driver,
+    D::Color: Clone,
+    LinearSrgb: FromColor<D::Color>,
+{
+    type Error = D::Error;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let colors: Vec<Self::Color, PIXEL_COUNT> = pixels
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .map(Self::Color::from_color)
+            .collect();
+        let linear_colors: Vec<LinearSrgb, PIXEL_COUNT> =
+            colors.iter().cloned().map(LinearSrgb::from_color).collect();
+
+        let span = self
+            .previous
+            .diff(&linear_colors, self.threshold, brightness, correction);
+        self.is_skipped = span.is_none();
+        if self.is_skipped {
+            return Vec::new();
+        }
+
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                colors, brightness, correction,
+            )
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        if self.is_skipped {
+            return Ok(());
+        }
+        self.driver.write(frame, brightness, correction)?;
+        self.previous.commit();
+        Ok(())
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        // The LEDs change even if the colors don't
+        self.previous.invalidate();
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D, const CAPACITY: usize> DriverAsync for FrameDiffer<D, CAPACITY>
+where
+    D: DriverAsync,
+    D::Color: Clone,
+    LinearSrgb: FromColor<D::Color>,
+{
+    type Error = D::Error;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let colors: Vec<Self::Color, PIXEL_COUNT> = pixels
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .map(Self::Color::from_color)
+            .collect();
+        let linear_colors: Vec<LinearSrgb, PIXEL_COUNT> =
+            colors.iter().cloned().map(LinearSrgb::from_color).collect();
+
+        let span = self
+            .previous
+            .diff(&linear_colors, self.threshold, brightness, correction);
+        self.is_skipped = span.is_none();
+        if self.is_skipped {
+            return Vec::new();
+        }
+
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                colors, brightness, correction,
+            )
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        if self.is_skipped {
+            return Ok(());
+        }
+        self.driver.write(frame).await?;
+        self.previous.commit();
+        Ok(())
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        // The LEDs change even if the colors don't
+        self.previous.invalidate();
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+/// A driver wrapper which forwards only the span of pixels which changed.
+///
+/// The span is from the first to the last changed pixel, including any unchanged pixels
+/// between them.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver, which supports partial updates
+/// - `CAPACITY` - The maximum number of pixels tracked. With more pixels than this, every frame
+///   is forwarded whole.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SpanDiffer<Driver, const CAPACITY: usize> {
+    driver: Driver,
+    threshold: f32,
+    previous: PreviousFrame<CAPACITY>,
+    span: Option<(usize, usize)>,
+}
+
+impl<Driver, const CAPACITY: usize> SpanDiffer<Driver, CAPACITY> {
+    /// Wraps a driver, forwarding spans with any change.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The inner driver
+    pub fn new(driver: Driver) -> Self {
+        Self {
+            driver,
+            threshold: 0.,
+            previous: PreviousFrame::new(),
+            span: None,
+        }
+    }
+
+    /// Sets the largest channel difference (from 0.0 to 1.0) not counted as a change.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Returns the span of pixels forwarded in the last frame, as start and end (exclusive)
+    /// indices, or `None` if the frame was skipped as unchanged.
+    pub fn span(&self) -> Option<(usize, usize)> {
+        self.span
+    }
+
+    /// Unwraps the inner driver.
+    pub fn into_inner(self) -> Driver {
+        self.driver
+    }
+}
+
+impl<D, const CAPACITY: usize> Driver for SpanDiffer<D, CAPACITY>
+where
+    D: PartialUpdate,
+    D::Color: Clone,
+    LinearSrgb: FromColor<D::Color>,
+{
+    type Error = D::Error;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let colors: Vec<Self::Color, PIXEL_COUNT> = pixels
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .map(Self::Color::from_color)
+            .collect();
+        let linear_colors: Vec<LinearSrgb, PIXEL_COUNT> =
+            colors.iter().cloned().map(LinearSrgb::from_color).collect();
+
+        self.span = self
+            .previous
+            .diff(&linear_colors, self.threshold, brightness, correction);
+        let Some((start, end)) = self.span else {
+            return Vec::new();
+        };
+
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                colors.into_iter().skip(start).take(end - start),
+                brightness,
+                correction,
+            )
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        let Some((start, _)) = self.span else {
+            return Ok(());
+        };
+        self.driver
+            .write_span(start, frame, brightness, correction)?;
+        self.previous.commit();
+        Ok(())
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        // The LEDs change even if the colors don't
+        self.previous.invalidate();
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D, const CAPACITY: usize> DriverAsync for SpanDiffer<D, CAPACITY>
+where
+    D: PartialUpdateAsync,
+    D::Color: Clone,
+    LinearSrgb: FromColor<D::Color>,
+{
+    type Error = D::Error;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let colors: Vec<Self::Color, PIXEL_COUNT> = pixels
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .map(Self::Color::from_color)
+            .collect();
+        let linear_colors: Vec<LinearSrgb, PIXEL_COUNT> =
+            colors.iter().cloned().map(LinearSrgb::from_color).collect();
+
+        self.span = self
+            .previous
+            .diff(&linear_colors, self.threshold, brightness, correction);
+        let Some((start, end)) = self.span else {
+            return Vec::new();
+        };
+
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                colors.into_iter().skip(start).take(end - start),
+                brightness,
+                correction,
+            )
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        let Some((start, _)) = self.span else {
+            return Ok(());
+        };
+        self.driver.write_span(start, frame).await?;
+        self.previous.commit();
+        Ok(())
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        // The LEDs change even if the colors don't
+        self.previous.invalidate();
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: LinearSrgb = LinearSrgb {
+        red: 0.,
+        green: 0.,
+        blue: 0.,
+    };
+    const WHITE: LinearSrgb = LinearSrgb {
+        red: 1.,
+        green: 1.,
+        blue: 1.,
+    };
+
+    /// Updates the previous frame as if written.
+    fn update<const CAPACITY: usize>(
+        previous: &mut PreviousFrame<CAPACITY>,
+        colors: &[LinearSrgb],
+        threshold: f32,
+        brightness: f32,
+    ) -> Option<(usize, usize)> {
+        let span = previous.diff(colors, threshold, brightness, ColorCorrection::default());
+        previous.commit();
+        span
+    }
+
+    #[test]
+    fn test_previous_frame_spans() {
+        let mut previous = PreviousFrame::<4>::new();
+
+        let frame = [BLACK; 4];
+        assert_eq!(update(&mut previous, &frame, 0., 1.), Some((0, 4)));
+        assert_eq!(update(&mut previous, &frame, 0., 1.), None);
+
+        let frame = [BLACK, WHITE, BLACK, WHITE];
+        assert_eq!(update(&mut previous, &frame, 0., 1.), Some((1, 4)));
+        assert_eq!(update(&mut previous, &frame, 0., 1.), None);
+
+        // Below threshold
+        let frame = [LinearSrgb::new(0.001, 0., 0.), WHITE, BLACK, WHITE];
+        assert_eq!(update(&mut previous, &frame, 0.01, 1.), None);
+
+        // Brightness changed
+        assert_eq!(update(&mut previous, &frame, 0.01, 0.5), Some((0, 4)));
+    }
+
+    #[test]
+    fn test_previous_frame_over_capacity() {
+        let mut previous = PreviousFrame::<2>::new();
+
+        let frame = [BLACK; 4];
+        assert_eq!(update(&mut previous, &frame, 0., 1.), Some((0, 4)));
+        assert_eq!(update(&mut previous, &frame, 0., 1.), Some((0, 4)));
+    }
+
+    #[test]
+    fn test_previous_frame_records_only_committed_frames() {
+        let correction = ColorCorrection::default();
+        let mut previous = PreviousFrame::<4>::new();
+
+        // The first frame failed to write
+        let frame = [BLACK; 4];
+        assert_eq!(previous.diff(&frame, 0., 1., correction), Some((0, 4)));
+        assert_eq!(update(&mut previous, &frame, 0., 1.), Some((0, 4)));
+
+        // A span failed to write
+        let frame = [BLACK, WHITE, BLACK, BLACK];
+        assert_eq!(previous.diff(&frame, 0., 1., correction), Some((1, 2)));
+        assert_eq!(update(&mut previous, &frame, 0., 1.), Some((1, 2)));
+        assert_eq!(update(&mut previous, &frame, 0., 1.), None);
+    }
+
+    #[derive(Default)]
+    struct FailingDriver {
+        failures: u32,
+        frame_count: usize,
+    }
+
+    impl Driver for FailingDriver {
+        type Error = ();
+        type Color = LinearSrgb;
+        type Word = ();
+
+        fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+            &mut self,
+            _pixels: Pixels,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+        where
+            Pixels: IntoIterator<Item = Color>,
+            Self::Color: FromColor<Color>,
+        {
+            Vec::new()
+        }
+
+        fn write<const FRAME_BUFFER_SIZE: usize>(
+            &mut self,
+            _frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> Result<(), Self::Error> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(());
+            }
+            self.frame_count += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_frame_differ_sends_failed_frame_again() {
+        let correction = ColorCorrection::default();
+        let mut differ = FrameDiffer::<_, 4>::new(FailingDriver {
+            failures: 1,
+            ..Default::default()
+        });
+
+        let frame = differ.encode::<4, 0, _, _>([WHITE; 4], 1., correction);
+        assert!(differ.write(frame, 1., correction).is_err());
+
+        // The same frame isn't skipped, as it wasn't written
+        let frame = differ.encode::<4, 0, _, _>([WHITE; 4], 1., correction);
+        assert!(!differ.is_skipped());
+        assert!(differ.write(frame, 1., correction).is_ok());
+
+        let frame = differ.encode::<4, 0, _, _>([WHITE; 4], 1., correction);
+        assert!(differ.is_skipped());
+        assert!(differ.write(frame, 1., correction).is_ok());
+        assert_eq!(differ.into_inner().frame_count, 1);
+    }
+}
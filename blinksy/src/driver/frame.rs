@@ -0,0 +1,208 @@
+//! # Handed-off Frames
+//!
+//! To render on one task or core, and show on another, the render side's driver hands off each
+//! frame of pixels as a [`Frame`], with the brightness, color correction, and
+//! [global brightness](GlobalBrightness) of the control, and the other side shows it with the
+//! real driver: see the `pipeline` module (feature `embassy`) for Embassy tasks, or the
+//! `dual_core` module of `blinksy-esp` for the cores of an ESP32.
+//!
+//! Both sides go through [`Frame::new`] and [`Frame::show`], so every hand-off shows a frame
+//! the same way.
+
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use super::{Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor, LinearSrgb};
+
+/// A frame of pixels, with the brightness and color correction to show it with.
+///
+/// # Type Parameters
+///
+/// - `PIXEL_COUNT` - The number of pixels in the frame
+#[derive(Debug, Clone)]
+pub struct Frame<const PIXEL_COUNT: usize> {
+    /// The color of each pixel
+    pub pixels: Vec<LinearSrgb, PIXEL_COUNT>,
+    /// Global brightness scaling factor (0.0 to 1.0)
+    pub brightness: f32,
+    /// Color correction factors
+    pub correction: ColorCorrection,
+    /// How to choose the global brightness field of the LEDs
+    pub global_brightness: GlobalBrightness,
+}
+
+impl<const PIXEL_COUNT: usize> Frame<PIXEL_COUNT> {
+    /// Creates a frame, from the pixels written to the render side's driver.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels` - The color of each pixel, of which the first `PIXEL_COUNT` are kept
+    /// - `brightness` - Global brightness scaling factor (0.0 to 1.0)
+    /// - `correction` - Color correction factors
+    /// - `global_brightness` - How to choose the global brightness field of the LEDs
+    pub fn new<Pixels>(
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+        global_brightness: GlobalBrightness,
+    ) -> Self
+    where
+        Pixels: IntoIterator<Item = LinearSrgb>,
+    {
+        Self {
+            pixels: pixels.into_iter().take(PIXEL_COUNT).collect(),
+            brightness,
+            correction,
+            global_brightness,
+        }
+    }
+
+    /// Shows the frame with a driver.
+    ///
+    /// The global brightness is set on the driver first, if it changed since the last frame.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `FRAME_BUFFER_SIZE` - Length of the encoded frame buffer of the driver, in words
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The LED driver
+    /// - `global_brightness` - The global brightness last set on the driver, updated to this
+    ///   frame's
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error from the driver
+    pub fn show<D, const FRAME_BUFFER_SIZE: usize>(
+        self,
+        driver: &mut D,
+        global_brightness: &mut GlobalBrightness,
+    ) -> Result<(), D::Error>
+    where
+        D: Driver,
+        D::Color: FromColor<LinearSrgb>,
+    {
+        if self.global_brightness != *global_brightness {
+            *global_brightness = self.global_brightness;
+            driver.set_global_brightness(self.global_brightness);
+        }
+        driver.show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+            self.pixels,
+            self.brightness,
+            self.correction,
+        )
+    }
+
+    /// Shows the frame with an async driver.
+    ///
+    /// The global brightness is set on the driver first, if it changed since the last frame.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `FRAME_BUFFER_SIZE` - Length of the encoded frame buffer of the driver, in words
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The LED driver
+    /// - `global_brightness` - The global brightness last set on the driver, updated to this
+    ///   frame's
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error from the driver
+    #[cfg(feature = "async")]
+    pub async fn show_async<D, const FRAME_BUFFER_SIZE: usize>(
+        self,
+        driver: &mut D,
+        global_brightness: &mut GlobalBrightness,
+    ) -> Result<(), D::Error>
+    where
+        D: DriverAsync,
+        D::Color: FromColor<LinearSrgb>,
+    {
+        if self.global_brightness != *global_brightness {
+            *global_brightness = self.global_brightness;
+            driver.set_global_brightness(self.global_brightness);
+        }
+        driver
+            .show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+                self.pixels,
+                self.brightness,
+                self.correction,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct GlobalBrightnessDriver {
+        global_brightness_count: usize,
+        pixel_count: usize,
+    }
+
+    impl Driver for GlobalBrightnessDriver {
+        type Error = Infallible;
+        type Color = LinearSrgb;
+        type Word = ();
+
+        fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+            &mut self,
+            pixels: Pixels,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+        where
+            Pixels: IntoIterator<Item = Color>,
+            Self::Color: FromColor<Color>,
+        {
+            self.pixel_count = pixels.into_iter().count();
+            Vec::new()
+        }
+
+        fn write<const FRAME_BUFFER_SIZE: usize>(
+            &mut self,
+            _frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn set_global_brightness(&mut self, _global_brightness: GlobalBrightness) {
+            self.global_brightness_count += 1;
+        }
+    }
+
+    #[test]
+    fn test_frame_sets_global_brightness_only_on_change() {
+        let correction = ColorCorrection::default();
+        let pixels = [LinearSrgb::new(1., 1., 1.); 6];
+        let mut driver = GlobalBrightnessDriver::default();
+        let mut global_brightness = GlobalBrightness::default();
+
+        let frame = Frame::<4>::new(pixels, 1., correction, GlobalBrightness::Adaptive);
+        frame
+            .show::<_, 0>(&mut driver, &mut global_brightness)
+            .unwrap();
+        assert_eq!(driver.pixel_count, 4);
+        assert_eq!(driver.global_brightness_count, 0);
+
+        for _ in 0..2 {
+            let frame = Frame::<4>::new(pixels, 1., correction, GlobalBrightness::Locked(8));
+            frame
+                .show::<_, 0>(&mut driver, &mut global_brightness)
+                .unwrap();
+        }
+        assert_eq!(driver.global_brightness_count, 1);
+        assert_eq!(global_brightness, GlobalBrightness::Locked(8));
+    }
+}
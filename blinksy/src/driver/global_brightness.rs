@@ -0,0 +1,74 @@
+//! # Global Brightness
+//!
+//! Some LEDs (like the [`APA102`](crate::leds::Apa102)) have a global brightness field per LED,
+//! besides their color channels. By default, drivers choose this field for each frame to keep
+//! the most precision, but it's applied by a slower PWM than the channels, so as it changes
+//! between frames, the PWM depth changes too: which is visible as flicker on high-speed (or
+//! rolling-shutter) cameras.
+//!
+//! For camera and film work, lock the field to a constant value with
+//! [`GlobalBrightness::Locked`], through
+//! [`Control::set_global_brightness`](crate::control::Control::set_global_brightness): then only
+//! the color channels change between frames.
+//!
+//! Drivers for LEDs without a global brightness field ignore this.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::{ColorCorrection, LinearSrgb},
+//!     driver::{ClockedLed, GlobalBrightness},
+//!     leds::Apa102,
+//! };
+//!
+//! let color = LinearSrgb::new(0.5, 0.5, 0.5);
+//! let locked = GlobalBrightness::Locked(GlobalBrightness::MAX_LEVEL);
+//!
+//! // Whatever the brightness, the 5-bit brightness of each LED stays the same
+//! for brightness in [0.1, 0.5, 1.] {
+//!     let brightness_byte = Apa102::led_with_global_brightness(
+//!         color,
+//!         brightness,
+//!         ColorCorrection::default(),
+//!         locked,
+//!     )
+//!     .into_iter()
+//!     .next();
+//!     assert_eq!(brightness_byte, Some(0b1110_0000 | GlobalBrightness::MAX_LEVEL));
+//! }
+//! ```
+//!
+//! Then, with your control:
+//!
+//! ```rust,ignore
+//! control.set_global_brightness(GlobalBrightness::Locked(GlobalBrightness::MAX_LEVEL));
+//! ```
+
+/// How a driver chooses the global brightness field of its LEDs, if they have one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GlobalBrightness {
+    /// Chosen per frame by the LEDs, e.g. to keep the most precision
+    #[default]
+    Adaptive,
+    /// Locked to a level, from 1 to [`MAX_LEVEL`](Self::MAX_LEVEL)
+    ///
+    /// The output is at most `level / MAX_LEVEL` of full brightness, so a lower level trades
+    /// the highest output for more precision: a frame brighter than this is clipped.
+    Locked(u8),
+}
+
+impl GlobalBrightness {
+    /// The highest level of a 5-bit global brightness field.
+    pub const MAX_LEVEL: u8 = 31;
+
+    /// Returns the locked level, clamped to 1 to [`MAX_LEVEL`](Self::MAX_LEVEL), if locked.
+    pub fn locked_level(self) -> Option<u8> {
+        match self {
+            GlobalBrightness::Adaptive => None,
+            GlobalBrightness::Locked(level) => Some(level.clamp(1, Self::MAX_LEVEL)),
+        }
+    }
+}
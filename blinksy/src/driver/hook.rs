@@ -0,0 +1,157 @@
+//! # Frame Completion Hook
+//!
+//! [`OnFrameWritten`] wraps any driver and calls a hook each time a frame has been written to
+//! the LED hardware, so other actions (camera triggers, audio cues, power relays) can be
+//! synchronized to the moment a frame latched.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use embedded_hal::{delay::DelayNs, digital::OutputPin};
+//! use blinksy::{
+//!     driver::{ClocklessDelayBuilder, ClocklessDriver, Driver, OnFrameWritten},
+//!     leds::Ws2812,
+//! };
+//!
+//! fn setup_leds<Data, Delay>(data: Data, delay: Delay) -> impl Driver
+//! where
+//!     Data: OutputPin,
+//!     Delay: DelayNs,
+//! {
+//!     let writer = ClocklessDelayBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_data(data)
+//!         .with_delay(delay)
+//!         .build();
+//!     let driver = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(writer);
+//!
+//!     OnFrameWritten::new(driver, || {
+//!         // Frame latched: trigger camera, cue audio, etc.
+//!     })
+//! }
+//! ```
+
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use super::{ColorCapability, Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor};
+
+/// A driver wrapper which calls a hook after each frame is written.
+///
+/// The hook is only called when the inner driver's write succeeds.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver
+/// - `Hook` - The hook, called with no arguments after each frame is written
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OnFrameWritten<Driver, Hook> {
+    driver: Driver,
+    hook: Hook,
+}
+
+impl<Driver, Hook> OnFrameWritten<Driver, Hook>
+where
+    Hook: FnMut(),
+{
+    /// Wraps a driver with a frame completion hook.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The inner driver
+    /// - `hook` - Called after each frame is written
+    pub fn new(driver: Driver, hook: Hook) -> Self {
+        Self { driver, hook }
+    }
+
+    /// Unwraps the inner driver and hook.
+    pub fn into_inner(self) -> (Driver, Hook) {
+        (self.driver, self.hook)
+    }
+}
+
+impl<D, Hook> Driver for OnFrameWritten<D, Hook>
+where
+    D: Driver,
+    Hook: FnMut(),
+{
+    type Error = D::Error;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction)
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        self.driver.write(frame, brightness, correction)?;
+        (self.hook)();
+        Ok(())
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D, Hook> DriverAsync for OnFrameWritten<D, Hook>
+where
+    D: DriverAsync,
+    Hook: FnMut(),
+{
+    type Error = D::Error;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction)
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.driver.write(frame).await?;
+        (self.hook)();
+        Ok(())
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
@@ -0,0 +1,271 @@
+//! # Logging Driver
+//!
+//! [`LogDriver`] logs each frame instead of showing it: a line of [`FrameStats`], then the
+//! colors of the first few pixels. Useful for bring-up on boards whose output isn't ready yet,
+//! and to debug patterns without LEDs.
+//!
+//! Lines are logged to any [`LogOutput`], including any closure taking a `&str`, or to
+//! [`defmt`] with [`DefmtOutput`] (feature `defmt`).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     driver::LogDriver,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     ControlBuilder,
+//! };
+//!
+//! layout1d!(Layout, 60);
+//!
+//! let mut lines = Vec::new();
+//!
+//! // Log the first 2 pixels of each frame
+//! let driver = LogDriver::<_, 2>::new(|line: &str| lines.push(line.to_string()));
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Rainbow>(RainbowParams::default())
+//!     .with_driver(driver)
+//!     .with_frame_buffer_size::<0>()
+//!     .build();
+//!
+//! control.tick(0).unwrap();
+//! drop(control);
+//!
+//! assert_eq!(lines.len(), 3);
+//! assert!(lines[0].starts_with("frame 0: 60 pixels"));
+//! ```
+
+use core::{convert::Infallible, fmt::Write};
+
+use heapless::{String, Vec};
+
+use super::Driver;
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use crate::color::{ColorCorrection, FromColor, LinearSrgb};
+
+/// Maximum length of a logged line, in bytes.
+const LINE_CAPACITY: usize = 96;
+
+/// Where a [`LogDriver`] logs its lines.
+pub trait LogOutput {
+    /// Logs one line, without a trailing newline.
+    fn log(&mut self, line: &str);
+}
+
+impl<F> LogOutput for F
+where
+    F: FnMut(&str),
+{
+    fn log(&mut self, line: &str) {
+        self(line)
+    }
+}
+
+/// Logs each line with [`defmt::info!`].
+#[cfg(feature = "defmt")]
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DefmtOutput;
+
+#[cfg(feature = "defmt")]
+impl LogOutput for DefmtOutput {
+    fn log(&mut self, line: &str) {
+        defmt::info!("{=str}", line);
+    }
+}
+
+/// Statistics of a frame.
+///
+/// Levels are of the colors' linear channels, before brightness and color correction.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameStats {
+    /// Index of the frame, counting from 0
+    pub frame_index: u32,
+    /// Number of pixels in the frame
+    pub pixel_count: usize,
+    /// Global brightness of the frame
+    pub brightness: f32,
+    /// Mean level of all channels of all pixels, from 0.0 to 1.0
+    pub mean_level: f32,
+    /// Maximum level of any channel of any pixel, from 0.0 to 1.0
+    pub max_level: f32,
+}
+
+/// A driver which logs each frame instead of showing it.
+///
+/// It encodes nothing, so the frame buffer size can be 0.
+///
+/// # Type Parameters
+///
+/// - `Output` - Where lines are logged
+/// - `PIXELS_SHOWN` - How many pixels (from the start) to log the colors of
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LogDriver<Output, const PIXELS_SHOWN: usize> {
+    output: Output,
+    frame_index: u32,
+    stats: FrameStats,
+    pixels: Vec<LinearSrgb, PIXELS_SHOWN>,
+}
+
+impl<Output, const PIXELS_SHOWN: usize> LogDriver<Output, PIXELS_SHOWN>
+where
+    Output: LogOutput,
+{
+    /// Creates a logging driver.
+    ///
+    /// # Arguments
+    ///
+    /// - `output` - Where lines are logged
+    pub fn new(output: Output) -> Self {
+        Self {
+            output,
+            frame_index: 0,
+            stats: FrameStats::default(),
+            pixels: Vec::new(),
+        }
+    }
+
+    /// Returns the statistics of the last encoded frame.
+    pub fn stats(&self) -> &FrameStats {
+        &self.stats
+    }
+
+    /// Unwraps the output.
+    pub fn into_inner(self) -> Output {
+        self.output
+    }
+
+    fn update<Pixels, Color>(&mut self, pixels: Pixels, brightness: f32)
+    where
+        Pixels: IntoIterator<Item = Color>,
+        LinearSrgb: FromColor<Color>,
+    {
+        self.pixels.clear();
+
+        let mut pixel_count = 0;
+        let mut total_level = 0.;
+        let mut max_level: f32 = 0.;
+        for color in pixels {
+            let color = LinearSrgb::from_color(color);
+            pixel_count += 1;
+            total_level += color.red + color.green + color.blue;
+            max_level = max_level.max(color.red).max(color.green).max(color.blue);
+            let _ = self.pixels.push(color);
+        }
+
+        self.stats = FrameStats {
+            frame_index: self.frame_index,
+            pixel_count,
+            brightness,
+            mean_level: if pixel_count > 0 {
+                total_level / (3 * pixel_count) as f32
+            } else {
+                0.
+            },
+            max_level,
+        };
+    }
+
+    fn log_frame(&mut self) {
+        let FrameStats {
+            frame_index,
+            pixel_count,
+            brightness,
+            mean_level,
+            max_level,
+        } = self.stats;
+
+        let mut line: String<LINE_CAPACITY> = String::new();
+        // Lines are short enough to never overflow
+        let _ = write!(
+            line,
+            "frame {}: {} pixels, brightness {:.2}, mean {:.3}, max {:.3}",
+            frame_index, pixel_count, brightness, mean_level, max_level
+        );
+        self.output.log(&line);
+
+        for (index, color) in self.pixels.iter().enumerate() {
+            line.clear();
+            let _ = write!(
+                line,
+                "  [{}] red {:.3}, green {:.3}, blue {:.3}",
+                index, color.red, color.green, color.blue
+            );
+            self.output.log(&line);
+        }
+
+        self.frame_index = self.frame_index.wrapping_add(1);
+    }
+}
+
+impl<Output, const PIXELS_SHOWN: usize> Driver for LogDriver<Output, PIXELS_SHOWN>
+where
+    Output: LogOutput,
+{
+    type Error = Infallible;
+    type Color = LinearSrgb;
+    type Word = ();
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        self.update(pixels, brightness);
+        Vec::new()
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        _frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        self.log_frame();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Output, const PIXELS_SHOWN: usize> DriverAsync for LogDriver<Output, PIXELS_SHOWN>
+where
+    Output: LogOutput,
+{
+    type Error = Infallible;
+    type Color = LinearSrgb;
+    type Word = ();
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        self.update(pixels, brightness);
+        Vec::new()
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        _frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.log_frame();
+        Ok(())
+    }
+}
@@ -7,20 +7,76 @@
 //! - [`Driver`]: For all blocking drivers
 //! - [`DriverAsync`]: For all async drivers
 //!
+//! Each driver advertises the [`ColorCapability`] of its LEDs, for patterns to match, and may
+//! take a [`GlobalBrightness`] hint, for LEDs with a global brightness field.
+//!
 //! ## Re-usable implementations
 //!
 //! - [`clocked`]: For clocked (two-wire) protocols (like [`APA102`](crate::leds::Apa102))
 //! - [`clockless`]: For clockless (one-wire) protocols (like [`WS2812`](crate::leds::Ws2812))
+//! - [`Frame`]: A frame of pixels, handed off to be shown by a driver on another task or core
+//!
+//! ## Development drivers
+//!
+//! - [`NullDriver`]: Accepts each frame and does nothing
+//! - [`LogDriver`]: Logs stats and the first pixels of each frame
+//!
+//! ## Adapters
+//!
+//! - [`OnFrameWritten`]: Calls a hook after each frame is written
+//! - [`FrameDiffer`]: Forwards only changed frames
+//! - [`SpanDiffer`]: Forwards only the changed span of each frame
+//! - [`Recorder`]: Records each frame to a sink (feature `postcard`)
+//! - [`TeeDriver`]: Writes each frame to two drivers
+//! - [`SplitDriver`]: Sends ranges of each frame to different drivers
+//! - [`OffsetDriver`]: Writes each frame at an offset of a [`SharedChain`], with other segments
+//! - [`StatusPixels`]: Reserves the first pixels of a chain to show the status of the device
+//! - [`ChannelReorder`]: Reorders the color channels of each pixel
+//! - [`ThermalThrottle`]: Scales brightness down when too hot
+//! - [`ComputeBudget`]: Measures the compute time of each frame, against a budget
+//! - [`Decay`]: Blends each frame with a fraction of the last frame, for trails
 
 use heapless::Vec;
 
-use crate::color::{ColorCorrection, FromColor};
+use crate::color::{ColorCorrection, FromColor, LinearSrgb};
 
+pub mod budget;
+pub mod capability;
 pub mod clocked;
 pub mod clockless;
+pub mod decay;
+pub mod diff;
+pub mod frame;
+pub mod global_brightness;
+pub mod hook;
+pub mod log;
+pub mod null;
+pub mod offset;
+#[cfg(feature = "postcard")]
+pub mod record;
+pub mod split;
+pub mod status;
+pub mod tee;
+pub mod thermal;
 
+pub use budget::*;
+pub use capability::*;
 pub use clocked::*;
 pub use clockless::*;
+pub use decay::*;
+pub use diff::*;
+pub use frame::*;
+pub use global_brightness::*;
+pub use hook::*;
+pub use log::*;
+pub use null::*;
+pub use offset::*;
+#[cfg(feature = "postcard")]
+pub use record::*;
+pub use split::*;
+pub use status::*;
+pub use tee::*;
+pub use thermal::*;
 
 /// Core trait for all blocking LED drivers.
 ///
@@ -90,6 +146,11 @@ pub trait Driver {
     /// The word of the frame buffer.
     type Word;
 
+    /// The native color depth and space of the LEDs, for patterns to match.
+    ///
+    /// Defaults to [`ColorCapability::RGB8`].
+    const COLOR_CAPABILITY: ColorCapability = ColorCapability::RGB8;
+
     /// Encodes an update frame buffer for the LED hardware.
     ///
     /// # Type Parameters
@@ -170,6 +231,41 @@ pub trait Driver {
             self.encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction);
         self.write(frame_buffer, brightness, correction)
     }
+
+    /// Sets how to choose the global brightness field of the LEDs, if they have one.
+    ///
+    /// Defaults to ignoring the hint, for LEDs without a global brightness field. Adapters
+    /// forward it to their drivers.
+    ///
+    /// # Arguments
+    ///
+    /// - `global_brightness` - How to choose the global brightness field
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        let _ = global_brightness;
+    }
+
+    /// Shows an all-off frame on the LED hardware.
+    ///
+    /// Use this to turn the LEDs off, e.g. from a panic handler, or to clear a frame latched
+    /// before a reset.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `PIXEL_COUNT` - Number of pixels in frame
+    /// - `FRAME_BUFFER_SIZE` - Length of encoded frame buffer, in words.
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error
+    fn blank<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Color: FromColor<LinearSrgb>,
+    {
+        let pixels = core::iter::repeat_n(LinearSrgb::new(0., 0., 0.), PIXEL_COUNT);
+        self.show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, 0., ColorCorrection::default())
+    }
 }
 
 /// Core trait for all async LED drivers.
@@ -237,6 +333,11 @@ pub trait DriverAsync {
     /// The word of the frame buffer.
     type Word;
 
+    /// The native color depth and space of the LEDs, for patterns to match.
+    ///
+    /// Defaults to [`ColorCapability::RGB8`].
+    const COLOR_CAPABILITY: ColorCapability = ColorCapability::RGB8;
+
     /// Encodes an update frame buffer for the LED hardware.
     ///
     /// # Type Parameters
@@ -317,4 +418,38 @@ pub trait DriverAsync {
             self.encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction);
         self.write(frame_buffer).await
     }
+
+    /// Sets how to choose the global brightness field of the LEDs, if they have one.
+    ///
+    /// Defaults to ignoring the hint, for LEDs without a global brightness field. Adapters
+    /// forward it to their drivers.
+    ///
+    /// # Arguments
+    ///
+    /// - `global_brightness` - How to choose the global brightness field
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        let _ = global_brightness;
+    }
+
+    #[allow(async_fn_in_trait)]
+    /// Shows an all-off frame on the LED hardware, asynchronously.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `PIXEL_COUNT` - Number of pixels in frame
+    /// - `FRAME_BUFFER_SIZE` - Length of encoded frame buffer, in words.
+    ///
+    /// # Returns
+    ///
+    /// Future that resolves to a Result indicating success or an error
+    async fn blank<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Color: FromColor<LinearSrgb>,
+    {
+        let pixels = core::iter::repeat_n(LinearSrgb::new(0., 0., 0.), PIXEL_COUNT);
+        self.show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, 0., ColorCorrection::default())
+            .await
+    }
 }
@@ -0,0 +1,113 @@
+//! # Null Driver
+//!
+//! [`NullDriver`] accepts every frame and does nothing with it, other than render its pixels:
+//! for bring-up on boards whose output isn't ready yet, for benchmarking patterns without the
+//! cost of output, and for tests.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     driver::NullDriver,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     ControlBuilder,
+//! };
+//!
+//! layout1d!(Layout, 60);
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Rainbow>(RainbowParams::default())
+//!     .with_driver(NullDriver)
+//!     .with_frame_buffer_size::<0>()
+//!     .build();
+//!
+//! control.tick(0).unwrap();
+//! ```
+
+use core::{convert::Infallible, hint::black_box};
+
+use heapless::Vec;
+
+use super::Driver;
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use crate::color::{ColorCorrection, FromColor, LinearSrgb};
+
+/// A driver which accepts every frame and does nothing with it.
+///
+/// Each pixel is still converted to [`LinearSrgb`], so the pattern renders the whole frame. It
+/// encodes nothing, so the frame buffer size can be 0.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NullDriver;
+
+impl Driver for NullDriver {
+    type Error = Infallible;
+    type Color = LinearSrgb;
+    type Word = ();
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        render(pixels);
+        Vec::new()
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        _frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Converts every pixel, as a driver would, without keeping them.
+fn render<Pixels, Color>(pixels: Pixels)
+where
+    Pixels: IntoIterator<Item = Color>,
+    LinearSrgb: FromColor<Color>,
+{
+    for pixel in pixels {
+        black_box(LinearSrgb::from_color(pixel));
+    }
+}
+
+#[cfg(feature = "async")]
+impl DriverAsync for NullDriver {
+    type Error = Infallible;
+    type Color = LinearSrgb;
+    type Word = ();
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        render(pixels);
+        Vec::new()
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        _frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,360 @@
+//! # Offset Output
+//!
+//! [`OffsetDriver`] writes a frame at an offset of one physical chain of LEDs, shared with other
+//! segments through a [`SharedChain`]. With a [`SubLayout`](crate::layout::SubLayout), each
+//! [`Control`](crate::control::Control) can own one segment of the chain: e.g. the stairs and the
+//! landing of one strip can each have their own pattern.
+//!
+//! Clockless chipsets (like the [`WS2812`](crate::leds::Ws2812)) can't be partially updated:
+//! each frame is shifted through the chain from the first LED. So the [`SharedChain`] keeps the
+//! last colors of every pixel, and each segment's frame is written with the last frames of the
+//! other segments passed through. Pixels not in any segment are dark.
+//!
+//! Each segment's pixels are kept at its own brightness, so every
+//! [`Control`](crate::control::Control) of a chain keeps its own brightness.
+//!
+//! ## Frame buffer size
+//!
+//! The whole chain is encoded with each frame, so the frame buffer size must be for
+//! `CHAIN_PIXEL_COUNT` pixels.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use embedded_hal::{delay::DelayNs, digital::OutputPin};
+//! use blinksy::{
+//!     control::ControlBuilder,
+//!     driver::{ClocklessDelayBuilder, ClocklessDriver, SharedChain},
+//!     layout::{Layout1d, SubLayout},
+//!     layout1d,
+//!     leds::Ws2812,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//! };
+//!
+//! layout1d!(Strip, 60);
+//!
+//! // The first 20 pixels of the strip are the stairs, and the rest is the landing
+//! type Stairs = SubLayout<Strip, 0, 20>;
+//! type Landing = SubLayout<Strip, 20, 40>;
+//!
+//! fn run<Data, Delay>(data: Data, delay: Delay)
+//! where
+//!     Data: OutputPin,
+//!     Delay: DelayNs,
+//! {
+//!     let writer = ClocklessDelayBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_data(data)
+//!         .with_delay(delay)
+//!         .build();
+//!     let driver = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(writer);
+//!     let chain = SharedChain::<_, { Strip::PIXEL_COUNT }>::new(driver);
+//!
+//!     let mut stairs = ControlBuilder::new_1d()
+//!         .with_layout::<Stairs, { Stairs::PIXEL_COUNT }>()
+//!         .with_pattern::<Rainbow>(RainbowParams::default())
+//!         .with_driver(chain.offset::<0>())
+//!         .with_frame_buffer_size::<{ Ws2812::frame_buffer_size(Strip::PIXEL_COUNT) }>()
+//!         .build();
+//!     let mut landing = ControlBuilder::new_1d()
+//!         .with_layout::<Landing, { Landing::PIXEL_COUNT }>()
+//!         .with_pattern::<Rainbow>(RainbowParams::default())
+//!         .with_driver(chain.offset::<20>())
+//!         .with_frame_buffer_size::<{ Ws2812::frame_buffer_size(Strip::PIXEL_COUNT) }>()
+//!         .build();
+//!     landing.set_brightness(0.2);
+//!
+//!     stairs.tick(0).unwrap();
+//!     landing.tick(0).unwrap();
+//! }
+//! ```
+
+use core::cell::RefCell;
+
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use super::{ColorCapability, Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor, LinearSrgb};
+
+/// One chain of `CHAIN_PIXEL_COUNT` LEDs, shared by the [`OffsetDriver`] of each segment.
+///
+/// Keeps the last colors of every pixel of the chain, so each segment's frame passes through the
+/// others.
+///
+/// Tick the controls of one chain one after the other: with the async driver, a segment
+/// panics if it writes a frame while another segment's frame is being written.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver, for the whole chain
+/// - `CHAIN_PIXEL_COUNT` - The number of pixels in the whole chain
+#[derive(Debug)]
+pub struct SharedChain<Driver, const CHAIN_PIXEL_COUNT: usize> {
+    state: RefCell<ChainState<Driver, CHAIN_PIXEL_COUNT>>,
+}
+
+#[derive(Debug)]
+struct ChainState<Driver, const CHAIN_PIXEL_COUNT: usize> {
+    driver: Driver,
+    /// The last colors of every pixel, at the brightness of their segment
+    pixels: [LinearSrgb; CHAIN_PIXEL_COUNT],
+}
+
+impl<Driver, const CHAIN_PIXEL_COUNT: usize> SharedChain<Driver, CHAIN_PIXEL_COUNT> {
+    /// Shares a driver between segments, with dark pixels.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The inner driver, for the whole chain
+    pub fn new(driver: Driver) -> Self {
+        Self {
+            state: RefCell::new(ChainState {
+                driver,
+                pixels: [LinearSrgb::new(0., 0., 0.); CHAIN_PIXEL_COUNT],
+            }),
+        }
+    }
+
+    /// Returns a driver for the segment after `OFFSET` pixels.
+    ///
+    /// The length of the segment is the pixel count of the frames written to it.
+    pub fn offset<const OFFSET: usize>(
+        &self,
+    ) -> OffsetDriver<'_, Driver, OFFSET, CHAIN_PIXEL_COUNT> {
+        OffsetDriver { chain: self }
+    }
+
+    /// Returns the last colors of every pixel, at the brightness of their segment.
+    pub fn pixels(&self) -> [LinearSrgb; CHAIN_PIXEL_COUNT] {
+        self.state.borrow().pixels
+    }
+
+    /// Unwraps the inner driver.
+    pub fn into_inner(self) -> Driver {
+        self.state.into_inner().driver
+    }
+}
+
+impl<Driver, const CHAIN_PIXEL_COUNT: usize> ChainState<Driver, CHAIN_PIXEL_COUNT> {
+    /// Updates the pixels of a segment, and returns the colors of the whole chain.
+    fn update<const OFFSET: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+    ) -> [LinearSrgb; CHAIN_PIXEL_COUNT]
+    where
+        Pixels: IntoIterator<Item = Color>,
+        LinearSrgb: FromColor<Color>,
+    {
+        let segment = self.pixels.iter_mut().skip(OFFSET);
+        for (pixel, color) in segment.zip(pixels) {
+            let LinearSrgb { red, green, blue } = LinearSrgb::from_color(color);
+            *pixel = LinearSrgb::new(red * brightness, green * brightness, blue * brightness);
+        }
+        self.pixels
+    }
+}
+
+/// A driver for a segment of a [`SharedChain`], which writes each frame after `OFFSET` pixels.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver, for the whole chain
+/// - `OFFSET` - The number of pixels before the segment
+/// - `CHAIN_PIXEL_COUNT` - The number of pixels in the whole chain
+#[derive(Debug)]
+pub struct OffsetDriver<'a, Driver, const OFFSET: usize, const CHAIN_PIXEL_COUNT: usize> {
+    chain: &'a SharedChain<Driver, CHAIN_PIXEL_COUNT>,
+}
+
+impl<D, const OFFSET: usize, const CHAIN_PIXEL_COUNT: usize> Driver
+    for OffsetDriver<'_, D, OFFSET, CHAIN_PIXEL_COUNT>
+where
+    D: Driver,
+    D::Color: FromColor<LinearSrgb>,
+{
+    type Error = D::Error;
+    type Color = LinearSrgb;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let mut state = self.chain.state.borrow_mut();
+        let pixels = pixels.into_iter().take(PIXEL_COUNT);
+        let chain_pixels = state.update::<OFFSET, _, _>(pixels, brightness);
+        // The brightness of each segment is already applied
+        state
+            .driver
+            .encode::<CHAIN_PIXEL_COUNT, FRAME_BUFFER_SIZE, _, LinearSrgb>(
+                chain_pixels,
+                1.,
+                correction,
+            )
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        _brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        let mut state = self.chain.state.borrow_mut();
+        state.driver.write(frame, 1., correction)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        let mut state = self.chain.state.borrow_mut();
+        state.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D, const OFFSET: usize, const CHAIN_PIXEL_COUNT: usize> DriverAsync
+    for OffsetDriver<'_, D, OFFSET, CHAIN_PIXEL_COUNT>
+where
+    D: DriverAsync,
+    D::Color: FromColor<LinearSrgb>,
+{
+    type Error = D::Error;
+    type Color = LinearSrgb;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let mut state = self.chain.state.borrow_mut();
+        let pixels = pixels.into_iter().take(PIXEL_COUNT);
+        let chain_pixels = state.update::<OFFSET, _, _>(pixels, brightness);
+        // The brightness of each segment is already applied
+        state
+            .driver
+            .encode::<CHAIN_PIXEL_COUNT, FRAME_BUFFER_SIZE, _, LinearSrgb>(
+                chain_pixels,
+                1.,
+                correction,
+            )
+    }
+
+    // The segments of a chain are written one after the other (see `SharedChain`)
+    #[allow(clippy::await_holding_refcell_ref)]
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        let mut state = self.chain.state.borrow_mut();
+        state.driver.write(frame).await
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        let mut state = self.chain.state.borrow_mut();
+        state.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes each pixel as its color, and keeps the last written frame.
+    #[derive(Default)]
+    struct ChainDriver {
+        frame: Vec<LinearSrgb, 4>,
+    }
+
+    impl Driver for ChainDriver {
+        type Error = ();
+        type Color = LinearSrgb;
+        type Word = LinearSrgb;
+
+        fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+            &mut self,
+            pixels: Pixels,
+            brightness: f32,
+            _correction: ColorCorrection,
+        ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+        where
+            Pixels: IntoIterator<Item = Color>,
+            Self::Color: FromColor<Color>,
+        {
+            assert_eq!(brightness, 1.);
+            pixels
+                .into_iter()
+                .map(LinearSrgb::from_color)
+                .take(FRAME_BUFFER_SIZE)
+                .collect()
+        }
+
+        fn write<const FRAME_BUFFER_SIZE: usize>(
+            &mut self,
+            frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> Result<(), Self::Error> {
+            self.frame = frame.into_iter().collect();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_segments_pass_through_each_other() {
+        let black = LinearSrgb::new(0., 0., 0.);
+        let red = LinearSrgb::new(1., 0., 0.);
+        let green = LinearSrgb::new(0., 1., 0.);
+        let blue = LinearSrgb::new(0., 0., 1.);
+        let correction = ColorCorrection::default();
+
+        let chain = SharedChain::<_, 4>::new(ChainDriver::default());
+        let mut first = chain.offset::<0>();
+        let mut second = chain.offset::<2>();
+
+        second
+            .show::<2, 4, _, _>([green; 2], 0.5, correction)
+            .unwrap();
+        assert_eq!(
+            chain.pixels(),
+            [
+                black,
+                black,
+                LinearSrgb::new(0., 0.5, 0.),
+                LinearSrgb::new(0., 0.5, 0.)
+            ]
+        );
+
+        first.show::<2, 4, _, _>([red; 2], 1., correction).unwrap();
+        first.show::<2, 4, _, _>([blue; 3], 1., correction).unwrap();
+
+        let driver = chain.into_inner();
+        assert_eq!(
+            driver.frame.as_slice(),
+            &[
+                blue,
+                blue,
+                LinearSrgb::new(0., 0.5, 0.),
+                LinearSrgb::new(0., 0.5, 0.)
+            ]
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! # Frame Recorder
+//!
+//! [`Recorder`] wraps any driver and serializes each frame, using [`postcard`], to a sink
+//! implementing [`embedded_io::Write`] (flash, SD card, socket, ...).
+//!
+//! The recorded stream can be played back with the [`Replay`](crate::patterns::replay::Replay)
+//! pattern: "bake once, replay forever".
+//!
+//! ## Stream format
+//!
+//! Each frame is a postcard-encoded sequence of colors, in the color type of the inner driver
+//! (e.g. [`LinearSrgb`](crate::color::LinearSrgb)): a varint length followed by each color.
+//! Frames are concatenated with no other framing.
+//!
+//! Colors are recorded before brightness and color correction are applied.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::LinearSrgb,
+//!     driver::{record::record_buffer_size, ClocklessDelayBuilder, ClocklessDriver, Recorder},
+//!     leds::Ws2812,
+//! };
+//! use embedded_hal::{delay::DelayNs, digital::OutputPin};
+//! use embedded_io::Write;
+//!
+//! fn setup_leds<Data, Delay, Sink>(data: Data, delay: Delay, sink: Sink)
+//! where
+//!     Data: OutputPin,
+//!     Delay: DelayNs,
+//!     Sink: Write,
+//! {
+//!     let writer = ClocklessDelayBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_data(data)
+//!         .with_delay(delay)
+//!         .build();
+//!     let driver = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(writer);
+//!
+//!     let recorder =
+//!         Recorder::<_, _, { record_buffer_size::<LinearSrgb>(60) }>::new(driver, sink);
+//! }
+//! ```
+
+use core::mem::size_of;
+
+use embedded_io::Write;
+use heapless::Vec;
+use serde::Serialize;
+
+use super::{ColorCapability, Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor};
+
+/// Returns the size of the buffer needed to record one frame.
+///
+/// This is an upper bound for colors made of fixed-size fields (like `f32`), which postcard
+/// encodes with the same size as in memory.
+///
+/// # Type Parameters
+///
+/// - `Color` - The color type of the inner driver
+///
+/// # Arguments
+///
+/// - `pixel_count` - Number of pixels in each frame
+pub const fn record_buffer_size<Color>(pixel_count: usize) -> usize {
+    // The sequence length is encoded as a varint, at most 5 bytes for a `u32`.
+    5 + pixel_count * size_of::<Color>()
+}
+
+/// All types of errors that can happen while recording frames.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RecorderError<DriverError, SinkError> {
+    /// Raised if the inner driver fails to write the frame
+    Driver(DriverError),
+    /// Raised if the sink fails to write the recorded frame
+    Sink(SinkError),
+    /// Raised if the frame can't be serialized, e.g. the buffer is too small
+    Serialize(postcard::Error),
+}
+
+/// A driver wrapper which records each frame to a sink.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver
+/// - `Sink` - Where each recorded frame is written
+/// - `BUFFER_SIZE` - Size of the buffer for one recorded frame, see [`record_buffer_size`]
+pub struct Recorder<Driver, Sink, const BUFFER_SIZE: usize> {
+    driver: Driver,
+    sink: Sink,
+    buffer: [u8; BUFFER_SIZE],
+    recorded: Result<usize, postcard::Error>,
+}
+
+impl<Driver, Sink, const BUFFER_SIZE: usize> Recorder<Driver, Sink, BUFFER_SIZE>
+where
+    Sink: Write,
+{
+    /// Wraps a driver with a recorder.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The inner driver
+    /// - `sink` - Where each recorded frame is written
+    pub fn new(driver: Driver, sink: Sink) -> Self {
+        Self {
+            driver,
+            sink,
+            buffer: [0; BUFFER_SIZE],
+            recorded: Ok(0),
+        }
+    }
+
+    /// Unwraps the inner driver and sink.
+    pub fn into_inner(self) -> (Driver, Sink) {
+        (self.driver, self.sink)
+    }
+}
+
+impl<D, Sink, const BUFFER_SIZE: usize> Driver for Recorder<D, Sink, BUFFER_SIZE>
+where
+    D: Driver,
+    D::Color: Serialize,
+    Sink: Write,
+{
+    type Error = RecorderError<D::Error, Sink::Error>;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let colors: Vec<Self::Color, PIXEL_COUNT> = pixels
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .map(Self::Color::from_color)
+            .collect();
+
+        self.recorded =
+            postcard::to_slice(colors.as_slice(), &mut self.buffer).map(|bytes| bytes.len());
+
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, D::Color>(colors, brightness, correction)
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        self.driver
+            .write(frame, brightness, correction)
+            .map_err(RecorderError::Driver)?;
+
+        let len = self.recorded.clone().map_err(RecorderError::Serialize)?;
+        self.sink
+            .write_all(&self.buffer[..len])
+            .map_err(RecorderError::Sink)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
@@ -0,0 +1,513 @@
+//! # Output Mapping
+//!
+//! [`SplitDriver`] routes ranges of one layout to different outputs: the pixels before a split
+//! point go to the first driver, the rest go to the second driver. Each output can have its own
+//! chipset, so one controller can drive mixed hardware, e.g. a WS2812 section and an APA102
+//! section. For more than two outputs, split again: `SplitDriver::new(a, SplitDriver::new(b, c,
+//! ..), ..)`.
+//!
+//! The split point is set at runtime, so it can come from a
+//! [`DeviceConfig`](crate::config::DeviceConfig). To fix a section wired with a different
+//! channel order than its chipset, wrap its driver in a [`ChannelReorder`].
+//!
+//! ## Frame buffer size
+//!
+//! Both frames are encoded into one frame buffer, so its size must be the sum of the frame
+//! buffer sizes of both drivers.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use embedded_hal::{delay::DelayNs, digital::OutputPin};
+//! use blinksy::{
+//!     color::RgbChannels,
+//!     driver::{
+//!         ChannelReorder, ClockedDelayBuilder, ClockedDriver, ClocklessDelayBuilder,
+//!         ClocklessDriver, Driver, SplitDriver,
+//!     },
+//!     leds::{Apa102, Ws2812},
+//!     time::Megahertz,
+//! };
+//!
+//! fn setup_leds<Data, ClockedData, Clock, Delay>(
+//!     data: Data,
+//!     clocked_data: ClockedData,
+//!     clock: Clock,
+//!     delay: Delay,
+//! ) -> impl Driver
+//! where
+//!     Data: OutputPin,
+//!     ClockedData: OutputPin,
+//!     Clock: OutputPin,
+//!     Delay: DelayNs + Clone,
+//! {
+//!     let writer = ClocklessDelayBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_data(data)
+//!         .with_delay(delay.clone())
+//!         .build();
+//!     let ws2812 = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(writer);
+//!
+//!     let apa102 = ClockedDriver::default().with_led::<Apa102>().with_writer(
+//!         ClockedDelayBuilder::default()
+//!             .with_data(clocked_data)
+//!             .with_clock(clock)
+//!             .with_delay(delay)
+//!             .with_data_rate(Megahertz::MHz(2))
+//!             .build(),
+//!     );
+//!
+//!     // The first 60 pixels on the WS2812 strip, the rest on the APA102 strip, which is
+//!     // wired with red and green swapped
+//!     SplitDriver::new(ws2812, ChannelReorder::new(apa102, RgbChannels::GRB), 60)
+//! }
+//! ```
+
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use super::{ColorCapability, Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor, LinearSrgb, RgbChannels};
+
+/// All types of errors that can happen while writing to split outputs.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SplitError<FirstError, SecondError> {
+    /// Raised if the first output fails to write its frame
+    First(FirstError),
+    /// Raised if the second output fails to write its frame
+    Second(SecondError),
+}
+
+/// A word of a [`SplitDriver`] frame buffer: a word of either output's frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SplitWord<FirstWord, SecondWord> {
+    /// A word of the first output's frame
+    First(FirstWord),
+    /// A word of the second output's frame
+    Second(SecondWord),
+}
+
+/// A driver which sends the pixels before a split point to one driver, and the rest to
+/// another driver.
+///
+/// Pixels are converted to the first output's color type, then for the second output, from that
+/// to its color type.
+///
+/// Both outputs are always written, even if the first fails.
+///
+/// # Type Parameters
+///
+/// - `First` - The driver for the pixels before the split point
+/// - `Second` - The driver for the pixels from the split point
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SplitDriver<First, Second> {
+    first: First,
+    second: Second,
+    split_at: usize,
+}
+
+impl<First, Second> SplitDriver<First, Second> {
+    /// Combines two drivers.
+    ///
+    /// # Arguments
+    ///
+    /// - `first` - The driver for the pixels before the split point
+    /// - `second` - The driver for the pixels from the split point
+    /// - `split_at` - The index of the first pixel sent to the second driver
+    pub fn new(first: First, second: Second, split_at: usize) -> Self {
+        Self {
+            first,
+            second,
+            split_at,
+        }
+    }
+
+    /// Sets the index of the first pixel sent to the second driver.
+    pub fn set_split_at(&mut self, split_at: usize) {
+        self.split_at = split_at;
+    }
+
+    /// Returns the index of the first pixel sent to the second driver.
+    pub fn split_at(&self) -> usize {
+        self.split_at
+    }
+
+    /// Unwraps the inner drivers.
+    pub fn into_inner(self) -> (First, Second) {
+        (self.first, self.second)
+    }
+
+    /// Splits a frame buffer into the frames of each output.
+    fn split<FirstWord, SecondWord, const FRAME_BUFFER_SIZE: usize>(
+        frame: Vec<SplitWord<FirstWord, SecondWord>, FRAME_BUFFER_SIZE>,
+    ) -> (
+        Vec<FirstWord, FRAME_BUFFER_SIZE>,
+        Vec<SecondWord, FRAME_BUFFER_SIZE>,
+    ) {
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+        for word in frame {
+            // Neither can overflow, as each is at most the size of the whole frame
+            match word {
+                SplitWord::First(word) => {
+                    let _ = first.push(word);
+                }
+                SplitWord::Second(word) => {
+                    let _ = second.push(word);
+                }
+            }
+        }
+        (first, second)
+    }
+
+    /// Combines the results of writing to each output.
+    fn combine<FirstError, SecondError>(
+        first: Result<(), FirstError>,
+        second: Result<(), SecondError>,
+    ) -> Result<(), SplitError<FirstError, SecondError>> {
+        first.map_err(SplitError::First)?;
+        second.map_err(SplitError::Second)
+    }
+}
+
+impl<A, B> Driver for SplitDriver<A, B>
+where
+    A: Driver,
+    B: Driver,
+    B::Color: FromColor<A::Color>,
+{
+    type Error = SplitError<A::Error, B::Error>;
+    type Color = A::Color;
+    type Word = SplitWord<A::Word, B::Word>;
+
+    const COLOR_CAPABILITY: ColorCapability = A::COLOR_CAPABILITY.common(B::COLOR_CAPABILITY);
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let mut pixels = pixels
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .map(Self::Color::from_color);
+
+        let first = self
+            .first
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                pixels.by_ref().take(self.split_at),
+                brightness,
+                correction,
+            );
+        let second = self
+            .second
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                pixels, brightness, correction,
+            );
+
+        first
+            .into_iter()
+            .map(SplitWord::First)
+            .chain(second.into_iter().map(SplitWord::Second))
+            .take(FRAME_BUFFER_SIZE)
+            .collect()
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        let (first_frame, second_frame) = Self::split(frame);
+        let first = self.first.write(first_frame, brightness, correction);
+        let second = self.second.write(second_frame, brightness, correction);
+        Self::combine(first, second)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.first.set_global_brightness(global_brightness);
+        self.second.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<A, B> DriverAsync for SplitDriver<A, B>
+where
+    A: DriverAsync,
+    B: DriverAsync,
+    B::Color: FromColor<A::Color>,
+{
+    type Error = SplitError<A::Error, B::Error>;
+    type Color = A::Color;
+    type Word = SplitWord<A::Word, B::Word>;
+
+    const COLOR_CAPABILITY: ColorCapability = A::COLOR_CAPABILITY.common(B::COLOR_CAPABILITY);
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let mut pixels = pixels
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .map(Self::Color::from_color);
+
+        let first = self
+            .first
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                pixels.by_ref().take(self.split_at),
+                brightness,
+                correction,
+            );
+        let second = self
+            .second
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                pixels, brightness, correction,
+            );
+
+        first
+            .into_iter()
+            .map(SplitWord::First)
+            .chain(second.into_iter().map(SplitWord::Second))
+            .take(FRAME_BUFFER_SIZE)
+            .collect()
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        let (first_frame, second_frame) = Self::split(frame);
+        let first = self.first.write(first_frame).await;
+        let second = self.second.write(second_frame).await;
+        Self::combine(first, second)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.first.set_global_brightness(global_brightness);
+        self.second.set_global_brightness(global_brightness);
+    }
+}
+
+/// A driver wrapper which reorders the color channels of each pixel, for LEDs wired with a
+/// different channel order than their chipset.
+///
+/// The channels are reordered before the inner driver's own channel order: with
+/// [`RgbChannels::GRB`], red and green are swapped. The color correction is reordered the same
+/// way, so each factor still applies to its own color.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelReorder<Driver> {
+    driver: Driver,
+    channels: RgbChannels,
+}
+
+impl<Driver> ChannelReorder<Driver> {
+    /// Wraps a driver with a channel reorder.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The inner driver
+    /// - `channels` - The new order of the red, green, and blue channels
+    pub fn new(driver: Driver, channels: RgbChannels) -> Self {
+        Self { driver, channels }
+    }
+
+    /// Sets the new order of the red, green, and blue channels.
+    pub fn set_channels(&mut self, channels: RgbChannels) {
+        self.channels = channels;
+    }
+
+    /// Unwraps the inner driver.
+    pub fn into_inner(self) -> Driver {
+        self.driver
+    }
+
+    fn reorder(channels: RgbChannels, color: LinearSrgb) -> LinearSrgb {
+        let [red, green, blue] = channels.reorder([color.red, color.green, color.blue]);
+        LinearSrgb { red, green, blue }
+    }
+
+    fn reorder_correction(channels: RgbChannels, correction: ColorCorrection) -> ColorCorrection {
+        let [red, green, blue] =
+            channels.reorder([correction.red, correction.green, correction.blue]);
+        ColorCorrection { red, green, blue }
+    }
+}
+
+impl<D> Driver for ChannelReorder<D>
+where
+    D: Driver<Color = LinearSrgb>,
+{
+    type Error = D::Error;
+    type Color = LinearSrgb;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let channels = self.channels;
+        let pixels = pixels
+            .into_iter()
+            .map(move |color| Self::reorder(channels, LinearSrgb::from_color(color)));
+        let correction = Self::reorder_correction(channels, correction);
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, LinearSrgb>(pixels, brightness, correction)
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        let correction = Self::reorder_correction(self.channels, correction);
+        self.driver.write(frame, brightness, correction)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D> DriverAsync for ChannelReorder<D>
+where
+    D: DriverAsync<Color = LinearSrgb>,
+{
+    type Error = D::Error;
+    type Color = LinearSrgb;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let channels = self.channels;
+        let pixels = pixels
+            .into_iter()
+            .map(move |color| Self::reorder(channels, LinearSrgb::from_color(color)));
+        let correction = Self::reorder_correction(channels, correction);
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, LinearSrgb>(pixels, brightness, correction)
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.driver.write(frame).await
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::apply_output_pipeline;
+
+    /// Encodes each pixel as its corrected channels, in RGB order.
+    struct CorrectingDriver;
+
+    impl Driver for CorrectingDriver {
+        type Error = ();
+        type Color = LinearSrgb;
+        type Word = f32;
+
+        fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+            &mut self,
+            pixels: Pixels,
+            brightness: f32,
+            correction: ColorCorrection,
+        ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+        where
+            Pixels: IntoIterator<Item = Color>,
+            Self::Color: FromColor<Color>,
+        {
+            pixels
+                .into_iter()
+                .flat_map(|color| {
+                    let color = LinearSrgb::from_color(color);
+                    let LinearSrgb { red, green, blue } =
+                        apply_output_pipeline(color, brightness, correction);
+                    [red, green, blue]
+                })
+                .take(FRAME_BUFFER_SIZE)
+                .collect()
+        }
+
+        fn write<const FRAME_BUFFER_SIZE: usize>(
+            &mut self,
+            _frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_channel_reorder_reorders_correction() {
+        let mut driver = ChannelReorder::new(CorrectingDriver, RgbChannels::GRB);
+        let correction = ColorCorrection {
+            red: 0.5,
+            green: 1.,
+            blue: 0.25,
+        };
+
+        let frame = driver.encode::<3, 9, _, _>(
+            [
+                LinearSrgb::new(1., 0., 0.),
+                LinearSrgb::new(0., 1., 0.),
+                LinearSrgb::new(0., 0., 1.),
+            ],
+            1.,
+            correction,
+        );
+
+        // Red is wired to the green output, and still corrected as red
+        assert_eq!(frame.as_slice(), &[0., 0.5, 0., 1., 0., 0., 0., 0., 0.25]);
+    }
+}
@@ -0,0 +1,375 @@
+//! # Status Pixels
+//!
+//! [`StatusPixels`] reserves the first pixels of a chain as status indicators of the device,
+//! and writes each frame of the pattern after them. So the pattern's layout only describes the
+//! remaining pixels, and the pattern never needs to know about the status pixels.
+//!
+//! The [`DeviceStatus`] shown is:
+//!
+//! - The [`NetworkStatus`], e.g. of the WiFi, set by the firmware
+//! - Whether a realtime stream is active, set by the firmware (e.g. from a
+//!   [`SourceArbiter`](crate::arbiter::SourceArbiter))
+//! - Whether writing a frame failed, set by the adapter itself, until
+//!   [cleared](StatusPixels::clear_error)
+//!
+//! With one status pixel, it shows the most important status: an error, then a stream, then
+//! the network. With more, each shows one: see [`DeviceStatus::color`].
+//!
+//! The number of status pixels is set at runtime, e.g. from the
+//! [`status_pixel_count`](crate::config::DeviceConfig::status_pixel_count) of a config.
+//!
+//! ## Brightness
+//!
+//! The brightness of the [`Control`](crate::control::Control) only applies to the pattern's
+//! pixels: the status pixels always show at their own level. So the status stays readable
+//! with the pattern dimmed, and while the LEDs are [off](crate::control::Control::turn_off),
+//! as the all-off frame still shows the status pixels. To show a status changed while the
+//! LEDs are off, turn them off again.
+//!
+//! ## Frame buffer size
+//!
+//! The status pixels are encoded with the frame, so the frame buffer size must be for
+//! `CHAIN_PIXEL_COUNT` pixels.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use embedded_hal::{delay::DelayNs, digital::OutputPin};
+//! use blinksy::{
+//!     color::LinearSrgb,
+//!     config::DeviceConfig,
+//!     driver::{ClocklessDelayBuilder, ClocklessDriver, Driver, StatusPixels},
+//!     leds::Ws2812,
+//! };
+//!
+//! // The pattern's layout has 60 pixels, after up to 4 status pixels
+//! const CHAIN_PIXEL_COUNT: usize = 64;
+//!
+//! fn setup_leds<Data, Delay>(
+//!     data: Data,
+//!     delay: Delay,
+//!     config: &DeviceConfig,
+//! ) -> StatusPixels<impl Driver<Color = LinearSrgb>, CHAIN_PIXEL_COUNT>
+//! where
+//!     Data: OutputPin,
+//!     Delay: DelayNs,
+//! {
+//!     let writer = ClocklessDelayBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_data(data)
+//!         .with_delay(delay)
+//!         .build();
+//!     let driver = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(writer);
+//!
+//!     StatusPixels::new(driver, config.status_pixel_count.into())
+//! }
+//!
+//! // Then, e.g. as the WiFi connects:
+//! // control.driver_mut().status_mut().network = NetworkStatus::Connected;
+//! ```
+
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use super::{ColorCapability, Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor, LinearSrgb};
+
+const OFF: LinearSrgb = LinearSrgb::new(0., 0., 0.);
+const RED: LinearSrgb = LinearSrgb::new(0.5, 0., 0.);
+const AMBER: LinearSrgb = LinearSrgb::new(0.5, 0.2, 0.);
+const GREEN: LinearSrgb = LinearSrgb::new(0., 0.5, 0.);
+const BLUE: LinearSrgb = LinearSrgb::new(0., 0., 0.5);
+
+/// The state of the device's network connection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NetworkStatus {
+    /// No network, shown dark
+    #[default]
+    Disabled,
+    /// Connecting, shown amber
+    Connecting,
+    /// Connected, shown green
+    Connected,
+    /// Disconnected after failing to connect, shown red
+    Disconnected,
+}
+
+/// The status of a device, as shown by [`StatusPixels`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceStatus {
+    /// The state of the network connection
+    pub network: NetworkStatus,
+    /// Whether a realtime stream is active, shown blue
+    pub is_streaming: bool,
+    /// Whether an error happened, shown red
+    pub has_error: bool,
+}
+
+impl DeviceStatus {
+    /// Returns the color of a status pixel.
+    ///
+    /// With one status pixel, it shows an error, else a stream, else the network. With more,
+    /// the first shows the network, the second the stream, the third any error, and any others
+    /// are dark.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the status pixel
+    /// - `count` - The number of status pixels
+    pub fn color(&self, index: usize, count: usize) -> LinearSrgb {
+        let network = match self.network {
+            NetworkStatus::Disabled => OFF,
+            NetworkStatus::Connecting => AMBER,
+            NetworkStatus::Connected => GREEN,
+            NetworkStatus::Disconnected => RED,
+        };
+        let stream = if self.is_streaming { BLUE } else { OFF };
+        let error = if self.has_error { RED } else { OFF };
+        match (count, index) {
+            (1, _) if self.has_error => error,
+            (1, _) if self.is_streaming => stream,
+            (1, _) => network,
+            (_, 0) => network,
+            (_, 1) => stream,
+            (_, 2) => error,
+            _ => OFF,
+        }
+    }
+}
+
+/// A driver wrapper which reserves the first pixels of a chain of up to `CHAIN_PIXEL_COUNT`
+/// LEDs as status pixels, and writes each frame after them.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver, for the whole chain
+/// - `CHAIN_PIXEL_COUNT` - The most pixels in the whole chain: the pattern's pixels, plus the
+///   status pixels
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StatusPixels<Driver, const CHAIN_PIXEL_COUNT: usize> {
+    driver: Driver,
+    count: usize,
+    status: DeviceStatus,
+}
+
+impl<Driver, const CHAIN_PIXEL_COUNT: usize> StatusPixels<Driver, CHAIN_PIXEL_COUNT> {
+    /// Wraps a driver, reserving status pixels.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The inner driver, for the whole chain
+    /// - `count` - The number of status pixels, or 0 for none
+    pub fn new(driver: Driver, count: usize) -> Self {
+        Self {
+            driver,
+            count,
+            status: DeviceStatus::default(),
+        }
+    }
+
+    /// Returns the number of status pixels.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Sets the number of status pixels, e.g. after the config changed.
+    ///
+    /// # Arguments
+    ///
+    /// - `count` - The number of status pixels, or 0 for none
+    pub fn set_count(&mut self, count: usize) {
+        self.count = count;
+    }
+
+    /// Returns the status shown.
+    pub fn status(&self) -> &DeviceStatus {
+        &self.status
+    }
+
+    /// Returns the status shown, to change it.
+    pub fn status_mut(&mut self) -> &mut DeviceStatus {
+        &mut self.status
+    }
+
+    /// Clears the error shown after a failed write.
+    pub fn clear_error(&mut self) {
+        self.status.has_error = false;
+    }
+
+    /// Unwraps the inner driver.
+    pub fn into_inner(self) -> Driver {
+        self.driver
+    }
+
+    /// Returns the status pixels, then the pixels of the frame at the brightness.
+    fn chain<Pixels, Color>(
+        &self,
+        pixels: Pixels,
+        brightness: f32,
+    ) -> impl Iterator<Item = LinearSrgb>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        LinearSrgb: FromColor<Color>,
+    {
+        let status = self.status;
+        let count = self.count;
+        let frame = pixels.into_iter().map(move |pixel| {
+            let LinearSrgb { red, green, blue } = LinearSrgb::from_color(pixel);
+            LinearSrgb::new(red * brightness, green * brightness, blue * brightness)
+        });
+        (0..count)
+            .map(move |index| status.color(index, count))
+            .chain(frame)
+            .take(CHAIN_PIXEL_COUNT)
+    }
+
+    /// Marks an error, if writing a frame failed.
+    fn record<Error>(&mut self, result: Result<(), Error>) -> Result<(), Error> {
+        if result.is_err() {
+            self.status.has_error = true;
+        }
+        result
+    }
+}
+
+impl<D, const CHAIN_PIXEL_COUNT: usize> Driver for StatusPixels<D, CHAIN_PIXEL_COUNT>
+where
+    D: Driver,
+    D::Color: FromColor<LinearSrgb>,
+{
+    type Error = D::Error;
+    type Color = LinearSrgb;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let pixels = self.chain(pixels, brightness);
+        // The brightness of the frame is already applied, and none to the status pixels
+        self.driver
+            .encode::<CHAIN_PIXEL_COUNT, FRAME_BUFFER_SIZE, _, LinearSrgb>(pixels, 1., correction)
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        _brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        let result = self.driver.write(frame, 1., correction);
+        self.record(result)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D, const CHAIN_PIXEL_COUNT: usize> DriverAsync for StatusPixels<D, CHAIN_PIXEL_COUNT>
+where
+    D: DriverAsync,
+    D::Color: FromColor<LinearSrgb>,
+{
+    type Error = D::Error;
+    type Color = LinearSrgb;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let pixels = self.chain(pixels, brightness);
+        // The brightness of the frame is already applied, and none to the status pixels
+        self.driver
+            .encode::<CHAIN_PIXEL_COUNT, FRAME_BUFFER_SIZE, _, LinearSrgb>(pixels, 1., correction)
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        let result = self.driver.write(frame).await;
+        self.record(result)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::driver::NullDriver;
+
+    #[test]
+    fn test_status_pixels_before_frame() {
+        let mut driver = StatusPixels::<NullDriver, 4>::new(NullDriver, 2);
+        driver.status_mut().network = NetworkStatus::Connected;
+
+        let white = LinearSrgb::new(1., 1., 1.);
+        let mut chain = driver.chain([white; 3], 1.);
+        assert_eq!(chain.next(), Some(GREEN));
+        assert_eq!(chain.next(), Some(OFF));
+        assert_eq!(chain.next(), Some(white));
+        assert_eq!(chain.next(), Some(white));
+        assert_eq!(chain.next(), None);
+    }
+
+    #[test]
+    fn test_status_pixels_ignore_brightness() {
+        let mut driver = StatusPixels::<NullDriver, 2>::new(NullDriver, 1);
+        driver.status_mut().network = NetworkStatus::Connected;
+
+        let white = LinearSrgb::new(1., 1., 1.);
+        let mut chain = driver.chain([white], 0.5);
+        assert_eq!(chain.next(), Some(GREEN));
+        assert_eq!(chain.next(), Some(LinearSrgb::new(0.5, 0.5, 0.5)));
+
+        // The all-off frame of LEDs turned off
+        let mut chain = driver.chain([white], 0.);
+        assert_eq!(chain.next(), Some(GREEN));
+        assert_eq!(chain.next(), Some(OFF));
+    }
+
+    #[test]
+    fn test_one_status_pixel_shows_most_important() {
+        let mut status = DeviceStatus {
+            network: NetworkStatus::Connecting,
+            ..Default::default()
+        };
+        assert_eq!(status.color(0, 1), AMBER);
+        status.is_streaming = true;
+        assert_eq!(status.color(0, 1), BLUE);
+        status.has_error = true;
+        assert_eq!(status.color(0, 1), RED);
+        assert_eq!(status.color(0, 3), AMBER);
+        assert_eq!(status.color(3, 4), OFF);
+    }
+}
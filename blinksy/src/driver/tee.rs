@@ -0,0 +1,333 @@
+//! # Dual Output
+//!
+//! [`TeeDriver`] writes every frame to two drivers: for example, physical LEDs plus a network
+//! mirror, so an installation can be monitored live from a dashboard.
+//!
+//! The first driver is the primary output, the second is the secondary output (the mirror). By
+//! default, an error on either output is returned, but with [`TeeErrorPolicy::PrimaryOnly`],
+//! errors from the secondary output are ignored, so a flaky mirror never interrupts the LEDs.
+//!
+//! ## Frame buffer size
+//!
+//! Both frames are encoded into one frame buffer, so its size must be the sum of the frame
+//! buffer sizes of both drivers.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use embedded_hal::{delay::DelayNs, digital::OutputPin};
+//! use blinksy::{
+//!     driver::{ClocklessDelayBuilder, ClocklessDriver, Driver, TeeDriver, TeeErrorPolicy},
+//!     leds::Ws2812,
+//! };
+//!
+//! fn setup_leds<Data, MirrorData, Delay>(
+//!     data: Data,
+//!     mirror_data: MirrorData,
+//!     delay: Delay,
+//! ) -> impl Driver
+//! where
+//!     Data: OutputPin,
+//!     MirrorData: OutputPin,
+//!     Delay: DelayNs + Clone,
+//! {
+//!     let writer = ClocklessDelayBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_data(data)
+//!         .with_delay(delay.clone())
+//!         .build();
+//!     let driver = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(writer);
+//!
+//!     let mirror_writer = ClocklessDelayBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_data(mirror_data)
+//!         .with_delay(delay)
+//!         .build();
+//!     let mirror = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(mirror_writer);
+//!
+//!     TeeDriver::new(driver, mirror).with_error_policy(TeeErrorPolicy::PrimaryOnly)
+//! }
+//! ```
+//!
+//! Then build your control with a frame buffer for both:
+//! `.with_frame_buffer_size::<{ 2 * Ws2812::frame_buffer_size(PIXEL_COUNT) }>()`.
+
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use super::{ColorCapability, Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor};
+
+/// How a [`TeeDriver`] handles errors from its outputs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TeeErrorPolicy {
+    /// Return an error from either output
+    ///
+    /// Both outputs are always written, even if the primary output fails.
+    #[default]
+    All,
+    /// Return only errors from the primary output, ignoring errors from the secondary output
+    ///
+    /// Ignored errors are counted, see [`TeeDriver::ignored_error_count`].
+    PrimaryOnly,
+}
+
+/// All types of errors that can happen while writing to both outputs.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TeeError<PrimaryError, SecondaryError> {
+    /// Raised if the primary output fails to write the frame
+    Primary(PrimaryError),
+    /// Raised if the secondary output fails to write the frame
+    Secondary(SecondaryError),
+}
+
+/// A word of a [`TeeDriver`] frame buffer: a word of either output's frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TeeWord<PrimaryWord, SecondaryWord> {
+    /// A word of the primary output's frame
+    Primary(PrimaryWord),
+    /// A word of the secondary output's frame
+    Secondary(SecondaryWord),
+}
+
+/// A driver which writes every frame to two drivers.
+///
+/// Pixels are converted to the primary output's color type, then from that to the secondary
+/// output's color type.
+///
+/// # Type Parameters
+///
+/// - `Primary` - The primary output driver
+/// - `Secondary` - The secondary output driver
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TeeDriver<Primary, Secondary> {
+    primary: Primary,
+    secondary: Secondary,
+    error_policy: TeeErrorPolicy,
+    ignored_error_count: u32,
+}
+
+impl<Primary, Secondary> TeeDriver<Primary, Secondary> {
+    /// Combines two drivers, with the [default](TeeErrorPolicy::All) error policy.
+    ///
+    /// # Arguments
+    ///
+    /// - `primary` - The primary output driver
+    /// - `secondary` - The secondary output driver
+    pub fn new(primary: Primary, secondary: Secondary) -> Self {
+        Self {
+            primary,
+            secondary,
+            error_policy: TeeErrorPolicy::default(),
+            ignored_error_count: 0,
+        }
+    }
+
+    /// Sets how errors from the outputs are handled.
+    pub fn with_error_policy(mut self, error_policy: TeeErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// Sets how errors from the outputs are handled.
+    pub fn set_error_policy(&mut self, error_policy: TeeErrorPolicy) {
+        self.error_policy = error_policy;
+    }
+
+    /// Returns the number of errors from the secondary output ignored due to
+    /// [`TeeErrorPolicy::PrimaryOnly`].
+    pub fn ignored_error_count(&self) -> u32 {
+        self.ignored_error_count
+    }
+
+    /// Unwraps the inner drivers.
+    pub fn into_inner(self) -> (Primary, Secondary) {
+        (self.primary, self.secondary)
+    }
+
+    /// Splits a frame buffer into the frames of each output.
+    fn split<PrimaryWord, SecondaryWord, const FRAME_BUFFER_SIZE: usize>(
+        frame: Vec<TeeWord<PrimaryWord, SecondaryWord>, FRAME_BUFFER_SIZE>,
+    ) -> (
+        Vec<PrimaryWord, FRAME_BUFFER_SIZE>,
+        Vec<SecondaryWord, FRAME_BUFFER_SIZE>,
+    ) {
+        let mut primary = Vec::new();
+        let mut secondary = Vec::new();
+        for word in frame {
+            // Neither can overflow, as each is at most the size of the whole frame
+            match word {
+                TeeWord::Primary(word) => {
+                    let _ = primary.push(word);
+                }
+                TeeWord::Secondary(word) => {
+                    let _ = secondary.push(word);
+                }
+            }
+        }
+        (primary, secondary)
+    }
+
+    /// Combines the results of writing to each output, following the error policy.
+    fn combine<PrimaryError, SecondaryError>(
+        &mut self,
+        primary: Result<(), PrimaryError>,
+        secondary: Result<(), SecondaryError>,
+    ) -> Result<(), TeeError<PrimaryError, SecondaryError>> {
+        primary.map_err(TeeError::Primary)?;
+        match (secondary, self.error_policy) {
+            (Ok(()), _) => Ok(()),
+            (Err(error), TeeErrorPolicy::All) => Err(TeeError::Secondary(error)),
+            (Err(_), TeeErrorPolicy::PrimaryOnly) => {
+                self.ignored_error_count = self.ignored_error_count.saturating_add(1);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<A, B> Driver for TeeDriver<A, B>
+where
+    A: Driver,
+    A::Color: Clone,
+    B: Driver,
+    B::Color: FromColor<A::Color>,
+{
+    type Error = TeeError<A::Error, B::Error>;
+    type Color = A::Color;
+    type Word = TeeWord<A::Word, B::Word>;
+
+    const COLOR_CAPABILITY: ColorCapability = A::COLOR_CAPABILITY.common(B::COLOR_CAPABILITY);
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let colors: Vec<Self::Color, PIXEL_COUNT> = pixels
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .map(Self::Color::from_color)
+            .collect();
+
+        let primary = self
+            .primary
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                colors.iter().cloned(),
+                brightness,
+                correction,
+            );
+        let secondary = self
+            .secondary
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                colors, brightness, correction,
+            );
+
+        primary
+            .into_iter()
+            .map(TeeWord::Primary)
+            .chain(secondary.into_iter().map(TeeWord::Secondary))
+            .take(FRAME_BUFFER_SIZE)
+            .collect()
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        let (primary_frame, secondary_frame) = Self::split(frame);
+        let primary = self.primary.write(primary_frame, brightness, correction);
+        let secondary = self
+            .secondary
+            .write(secondary_frame, brightness, correction);
+        self.combine(primary, secondary)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.primary.set_global_brightness(global_brightness);
+        self.secondary.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<A, B> DriverAsync for TeeDriver<A, B>
+where
+    A: DriverAsync,
+    A::Color: Clone,
+    B: DriverAsync,
+    B::Color: FromColor<A::Color>,
+{
+    type Error = TeeError<A::Error, B::Error>;
+    type Color = A::Color;
+    type Word = TeeWord<A::Word, B::Word>;
+
+    const COLOR_CAPABILITY: ColorCapability = A::COLOR_CAPABILITY.common(B::COLOR_CAPABILITY);
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let colors: Vec<Self::Color, PIXEL_COUNT> = pixels
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .map(Self::Color::from_color)
+            .collect();
+
+        let primary = self
+            .primary
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                colors.iter().cloned(),
+                brightness,
+                correction,
+            );
+        let secondary = self
+            .secondary
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, Self::Color>(
+                colors, brightness, correction,
+            );
+
+        primary
+            .into_iter()
+            .map(TeeWord::Primary)
+            .chain(secondary.into_iter().map(TeeWord::Secondary))
+            .take(FRAME_BUFFER_SIZE)
+            .collect()
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        let (primary_frame, secondary_frame) = Self::split(frame);
+        let primary = self.primary.write(primary_frame).await;
+        let secondary = self.secondary.write(secondary_frame).await;
+        self.combine(primary, secondary)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.primary.set_global_brightness(global_brightness);
+        self.secondary.set_global_brightness(global_brightness);
+    }
+}
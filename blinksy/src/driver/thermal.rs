@@ -0,0 +1,253 @@
+//! # Thermal Throttling
+//!
+//! [`ThermalThrottle`] wraps any driver and scales the global brightness down when a
+//! temperature sensor reads above a threshold, protecting controllers and LEDs in enclosed
+//! installations from overheating.
+//!
+//! The sensor is anything implementing [`TemperatureSensor`], including any closure returning
+//! a temperature in degrees Celsius. (On ESP32 chips with an internal temperature sensor,
+//! `blinksy-esp` provides one.)
+//!
+//! ## Example
+//!
+//! ```rust
+//! use embedded_hal::{delay::DelayNs, digital::OutputPin};
+//! use blinksy::{
+//!     driver::{ClocklessDelayBuilder, ClocklessDriver, Driver, ThermalLimits, ThermalThrottle},
+//!     leds::Ws2812,
+//! };
+//!
+//! fn setup_leds<Data, Delay>(data: Data, delay: Delay) -> impl Driver
+//! where
+//!     Data: OutputPin,
+//!     Delay: DelayNs,
+//! {
+//!     let writer = ClocklessDelayBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_data(data)
+//!         .with_delay(delay)
+//!         .build();
+//!     let driver = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(writer);
+//!
+//!     let read_temperature = || {
+//!         // Read your temperature sensor, in degrees Celsius.
+//!         25.0
+//!     };
+//!
+//!     ThermalThrottle::new(driver, read_temperature, ThermalLimits::default())
+//! }
+//! ```
+
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use super::DriverAsync;
+use super::{ColorCapability, Driver, GlobalBrightness};
+use crate::color::{ColorCorrection, FromColor};
+
+/// A temperature sensor, read before each frame.
+pub trait TemperatureSensor {
+    /// Returns the current temperature, in degrees Celsius.
+    fn temperature_in_celsius(&mut self) -> f32;
+}
+
+impl<F> TemperatureSensor for F
+where
+    F: FnMut() -> f32,
+{
+    fn temperature_in_celsius(&mut self) -> f32 {
+        self()
+    }
+}
+
+/// The temperatures at which brightness is throttled.
+///
+/// Below `throttle_in_celsius`, brightness is unchanged. From there up to `max_in_celsius`,
+/// brightness is scaled down linearly, until it reaches `min_brightness_scale`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThermalLimits {
+    /// Temperature where throttling begins, in degrees Celsius (default: 70)
+    pub throttle_in_celsius: f32,
+    /// Temperature where throttling reaches its minimum, in degrees Celsius (default: 85)
+    pub max_in_celsius: f32,
+    /// Brightness scale at or above the max temperature, from 0.0 to 1.0 (default: 0.1)
+    pub min_brightness_scale: f32,
+}
+
+impl Default for ThermalLimits {
+    fn default() -> Self {
+        Self {
+            throttle_in_celsius: 70.,
+            max_in_celsius: 85.,
+            min_brightness_scale: 0.1,
+        }
+    }
+}
+
+impl ThermalLimits {
+    /// Returns the brightness scale for a temperature.
+    ///
+    /// # Arguments
+    ///
+    /// - `temperature_in_celsius` - The current temperature
+    ///
+    /// # Returns
+    ///
+    /// A factor from `min_brightness_scale` to 1.0, to multiply brightness by
+    pub fn brightness_scale(&self, temperature_in_celsius: f32) -> f32 {
+        if temperature_in_celsius.is_nan() || temperature_in_celsius >= self.max_in_celsius {
+            return self.min_brightness_scale;
+        }
+        if temperature_in_celsius <= self.throttle_in_celsius {
+            return 1.;
+        }
+        let progress = (temperature_in_celsius - self.throttle_in_celsius)
+            / (self.max_in_celsius - self.throttle_in_celsius);
+        1. - progress * (1. - self.min_brightness_scale)
+    }
+}
+
+/// A driver wrapper which scales brightness down when the temperature is too high.
+///
+/// The sensor is read once per frame, before encoding.
+///
+/// # Type Parameters
+///
+/// - `Driver` - The inner driver
+/// - `Sensor` - The temperature sensor
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ThermalThrottle<Driver, Sensor> {
+    driver: Driver,
+    sensor: Sensor,
+    limits: ThermalLimits,
+    brightness_scale: f32,
+}
+
+impl<Driver, Sensor> ThermalThrottle<Driver, Sensor>
+where
+    Sensor: TemperatureSensor,
+{
+    /// Wraps a driver with thermal throttling.
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The inner driver
+    /// - `sensor` - The temperature sensor
+    /// - `limits` - The temperatures at which brightness is throttled
+    pub fn new(driver: Driver, sensor: Sensor, limits: ThermalLimits) -> Self {
+        Self {
+            driver,
+            sensor,
+            limits,
+            brightness_scale: 1.,
+        }
+    }
+
+    /// Returns the brightness scale applied to the last frame.
+    ///
+    /// A value below 1.0 means brightness is being throttled.
+    pub fn brightness_scale(&self) -> f32 {
+        self.brightness_scale
+    }
+
+    /// Sets the temperatures at which brightness is throttled.
+    pub fn set_limits(&mut self, limits: ThermalLimits) {
+        self.limits = limits;
+    }
+
+    /// Unwraps the inner driver and sensor.
+    pub fn into_inner(self) -> (Driver, Sensor) {
+        (self.driver, self.sensor)
+    }
+
+    fn update_brightness_scale(&mut self) -> f32 {
+        let temperature = self.sensor.temperature_in_celsius();
+        self.brightness_scale = self.limits.brightness_scale(temperature);
+        self.brightness_scale
+    }
+}
+
+impl<D, Sensor> Driver for ThermalThrottle<D, Sensor>
+where
+    D: Driver,
+    Sensor: TemperatureSensor,
+{
+    type Error = D::Error;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let brightness = brightness * self.update_brightness_scale();
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction)
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        self.driver
+            .write(frame, brightness * self.brightness_scale, correction)
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D, Sensor> DriverAsync for ThermalThrottle<D, Sensor>
+where
+    D: DriverAsync,
+    Sensor: TemperatureSensor,
+{
+    type Error = D::Error;
+    type Color = D::Color;
+    type Word = D::Word;
+
+    const COLOR_CAPABILITY: ColorCapability = D::COLOR_CAPABILITY;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let brightness = brightness * self.update_brightness_scale();
+        self.driver
+            .encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction)
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.driver.write(frame).await
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.driver.set_global_brightness(global_brightness);
+    }
+}
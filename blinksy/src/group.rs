@@ -0,0 +1,329 @@
+//! # Control Group
+//!
+//! A device may drive several outputs with different layouts, such as a matrix plus two
+//! strips, which should behave as one fixture. A [`ControlGroup`] combines two [`Fixture`]s
+//! (each a [`Control`], or another group) so:
+//!
+//! - Each frame, every control ticks from the same timestamp, so their patterns stay in sync
+//! - The brightness, and on or off, are set for the whole group at once
+//!
+//! Nest groups for more than two controls.
+//!
+//! A failed frame on one control doesn't stop the others: every control is ticked, then the
+//! first error, if any, is returned.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     group::{ControlGroup, Fixture},
+//!     layout::{Layout1d, Layout2d, Shape2d, Vec2},
+//!     layout1d, layout2d,
+//!     patterns::{
+//!         noise::{noise_fns, Noise2d, NoiseParams},
+//!         rainbow::{Rainbow, RainbowParams},
+//!     },
+//!     ControlBuilder,
+//! };
+//! # use blinksy::driver::NullDriver;
+//!
+//! layout2d!(
+//!     Matrix,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 16,
+//!         vertical_pixel_count: 16,
+//!         serpentine: true,
+//!     }]
+//! );
+//! layout1d!(Strip, 60);
+//!
+//! let matrix = ControlBuilder::new_2d()
+//!     .with_layout::<Matrix, { Matrix::PIXEL_COUNT }>()
+//!     .with_pattern::<Noise2d<noise_fns::Perlin>>(NoiseParams::default())
+//! #   .with_driver(NullDriver)
+//! #   .with_frame_buffer_size::<0>()
+//!     // ...
+//!     .build();
+//! let strip = |_| {
+//!     ControlBuilder::new_1d()
+//!         .with_layout::<Strip, { Strip::PIXEL_COUNT }>()
+//!         .with_pattern::<Rainbow>(RainbowParams::default())
+//! #       .with_driver(NullDriver)
+//! #       .with_frame_buffer_size::<0>()
+//!         // ...
+//!         .build()
+//! };
+//!
+//! // The matrix, then both strips
+//! let mut group = ControlGroup::new(matrix, ControlGroup::new(strip(0), strip(1)));
+//!
+//! group.set_brightness(0.5);
+//! group.tick(0).unwrap();
+//!
+//! group.set_on(false);
+//! assert!(!group.is_on());
+//! ```
+
+use crate::{
+    color::{FromColor, LinearSrgb},
+    control::{Control, FrameHooks},
+    driver::Driver,
+    layout::LayoutForDim,
+    markers::Blocking,
+    pattern::Pattern,
+};
+#[cfg(feature = "async")]
+use crate::{driver::DriverAsync, markers::Async};
+
+/// Something ticked as part of a [`ControlGroup`]: a [`Control`], or another group.
+pub trait Fixture {
+    /// The error type that may be returned by a tick.
+    type Error;
+
+    /// Updates the LEDs based on the current time.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - Current time in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error
+    fn tick(&mut self, time_in_ms: u64) -> Result<(), Self::Error>;
+
+    /// Sets the overall brightness level.
+    ///
+    /// # Arguments
+    ///
+    /// - `brightness` - Brightness level from 0.0 (off) to 1.0 (full)
+    fn set_brightness(&mut self, brightness: f32);
+
+    /// Turns the LEDs on or off.
+    ///
+    /// # Arguments
+    ///
+    /// - `is_on` - Whether the LEDs are on
+    fn set_on(&mut self, is_on: bool);
+
+    /// Returns whether any LEDs are on.
+    fn is_on(&self) -> bool;
+}
+
+/// Something ticked asynchronously as part of a [`ControlGroup`]: a [`Control`], or another
+/// group.
+#[cfg(feature = "async")]
+pub trait FixtureAsync {
+    /// The error type that may be returned by a tick.
+    type Error;
+
+    /// Updates the LEDs based on the current time, asynchronously.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - Current time in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error
+    #[allow(async_fn_in_trait)]
+    async fn tick(&mut self, time_in_ms: u64) -> Result<(), Self::Error>;
+
+    /// Sets the overall brightness level.
+    ///
+    /// # Arguments
+    ///
+    /// - `brightness` - Brightness level from 0.0 (off) to 1.0 (full)
+    fn set_brightness(&mut self, brightness: f32);
+
+    /// Turns the LEDs on or off.
+    ///
+    /// # Arguments
+    ///
+    /// - `is_on` - Whether the LEDs are on
+    fn set_on(&mut self, is_on: bool);
+
+    /// Returns whether any LEDs are on.
+    fn is_on(&self) -> bool;
+}
+
+impl<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Dim, Layout, P, D, Hooks> Fixture
+    for Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Blocking, Layout, P, D, Hooks>
+where
+    Layout: LayoutForDim<Dim>,
+    P: Pattern<Dim, Layout>,
+    Hooks: FrameHooks,
+    D: Driver,
+    D::Color: FromColor<P::Color> + FromColor<LinearSrgb>,
+    D::Word: Clone,
+    LinearSrgb: FromColor<P::Color>,
+{
+    type Error = D::Error;
+
+    fn tick(&mut self, time_in_ms: u64) -> Result<(), Self::Error> {
+        Self::tick(self, time_in_ms)
+    }
+
+    fn set_brightness(&mut self, brightness: f32) {
+        Control::set_brightness(self, brightness);
+    }
+
+    fn set_on(&mut self, is_on: bool) {
+        Control::set_on(self, is_on);
+    }
+
+    fn is_on(&self) -> bool {
+        Control::is_on(self)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Dim, Layout, P, D, Hooks>
+    FixtureAsync for Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Async, Layout, P, D, Hooks>
+where
+    Layout: LayoutForDim<Dim>,
+    P: Pattern<Dim, Layout>,
+    Hooks: FrameHooks,
+    D: DriverAsync,
+    D::Color: FromColor<P::Color> + FromColor<LinearSrgb>,
+    D::Word: Clone,
+    LinearSrgb: FromColor<P::Color>,
+{
+    type Error = D::Error;
+
+    async fn tick(&mut self, time_in_ms: u64) -> Result<(), Self::Error> {
+        Self::tick(self, time_in_ms).await
+    }
+
+    fn set_brightness(&mut self, brightness: f32) {
+        Control::set_brightness(self, brightness);
+    }
+
+    fn set_on(&mut self, is_on: bool) {
+        Control::set_on(self, is_on);
+    }
+
+    fn is_on(&self) -> bool {
+        Control::is_on(self)
+    }
+}
+
+/// All types of errors that can happen while ticking a [`ControlGroup`].
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum GroupError<FirstError, SecondError> {
+    /// Raised if the first fixture fails to tick
+    First(FirstError),
+    /// Raised if the second fixture fails to tick
+    Second(SecondError),
+}
+
+/// Two fixtures, ticked from the same timestamp, with the same brightness and on or off.
+///
+/// # Type Parameters
+///
+/// - `First` - The first fixture, ticked first
+/// - `Second` - The second fixture
+#[derive(Debug)]
+pub struct ControlGroup<First, Second> {
+    first: First,
+    second: Second,
+}
+
+impl<First, Second> ControlGroup<First, Second> {
+    /// Groups two fixtures.
+    ///
+    /// # Arguments
+    ///
+    /// - `first` - The first fixture, ticked first
+    /// - `second` - The second fixture
+    pub fn new(first: First, second: Second) -> Self {
+        Self { first, second }
+    }
+
+    /// Returns a reference to the first fixture.
+    pub fn first(&self) -> &First {
+        &self.first
+    }
+
+    /// Returns a mutable reference to the first fixture, e.g. to change its pattern.
+    pub fn first_mut(&mut self) -> &mut First {
+        &mut self.first
+    }
+
+    /// Returns a reference to the second fixture.
+    pub fn second(&self) -> &Second {
+        &self.second
+    }
+
+    /// Returns a mutable reference to the second fixture, e.g. to change its pattern.
+    pub fn second_mut(&mut self) -> &mut Second {
+        &mut self.second
+    }
+
+    /// Ungroups the fixtures.
+    pub fn into_inner(self) -> (First, Second) {
+        (self.first, self.second)
+    }
+}
+
+impl<First, Second> Fixture for ControlGroup<First, Second>
+where
+    First: Fixture,
+    Second: Fixture,
+{
+    type Error = GroupError<First::Error, Second::Error>;
+
+    fn tick(&mut self, time_in_ms: u64) -> Result<(), Self::Error> {
+        let first = self.first.tick(time_in_ms);
+        let second = self.second.tick(time_in_ms);
+        first.map_err(GroupError::First)?;
+        second.map_err(GroupError::Second)
+    }
+
+    fn set_brightness(&mut self, brightness: f32) {
+        self.first.set_brightness(brightness);
+        self.second.set_brightness(brightness);
+    }
+
+    fn set_on(&mut self, is_on: bool) {
+        self.first.set_on(is_on);
+        self.second.set_on(is_on);
+    }
+
+    fn is_on(&self) -> bool {
+        self.first.is_on() || self.second.is_on()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<First, Second> FixtureAsync for ControlGroup<First, Second>
+where
+    First: FixtureAsync,
+    Second: FixtureAsync,
+{
+    type Error = GroupError<First::Error, Second::Error>;
+
+    async fn tick(&mut self, time_in_ms: u64) -> Result<(), Self::Error> {
+        let first = self.first.tick(time_in_ms).await;
+        let second = self.second.tick(time_in_ms).await;
+        first.map_err(GroupError::First)?;
+        second.map_err(GroupError::Second)
+    }
+
+    fn set_brightness(&mut self, brightness: f32) {
+        self.first.set_brightness(brightness);
+        self.second.set_brightness(brightness);
+    }
+
+    fn set_on(&mut self, is_on: bool) {
+        self.first.set_on(is_on);
+        self.second.set_on(is_on);
+    }
+
+    fn is_on(&self) -> bool {
+        self.first.is_on() || self.second.is_on()
+    }
+}
@@ -0,0 +1,155 @@
+//! # Device Input
+//!
+//! [`DeviceControls`] is the state machine behind a device's button UX: which pattern is
+//! selected, at which brightness, and how [`ButtonEvent`]s change them.
+//!
+//! It doesn't read any buttons itself. On hardware, a button driver detects clicks and feeds
+//! them in; on desktop, `blinksy-desktop` feeds in synthetic events from keyboard shortcuts.
+//! Either way, the device behaves the same.
+//!
+//! | Event         | Action                                    |
+//! |---------------|-------------------------------------------|
+//! | Click         | Next pattern                              |
+//! | Double click  | Next brightness step, from [`BRIGHTNESS_STEPS`] |
+//! | Triple click  | Previous pattern                          |
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::input::{ButtonEvent, ControlEvent, DeviceControls};
+//!
+//! let mut controls = DeviceControls::new(3);
+//! assert_eq!(controls.pattern_index(), 0);
+//!
+//! assert_eq!(
+//!     controls.handle(ButtonEvent::Click),
+//!     Some(ControlEvent::PatternChanged(1))
+//! );
+//! assert_eq!(
+//!     controls.handle(ButtonEvent::TripleClick),
+//!     Some(ControlEvent::PatternChanged(0))
+//! );
+//! assert!(matches!(
+//!     controls.handle(ButtonEvent::DoubleClick),
+//!     Some(ControlEvent::BrightnessChanged(_))
+//! ));
+//!
+//! // Then apply the change to your control system:
+//! // control.set_brightness(controls.brightness());
+//! ```
+
+/// The brightness levels cycled through with a double click.
+pub const BRIGHTNESS_STEPS: &[f32] = &[0.05, 0.1, 0.25, 0.5, 0.75, 1.0];
+
+/// The brightness step selected by default.
+pub const DEFAULT_BRIGHTNESS_INDEX: usize = 2;
+
+/// A gesture on a device button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ButtonEvent {
+    /// A single click
+    Click,
+    /// Two clicks in quick succession
+    DoubleClick,
+    /// Three clicks in quick succession
+    TripleClick,
+}
+
+/// A change requested through the device controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ControlEvent {
+    /// Show the pattern at this index
+    PatternChanged(usize),
+    /// Set the brightness to this level
+    BrightnessChanged(f32),
+    /// Turn the LEDs on (`true`) or off (`false`), e.g. from a remote power switch
+    PowerChanged(bool),
+}
+
+/// The state of a device's controls: selected pattern and brightness, and how button events
+/// change them.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceControls {
+    pattern_count: usize,
+    pattern_index: usize,
+    brightness_index: usize,
+}
+
+impl DeviceControls {
+    /// Creates the controls, with the first pattern and default brightness selected.
+    ///
+    /// # Arguments
+    ///
+    /// - `pattern_count` - The number of patterns to cycle through
+    pub fn new(pattern_count: usize) -> Self {
+        Self {
+            pattern_count,
+            pattern_index: 0,
+            brightness_index: DEFAULT_BRIGHTNESS_INDEX,
+        }
+    }
+
+    /// Returns the index of the selected pattern.
+    pub fn pattern_index(&self) -> usize {
+        self.pattern_index
+    }
+
+    /// Selects a pattern, e.g. when restoring saved settings.
+    ///
+    /// An out-of-range index selects the first pattern.
+    pub fn set_pattern_index(&mut self, index: usize) {
+        self.pattern_index = if index < self.pattern_count { index } else { 0 };
+    }
+
+    /// Returns the index of the selected brightness step.
+    pub fn brightness_index(&self) -> usize {
+        self.brightness_index
+    }
+
+    /// Selects a brightness step, e.g. when restoring saved settings.
+    ///
+    /// An out-of-range index selects the default step.
+    pub fn set_brightness_index(&mut self, index: usize) {
+        self.brightness_index = if index < BRIGHTNESS_STEPS.len() {
+            index
+        } else {
+            DEFAULT_BRIGHTNESS_INDEX
+        };
+    }
+
+    /// Returns the selected brightness level.
+    pub fn brightness(&self) -> f32 {
+        BRIGHTNESS_STEPS[self.brightness_index]
+    }
+
+    /// Handles a button event.
+    ///
+    /// # Arguments
+    ///
+    /// - `event` - The button event
+    ///
+    /// # Returns
+    ///
+    /// The requested change, if any
+    pub fn handle(&mut self, event: ButtonEvent) -> Option<ControlEvent> {
+        let pattern_count = self.pattern_count.max(1);
+        match event {
+            ButtonEvent::Click => {
+                self.pattern_index = (self.pattern_index + 1) % pattern_count;
+                Some(ControlEvent::PatternChanged(self.pattern_index))
+            }
+            ButtonEvent::DoubleClick => {
+                self.brightness_index = (self.brightness_index + 1) % BRIGHTNESS_STEPS.len();
+                Some(ControlEvent::BrightnessChanged(self.brightness()))
+            }
+            ButtonEvent::TripleClick => {
+                self.pattern_index = (self.pattern_index + pattern_count - 1) % pattern_count;
+                Some(ControlEvent::PatternChanged(self.pattern_index))
+            }
+        }
+    }
+}
@@ -32,6 +32,59 @@ pub struct ArcStepIterator<Item> {
     end_angle_in_radians: f32,
     index: usize,
     length: usize,
+    arc_length: Option<ArcLength>,
+}
+
+/// The number of steps to numerically integrate the length of an arc with.
+const ARC_LENGTH_STEPS: usize = 256;
+
+/// The state of an arc traced by uniform arc length.
+#[derive(Debug, Clone)]
+struct ArcLength {
+    /// The dot products of the axes: `[u · u, u · v, v · v]`
+    axis_dots: [f32; 3],
+    /// The total length of the arc
+    total: f32,
+    /// The start of the current integration step
+    step: usize,
+    /// The length of the arc to the start of the current integration step
+    travelled: f32,
+}
+
+impl ArcLength {
+    /// Returns the speed of the point along the arc at an angle: the length of the
+    /// derivative, `-sin(theta) * u + cos(theta) * v`.
+    fn speed(&self, theta: f32) -> f32 {
+        let [uu, uv, vv] = self.axis_dots;
+        let (sin, cos) = theta.sin_cos();
+        (sin * sin * uu - 2. * sin * cos * uv + cos * cos * vv)
+            .max(0.)
+            .sqrt()
+    }
+
+    /// Returns the angle at a fraction of the total length of the arc.
+    ///
+    /// The fraction must not decrease between calls: the integration continues from the last
+    /// angle.
+    fn theta(&mut self, t: f32, start_angle_in_radians: f32, sweep: f32) -> f32 {
+        let step_angle = sweep / ARC_LENGTH_STEPS as f32;
+        let target = t * self.total;
+        while self.step < ARC_LENGTH_STEPS {
+            let theta = start_angle_in_radians + self.step as f32 * step_angle;
+            let segment = self.speed(theta + 0.5 * step_angle) * step_angle.abs();
+            if self.travelled + segment >= target {
+                let fraction = if segment > 0. {
+                    ((target - self.travelled) / segment).clamp(0., 1.)
+                } else {
+                    0.
+                };
+                return theta + fraction * step_angle;
+            }
+            self.travelled += segment;
+            self.step += 1;
+        }
+        start_angle_in_radians + sweep
+    }
 }
 
 impl<Item> ArcStepIterator<Item> {
@@ -58,6 +111,54 @@ impl<Item> ArcStepIterator<Item> {
             end_angle_in_radians,
             index: 0,
             length,
+            arc_length: None,
+        }
+    }
+
+    /// Create a new arc iterator, with points spaced by uniform arc length rather than uniform
+    /// angle.
+    ///
+    /// The arc length is integrated numerically, from the dot products of the basis vectors.
+    ///
+    /// - `origin`: center of the arc
+    /// - `cos_scalar`, `sin_scalar`: basis vectors used in the parametric form
+    /// - `axis_dots`: the dot products `[cos · cos, cos · sin, sin · sin]` of the basis vectors
+    /// - `start_angle_in_radians`: start angle (radians)
+    /// - `end_angle_in_radians`: end angle (radians)
+    /// - `length`: number of samples; 0 yields no points
+    pub fn new_uniform_length(
+        origin: Item,
+        cos_scalar: Item,
+        sin_scalar: Item,
+        axis_dots: [f32; 3],
+        start_angle_in_radians: f32,
+        end_angle_in_radians: f32,
+        length: usize,
+    ) -> Self {
+        let mut arc_length = ArcLength {
+            axis_dots,
+            total: 0.,
+            step: 0,
+            travelled: 0.,
+        };
+        let step_angle = (end_angle_in_radians - start_angle_in_radians) / ARC_LENGTH_STEPS as f32;
+        arc_length.total = (0..ARC_LENGTH_STEPS)
+            .map(|step| {
+                let theta = start_angle_in_radians + (step as f32 + 0.5) * step_angle;
+                arc_length.speed(theta) * step_angle.abs()
+            })
+            .sum();
+
+        Self {
+            origin,
+            cos_scalar,
+            sin_scalar,
+            start_angle_in_radians,
+            end_angle_in_radians,
+            index: 0,
+            length,
+            // Without any length, fall back to uniform angles
+            arc_length: (arc_length.total > 0.).then_some(arc_length),
         }
     }
 }
@@ -82,7 +183,10 @@ where
 
         let t: f32 = (self.index as f32) / denom;
         let sweep: f32 = self.end_angle_in_radians - self.start_angle_in_radians;
-        let theta: f32 = self.start_angle_in_radians + t * sweep;
+        let theta: f32 = match &mut self.arc_length {
+            Some(arc_length) => arc_length.theta(t, self.start_angle_in_radians, sweep),
+            None => self.start_angle_in_radians + t * sweep,
+        };
 
         let point = self.origin + theta.cos() * self.cos_scalar + theta.sin() * self.sin_scalar;
 
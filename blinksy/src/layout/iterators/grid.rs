@@ -59,7 +59,7 @@ where
     type Item = Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.vertical_index >= self.vertical_pixel_count {
+        if self.vertical_index >= self.vertical_pixel_count || self.horizontal_pixel_count == 0 {
             return None;
         }
         let vertical_index = Scalar::from_usize(self.vertical_index)?;
@@ -27,6 +27,7 @@ pub trait Layout1d {
 /// - `#[$attr]` - Optional attributes to apply to the struct (e.g., `#[derive(Debug)]`)
 /// - `$vis` - Optional visibility modifier (e.g., `pub`)
 /// - `$name` - The name of the layout type to create
+/// - `<const $param: $type, ...>` - Optional const generic parameters of the layout type
 /// - `$pixel_count` - The number of LEDs in the layout
 ///
 /// # Output
@@ -52,14 +53,42 @@ pub trait Layout1d {
 ///     60
 /// );
 /// ```
+///
+/// # Example (Generic)
+///
+/// Layouts can be defined inside functions, but like any item, can't use the generic
+/// parameters of the function. Instead, give the layout its own const generic parameters:
+///
+/// ```rust
+/// use blinksy::{layout::Layout1d, layout1d};
+///
+/// fn points<const NUM_LEDS: usize>() -> impl Iterator<Item = f32> {
+///     layout1d!(Layout<const PIXEL_COUNT: usize>, PIXEL_COUNT);
+///
+///     Layout::<NUM_LEDS>::points()
+/// }
+///
+/// assert_eq!(points::<60>().count(), 60);
+/// ```
 #[macro_export]
 macro_rules! layout1d {
+    (
+        $(#[$attr:meta])* $vis:vis $name:ident<$(const $param:ident: $param_ty:ty),+ $(,)?>,
+        $pixel_count:expr
+    ) => {
+        $(#[$attr])*
+        $vis struct $name<$(const $param: $param_ty),+>;
+
+        impl<$(const $param: $param_ty),+> $crate::layout::Layout1d for $name<$($param),+> {
+            const PIXEL_COUNT: ::core::primitive::usize = $pixel_count;
+        }
+    };
     ($(#[$attr:meta])* $vis:vis $name:ident, $pixel_count:expr) => {
         $(#[$attr])*
         $vis struct $name;
 
         impl $crate::layout::Layout1d for $name {
-            const PIXEL_COUNT: usize = $pixel_count;
+            const PIXEL_COUNT: ::core::primitive::usize = $pixel_count;
         }
     };
 }
@@ -1,4 +1,7 @@
-use core::iter::{once, Once};
+use core::{
+    iter::{once, Copied, Once},
+    slice,
+};
 
 use crate::layout::ArcStepIterator;
 
@@ -14,7 +17,16 @@ pub enum Shape2d {
     /// A single point at the specified location.
     Point(Vec2),
 
+    /// A list of points, one for each LED, in order.
+    ///
+    /// For irregular installations, e.g. with positions measured by hand or mapped with a
+    /// camera.
+    Points(&'static [Vec2]),
+
     /// A line of LEDs from `start` to `end` with `pixel_count` LEDs.
+    ///
+    /// The endpoints are inclusive: the first LED is at `start` and the last LED is at `end`,
+    /// with the LEDs evenly spaced between. A single LED is at `start`.
     Line {
         /// Starting point of the line
         start: Vec2,
@@ -25,6 +37,9 @@ pub enum Shape2d {
     },
 
     /// A grid of LEDs defined by three corners and dimensions.
+    ///
+    /// Like a [`Line`](Self::Line), the corners are inclusive: the first row runs from `start`
+    /// to `horizontal_end`, and the first column runs from `start` to `vertical_end`.
     Grid {
         /// Starting point (origin) of the grid
         start: Vec2,
@@ -75,7 +90,8 @@ pub enum Shape2d {
     /// - `axis_u` and `axis_v` need not be unit length of perpendicular.
     /// - The points returned by `shape::points()` of a `Shape2d::Arc`:
     ///   - Will have uniform density if a circular arc
-    ///   - Will **not** have uniform density if an elliptical arc, as the points correspond to `theta`.
+    ///   - Will **not** have uniform density if an elliptical arc, as the points correspond to
+    ///     `theta`. Use [`Shape2d::UniformArc`] for points spaced by uniform arc length.
     ///
     /// [`TAU`]: https://doc.rust-lang.org/core/f32/consts/constant.TAU.html
     Arc {
@@ -92,6 +108,26 @@ pub enum Shape2d {
         /// Number of LEDs
         pixel_count: usize,
     },
+
+    /// An arc like [`Shape2d::Arc`], with LEDs spaced by uniform arc length, as with evenly spaced
+    /// LEDs on an elliptical fixture.
+    ///
+    /// The arc length is integrated numerically, so each point is within a small fraction of a
+    /// spacing of its exact position.
+    UniformArc {
+        /// Center of the ellipse
+        center: Vec2,
+        /// Cosine-axis vector
+        axis_u: Vec2,
+        /// Sine-axis vector
+        axis_v: Vec2,
+        /// Start angle in radians
+        start_angle_in_radians: f32,
+        /// End angle in radians
+        end_angle_in_radians: f32,
+        /// Number of LEDs
+        pixel_count: usize,
+    },
 }
 
 /// Iterator over points in a 2D shape.
@@ -99,6 +135,8 @@ pub enum Shape2d {
 pub enum Shape2dPointsIterator {
     /// Iterator for a single point
     Point(Once<Vec2>),
+    /// Iterator for a list of points
+    Points(Copied<slice::Iter<'static, Vec2>>),
     /// Iterator for points along a line
     Line(LineStepIterator<Vec2, f32>),
     /// Iterator for points in a grid
@@ -113,6 +151,7 @@ impl Iterator for Shape2dPointsIterator {
     fn next(&mut self) -> Option<Self::Item> {
         match self {
             Shape2dPointsIterator::Point(iter) => iter.next(),
+            Shape2dPointsIterator::Points(iter) => iter.next(),
             Shape2dPointsIterator::Line(iter) => iter.next(),
             Shape2dPointsIterator::Grid(iter) => iter.next(),
             Shape2dPointsIterator::Arc(iter) => iter.next(),
@@ -126,6 +165,12 @@ impl From<Once<Vec2>> for Shape2dPointsIterator {
     }
 }
 
+impl From<Copied<slice::Iter<'static, Vec2>>> for Shape2dPointsIterator {
+    fn from(value: Copied<slice::Iter<'static, Vec2>>) -> Self {
+        Shape2dPointsIterator::Points(value)
+    }
+}
+
 impl From<LineStepIterator<Vec2, f32>> for Shape2dPointsIterator {
     fn from(value: LineStepIterator<Vec2, f32>) -> Self {
         Shape2dPointsIterator::Line(value)
@@ -149,6 +194,7 @@ impl Shape2d {
     pub const fn pixel_count(&self) -> usize {
         match *self {
             Shape2d::Point(_) => 1,
+            Shape2d::Points(points) => points.len(),
             Shape2d::Line { pixel_count, .. } => pixel_count,
             Shape2d::Grid {
                 horizontal_pixel_count,
@@ -156,6 +202,7 @@ impl Shape2d {
                 ..
             } => horizontal_pixel_count * vertical_pixel_count,
             Shape2d::Arc { pixel_count, .. } => pixel_count,
+            Shape2d::UniformArc { pixel_count, .. } => pixel_count,
         }
     }
 
@@ -163,12 +210,13 @@ impl Shape2d {
     pub fn points(&self) -> Shape2dPointsIterator {
         match *self {
             Shape2d::Point(point) => once(point).into(),
+            Shape2d::Points(points) => points.iter().copied().into(),
             Shape2d::Line {
                 start,
                 end,
                 pixel_count,
             } => {
-                let step = (end - start) / ((pixel_count - 1) as f32).max(1.);
+                let step = (end - start) / (pixel_count as f32 - 1.).max(1.);
                 LineStepIterator::new(start, step, pixel_count).into()
             }
             Shape2d::Grid {
@@ -210,6 +258,23 @@ impl Shape2d {
                 pixel_count,
             )
             .into(),
+            Shape2d::UniformArc {
+                center,
+                axis_u,
+                axis_v,
+                start_angle_in_radians,
+                end_angle_in_radians,
+                pixel_count,
+            } => ArcStepIterator::new_uniform_length(
+                center,
+                axis_u,
+                axis_v,
+                [axis_u.dot(axis_u), axis_u.dot(axis_v), axis_v.dot(axis_v)],
+                start_angle_in_radians,
+                end_angle_in_radians,
+                pixel_count,
+            )
+            .into(),
         }
     }
 }
@@ -246,6 +311,7 @@ pub trait Layout2d {
 /// - `#[$attr]` - Optional attributes to apply to the struct (e.g., `#[derive(Debug)]`)
 /// - `$vis` - Optional visibility modifier (e.g., `pub`)
 /// - `$name` - The name of the layout type to create
+/// - `<const $param: $type, ...>` - Optional const generic parameters of the layout type
 /// - `[$($shape:expr),*]` - A list of Shape2d instances defining the layout
 ///
 /// # Output
@@ -269,18 +335,253 @@ pub trait Layout2d {
 ///     }]
 /// );
 /// ```
+///
+/// # Example (Points)
+///
+/// For an irregular installation, list the position of each LED:
+///
+/// ```rust
+/// use blinksy::{layout2d, layout::Layout2d, layout::Shape2d, layout::Vec2};
+///
+/// // Positions mapped with a camera
+/// const POSITIONS: [Vec2; 4] = [
+///     Vec2::new(-0.8, -0.3),
+///     Vec2::new(-0.1, 0.6),
+///     Vec2::new(0.4, -0.9),
+///     Vec2::new(0.9, 0.2),
+/// ];
+///
+/// layout2d!(Layout, [Shape2d::Points(&POSITIONS)]);
+///
+/// assert_eq!(Layout::PIXEL_COUNT, 4);
+/// assert!(Layout::points().eq(POSITIONS));
+/// ```
+///
+/// # Example (Generic)
+///
+/// The shapes can use the const generic parameters of the layout type:
+///
+/// ```rust
+/// use blinksy::{layout2d, layout::Layout2d, layout::Shape2d, layout::Vec2};
+///
+/// fn pixel_count<const WIDTH: usize, const HEIGHT: usize>() -> usize {
+///     layout2d!(
+///         Layout<const W: usize, const H: usize>,
+///         [Shape2d::Grid {
+///             start: Vec2::new(-1., -1.),
+///             horizontal_end: Vec2::new(1., -1.),
+///             vertical_end: Vec2::new(-1., 1.),
+///             horizontal_pixel_count: W,
+///             vertical_pixel_count: H,
+///             serpentine: true,
+///         }]
+///     );
+///
+///     Layout::<WIDTH, HEIGHT>::PIXEL_COUNT
+/// }
+///
+/// assert_eq!(pixel_count::<16, 8>(), 128);
+/// ```
 #[macro_export]
 macro_rules! layout2d {
+    (
+        $(#[$attr:meta])* $vis:vis $name:ident<$(const $param:ident: $param_ty:ty),+ $(,)?>,
+        [$($shape:expr),* $(,)?]
+    ) => {
+        $(#[$attr])*
+        $vis struct $name<$(const $param: $param_ty),+>;
+
+        impl<$(const $param: $param_ty),+> $crate::layout::Layout2d for $name<$($param),+> {
+            const PIXEL_COUNT: ::core::primitive::usize = 0 $(+ $shape.pixel_count())*;
+
+            fn shapes() -> impl ::core::iter::Iterator<Item = $crate::layout::Shape2d> {
+                ::core::iter::IntoIterator::into_iter([$($shape),*])
+            }
+        }
+    };
     ($(#[$attr:meta])* $vis:vis $name:ident, [$($shape:expr),* $(,)?]) => {
         $(#[$attr])*
         $vis struct $name;
 
         impl $crate::layout::Layout2d for $name {
-            const PIXEL_COUNT: usize = 0 $(+ $shape.pixel_count())*;
+            const PIXEL_COUNT: ::core::primitive::usize = 0 $(+ $shape.pixel_count())*;
 
-            fn shapes() -> impl Iterator<Item = $crate::layout::Shape2d> {
-                [$($shape),*].into_iter()
+            fn shapes() -> impl ::core::iter::Iterator<Item = $crate::layout::Shape2d> {
+                ::core::iter::IntoIterator::into_iter([$($shape),*])
             }
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::{PI, TAU};
+
+    use super::*;
+
+    fn assert_near(actual: Option<Vec2>, expected: Vec2) {
+        let actual = actual.expect("shape has no points");
+        assert!(
+            actual.abs_diff_eq(expected, 1e-5),
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    fn line(pixel_count: usize) -> Shape2d {
+        Shape2d::Line {
+            start: Vec2::new(-1., 0.5),
+            end: Vec2::new(1., -0.5),
+            pixel_count,
+        }
+    }
+
+    fn grid(
+        horizontal_pixel_count: usize,
+        vertical_pixel_count: usize,
+        serpentine: bool,
+    ) -> Shape2d {
+        Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count,
+            vertical_pixel_count,
+            serpentine,
+        }
+    }
+
+    fn arc(pixel_count: usize) -> Shape2d {
+        Shape2d::Arc {
+            center: Vec2::new(0.5, 0.),
+            axis_u: Vec2::new(0.5, 0.),
+            axis_v: Vec2::new(0., 0.25),
+            start_angle_in_radians: 0.,
+            end_angle_in_radians: PI,
+            pixel_count,
+        }
+    }
+
+    #[test]
+    fn test_points_count_matches_pixel_count() {
+        const POINTS: [Vec2; 3] = [Vec2::ZERO, Vec2::X, Vec2::Y];
+        let simple_shapes = [
+            Shape2d::Point(Vec2::ZERO),
+            Shape2d::Points(&POINTS),
+            Shape2d::Points(&[]),
+        ];
+        let mut shapes = simple_shapes.into_iter().chain(
+            [0, 1, 2, 3, 60]
+                .into_iter()
+                .flat_map(|pixel_count| [line(pixel_count), arc(pixel_count)]),
+        );
+        assert!(shapes.all(|shape| shape.points().count() == shape.pixel_count()));
+
+        let sizes = [
+            (0, 0),
+            (0, 3),
+            (3, 0),
+            (1, 1),
+            (1, 4),
+            (4, 1),
+            (16, 16),
+            (5, 3),
+        ];
+        for (horizontal_pixel_count, vertical_pixel_count) in sizes {
+            for serpentine in [false, true] {
+                let shape = grid(horizontal_pixel_count, vertical_pixel_count, serpentine);
+                assert_eq!(shape.points().count(), shape.pixel_count(), "{shape:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_point() {
+        let shape = Shape2d::Point(Vec2::new(0.25, -0.75));
+        assert_near(shape.points().next(), Vec2::new(0.25, -0.75));
+    }
+
+    #[test]
+    fn test_points() {
+        const POINTS: [Vec2; 3] = [Vec2::new(0.5, 0.5), Vec2::new(-1., 0.), Vec2::new(0., -1.)];
+        assert!(Shape2d::Points(&POINTS).points().eq(POINTS));
+    }
+
+    #[test]
+    fn test_line_endpoints() {
+        for pixel_count in [2, 3, 10, 60, 300] {
+            let shape = line(pixel_count);
+            assert_near(shape.points().next(), Vec2::new(-1., 0.5));
+            assert_near(shape.points().last(), Vec2::new(1., -0.5));
+        }
+
+        // Even spacing, without drift
+        let mut points = line(5).points();
+        assert_near(points.nth(1), Vec2::new(-0.5, 0.25));
+        assert_near(points.next(), Vec2::new(0., 0.));
+        assert_near(points.next(), Vec2::new(0.5, -0.25));
+
+        // A single LED is at the start
+        assert_near(line(1).points().next(), Vec2::new(-1., 0.5));
+    }
+
+    #[test]
+    fn test_grid_corners() {
+        let shape = grid(4, 3, false);
+        let mut points = shape.points();
+        assert_near(points.next(), Vec2::new(-1., -1.));
+        assert_near(points.nth(2), Vec2::new(1., -1.));
+        assert_near(points.nth(4), Vec2::new(-1., 1.));
+        assert_near(points.last(), Vec2::new(1., 1.));
+
+        // Odd rows run backwards
+        let shape = grid(4, 3, true);
+        let mut points = shape.points();
+        assert_near(points.nth(3), Vec2::new(1., -1.));
+        assert_near(points.next(), Vec2::new(1., 0.));
+        assert_near(points.nth(2), Vec2::new(-1., 0.));
+        assert_near(points.last(), Vec2::new(1., 1.));
+    }
+
+    #[test]
+    fn test_arc_endpoints() {
+        let shape = arc(9);
+        assert_near(shape.points().next(), Vec2::new(1., 0.));
+        assert_near(shape.points().nth(4), Vec2::new(0.5, 0.25));
+        assert_near(shape.points().last(), Vec2::new(0., 0.));
+
+        // A full circle ends where it starts
+        let shape = Shape2d::Arc {
+            center: Vec2::ZERO,
+            axis_u: Vec2::X,
+            axis_v: Vec2::Y,
+            start_angle_in_radians: 0.,
+            end_angle_in_radians: TAU,
+            pixel_count: 8,
+        };
+        assert_near(shape.points().last(), Vec2::X);
+    }
+
+    #[test]
+    fn test_arc_uniform_length() {
+        let shape = Shape2d::UniformArc {
+            center: Vec2::ZERO,
+            axis_u: Vec2::new(1., 0.),
+            axis_v: Vec2::new(0., 0.75),
+            start_angle_in_radians: 0.,
+            end_angle_in_radians: PI,
+            pixel_count: 41,
+        };
+        assert_eq!(shape.points().count(), 41);
+        assert_near(shape.points().next(), Vec2::new(1., 0.));
+        assert_near(shape.points().nth(20), Vec2::new(0., 0.75));
+        assert_near(shape.points().last(), Vec2::new(-1., 0.));
+
+        // Consecutive points are evenly spaced along the ellipse
+        let mut distances = shape
+            .points()
+            .zip(shape.points().skip(1))
+            .map(|(a, b)| a.distance(b));
+        let first = distances.next().unwrap();
+        assert!(distances.all(|distance| (distance - first).abs() < 0.01 * first));
+    }
+}
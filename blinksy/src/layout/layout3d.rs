@@ -1,4 +1,7 @@
-use core::iter::{once, Once};
+use core::{
+    iter::{once, Copied, Once},
+    slice,
+};
 
 use crate::layout::ArcStepIterator;
 
@@ -14,7 +17,16 @@ pub enum Shape3d {
     /// A single point at the specified location.
     Point(Vec3),
 
+    /// A list of points, one for each LED, in order.
+    ///
+    /// For irregular installations, e.g. with positions measured by hand or mapped with a
+    /// camera.
+    Points(&'static [Vec3]),
+
     /// A line of LEDs from `start` to `end` with `pixel_count` LEDs.
+    ///
+    /// The endpoints are inclusive: the first LED is at `start` and the last LED is at `end`,
+    /// with the LEDs evenly spaced between. A single LED is at `start`.
     Line {
         /// Starting point of the line
         start: Vec3,
@@ -25,6 +37,9 @@ pub enum Shape3d {
     },
 
     /// A grid of LEDs defined by three corners and dimensions.
+    ///
+    /// Like a [`Line`](Self::Line), the corners are inclusive: the first row runs from `start`
+    /// to `horizontal_end`, and the first column runs from `start` to `vertical_end`.
     Grid {
         /// Starting point (origin) of the grid
         start: Vec3,
@@ -74,7 +89,8 @@ pub enum Shape3d {
     /// - Axes don’t need to be unit length or perpendicular; their lengths set the ellipse radii along their directions.
     /// - The points returned by `shape::points()` of a `Shape3d::Arc`:
     ///   - Will have uniform density if a circular arc
-    ///   - Will **not** have uniform density if an elliptical arc, as the points correspond to `theta`.
+    ///   - Will **not** have uniform density if an elliptical arc, as the points correspond to
+    ///     `theta`. Use [`Shape3d::UniformArc`] for points spaced by uniform arc length.
     ///
     /// [`TAU`]: https://doc.rust-lang.org/core/f32/consts/constant.TAU.html
     Arc {
@@ -91,6 +107,26 @@ pub enum Shape3d {
         /// Number of LEDs
         pixel_count: usize,
     },
+
+    /// An arc like [`Shape3d::Arc`], with LEDs spaced by uniform arc length, as with evenly spaced
+    /// LEDs on an elliptical fixture.
+    ///
+    /// The arc length is integrated numerically, so each point is within a small fraction of a
+    /// spacing of its exact position.
+    UniformArc {
+        /// Center of the ellipse
+        center: Vec3,
+        /// Cosine-axis vector
+        axis_u: Vec3,
+        /// Sine-axis vector
+        axis_v: Vec3,
+        /// Start angle in radians
+        start_angle_in_radians: f32,
+        /// End angle in radians
+        end_angle_in_radians: f32,
+        /// Number of LEDs
+        pixel_count: usize,
+    },
 }
 
 impl Shape3d {
@@ -98,6 +134,7 @@ impl Shape3d {
     pub const fn pixel_count(&self) -> usize {
         match *self {
             Shape3d::Point(_) => 1,
+            Shape3d::Points(points) => points.len(),
             Shape3d::Line { pixel_count, .. } => pixel_count,
             Shape3d::Grid {
                 horizontal_pixel_count,
@@ -105,6 +142,7 @@ impl Shape3d {
                 ..
             } => horizontal_pixel_count * vertical_pixel_count,
             Shape3d::Arc { pixel_count, .. } => pixel_count,
+            Shape3d::UniformArc { pixel_count, .. } => pixel_count,
         }
     }
 
@@ -112,12 +150,13 @@ impl Shape3d {
     pub fn points(&self) -> Shape3dPointsIterator {
         match *self {
             Shape3d::Point(point) => once(point).into(),
+            Shape3d::Points(points) => points.iter().copied().into(),
             Shape3d::Line {
                 start,
                 end,
                 pixel_count,
             } => {
-                let step = (end - start) / ((pixel_count - 1) as f32).max(1.);
+                let step = (end - start) / (pixel_count as f32 - 1.).max(1.);
                 LineStepIterator::new(start, step, pixel_count).into()
             }
             Shape3d::Grid {
@@ -158,6 +197,23 @@ impl Shape3d {
                 pixel_count,
             )
             .into(),
+            Shape3d::UniformArc {
+                center,
+                axis_u,
+                axis_v,
+                start_angle_in_radians,
+                end_angle_in_radians,
+                pixel_count,
+            } => ArcStepIterator::new_uniform_length(
+                center,
+                axis_u,
+                axis_v,
+                [axis_u.dot(axis_u), axis_u.dot(axis_v), axis_v.dot(axis_v)],
+                start_angle_in_radians,
+                end_angle_in_radians,
+                pixel_count,
+            )
+            .into(),
         }
     }
 }
@@ -196,6 +252,8 @@ pub trait Layout3d {
 pub enum Shape3dPointsIterator {
     /// Iterator for a single point
     Point(Once<Vec3>),
+    /// Iterator for a list of points
+    Points(Copied<slice::Iter<'static, Vec3>>),
     /// Iterator for points along a line
     Line(LineStepIterator<Vec3, f32>),
     /// Iterator for points in a grid
@@ -210,6 +268,7 @@ impl Iterator for Shape3dPointsIterator {
     fn next(&mut self) -> Option<Self::Item> {
         match self {
             Shape3dPointsIterator::Point(iter) => iter.next(),
+            Shape3dPointsIterator::Points(iter) => iter.next(),
             Shape3dPointsIterator::Line(iter) => iter.next(),
             Shape3dPointsIterator::Grid(iter) => iter.next(),
             Shape3dPointsIterator::Arc(iter) => iter.next(),
@@ -223,6 +282,12 @@ impl From<Once<Vec3>> for Shape3dPointsIterator {
     }
 }
 
+impl From<Copied<slice::Iter<'static, Vec3>>> for Shape3dPointsIterator {
+    fn from(value: Copied<slice::Iter<'static, Vec3>>) -> Self {
+        Shape3dPointsIterator::Points(value)
+    }
+}
+
 impl From<LineStepIterator<Vec3, f32>> for Shape3dPointsIterator {
     fn from(value: LineStepIterator<Vec3, f32>) -> Self {
         Shape3dPointsIterator::Line(value)
@@ -241,18 +306,190 @@ impl From<ArcStepIterator<Vec3>> for Shape3dPointsIterator {
     }
 }
 
+/// Creates a three-dimensional LED layout from a collection of shapes.
+///
+/// # Arguments
+///
+/// - `#[$attr]` - Optional attributes to apply to the struct (e.g., `#[derive(Debug)]`)
+/// - `$vis` - Optional visibility modifier (e.g., `pub`)
+/// - `$name` - The name of the layout type to create
+/// - `<const $param: $type, ...>` - Optional const generic parameters of the layout type
+/// - `[$($shape:expr),*]` - A list of Shape3d instances defining the layout
+///
+/// # Output
+///
+/// Macro output will be a type definition that implements [`Layout3d`].
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{layout3d, layout::Layout3d, layout::Shape3d, layout::Vec3};
+///
+/// // A vertical column of 30 LEDs
+/// layout3d!(
+///     Layout,
+///     [Shape3d::Line {
+///         start: Vec3::new(0., -1., 0.),
+///         end: Vec3::new(0., 1., 0.),
+///         pixel_count: 30,
+///     }]
+/// );
+/// ```
+///
+/// # Example (Generic)
+///
+/// ```rust
+/// use blinksy::{layout3d, layout::Layout3d, layout::Shape3d, layout::Vec3};
+///
+/// // A vertical column of any number of LEDs
+/// layout3d!(
+///     Column<const PIXEL_COUNT: usize>,
+///     [Shape3d::Line {
+///         start: Vec3::new(0., -1., 0.),
+///         end: Vec3::new(0., 1., 0.),
+///         pixel_count: PIXEL_COUNT,
+///     }]
+/// );
+///
+/// assert_eq!(Column::<30>::PIXEL_COUNT, 30);
+/// ```
 #[macro_export]
 macro_rules! layout3d {
+    (
+        $(#[$attr:meta])* $vis:vis $name:ident<$(const $param:ident: $param_ty:ty),+ $(,)?>,
+        [$($shape:expr),* $(,)?]
+    ) => {
+        $(#[$attr])*
+        $vis struct $name<$(const $param: $param_ty),+>;
+
+        impl<$(const $param: $param_ty),+> $crate::layout::Layout3d for $name<$($param),+> {
+            const PIXEL_COUNT: ::core::primitive::usize = 0 $(+ $shape.pixel_count())*;
+
+            fn shapes() -> impl ::core::iter::Iterator<Item = $crate::layout::Shape3d> {
+                ::core::iter::IntoIterator::into_iter([$($shape),*])
+            }
+        }
+    };
     ($(#[$attr:meta])* $vis:vis $name:ident, [$($shape:expr),* $(,)?]) => {
         $(#[$attr])*
         $vis struct $name;
 
         impl $crate::layout::Layout3d for $name {
-            const PIXEL_COUNT: usize = 0 $(+ $shape.pixel_count())*;
+            const PIXEL_COUNT: ::core::primitive::usize = 0 $(+ $shape.pixel_count())*;
 
-            fn shapes() -> impl Iterator<Item = $crate::layout::Shape3d> {
-                [$($shape),*].into_iter()
+            fn shapes() -> impl ::core::iter::Iterator<Item = $crate::layout::Shape3d> {
+                ::core::iter::IntoIterator::into_iter([$($shape),*])
             }
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    fn assert_near(actual: Option<Vec3>, expected: Vec3) {
+        let actual = actual.expect("shape has no points");
+        assert!(
+            actual.abs_diff_eq(expected, 1e-5),
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    fn line(pixel_count: usize) -> Shape3d {
+        Shape3d::Line {
+            start: Vec3::new(-1., -1., -1.),
+            end: Vec3::new(1., 1., 1.),
+            pixel_count,
+        }
+    }
+
+    fn grid(
+        horizontal_pixel_count: usize,
+        vertical_pixel_count: usize,
+        serpentine: bool,
+    ) -> Shape3d {
+        Shape3d::Grid {
+            start: Vec3::new(-1., -1., 1.),
+            horizontal_end: Vec3::new(1., -1., 1.),
+            vertical_end: Vec3::new(-1., 1., 1.),
+            horizontal_pixel_count,
+            vertical_pixel_count,
+            serpentine,
+        }
+    }
+
+    fn arc(pixel_count: usize) -> Shape3d {
+        Shape3d::Arc {
+            center: Vec3::ZERO,
+            axis_u: Vec3::X,
+            axis_v: Vec3::Z,
+            start_angle_in_radians: 0.,
+            end_angle_in_radians: FRAC_PI_2,
+            pixel_count,
+        }
+    }
+
+    #[test]
+    fn test_points_count_matches_pixel_count() {
+        const POINTS: [Vec3; 3] = [Vec3::ZERO, Vec3::X, Vec3::Z];
+        let simple_shapes = [
+            Shape3d::Point(Vec3::ZERO),
+            Shape3d::Points(&POINTS),
+            Shape3d::Points(&[]),
+        ];
+        let mut shapes = simple_shapes.into_iter().chain(
+            [0, 1, 2, 3, 60]
+                .into_iter()
+                .flat_map(|pixel_count| [line(pixel_count), arc(pixel_count)]),
+        );
+        assert!(shapes.all(|shape| shape.points().count() == shape.pixel_count()));
+
+        let sizes = [
+            (0, 0),
+            (0, 3),
+            (3, 0),
+            (1, 1),
+            (1, 4),
+            (4, 1),
+            (8, 8),
+            (5, 3),
+        ];
+        for (horizontal_pixel_count, vertical_pixel_count) in sizes {
+            for serpentine in [false, true] {
+                let shape = grid(horizontal_pixel_count, vertical_pixel_count, serpentine);
+                assert_eq!(shape.points().count(), shape.pixel_count(), "{shape:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_line_endpoints() {
+        for pixel_count in [2, 3, 10, 60, 300] {
+            let shape = line(pixel_count);
+            assert_near(shape.points().next(), Vec3::new(-1., -1., -1.));
+            assert_near(shape.points().last(), Vec3::new(1., 1., 1.));
+        }
+        assert_near(line(3).points().nth(1), Vec3::ZERO);
+        assert_near(line(1).points().next(), Vec3::new(-1., -1., -1.));
+    }
+
+    #[test]
+    fn test_grid_corners() {
+        let shape = grid(3, 3, true);
+        let mut points = shape.points();
+        assert_near(points.next(), Vec3::new(-1., -1., 1.));
+        assert_near(points.nth(1), Vec3::new(1., -1., 1.));
+        assert_near(points.next(), Vec3::new(1., 0., 1.));
+        assert_near(points.last(), Vec3::new(1., 1., 1.));
+    }
+
+    #[test]
+    fn test_arc_endpoints() {
+        let shape = arc(5);
+        assert_near(shape.points().next(), Vec3::X);
+        assert_near(shape.points().last(), Vec3::Z);
+    }
+}
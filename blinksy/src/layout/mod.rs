@@ -11,6 +11,9 @@
 //! `.points()`. method, which maps each LED pixel into a 1D, 2D, or 3D space between -1.0 and
 //! 1.0.
 //!
+//! The layout macros can be used inside functions, and can define layout types with const
+//! generic parameters, e.g. `layout1d!(Layout<const PIXEL_COUNT: usize>, PIXEL_COUNT)`.
+//!
 //! ## 1D Layouts
 //!
 //! For simple linear arrangements, use the [`layout1d!`] macro:
@@ -47,6 +50,22 @@
 //!
 //! For 3D layouts, use the [`layout3d!`] macro with one or more [`Shape3d`] definitions.
 //!
+//! For irregular installations, e.g. mapped with a camera, list the position of each LED with
+//! [`Shape2d::Points`] or [`Shape3d::Points`].
+//!
+//! ## Segments
+//!
+//! To use a contiguous segment of a layout as its own layout, use [`SubLayout`]:
+//!
+//! ```rust
+//! use blinksy::{layout::SubLayout, layout1d};
+//!
+//! layout1d!(Layout, 60);
+//!
+//! // The last 20 LEDs of the strip
+//! type Segment = SubLayout<Layout, 40, 20>;
+//! ```
+//!
 //! [`layout1d!`]: crate::layout1d!
 //! [`layout2d!`]: crate::layout2d!
 //! [`layout3d!`]: crate::layout3d!
@@ -57,11 +76,13 @@ mod iterators;
 mod layout1d;
 mod layout2d;
 mod layout3d;
+mod sub_layout;
 
 pub use iterators::*;
 pub use layout1d::*;
 pub use layout2d::*;
 pub use layout3d::*;
+pub use sub_layout::*;
 
 /// Trait for associating layout types with dimension markers.
 ///
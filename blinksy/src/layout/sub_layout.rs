@@ -0,0 +1,106 @@
+use core::marker::PhantomData;
+
+use super::{Layout1d, Layout2d, Layout3d, Shape2d, Shape3d, Vec2, Vec3};
+
+/// A contiguous segment of a parent layout, from pixel `START` with `LEN` pixels.
+///
+/// The segment is defined at compile time, so each segment of one physical strip can have its
+/// own [`Control`](crate::control::Control) and pattern, e.g. with a driver which writes each
+/// segment at its offset of the strip.
+///
+/// Positions are recomputed for the segment:
+///
+/// - In 1D, the segment spans the whole 1D space, from -1.0 to 1.0, as its own strip.
+/// - In 2D and 3D, each pixel keeps its position in the parent layout.
+///
+/// Using a segment which doesn't fit in the parent layout is a compile error.
+///
+/// # Type Parameters
+///
+/// - `Layout` - The parent layout
+/// - `START` - The index of the first pixel of the segment in the parent layout
+/// - `LEN` - The number of pixels in the segment
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{
+///     layout::{Layout1d, Layout2d, Shape2d, SubLayout, Vec2},
+///     layout1d, layout2d,
+/// };
+///
+/// layout1d!(Strip, 60);
+///
+/// // The first 20 pixels are the stairs, and the rest is the landing
+/// type Stairs = SubLayout<Strip, 0, 20>;
+/// type Landing = SubLayout<Strip, 20, 40>;
+///
+/// assert_eq!(Stairs::PIXEL_COUNT, 20);
+/// assert_eq!(Landing::points().next(), Some(-1.));
+/// assert_eq!(Landing::points().last(), Some(1.));
+///
+/// layout2d!(
+///     Panel,
+///     [Shape2d::Grid {
+///         start: Vec2::new(-1., -1.),
+///         horizontal_end: Vec2::new(1., -1.),
+///         vertical_end: Vec2::new(-1., 1.),
+///         horizontal_pixel_count: 4,
+///         vertical_pixel_count: 4,
+///         serpentine: false,
+///     }]
+/// );
+///
+/// // The bottom row of the panel
+/// type BottomRow = SubLayout<Panel, 0, 4>;
+///
+/// assert!(BottomRow::points().eq(Panel::points().take(4)));
+/// ```
+pub struct SubLayout<Layout, const START: usize, const LEN: usize> {
+    layout: PhantomData<Layout>,
+}
+
+impl<Layout, const START: usize, const LEN: usize> SubLayout<Layout, START, LEN> {
+    /// Checks the segment fits in a parent layout with the given number of pixels.
+    const fn checked_len(parent_pixel_count: usize) -> usize {
+        assert!(
+            START + LEN <= parent_pixel_count,
+            "SubLayout segment doesn't fit in its parent layout"
+        );
+        LEN
+    }
+}
+
+impl<Layout: Layout1d, const START: usize, const LEN: usize> Layout1d
+    for SubLayout<Layout, START, LEN>
+{
+    const PIXEL_COUNT: usize = Self::checked_len(Layout::PIXEL_COUNT);
+}
+
+impl<Layout: Layout2d, const START: usize, const LEN: usize> Layout2d
+    for SubLayout<Layout, START, LEN>
+{
+    const PIXEL_COUNT: usize = Self::checked_len(Layout::PIXEL_COUNT);
+
+    fn shapes() -> impl Iterator<Item = Shape2d> {
+        Self::points().map(Shape2d::Point)
+    }
+
+    fn points() -> impl Iterator<Item = Vec2> {
+        Layout::points().skip(START).take(Self::PIXEL_COUNT)
+    }
+}
+
+impl<Layout: Layout3d, const START: usize, const LEN: usize> Layout3d
+    for SubLayout<Layout, START, LEN>
+{
+    const PIXEL_COUNT: usize = Self::checked_len(Layout::PIXEL_COUNT);
+
+    fn shapes() -> impl Iterator<Item = Shape3d> {
+        Self::points().map(Shape3d::Point)
+    }
+
+    fn points() -> impl Iterator<Item = Vec3> {
+        Layout::points().skip(START).take(Self::PIXEL_COUNT)
+    }
+}
@@ -1,8 +1,9 @@
-use core::iter::repeat_n;
+use core::{iter::repeat_n, marker::PhantomData};
 
 use crate::{
-    color::{ColorCorrection, LinearSrgb, RgbChannels},
-    driver::clocked::ClockedLed,
+    color::{apply_output_pipeline, ColorCorrection, LinearSrgb, RgbChannels},
+    driver::{clocked::ClockedLed, GlobalBrightness},
+    power::CurrentDraw,
     util::component::Component,
 };
 
@@ -22,13 +23,93 @@ use crate::{
 /// - 5-bit global brightness control (0-31)
 /// - Supports high update rates (Bring-your-own clock rate)
 ///
-/// This implementation includes the "High Definition" color handling from FastLED, which
-/// optimizes the use of the 5-bit brightness and 8-bit per-channel values.
+/// ## Brightness
+///
+/// How global brightness is split between the 5-bit brightness and the 8-bit channels is
+/// chosen with the `Brightness` type parameter:
+///
+/// - [`Apa102`] ([`HdBrightness`]): The "High Definition" color handling from FastLED, which
+///   optimizes the use of the 5-bit brightness and 8-bit per-channel values.
+/// - [`Apa102Fixed`] ([`FixedBrightness`]): A fixed 5-bit brightness of 31, with brightness
+///   scaling the 8-bit channels.
+/// - [`Apa102Direct`] ([`DirectBrightness`]): Brightness maps directly to the 5-bit
+///   brightness, with unscaled 8-bit channels.
+///
+/// The 5-bit brightness is applied by a slower PWM than the channels, which can flicker on
+/// camera. For filming, choose [`Apa102Fixed`], so the 5-bit brightness (and its PWM depth)
+/// never changes, or lock it at runtime with
+/// [`Control::set_global_brightness`](crate::control::Control::set_global_brightness), which
+/// overrides the `Brightness` type parameter while locked.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Apa102Generic<Brightness: Apa102Brightness>(PhantomData<Brightness>);
+
+/// APA102 LEDs, with the "High Definition" brightness from FastLED.
+pub type Apa102 = Apa102Generic<HdBrightness>;
+
+/// APA102 LEDs, with a fixed 5-bit brightness.
+pub type Apa102Fixed = Apa102Generic<FixedBrightness>;
+
+/// APA102 LEDs, with brightness mapped directly to the 5-bit brightness.
+pub type Apa102Direct = Apa102Generic<DirectBrightness>;
+
+/// Trait for how an APA102 splits global brightness between its 5-bit brightness and its
+/// 8-bit channels.
+pub trait Apa102Brightness {
+    /// Encodes the channels and 5-bit brightness of an LED.
+    ///
+    /// # Arguments
+    ///
+    /// - `rgb` - The color corrected [R, G, B] channels, as 16-bit values
+    /// - `brightness` - Global brightness scaling factor (0.0 to 1.0)
+    ///
+    /// # Returns
+    ///
+    /// The [R, G, B] channels as 8-bit values, and the 5-bit brightness
+    fn encode(rgb: [u16; 3], brightness: f32) -> ([u8; 3], u8);
+}
+
+/// The "High Definition" brightness from FastLED, which "steals" bits between the 5-bit
+/// brightness and the 8-bit channels to keep as much precision as possible.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HdBrightness;
+
+impl Apa102Brightness for HdBrightness {
+    fn encode([red, green, blue]: [u16; 3], brightness: f32) -> ([u8; 3], u8) {
+        let brightness: u8 = Component::from_normalized_f32(brightness);
+        let ((red, green, blue), brightness) = five_bit_bitshift(red, green, blue, brightness);
+        ([red, green, blue], brightness)
+    }
+}
+
+/// A fixed 5-bit brightness of 31, with global brightness scaling the 8-bit channels.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FixedBrightness;
+
+impl Apa102Brightness for FixedBrightness {
+    fn encode(rgb: [u16; 3], brightness: f32) -> ([u8; 3], u8) {
+        let scale = |channel: u16| -> u8 {
+            Component::from_normalized_f32(channel.to_normalized_f32() * brightness)
+        };
+        (rgb.map(scale), 31)
+    }
+}
+
+/// Global brightness mapped directly to the 5-bit brightness, with unscaled 8-bit channels.
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct Apa102;
+pub struct DirectBrightness;
+
+impl Apa102Brightness for DirectBrightness {
+    fn encode(rgb: [u16; 3], brightness: f32) -> ([u8; 3], u8) {
+        let brightness = (brightness.clamp(0., 1.) * 31.) as u8;
+        (rgb.map(map16_to_8), brightness)
+    }
+}
 
-impl Apa102 {
+impl<Brightness: Apa102Brightness> Apa102Generic<Brightness> {
     /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
     ///
     /// ```rust,ignore
@@ -53,10 +134,13 @@ impl Apa102 {
 /// 3. End frame: (n/2) bits of zeros where n is the number of LEDs
 ///
 /// (References: [Hackaday](https://hackaday.com/2014/12/09/digging-into-the-apa102-serial-led-protocol/), [Pololu](https://www.pololu.com/product/2554))
-impl ClockedLed for Apa102 {
+impl<Brightness: Apa102Brightness> ClockedLed for Apa102Generic<Brightness> {
     type Word = u8;
     type Color = LinearSrgb;
 
+    // At 5V, ~20mA per channel at full, ~1mA at idle
+    const CURRENT_DRAW: CurrentDraw = CurrentDraw::new(20., 1.);
+
     fn start() -> impl IntoIterator<Item = Self::Word> {
         [0x00, 0x00, 0x00, 0x00]
     }
@@ -66,12 +150,22 @@ impl ClockedLed for Apa102 {
         brightness: f32,
         correction: ColorCorrection,
     ) -> impl IntoIterator<Item = Self::Word> {
-        let (red, green, blue) = (linear_rgb.red, linear_rgb.green, linear_rgb.blue);
+        Self::led_with_global_brightness(
+            linear_rgb,
+            brightness,
+            correction,
+            GlobalBrightness::Adaptive,
+        )
+    }
 
-        // Color correct
-        let red = red * correction.red;
-        let green = green * correction.green;
-        let blue = blue * correction.blue;
+    fn led_with_global_brightness(
+        linear_rgb: LinearSrgb,
+        brightness: f32,
+        correction: ColorCorrection,
+        global_brightness: GlobalBrightness,
+    ) -> impl IntoIterator<Item = Self::Word> {
+        // Color correct, with brightness applied by the brightness encoding
+        let LinearSrgb { red, green, blue } = apply_output_pipeline(linear_rgb, 1., correction);
 
         // Convert color components to u16's
         let (red_u16, green_u16, blue_u16) = (
@@ -80,10 +174,11 @@ impl ClockedLed for Apa102 {
             Component::from_normalized_f32(blue),
         );
 
-        // Continue with APA102HD algorithm from FastLED
-        let brightness: u8 = Component::from_normalized_f32(brightness);
-        let ((red_u8, green_u8, blue_u8), brightness) =
-            five_bit_bitshift(red_u16, green_u16, blue_u16, brightness);
+        let rgb = [red_u16, green_u16, blue_u16];
+        let ([red_u8, green_u8, blue_u8], brightness) = match global_brightness.locked_level() {
+            Some(level) => locked_encode(rgb, brightness, level),
+            None => Brightness::encode(rgb, brightness),
+        };
 
         let brightness_byte = 0b11100000 | (brightness & 0b00011111);
         let led_bytes = RgbChannels::BGR.reorder([red_u8, green_u8, blue_u8]);
@@ -97,6 +192,16 @@ impl ClockedLed for Apa102 {
     }
 }
 
+/// Encodes the channels with a locked 5-bit brightness, scaling the 8-bit channels to make up
+/// the rest of the brightness.
+fn locked_encode(rgb: [u16; 3], brightness: f32, level: u8) -> ([u8; 3], u8) {
+    let scale = brightness * f32::from(GlobalBrightness::MAX_LEVEL) / f32::from(level);
+    let scale = |channel: u16| -> u8 {
+        Component::from_normalized_f32((channel.to_normalized_f32() * scale).min(1.))
+    };
+    (rgb.map(scale), level)
+}
+
 /// Implements the core APA102HD "bitshift" routine. It takes 16‑bit color channels and an 8‑bit global
 /// brightness value, then "steals" brightness bits from the color channels into a 5‑bit driver brightness.
 ///
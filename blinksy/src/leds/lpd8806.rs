@@ -1,8 +1,9 @@
 use core::iter::repeat_n;
 
 use crate::{
-    color::{ColorCorrection, LinearSrgb, RgbChannels},
-    driver::clocked::ClockedLed,
+    color::{apply_output_pipeline, ColorCorrection, LinearSrgb, RgbChannels},
+    driver::{clocked::ClockedLed, ColorCapability},
+    power::CurrentDraw,
     util::component::Component,
 };
 
@@ -44,6 +45,12 @@ impl<O: Lpd8806Order> ClockedLed for Lpd8806Generic<O> {
     type Word = u8;
     type Color = LinearSrgb;
 
+    // The high bit of each channel byte is always set
+    const COLOR_CAPABILITY: ColorCapability = ColorCapability::new(7, false);
+
+    // At 5V, ~20mA per channel at full, ~1mA at idle
+    const CURRENT_DRAW: CurrentDraw = CurrentDraw::new(20., 1.);
+
     fn start() -> impl IntoIterator<Item = Self::Word> {
         [0x00, 0x00, 0x00, 0x00]
     }
@@ -53,11 +60,11 @@ impl<O: Lpd8806Order> ClockedLed for Lpd8806Generic<O> {
         _brightness: f32,
         correction: ColorCorrection,
     ) -> impl IntoIterator<Item = Self::Word> {
-        let (r, g, b) = (linear_rgb.red, linear_rgb.green, linear_rgb.blue);
-
-        let r = r * correction.red;
-        let g = g * correction.green;
-        let b = b * correction.blue;
+        let LinearSrgb {
+            red: r,
+            green: g,
+            blue: b,
+        } = apply_output_pipeline(linear_rgb, 1., correction);
 
         let (r16, g16, b16) = (
             Component::from_normalized_f32(r),
@@ -66,16 +73,18 @@ impl<O: Lpd8806Order> ClockedLed for Lpd8806Generic<O> {
         );
 
         let to_7bit = |x: u16| -> u8 {
-            let mut v = if x == 0 { 0 } else if x >= 0xff00 { 0xff } else { ((x + 128) >> 8) as u8 };
+            let mut v = if x == 0 {
+                0
+            } else if x >= 0xff00 {
+                0xff
+            } else {
+                ((x + 128) >> 8) as u8
+            };
             v >>= 1;
             0x80 | v
         };
 
-        let bytes = O::reorder([
-            to_7bit(r16),
-            to_7bit(g16),
-            to_7bit(b16),
-        ]);
+        let bytes = O::reorder([to_7bit(r16), to_7bit(g16), to_7bit(b16)]);
 
         [bytes[0], bytes[1], bytes[2]]
     }
@@ -1,24 +1,52 @@
 //! # LED Chipsets
 //!
-//! - [`Apa102`]: APA102 (DotStar) LEDs
+//! - [`Apa102`]: APA102 (DotStar) LEDs (and [`Apa102Fixed`] and [`Apa102Direct`], see [brightness](Apa102Generic#brightness))
 //! - [`Ws2812`]: WS2812 (NeoPixel) LEDs
+//! - [`Ws2813`]: WS2813 LEDs
+//! - [`Ws2815`]: WS2815 (12V) LEDs
+//! - [`Ws2816`]: WS2816 (16-bit) LEDs
 //! - [`Sk6812`]: SK6812 LEDs
 //! - [`Lpd8806`]: LPD8806 LEDs
+//! - [`P9813`]: P9813 LEDs
+//! - [`Ws2801`]: WS2801 LEDs
 //!
 //! If you want help to support a new chipset, [make an issue](https://github.com/ahdinosaur/blinksy/issues)!
 
 mod apa102;
+mod lpd8806;
+mod p9813;
 mod sk6812;
+mod ws2801;
 mod ws2812;
-mod lpd8806;
+mod ws2813;
+mod ws2815;
+mod ws2816;
 
-pub use apa102::Apa102;
+pub use apa102::{
+    Apa102, Apa102Brightness, Apa102Direct, Apa102Fixed, Apa102Generic, DirectBrightness,
+    FixedBrightness, HdBrightness,
+};
+pub use lpd8806::{Lpd8806, Lpd8806Brg};
+pub use p9813::P9813;
 pub use sk6812::Sk6812;
+pub use ws2801::Ws2801;
 pub use ws2812::Ws2812;
-pub use lpd8806::{Lpd8806, Lpd8806Brg};
+pub use ws2813::Ws2813;
+pub use ws2815::Ws2815;
+pub use ws2816::Ws2816;
 
-use crate::driver::ClocklessLed;
+use crate::driver::{
+    clocked::{end_frame_padding, led_byte_count},
+    ClocklessLed, SimpleClockedLed,
+};
 
 pub const fn clockless_frame_buffer_size<Led: ClocklessLed>(pixel_count: usize) -> usize {
     pixel_count * Led::LED_CHANNELS.channel_count()
 }
+
+pub const fn simple_clocked_frame_buffer_size<Led: SimpleClockedLed>(pixel_count: usize) -> usize {
+    Led::START_FRAME.len()
+        + pixel_count * led_byte_count::<Led>()
+        + Led::END_FRAME.len()
+        + end_frame_padding(pixel_count, Led::END_FRAME_PIXELS_PER_BYTE)
+}
@@ -0,0 +1,78 @@
+use crate::{
+    color::RgbChannels,
+    driver::{Bits, SimpleClockedLed},
+    power::CurrentDraw,
+};
+
+/// # P9813 LEDs
+///
+/// This type describes the P9813 LED driver chip, common in "Total Control Lighting" pixels
+/// and Grove chainable RGB LEDs.
+///
+/// ## Driver
+///
+/// - [`ClockedDriver`](crate::driver::ClockedDriver)
+///
+/// ## Key Features
+///
+/// - Two-wire [clocked protocol](crate::driver::clocked) (data and clock)
+/// - 24-bit color (8 bits per channel)
+/// - Each LED frame starts with a checksum of its color
+///
+/// ## Protocol Details
+///
+/// The P9813 protocol consists of:
+///
+/// 1. Start frame: 32 bits of zeros
+/// 2. LED frames: Each LED gets 32 bits (8-bit flag, 8-bit blue, 8-bit green, 8-bit red),
+///    where the flag is `0b11`, then the inverted top 2 bits of blue, green, and red
+/// 3. End frame: 32 bits of zeros
+///
+/// ```rust
+/// use blinksy::{
+///     color::{ColorCorrection, LinearSrgb},
+///     driver::ClockedLed,
+///     leds::P9813,
+/// };
+///
+/// let bytes: Vec<u8> = P9813::led(LinearSrgb::new(1., 0., 0.), 1., ColorCorrection::default())
+///     .into_iter()
+///     .collect();
+/// assert_eq!(bytes, [0b1111_1100, 0x00, 0x00, 0xff]);
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct P9813;
+
+impl P9813 {
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    ///
+    /// ```rust,ignore
+    /// layout1d!(Layout, 60);
+    ///
+    /// let mut control = ControlBuilder::new_1d()
+    ///   // ...
+    ///   .with_frame_buffer_size::<{ P9813::frame_buffer_size(Layout::PIXEL_COUNT) }>()
+    ///   .build();
+    /// ```
+    pub const fn frame_buffer_size(pixel_count: usize) -> usize {
+        super::simple_clocked_frame_buffer_size::<Self>(pixel_count)
+    }
+}
+
+impl SimpleClockedLed for P9813 {
+    const START_FRAME: &'static [u8] = &[0x00, 0x00, 0x00, 0x00];
+    const END_FRAME: &'static [u8] = &[0x00, 0x00, 0x00, 0x00];
+    const RGB_CHANNELS: RgbChannels = RgbChannels::BGR;
+    const LED_HEADER: Bits = Bits::new(0b1100_0000, 8);
+    // At 5V, ~20mA per channel at full, ~2mA at idle
+    const CURRENT_DRAW: CurrentDraw = CurrentDraw::new(20., 2.);
+
+    fn led_header([red, green, blue]: [u32; 3]) -> Bits {
+        let flag = 0b1100_0000
+            | ((!blue >> 6) & 0b11) << 4
+            | ((!green >> 6) & 0b11) << 2
+            | ((!red >> 6) & 0b11);
+        Bits::new(flag, 8)
+    }
+}
@@ -1,6 +1,6 @@
 use fugit::NanosDurationU32 as Nanoseconds;
 
-use crate::{color::LedChannels, driver::ClocklessLed};
+use crate::{color::LedChannels, driver::ClocklessLed, power::CurrentDraw};
 
 /// # SK6812 LEDs
 ///
@@ -63,4 +63,7 @@ impl ClocklessLed for Sk6812 {
 
     /// LED channel specification - SK6812 uses RGBW ordering
     const LED_CHANNELS: LedChannels = LedChannels::Rgbw(crate::color::RgbwChannels::RBGW);
+
+    /// Current draw - at 5V, ~18mA per RGB channel and ~20mA for white at full, ~1mA at idle
+    const CURRENT_DRAW: CurrentDraw = CurrentDraw::new(18., 1.).with_white(20.);
 }
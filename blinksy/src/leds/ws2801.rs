@@ -0,0 +1,82 @@
+use crate::{
+    color::RgbChannels,
+    driver::SimpleClockedLed,
+    power::CurrentDraw,
+    time::{Megahertz, Nanoseconds},
+};
+
+/// # WS2801 LEDs
+///
+/// This type describes the WS2801 LED driver chip, common in older LED strips and pixels.
+///
+/// Despite its name, the WS2801 is not like the clockless WS2812: it has a data and a clock
+/// line.
+///
+/// ## Driver
+///
+/// - [`ClockedDriver`](crate::driver::ClockedDriver)
+///
+/// ## Key Features
+///
+/// - Two-wire [clocked protocol](crate::driver::clocked) (data and clock)
+/// - 24-bit color (8 bits per channel)
+/// - No start or end frame
+///
+/// ## Protocol Details
+///
+/// The WS2801 protocol consists of:
+///
+/// 1. LED frames: Each LED gets 24 bits (8-bit red, 8-bit green, 8-bit blue)
+/// 2. Latch: the clock is held low for at least [`LATCH_TIME`](Self::LATCH_TIME), until the
+///    next frame
+///
+/// ## Timing
+///
+/// The chip is rated for up to 25 MHz, but long or legacy strips are only reliable at much
+/// lower data rates: use at most [`MAX_DATA_RATE`](Self::MAX_DATA_RATE), or as low as 500 kHz
+/// if the strip flickers.
+///
+/// ```rust
+/// use blinksy::{
+///     color::{ColorCorrection, LinearSrgb},
+///     driver::ClockedLed,
+///     leds::Ws2801,
+/// };
+///
+/// let bytes: Vec<u8> = Ws2801::led(LinearSrgb::new(1., 0., 0.), 1., ColorCorrection::default())
+///     .into_iter()
+///     .collect();
+/// assert_eq!(bytes, [0xff, 0x00, 0x00]);
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Ws2801;
+
+impl Ws2801 {
+    /// The highest data rate to use for most strips.
+    pub const MAX_DATA_RATE: Megahertz = Megahertz::MHz(1);
+
+    /// The time the clock is held low to latch a frame.
+    pub const LATCH_TIME: Nanoseconds = Nanoseconds::micros(500);
+
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    ///
+    /// ```rust,ignore
+    /// layout1d!(Layout, 60);
+    ///
+    /// let mut control = ControlBuilder::new_1d()
+    ///   // ...
+    ///   .with_frame_buffer_size::<{ Ws2801::frame_buffer_size(Layout::PIXEL_COUNT) }>()
+    ///   .build();
+    /// ```
+    pub const fn frame_buffer_size(pixel_count: usize) -> usize {
+        super::simple_clocked_frame_buffer_size::<Self>(pixel_count)
+    }
+}
+
+impl SimpleClockedLed for Ws2801 {
+    const START_FRAME: &'static [u8] = &[];
+    const RGB_CHANNELS: RgbChannels = RgbChannels::RGB;
+    // At 5V, ~20mA per channel at full, ~2mA at idle
+    const CURRENT_DRAW: CurrentDraw = CurrentDraw::new(20., 2.);
+}
@@ -3,6 +3,7 @@ use fugit::NanosDurationU32 as Nanoseconds;
 use crate::{
     color::{LedChannels, RgbChannels},
     driver::ClocklessLed,
+    power::CurrentDraw,
 };
 
 /// # WS2812 (NeoPixel) LEDs
@@ -69,4 +70,7 @@ impl ClocklessLed for Ws2812 {
 
     /// LED channel specification - WS2812 uses GRB ordering
     const LED_CHANNELS: LedChannels = LedChannels::Rgb(RgbChannels::GRB);
+
+    /// Current draw - at 5V, ~20mA per channel at full, ~1mA at idle
+    const CURRENT_DRAW: CurrentDraw = CurrentDraw::new(20., 1.);
 }
@@ -0,0 +1,79 @@
+use fugit::NanosDurationU32 as Nanoseconds;
+
+use crate::{
+    color::{LedChannels, RgbChannels},
+    driver::ClocklessLed,
+    power::CurrentDraw,
+};
+
+/// # WS2816 LEDs
+///
+/// This type describes the WS2816 LEDs, similar to [`super::Ws2813`] but with 16-bit channels,
+/// for smooth fades at low brightness.
+///
+/// # Driver
+///
+/// - [`ClocklessDriver`](crate::driver::ClocklessDriver)
+///
+/// ## Key Features
+///
+/// - Single-wire [clockless protocol](crate::driver::clockless) (data only, no clock)
+/// - 48-bit color (16 bits per channel, 3 channels)
+/// - Backup data line (wired by the strip, nothing to configure)
+/// - Longer reset period than WS2812: 280µs
+pub struct Ws2816;
+
+impl Ws2816 {
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    ///
+    /// ```rust,ignore
+    /// layout1d!(Layout, 60);
+    ///
+    /// let mut control = ControlBuilder::new_1d()
+    ///   // ...
+    ///   .with_frame_buffer_size::<{ Ws2816::frame_buffer_size(Layout::PIXEL_COUNT) }>()
+    ///   .build();
+    /// ```
+    pub const fn frame_buffer_size(pixel_count: usize) -> usize {
+        super::clockless_frame_buffer_size::<Self>(pixel_count)
+    }
+}
+
+/// ## Protocol Details
+///
+/// The WS2816 protocol uses precise timing of pulses on a single data line:
+///
+/// - A '0' bit is represented by a short high pulse (~300ns) followed by a long low pulse (~800ns)
+/// - A '1' bit is represented by a long high pulse (~750ns) followed by a short low pulse (~350ns)
+/// - After sending all bits, a reset pulse of at least 280µs is required
+///
+/// (References: Worldsemi WS2816 datasheet)
+///
+/// Each LED receives 48 bits (GRB) and then passes subsequent data to the next LED in the chain.
+///
+/// The reset period is much longer than for [`super::Ws2812`], which with the WS2812 profile
+/// glitches the first frame.
+impl ClocklessLed for Ws2816 {
+    type Word = u16;
+
+    /// Duration of high signal for '0' bit (~300ns)
+    const T_0H: Nanoseconds = Nanoseconds::nanos(300);
+
+    /// Duration of low signal for '0' bit (~800ns)
+    const T_0L: Nanoseconds = Nanoseconds::nanos(800);
+
+    /// Duration of high signal for '1' bit (~750ns)
+    const T_1H: Nanoseconds = Nanoseconds::nanos(750);
+
+    /// Duration of low signal for '1' bit (~350ns)
+    const T_1L: Nanoseconds = Nanoseconds::nanos(350);
+
+    /// Reset period (>280µs) - signals the end of a data stream
+    const T_RESET: Nanoseconds = Nanoseconds::micros(280);
+
+    /// LED channel specification - WS2816 uses GRB ordering
+    const LED_CHANNELS: LedChannels = LedChannels::Rgb(RgbChannels::GRB);
+
+    /// Current draw - at 5V, ~12mA per channel at full (constant current), ~1mA at idle
+    const CURRENT_DRAW: CurrentDraw = CurrentDraw::new(12., 1.);
+}
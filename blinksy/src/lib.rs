@@ -399,15 +399,42 @@
 //! ```
 //!
 
+pub mod ambient;
+pub mod arbiter;
+pub mod audio;
+pub mod blur;
+pub mod bridge;
+pub mod capacities;
 pub mod color;
+pub mod compositor;
+pub mod config;
 pub mod control;
 pub mod driver;
+pub mod group;
+pub mod input;
 pub mod layout;
 pub mod leds;
 pub mod markers;
 pub mod pattern;
 pub mod patterns;
+#[cfg(feature = "embassy")]
+pub mod pipeline;
+pub mod power;
+pub mod presence;
+pub mod projection;
+pub mod registry;
+pub mod remap;
+pub mod replicate;
+pub mod serial;
+pub mod switch;
+pub mod testing;
 pub mod time;
 pub mod util;
 
 pub use self::control::*;
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
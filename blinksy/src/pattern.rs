@@ -13,11 +13,21 @@
 //!
 //! - A sequence of colors for each LED in the layout
 //!
+//! A pattern may also match its output to the driver's [`ColorCapability`], see
+//! [`Pattern::set_color_capability`].
+//!
 //! For the library of built-in patterns, see [patterns](crate::patterns).
 //!
+//! Pattern parameters may implement [`PatternParams`], so user interfaces (HTTP, MQTT,
+//! desktop, ...) can list and change parameters without knowing the specific pattern. With the
+//! `derive` feature, `#[derive(PatternParams)]` implements it from the field attributes.
+//!
 //! [a WLED effect]: https://kno.wled.ge/features/effects/
 
-use crate::layout::LayoutForDim;
+#[cfg(feature = "derive")]
+pub use blinksy_derive::PatternParams;
+
+use crate::{driver::ColorCapability, layout::LayoutForDim};
 
 /// Trait for creating visual effects on LED layouts.
 ///
@@ -94,4 +104,190 @@ where
     ///
     /// An iterator yielding one color per LED in the layout
     fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color>;
+
+    /// Returns the parameters of this pattern, if they can be changed at runtime.
+    ///
+    /// By default, parameters are not exposed.
+    fn params(&self) -> Option<&Self::Params> {
+        None
+    }
+
+    /// Returns the parameters of this pattern mutably, if they can be changed at runtime.
+    ///
+    /// By default, parameters are not exposed.
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        None
+    }
+
+    /// Tells the pattern the [color capability](crate::driver::capability) of the driver, so it
+    /// can choose its color space and precision to match, e.g. render finer gradients for
+    /// 16-bit LEDs.
+    ///
+    /// [`Control`](crate::control::Control) calls this before the first frame, and again
+    /// after the pattern is replaced. By default, this does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// - `capability` - The native color depth and space of the driver's LEDs
+    fn set_color_capability(&mut self, _capability: ColorCapability) {}
+}
+
+/// Description of a single pattern parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ParamInfo {
+    /// Name of the parameter, matching the field name
+    pub name: &'static str,
+    /// Minimum value (inclusive)
+    pub min: f32,
+    /// Maximum value (inclusive)
+    pub max: f32,
+}
+
+/// Errors when setting a pattern parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ParamError {
+    /// No parameter exists at the given index
+    UnknownIndex,
+    /// The value is outside of the parameter's range
+    OutOfRange,
+}
+
+/// Trait for reflecting over pattern parameters.
+///
+/// Exposes each parameter's name and range, with getters and setters by index, so
+/// parameters can be listed and changed generically.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{pattern::PatternParams, patterns::rainbow::RainbowParams};
+///
+/// let mut params = RainbowParams::default();
+///
+/// for (index, info) in RainbowParams::PARAMS.iter().enumerate() {
+///     let value = params.get_param(index).unwrap();
+///     assert!(value >= info.min && value <= info.max);
+/// }
+///
+/// params.set_param(1, 2.).unwrap();
+/// assert_eq!(params.position_scalar, 2.);
+/// ```
+pub trait PatternParams {
+    /// Descriptions of each parameter, in index order.
+    const PARAMS: &'static [ParamInfo];
+
+    /// Gets the value of the parameter at the index.
+    ///
+    /// # Returns
+    ///
+    /// The value, or `None` if no parameter exists at the index
+    fn get_param(&self, index: usize) -> Option<f32>;
+
+    /// Sets the value of the parameter at the index.
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success, or an error if the index is unknown or the value is
+    /// out of range
+    fn set_param(&mut self, index: usize, value: f32) -> Result<(), ParamError>;
+
+    /// Finds the index of a parameter by name.
+    fn param_index(name: &str) -> Option<usize> {
+        Self::PARAMS.iter().position(|info| info.name == name)
+    }
+}
+
+/// Checks a value against the range of a parameter.
+pub(crate) fn check_param<P: PatternParams>(index: usize, value: f32) -> Result<(), ParamError> {
+    let info = P::PARAMS.get(index).ok_or(ParamError::UnknownIndex)?;
+    if !(info.min..=info.max).contains(&value) {
+        return Err(ParamError::OutOfRange);
+    }
+    Ok(())
+}
+
+/// Implements serde for derived [`PatternParams`], with missing parameters defaulted and out of
+/// range parameters clamped.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pattern_params_serde {
+    ($serde_crate:literal; $name:ident { $($field:ident: $range:expr),* $(,)? }) => {
+        const _: () = {
+            use $crate::__private::serde;
+
+            impl serde::Serialize for $name {
+                fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    use serde::ser::SerializeStruct;
+
+                    let len = 0 $(+ { let _ = stringify!($field); 1 })*;
+                    let mut state = serializer.serialize_struct(stringify!($name), len)?;
+                    $(state.serialize_field(stringify!($field), &self.$field)?;)*
+                    state.end()
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $name {
+                fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    #[derive(serde::Deserialize)]
+                    #[serde(crate = $serde_crate)]
+                    #[serde(rename = "Params")]
+                    struct Partial {
+                        $($field: ::core::option::Option<f32>,)*
+                    }
+
+                    let partial = <Partial as serde::Deserialize>::deserialize(deserializer)?;
+                    let mut params = <Self as ::core::default::Default>::default();
+                    $(
+                        if let ::core::option::Option::Some(value) = partial.$field {
+                            if !value.is_nan() {
+                                let range = $range;
+                                params.$field = value.clamp(*range.start(), *range.end());
+                            }
+                        }
+                    )*
+                    ::core::result::Result::Ok(params)
+                }
+            }
+        };
+    };
+}
+
+/// Implements serde for derived [`PatternParams`], without the `serde` feature: nothing.
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pattern_params_serde {
+    ($($tokens:tt)*) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::white::WhiteParams;
+
+    #[test]
+    fn check_param_rejects_out_of_range_and_nan() {
+        assert_eq!(check_param::<WhiteParams>(1, 0.5), Ok(()));
+        assert_eq!(
+            check_param::<WhiteParams>(1, 1.5),
+            Err(ParamError::OutOfRange)
+        );
+        assert_eq!(
+            check_param::<WhiteParams>(1, f32::NAN),
+            Err(ParamError::OutOfRange)
+        );
+        assert_eq!(
+            check_param::<WhiteParams>(9, 0.5),
+            Err(ParamError::UnknownIndex)
+        );
+    }
 }
@@ -0,0 +1,488 @@
+//! # FSEQ Pattern
+//!
+//! The FSEQ pattern plays back a pre-rendered show from an [xLights] FSEQ sequence file, so
+//! shows designed on a computer can run standalone on a microcontroller.
+//!
+//! The sequence is streamed, one frame at a time, from any source implementing
+//! [`embedded_io::Read`] and [`embedded_io::Seek`], such as a file on an SD card opened with
+//! [`embedded-sdmmc`].
+//!
+//! Supported are FSEQ versions 1 and 2, uncompressed and without sparse ranges. (In xLights,
+//! export with "Uncompressed" as the FSEQ compression.)
+//!
+//! Each pixel is read as 3 consecutive channels (red, green, blue), starting from a chosen
+//! channel. Missing or unreadable channels are shown as black.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::LinearSrgb,
+//!     driver::Driver,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::fseq::{Fseq, FseqParams},
+//!     ControlBuilder,
+//! };
+//! use embedded_io::{Read, Seek};
+//!
+//! layout1d!(Layout, 60);
+//!
+//! // The source is e.g. a file opened with `embedded-sdmmc`
+//! fn run<Source, Leds>(file: Source, driver: Leds, time_in_ms: u64) -> Result<(), Leds::Error>
+//! where
+//!     Source: Read + Seek,
+//!     Leds: Driver<Color = LinearSrgb>,
+//!     Leds::Word: Clone,
+//! {
+//!     let mut control = ControlBuilder::new_1d()
+//!         .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!         .with_pattern::<Fseq<Source>>(FseqParams {
+//!             source: file,
+//!             start_channel: 0,
+//!         })
+//!         .with_driver(driver)
+//!         .with_frame_buffer_size::<0>()
+//!         .build();
+//!
+//!     // Tick the control in your loop
+//!     control.tick(time_in_ms)
+//! }
+//! ```
+//!
+//! [xLights]: https://xlights.org/
+//! [`embedded-sdmmc`]: https://docs.rs/embedded-sdmmc
+
+use core::cell::{RefCell, RefMut};
+
+use embedded_io::{Read, ReadExactError, Seek, SeekFrom};
+
+use crate::{
+    color::Srgb,
+    layout::{Layout1d, Layout2d, Layout3d},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern,
+};
+
+/// Length of the fixed header at the start of every FSEQ file.
+const HEADER_SIZE: usize = 28;
+
+/// Number of bytes read from the source at a time.
+const CHUNK_SIZE: usize = 48;
+
+/// Errors when reading an FSEQ sequence.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FseqError<E> {
+    /// Raised if the source fails to read or seek
+    Io(E),
+    /// Raised if the source ends before the header does
+    UnexpectedEof,
+    /// Raised if the source doesn't start with an FSEQ magic number
+    InvalidMagic,
+    /// Raised if the sequence uses an unsupported version, compression, or sparse ranges
+    Unsupported,
+}
+
+impl<E> From<ReadExactError<E>> for FseqError<E> {
+    fn from(error: ReadExactError<E>) -> Self {
+        match error {
+            ReadExactError::UnexpectedEof => FseqError::UnexpectedEof,
+            ReadExactError::Other(error) => FseqError::Io(error),
+        }
+    }
+}
+
+/// The header of an FSEQ sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FseqHeader {
+    /// Major version of the file format (1 or 2)
+    pub major_version: u8,
+    /// Offset of the first frame, in bytes
+    pub channel_data_offset: u16,
+    /// Number of channels in each frame
+    pub channel_count: u32,
+    /// Number of frames in the sequence
+    pub frame_count: u32,
+    /// How long each frame is shown, in milliseconds
+    pub step_time_in_ms: u8,
+}
+
+impl FseqHeader {
+    /// Reads the header from the start of the source.
+    pub fn read<Source>(source: &mut Source) -> Result<Self, FseqError<Source::Error>>
+    where
+        Source: Read + Seek,
+    {
+        let mut header = [0; HEADER_SIZE];
+        source.seek(SeekFrom::Start(0)).map_err(FseqError::Io)?;
+        source.read_exact(&mut header)?;
+
+        if &header[0..4] != b"PSEQ" && &header[0..4] != b"FSEQ" {
+            return Err(FseqError::InvalidMagic);
+        }
+
+        let major_version = header[7];
+        match major_version {
+            1 => {}
+            2 => {
+                let compression_type = header[20] & 0x0F;
+                let sparse_range_count = header[22];
+                if compression_type != 0 || sparse_range_count != 0 {
+                    return Err(FseqError::Unsupported);
+                }
+            }
+            _ => return Err(FseqError::Unsupported),
+        }
+
+        Ok(Self {
+            major_version,
+            channel_data_offset: u16::from_le_bytes([header[4], header[5]]),
+            channel_count: u32::from_le_bytes([header[10], header[11], header[12], header[13]]),
+            frame_count: u32::from_le_bytes([header[14], header[15], header[16], header[17]]),
+            step_time_in_ms: header[18],
+        })
+    }
+}
+
+/// Configuration parameters for the FSEQ pattern.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FseqParams<Source> {
+    /// Where the sequence is read from
+    pub source: Source,
+    /// The channel of the first pixel's red component
+    pub start_channel: u32,
+}
+
+/// FSEQ pattern implementation.
+///
+/// Streams the frame for the current time from the sequence, looping forever.
+///
+/// # Type Parameters
+///
+/// - `Source` - Where the sequence is read from
+#[derive(Debug)]
+pub struct Fseq<Source>
+where
+    Source: Read + Seek,
+{
+    /// Where the sequence is read from
+    source: RefCell<Source>,
+    /// The channel of the first pixel's red component
+    start_channel: u32,
+    /// The parsed header, or the error from reading it
+    header: Result<FseqHeader, FseqError<Source::Error>>,
+}
+
+impl<Source> Fseq<Source>
+where
+    Source: Read + Seek,
+{
+    fn from_params(params: FseqParams<Source>) -> Self {
+        let FseqParams {
+            mut source,
+            start_channel,
+        } = params;
+        let header = FseqHeader::read(&mut source);
+        Self {
+            source: RefCell::new(source),
+            start_channel,
+            header,
+        }
+    }
+
+    /// Returns the header of the sequence.
+    ///
+    /// # Returns
+    ///
+    /// The header, or the error from reading it
+    pub fn header(&self) -> Result<&FseqHeader, &FseqError<Source::Error>> {
+        self.header.as_ref()
+    }
+
+    /// Streams the colors of the frame for the current time.
+    fn frame(&self, time_in_ms: u64, pixel_count: usize) -> FseqPixels<'_, Source> {
+        let mut pixels = FseqPixels {
+            source: self.source.borrow_mut(),
+            remaining_pixels: pixel_count,
+            remaining_channels: 0,
+            chunk: [0; CHUNK_SIZE],
+            chunk_len: 0,
+            chunk_pos: 0,
+        };
+
+        let Ok(header) = &self.header else {
+            return pixels;
+        };
+        if header.frame_count == 0 || self.start_channel >= header.channel_count {
+            return pixels;
+        }
+
+        let step_time_in_ms = (header.step_time_in_ms as u64).max(1);
+        let frame_index = (time_in_ms / step_time_in_ms) % header.frame_count as u64;
+        let offset = header.channel_data_offset as u64
+            + frame_index * header.channel_count as u64
+            + self.start_channel as u64;
+
+        if pixels.source.seek(SeekFrom::Start(offset)).is_ok() {
+            pixels.remaining_channels = (header.channel_count - self.start_channel) as usize;
+        }
+        pixels
+    }
+}
+
+/// Iterator over the colors of one frame, reading the source in chunks.
+struct FseqPixels<'a, Source> {
+    source: RefMut<'a, Source>,
+    remaining_pixels: usize,
+    remaining_channels: usize,
+    chunk: [u8; CHUNK_SIZE],
+    chunk_len: usize,
+    chunk_pos: usize,
+}
+
+impl<Source> FseqPixels<'_, Source>
+where
+    Source: Read,
+{
+    fn next_channel(&mut self) -> u8 {
+        if self.chunk_pos == self.chunk_len {
+            let len = self.remaining_channels.min(CHUNK_SIZE);
+            if len == 0 || self.source.read_exact(&mut self.chunk[..len]).is_err() {
+                self.remaining_channels = 0;
+                return 0;
+            }
+            self.remaining_channels -= len;
+            self.chunk_len = len;
+            self.chunk_pos = 0;
+        }
+        let channel = self.chunk[self.chunk_pos];
+        self.chunk_pos += 1;
+        channel
+    }
+}
+
+impl<Source> Iterator for FseqPixels<'_, Source>
+where
+    Source: Read,
+{
+    type Item = Srgb;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining_pixels == 0 {
+            return None;
+        }
+        self.remaining_pixels -= 1;
+
+        let red = self.next_channel();
+        let green = self.next_channel();
+        let blue = self.next_channel();
+        Some(Srgb::from_u8(red, green, blue))
+    }
+}
+
+impl<Layout, Source> Pattern<Dim1d, Layout> for Fseq<Source>
+where
+    Layout: Layout1d,
+    Source: Read + Seek,
+{
+    type Params = FseqParams<Source>;
+    type Color = Srgb;
+
+    /// Creates a new Fseq pattern, reading the header of the sequence.
+    fn new(params: Self::Params) -> Self {
+        Self::from_params(params)
+    }
+
+    /// Streams the frame for the current time from the sequence.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.frame(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+impl<Layout, Source> Pattern<Dim2d, Layout> for Fseq<Source>
+where
+    Layout: Layout2d,
+    Source: Read + Seek,
+{
+    type Params = FseqParams<Source>;
+    type Color = Srgb;
+
+    /// Creates a new Fseq pattern, reading the header of the sequence.
+    fn new(params: Self::Params) -> Self {
+        Self::from_params(params)
+    }
+
+    /// Streams the frame for the current time from the sequence.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.frame(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+impl<Layout, Source> Pattern<Dim3d, Layout> for Fseq<Source>
+where
+    Layout: Layout3d,
+    Source: Read + Seek,
+{
+    type Params = FseqParams<Source>;
+    type Color = Srgb;
+
+    /// Creates a new Fseq pattern, reading the header of the sequence.
+    fn new(params: Self::Params) -> Self {
+        Self::from_params(params)
+    }
+
+    /// Streams the frame for the current time from the sequence.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.frame(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_io::ErrorType;
+
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Layout, 2);
+
+    /// An in-memory source, like a file.
+    struct Cursor<'a> {
+        data: &'a [u8],
+        position: usize,
+    }
+
+    impl<'a> Cursor<'a> {
+        fn new(data: &'a [u8]) -> Self {
+            Self { data, position: 0 }
+        }
+    }
+
+    impl ErrorType for Cursor<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for Cursor<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let rest = &self.data[self.position.min(self.data.len())..];
+            let len = buf.len().min(rest.len());
+            buf[..len].copy_from_slice(&rest[..len]);
+            self.position += len;
+            Ok(len)
+        }
+    }
+
+    impl Seek for Cursor<'_> {
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+            self.position = match pos {
+                SeekFrom::Start(offset) => offset as usize,
+                SeekFrom::End(offset) => (self.data.len() as i64 + offset) as usize,
+                SeekFrom::Current(offset) => (self.position as i64 + offset) as usize,
+            };
+            Ok(self.position as u64)
+        }
+    }
+
+    /// Returns an uncompressed version 2 sequence of 2 frames, of 6 channels each, at 25 ms
+    /// per frame.
+    fn sequence() -> [u8; 32 + 12] {
+        let mut data = [0; 32 + 12];
+        data[0..4].copy_from_slice(b"PSEQ");
+        data[4..6].copy_from_slice(&32u16.to_le_bytes());
+        data[7] = 2;
+        data[8..10].copy_from_slice(&32u16.to_le_bytes());
+        data[10..14].copy_from_slice(&6u32.to_le_bytes());
+        data[14..18].copy_from_slice(&2u32.to_le_bytes());
+        data[18] = 25;
+        data[32..44].copy_from_slice(&[255, 0, 0, 0, 255, 0, 0, 0, 255, 10, 20, 30]);
+        data
+    }
+
+    fn frame(pattern: &Fseq<Cursor<'_>>, time_in_ms: u64) -> heapless::Vec<Srgb, 2> {
+        <Fseq<Cursor<'_>> as Pattern<Dim1d, Layout>>::tick(pattern, time_in_ms).collect()
+    }
+
+    #[test]
+    fn test_header() {
+        let data = sequence();
+        let header = FseqHeader::read(&mut Cursor::new(&data)).unwrap();
+        assert_eq!(
+            header,
+            FseqHeader {
+                major_version: 2,
+                channel_data_offset: 32,
+                channel_count: 6,
+                frame_count: 2,
+                step_time_in_ms: 25,
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_headers() {
+        let data = sequence();
+        assert!(matches!(
+            FseqHeader::read(&mut Cursor::new(&data[..20])),
+            Err(FseqError::UnexpectedEof)
+        ));
+
+        let mut invalid = data;
+        invalid[0..4].copy_from_slice(b"RIFF");
+        assert!(matches!(
+            FseqHeader::read(&mut Cursor::new(&invalid)),
+            Err(FseqError::InvalidMagic)
+        ));
+
+        let mut compressed = data;
+        compressed[20] = 1;
+        assert!(matches!(
+            FseqHeader::read(&mut Cursor::new(&compressed)),
+            Err(FseqError::Unsupported)
+        ));
+    }
+
+    #[test]
+    fn test_frames_by_step_time() {
+        let data = sequence();
+        let pattern = <Fseq<_> as Pattern<Dim1d, Layout>>::new(FseqParams {
+            source: Cursor::new(&data),
+            start_channel: 0,
+        });
+
+        let first = [Srgb::from_u8(255, 0, 0), Srgb::from_u8(0, 255, 0)];
+        let second = [Srgb::from_u8(0, 0, 255), Srgb::from_u8(10, 20, 30)];
+        assert_eq!(frame(&pattern, 0).as_slice(), &first);
+        assert_eq!(frame(&pattern, 24).as_slice(), &first);
+        assert_eq!(frame(&pattern, 25).as_slice(), &second);
+        // After the last frame, the sequence loops back to the first.
+        assert_eq!(frame(&pattern, 50).as_slice(), &first);
+    }
+
+    #[test]
+    fn test_missing_channels_are_black() {
+        let data = sequence();
+
+        // The second pixel starts past the last channel of the frame.
+        let pattern = <Fseq<_> as Pattern<Dim1d, Layout>>::new(FseqParams {
+            source: Cursor::new(&data),
+            start_channel: 3,
+        });
+        let black = Srgb::from_u8(0, 0, 0);
+        assert_eq!(
+            frame(&pattern, 0).as_slice(),
+            &[Srgb::from_u8(0, 255, 0), black]
+        );
+
+        // A truncated sequence shows black for the frames cut off.
+        let pattern = <Fseq<_> as Pattern<Dim1d, Layout>>::new(FseqParams {
+            source: Cursor::new(&data[..38]),
+            start_channel: 0,
+        });
+        assert_eq!(frame(&pattern, 25).as_slice(), &[black, black]);
+    }
+}
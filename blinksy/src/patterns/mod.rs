@@ -4,9 +4,19 @@
 //!
 //! - [`rainbow`][]: A basic scrolling rainbow.
 //! - [`noise`]: A flow through random noise functions.
+//! - [`stairs`]: A staircase lit step by step, in the direction of travel.
+//! - [`white`]: A solid white at a color temperature, using the white channel of RGBW LEDs.
+//! - [`replay`]: A playback of recorded frames (feature `postcard`).
+//! - [`fseq`]: A playback of xLights FSEQ sequences (feature `fseq`).
 //!
 //! If you want help to port a pattern from FastLED / WLED to Rust, [make an issue](https://github.com/ahdinosaur/blinksy/issues)!
 
+#[cfg(feature = "fseq")]
+pub mod fseq;
 #[cfg(feature = "noise")]
 pub mod noise;
 pub mod rainbow;
+#[cfg(feature = "postcard")]
+pub mod replay;
+pub mod stairs;
+pub mod white;
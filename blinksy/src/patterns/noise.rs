@@ -17,6 +17,18 @@
 //! input the time to the noise function as an additonal dimension. So a 1d layout will
 //! use a 2d noise function, a 2d layout a 3d noise function, and so on.
 //!
+//! With a [`Palette`], the noise picks a color from the palette instead of a hue.
+//!
+//! The patterns output [`LinearSrgb`] colors, since a palette's colors may be more saturated
+//! than [`Okhsv`] can hold. (Before palettes, the patterns output [`Okhsv`] colors: this is a
+//! breaking change for code which names `Pattern::Color` of a noise pattern.)
+//!
+//! For richer textures, the noise may be fractal Brownian motion (fBm): a sum of octaves of
+//! the noise function, each at a higher frequency (by the lacunarity) and a lower amplitude
+//! (by the persistence). The octaves are a const generic of the pattern, e.g.
+//! `Noise2d<noise_fns::Perlin, 3>`, defaulting to 1. Each octave is another noise sample per
+//! LED, so more than [`MAX_NOISE_OCTAVES`] fails to compile.
+//!
 //! This pattern is the same concept as what you see on [mikey.nz](https://mikey.nz/).
 //!
 //! ## Example
@@ -48,22 +60,46 @@
 //!     .with_pattern::<Noise2d<noise_fns::Perlin>>(NoiseParams {
 //!         time_scalar: 0.001,
 //!         position_scalar: 0.1,
+//!         ..Default::default()
 //!     })
 //!     .with_driver(/* Your driver */)
 //!     .with_frame_buffer_size::</* Length of frame buffer */>()
 //!     .build();
 //! ```
 //!
+//! To flow through a palette of warm colors:
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::{Palette, Srgb},
+//!     patterns::noise::NoiseParams,
+//! };
+//!
+//! let params = NoiseParams {
+//!     palette: Some(Palette::new([
+//!         Srgb::new(0.5, 0., 0.),
+//!         Srgb::new(1., 0.3, 0.),
+//!         Srgb::new(1., 0.8, 0.2),
+//!     ])),
+//!     saturation: 0.8,
+//!     // Stretch the noise vertically
+//!     axis_scalars: [1., 0.25, 1.],
+//!     ..Default::default()
+//! };
+//! ```
+//!
+//! [`LinearSrgb`]: crate::color::LinearSrgb
 //! [`Okhsv`]: crate::color::Okhsv
+//! [`Palette`]: crate::color::Palette
 //! [mikey.nz]: https://mikey.nz
 
 use noise_functions::{modifiers::Seeded, Noise as NoiseTrait, Sample};
 
 use crate::{
-    color::Okhsv,
+    color::{LinearSrgb, Okhsv, Oklab, Palette},
     layout::{Layout1d, Layout2d, Layout3d},
     markers::{Dim1d, Dim2d, Dim3d},
-    pattern::Pattern,
+    pattern::{check_param, ParamError, ParamInfo, Pattern, PatternParams},
 };
 
 /// Re-exports of noise functions from the noise crate.
@@ -71,14 +107,31 @@ pub mod noise_fns {
     pub use noise_functions::{OpenSimplex2, Perlin, Simplex};
 }
 
+/// The most octaves of fractal Brownian motion, to bound the cost of each frame.
+pub const MAX_NOISE_OCTAVES: usize = 8;
+
+/// Offset of the noise position between octaves, so they don't align at the origin.
+const OCTAVE_OFFSET: f32 = 17.31;
+
 /// Configuration parameters for noise patterns.
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoiseParams {
     /// Controls the speed of animation (higher = faster)
     pub time_scalar: f32,
     /// Controls the spatial scale of the noise (higher = more compressed)
     pub position_scalar: f32,
+    /// Scales of the x, y, and z axes, relative to the position scalar (default: 1.0 each)
+    pub axis_scalars: [f32; 3],
+    /// Saturation of the colors, from 0.0 (gray) to 1.0 (default: 1.0)
+    pub saturation: f32,
+    /// Palette to pick colors from, instead of the full hue range (default: None)
+    pub palette: Option<Palette>,
+    /// Frequency multiplier from one octave to the next (default: 2.0)
+    pub lacunarity: f32,
+    /// Amplitude multiplier from one octave to the next (default: 0.5)
+    pub persistence: f32,
 }
 
 impl Default for NoiseParams {
@@ -87,17 +140,151 @@ impl Default for NoiseParams {
         Self {
             time_scalar: 0.75 / MILLISECONDS_PER_SECOND,
             position_scalar: 0.5,
+            axis_scalars: [1., 1., 1.],
+            saturation: 1.,
+            palette: None,
+            lacunarity: 2.,
+            persistence: 0.5,
         }
     }
 }
 
+impl NoiseParams {
+    /// Returns the scale of each axis of the position.
+    fn scales(&self) -> [f32; 3] {
+        self.axis_scalars
+            .map(|axis_scalar| axis_scalar * self.position_scalar)
+    }
+
+    /// Samples the noise as fractal Brownian motion of `OCTAVES` octaves, from -1.0 to 1.0.
+    ///
+    /// With one octave, this is the noise function itself.
+    fn fbm<const OCTAVES: usize, const N: usize>(
+        &self,
+        args: [f32; N],
+        sample: impl Fn([f32; N]) -> f32,
+    ) -> f32 {
+        const {
+            assert!(
+                OCTAVES >= 1 && OCTAVES <= MAX_NOISE_OCTAVES,
+                "noise octaves must be from 1 to MAX_NOISE_OCTAVES"
+            )
+        };
+        let mut frequency = 1.;
+        let mut amplitude = 1.;
+        let mut sum = 0.;
+        let mut total_amplitude = 0.;
+        for octave in 0..OCTAVES {
+            let offset = octave as f32 * OCTAVE_OFFSET;
+            sum += amplitude * sample(args.map(|arg| arg * frequency + offset));
+            total_amplitude += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.persistence;
+        }
+        if total_amplitude > 0. {
+            sum / total_amplitude
+        } else {
+            0.
+        }
+    }
+
+    /// Returns the color for the noise values, from -1.0 to 1.0.
+    fn color(&self, hue_noise: f32, value_noise: f32) -> LinearSrgb {
+        let value = 0.75 + 0.25 * value_noise;
+        match &self.palette {
+            Some(palette) => {
+                let Oklab { l, a, b } = palette.sample(0.5 + 0.5 * hue_noise);
+                let chroma = self.saturation.clamp(0., 1.) * value;
+                Oklab::new(l * value, a * chroma, b * chroma).to_linear_srgb()
+            }
+            None => Okhsv::new(hue_noise, self.saturation, value).to_linear_srgb(),
+        }
+    }
+}
+
+impl PatternParams for NoiseParams {
+    const PARAMS: &'static [ParamInfo] = &[
+        ParamInfo {
+            name: "time_scalar",
+            min: 0.,
+            max: 0.01,
+        },
+        ParamInfo {
+            name: "position_scalar",
+            min: 0.,
+            max: 10.,
+        },
+        ParamInfo {
+            name: "saturation",
+            min: 0.,
+            max: 1.,
+        },
+        ParamInfo {
+            name: "x_scalar",
+            min: 0.,
+            max: 10.,
+        },
+        ParamInfo {
+            name: "y_scalar",
+            min: 0.,
+            max: 10.,
+        },
+        ParamInfo {
+            name: "z_scalar",
+            min: 0.,
+            max: 10.,
+        },
+        ParamInfo {
+            name: "lacunarity",
+            min: 1.,
+            max: 4.,
+        },
+        ParamInfo {
+            name: "persistence",
+            min: 0.,
+            max: 1.,
+        },
+    ];
+
+    fn get_param(&self, index: usize) -> Option<f32> {
+        match index {
+            0 => Some(self.time_scalar),
+            1 => Some(self.position_scalar),
+            2 => Some(self.saturation),
+            3..=5 => Some(self.axis_scalars[index - 3]),
+            6 => Some(self.lacunarity),
+            7 => Some(self.persistence),
+            _ => None,
+        }
+    }
+
+    fn set_param(&mut self, index: usize, value: f32) -> Result<(), ParamError> {
+        check_param::<Self>(index, value)?;
+        match index {
+            0 => self.time_scalar = value,
+            1 => self.position_scalar = value,
+            2 => self.saturation = value,
+            3..=5 => self.axis_scalars[index - 3] = value,
+            6 => self.lacunarity = value,
+            7 => self.persistence = value,
+            _ => return Err(ParamError::UnknownIndex),
+        }
+        Ok(())
+    }
+}
+
 /// One-dimensional noise pattern implementation.
 ///
 /// Creates flowing animations based on a 2D noise function, using
 /// time and the 1D position for the input coordinates.
+///
+/// # Type Parameters
+///
+/// - `Noise` - The noise function, from [`noise_fns`]
+/// - `OCTAVES` - Octaves of fractal Brownian motion, from 1 to [`MAX_NOISE_OCTAVES`] (default: 1)
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct Noise1d<Noise> {
+pub struct Noise1d<Noise, const OCTAVES: usize = 1> {
     /// The noise function used to get hue
     hue_noise: Seeded<Noise>,
     /// The noise function used to get value
@@ -106,13 +293,13 @@ pub struct Noise1d<Noise> {
     params: NoiseParams,
 }
 
-impl<Layout, Noise> Pattern<Dim1d, Layout> for Noise1d<Noise>
+impl<Layout, Noise, const OCTAVES: usize> Pattern<Dim1d, Layout> for Noise1d<Noise, OCTAVES>
 where
     Layout: Layout1d,
     Noise: NoiseTrait + Sample<2> + Default,
 {
     type Params = NoiseParams;
-    type Color = Okhsv;
+    type Color = LinearSrgb;
 
     /// Creates a new Noise1d pattern with the specified parameters.
     fn new(params: Self::Params) -> Self {
@@ -123,10 +310,19 @@ where
         }
     }
 
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
     /// Generates colors for a 1D layout using noise.
     ///
     /// The pattern uses the LED position and time as inputs to a 2D noise function,
-    /// mapping the noise value to a hue in the Okhsv color space.
+    /// mapping the noise value to a hue in the Okhsv color space, or to a color of the
+    /// palette.
     fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
         let Self {
             hue_noise,
@@ -134,19 +330,15 @@ where
             params,
         } = self;
 
-        let NoiseParams {
-            time_scalar,
-            position_scalar,
-        } = params;
-
-        let noise_time = time_in_ms as f32 * time_scalar;
+        let noise_time = time_in_ms as f32 * params.time_scalar;
+        let [x_scale, _, _] = params.scales();
 
         Layout::points().map(move |x| {
-            let noise_args = [position_scalar * x, noise_time];
-            let hue = hue_noise.sample2(noise_args);
-            let saturation = 1.;
-            let value = 0.75 + 0.25 * value_noise.sample2(noise_args);
-            Okhsv::new(hue, saturation, value)
+            let noise_args = [x_scale * x, noise_time];
+            params.color(
+                params.fbm::<OCTAVES, _>(noise_args, |args| hue_noise.sample2(args)),
+                params.fbm::<OCTAVES, _>(noise_args, |args| value_noise.sample2(args)),
+            )
         })
     }
 }
@@ -155,9 +347,14 @@ where
 ///
 /// Creates flowing animations based on a 3D noise function, using
 /// time and the 2D position for the input coordinates.
+///
+/// # Type Parameters
+///
+/// - `Noise` - The noise function, from [`noise_fns`]
+/// - `OCTAVES` - Octaves of fractal Brownian motion, from 1 to [`MAX_NOISE_OCTAVES`] (default: 1)
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct Noise2d<Noise> {
+pub struct Noise2d<Noise, const OCTAVES: usize = 1> {
     /// The noise function used to get hue
     hue_noise: Seeded<Noise>,
     /// The noise function used to get value
@@ -166,13 +363,13 @@ pub struct Noise2d<Noise> {
     params: NoiseParams,
 }
 
-impl<Layout, Noise> Pattern<Dim2d, Layout> for Noise2d<Noise>
+impl<Layout, Noise, const OCTAVES: usize> Pattern<Dim2d, Layout> for Noise2d<Noise, OCTAVES>
 where
     Layout: Layout2d,
     Noise: NoiseTrait + Sample<3> + Default,
 {
     type Params = NoiseParams;
-    type Color = Okhsv;
+    type Color = LinearSrgb;
 
     /// Creates a new Noise2d pattern with the specified parameters.
     fn new(params: Self::Params) -> Self {
@@ -183,10 +380,19 @@ where
         }
     }
 
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
     /// Generates colors for a 2D layout using noise.
     ///
     /// The pattern uses the LED x,y position and time as inputs to a 3D noise function,
-    /// mapping the noise value to a hue in the Okhsv color space.
+    /// mapping the noise value to a hue in the Okhsv color space, or to a color of the
+    /// palette.
     fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
         let Self {
             hue_noise,
@@ -194,23 +400,15 @@ where
             params,
         } = self;
 
-        let NoiseParams {
-            time_scalar,
-            position_scalar,
-        } = params;
-
-        let noise_time = time_in_ms as f32 * time_scalar;
+        let noise_time = time_in_ms as f32 * params.time_scalar;
+        let [x_scale, y_scale, _] = params.scales();
 
         Layout::points().map(move |point| {
-            let noise_args = [
-                position_scalar * point.x,
-                position_scalar * point.y,
-                noise_time,
-            ];
-            let hue = hue_noise.sample3(noise_args);
-            let saturation = 1.;
-            let value = 0.75 + 0.25 * value_noise.sample3(noise_args);
-            Okhsv::new(hue, saturation, value)
+            let noise_args = [x_scale * point.x, y_scale * point.y, noise_time];
+            params.color(
+                params.fbm::<OCTAVES, _>(noise_args, |args| hue_noise.sample3(args)),
+                params.fbm::<OCTAVES, _>(noise_args, |args| value_noise.sample3(args)),
+            )
         })
     }
 }
@@ -219,9 +417,14 @@ where
 ///
 /// Creates flowing animations based on a 4D noise function, using
 /// time and the 3D position for the input coordinates.
+///
+/// # Type Parameters
+///
+/// - `Noise` - The noise function, from [`noise_fns`]
+/// - `OCTAVES` - Octaves of fractal Brownian motion, from 1 to [`MAX_NOISE_OCTAVES`] (default: 1)
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct Noise3d<Noise>
+pub struct Noise3d<Noise, const OCTAVES: usize = 1>
 where
     Noise: NoiseTrait,
 {
@@ -233,13 +436,13 @@ where
     params: NoiseParams,
 }
 
-impl<Layout, Noise> Pattern<Dim3d, Layout> for Noise3d<Noise>
+impl<Layout, Noise, const OCTAVES: usize> Pattern<Dim3d, Layout> for Noise3d<Noise, OCTAVES>
 where
     Layout: Layout3d,
     Noise: NoiseTrait + Sample<4> + Default,
 {
     type Params = NoiseParams;
-    type Color = Okhsv;
+    type Color = LinearSrgb;
 
     /// Creates a new Noise2d pattern with the specified parameters.
     fn new(params: Self::Params) -> Self {
@@ -250,10 +453,19 @@ where
         }
     }
 
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
     /// Generates colors for a 3D layout using noise.
     ///
     /// The pattern uses the LED x,y,z position and time as inputs to a 4D noise function,
-    /// mapping the noise value to a hue in the HSV color space.
+    /// mapping the noise value to a hue in the Okhsv color space, or to a color of the
+    /// palette.
     fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
         let Self {
             hue_noise,
@@ -261,24 +473,95 @@ where
             params,
         } = self;
 
-        let NoiseParams {
-            time_scalar,
-            position_scalar,
-        } = params;
-
-        let noise_time = time_in_ms as f32 * time_scalar;
+        let noise_time = time_in_ms as f32 * params.time_scalar;
+        let [x_scale, y_scale, z_scale] = params.scales();
 
         Layout::points().map(move |point| {
             let noise_args = [
-                position_scalar * point.x,
-                position_scalar * point.y,
-                position_scalar * point.z,
+                x_scale * point.x,
+                y_scale * point.y,
+                z_scale * point.z,
                 noise_time,
             ];
-            let hue = hue_noise.sample4(noise_args);
-            let saturation = 1.;
-            let value = 0.75 + 0.25 * value_noise.sample4(noise_args);
-            Okhsv::new(hue, saturation, value)
+            params.color(
+                params.fbm::<OCTAVES, _>(noise_args, |args| hue_noise.sample4(args)),
+                params.fbm::<OCTAVES, _>(noise_args, |args| value_noise.sample4(args)),
+            )
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_color_near,
+        color::{FromColor, Srgb},
+        testing::{PatternHarness, Strip4},
+    };
+
+    fn fbm<const OCTAVES: usize>(params: &NoiseParams, x: f32) -> f32 {
+        let noise = noise_fns::Perlin.seed(0);
+        params.fbm::<OCTAVES, 2>([x, 0.5], |args| noise.sample2(args))
+    }
+
+    #[test]
+    fn one_octave_is_the_noise_function() {
+        let params = NoiseParams::default();
+        let noise = noise_fns::Perlin.seed(0);
+        for x in [-1., -0.3, 0.2, 0.7] {
+            assert_eq!(fbm::<1>(&params, x), noise.sample2([x, 0.5]));
+        }
+    }
+
+    #[test]
+    fn octaves_stay_in_range() {
+        let params = NoiseParams::default();
+        let mut is_detailed = false;
+        for step in 0..200 {
+            let x = step as f32 * 0.137 - 10.;
+            let fine = fbm::<{ MAX_NOISE_OCTAVES }>(&params, x);
+            assert!((-1. ..=1.).contains(&fine), "{fine} at {x}");
+            is_detailed |= (fine - fbm::<1>(&params, x)).abs() > 1e-3;
+        }
+        assert!(is_detailed, "more octaves should add detail");
+    }
+
+    fn frame(params: NoiseParams) -> [LinearSrgb; 4] {
+        PatternHarness::<Dim1d, Strip4, Noise1d<noise_fns::Perlin>>::new(params).frame(1234)
+    }
+
+    #[test]
+    fn zero_saturation_is_gray() {
+        let palette = Palette::new([Srgb::new(1., 0., 0.), Srgb::new(0., 0., 1.)]);
+        for palette in [None, Some(palette)] {
+            let params = NoiseParams {
+                saturation: 0.,
+                palette,
+                ..Default::default()
+            };
+            for color in frame(params) {
+                let gray = color.green;
+                assert_color_near!(color, LinearSrgb::new(gray, gray, gray));
+            }
+        }
+    }
+
+    #[test]
+    fn palette_colors_keep_their_hue() {
+        let red = Oklab::from_linear_srgb(LinearSrgb::from_color(Srgb::new(1., 0., 0.)));
+        let params = NoiseParams {
+            palette: Some(Palette::new([Srgb::new(1., 0., 0.)])),
+            ..Default::default()
+        };
+        for color in frame(params) {
+            // The value noise only darkens a one-color palette
+            let value = Oklab::from_linear_srgb(color).l / red.l;
+            assert!((0.5 - 1e-3..=1. + 1e-3).contains(&value), "{color:?}");
+            assert_color_near!(
+                color,
+                Oklab::new(red.l * value, red.a * value, red.b * value)
+            );
+        }
+    }
+}
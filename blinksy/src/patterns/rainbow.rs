@@ -33,12 +33,13 @@ use crate::{
     color::{Hsv, HsvHueRainbow},
     layout::{Layout1d, Layout2d, Layout3d},
     markers::{Dim1d, Dim2d, Dim3d},
-    pattern::Pattern,
+    pattern::{check_param, ParamError, ParamInfo, Pattern, PatternParams},
 };
 
 /// Configuration parameters for the Rainbow pattern.
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RainbowParams {
     /// Controls the speed of the animation (higher = faster)
     pub time_scalar: f32,
@@ -56,6 +57,39 @@ impl Default for RainbowParams {
     }
 }
 
+impl PatternParams for RainbowParams {
+    const PARAMS: &'static [ParamInfo] = &[
+        ParamInfo {
+            name: "time_scalar",
+            min: 0.,
+            max: 0.01,
+        },
+        ParamInfo {
+            name: "position_scalar",
+            min: 0.,
+            max: 10.,
+        },
+    ];
+
+    fn get_param(&self, index: usize) -> Option<f32> {
+        match index {
+            0 => Some(self.time_scalar),
+            1 => Some(self.position_scalar),
+            _ => None,
+        }
+    }
+
+    fn set_param(&mut self, index: usize, value: f32) -> Result<(), ParamError> {
+        check_param::<Self>(index, value)?;
+        match index {
+            0 => self.time_scalar = value,
+            1 => self.position_scalar = value,
+            _ => return Err(ParamError::UnknownIndex),
+        }
+        Ok(())
+    }
+}
+
 /// Rainbow pattern implementation.
 ///
 /// Creates a smooth transition through the full HSV spectrum across the LED layout.
@@ -78,6 +112,14 @@ where
         Self { params }
     }
 
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
     /// Generates colors for a 1D layout.
     ///
     /// The rainbow pattern creates a smooth transition of hues across the layout,
@@ -113,6 +155,14 @@ where
         Self { params }
     }
 
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
     /// Generates colors for a 2D layout.
     ///
     /// In 2D, the rainbow pattern uses the x-coordinate to determine hue,
@@ -148,6 +198,14 @@ where
         Self { params }
     }
 
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
     /// Generates colors for a 3D layout.
     ///
     /// In 3D, the rainbow pattern uses the x-coordinate to determine hue,
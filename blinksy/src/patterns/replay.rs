@@ -0,0 +1,218 @@
+//! # Replay Pattern
+//!
+//! The replay pattern plays back a stream of frames recorded with the
+//! [`Recorder`](crate::driver::record::Recorder) driver, looping forever.
+//!
+//! The stream is read from a `&'static [u8]`, such as flash memory with [`include_bytes!`].
+//!
+//! Every frame in the stream must have the same encoded size, which is the case for a fixed
+//! pixel count and a color type made of fixed-size fields (like [`LinearSrgb`]).
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     color::LinearSrgb,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::replay::{Replay, ReplayParams},
+//!     ControlBuilder,
+//! };
+//!
+//! layout1d!(Layout, 60);
+//!
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Replay<LinearSrgb>>(ReplayParams {
+//!         data: include_bytes!("show.bin"),
+//!         frame_duration_in_ms: 16,
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+//!
+//! [`LinearSrgb`]: crate::color::LinearSrgb
+
+use core::{iter, marker::PhantomData};
+
+use serde::de::DeserializeOwned;
+
+use crate::{layout::LayoutForDim, pattern::Pattern};
+
+/// Configuration parameters for the Replay pattern.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReplayParams {
+    /// The recorded stream of frames
+    pub data: &'static [u8],
+    /// How long each frame is shown, in milliseconds
+    pub frame_duration_in_ms: u64,
+}
+
+/// Replay pattern implementation.
+///
+/// Plays back a recorded stream of frames, selecting the frame from the current time.
+///
+/// # Type Parameters
+///
+/// - `Color` - The color type the stream was recorded with
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Replay<Color> {
+    /// Configuration parameters
+    params: ReplayParams,
+    /// The encoded size of each frame, in bytes
+    frame_size: usize,
+    /// The number of frames in the stream
+    frame_count: usize,
+    /// Marker for the color type
+    color: PhantomData<Color>,
+}
+
+impl<Color> Replay<Color>
+where
+    Color: DeserializeOwned,
+{
+    /// Returns the encoded size of the first frame, if it can be decoded.
+    fn first_frame_size(data: &[u8]) -> Option<usize> {
+        let (len, mut rest) = postcard::take_from_bytes::<usize>(data).ok()?;
+        for _ in 0..len {
+            (_, rest) = postcard::take_from_bytes::<Color>(rest).ok()?;
+        }
+        Some(data.len() - rest.len())
+    }
+
+    /// Returns the number of frames in the stream.
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+}
+
+impl<Dim, Layout, Color> Pattern<Dim, Layout> for Replay<Color>
+where
+    Layout: LayoutForDim<Dim>,
+    Color: DeserializeOwned,
+{
+    type Params = ReplayParams;
+    type Color = Color;
+
+    /// Creates a new Replay pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        let (frame_size, frame_count) = match Self::first_frame_size(params.data) {
+            Some(frame_size) if frame_size > 0 => (frame_size, params.data.len() / frame_size),
+            _ => (0, 0),
+        };
+        Self {
+            params,
+            frame_size,
+            frame_count,
+            color: PhantomData,
+        }
+    }
+
+    /// Decodes the frame for the current time.
+    ///
+    /// If the stream has no frames, no colors are generated.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let frame = if self.frame_count == 0 {
+            &[][..]
+        } else {
+            let frame_index =
+                (time_in_ms / self.params.frame_duration_in_ms.max(1)) as usize % self.frame_count;
+            let start = frame_index * self.frame_size;
+            &self.params.data[start..start + self.frame_size]
+        };
+
+        let (mut remaining, mut rest) =
+            postcard::take_from_bytes::<usize>(frame).unwrap_or((0, &[]));
+        iter::from_fn(move || {
+            if remaining == 0 {
+                return None;
+            }
+            remaining -= 1;
+            let (color, next) = postcard::take_from_bytes::<Color>(rest).ok()?;
+            rest = next;
+            Some(color)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::convert::Infallible;
+
+    use super::*;
+    use crate::{
+        color::{ColorCorrection, LinearSrgb},
+        driver::{record::record_buffer_size, Driver, NullDriver, Recorder},
+        layout1d,
+        markers::Dim1d,
+    };
+
+    layout1d!(Layout, 2);
+
+    /// A sink collecting everything written to it.
+    struct VecSink(heapless::Vec<u8, 256>);
+
+    impl embedded_io::ErrorType for VecSink {
+        type Error = Infallible;
+    }
+
+    impl embedded_io::Write for VecSink {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            let len = buf.len().min(self.0.capacity() - self.0.len());
+            self.0.extend_from_slice(&buf[..len]).unwrap();
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_recorded_frames_replay_in_a_loop() {
+        let frames = [
+            [LinearSrgb::new(1., 0., 0.), LinearSrgb::new(0., 1., 0.)],
+            [LinearSrgb::new(0., 0., 1.), LinearSrgb::new(0.5, 0.5, 0.)],
+            [LinearSrgb::new(0.25, 0., 0.75), LinearSrgb::new(0., 0., 0.)],
+        ];
+
+        let mut recorder = Recorder::<_, _, { record_buffer_size::<LinearSrgb>(2) }>::new(
+            NullDriver,
+            VecSink(heapless::Vec::new()),
+        );
+        for frame in frames {
+            recorder
+                .show::<2, 0, _, _>(frame, 1., ColorCorrection::default())
+                .unwrap();
+        }
+        let (_, VecSink(recorded)) = recorder.into_inner();
+
+        // A partial frame at the end of the stream, e.g. from a recording cut short, is
+        // ignored.
+        let mut stream = std::vec::Vec::from(recorded.as_slice());
+        stream.extend_from_slice(&recorded[..3]);
+        let data: &'static [u8] = stream.leak();
+
+        let replay = <Replay<LinearSrgb> as Pattern<Dim1d, Layout>>::new(ReplayParams {
+            data,
+            frame_duration_in_ms: 10,
+        });
+        assert_eq!(replay.frame_count(), frames.len());
+
+        let replayed = |time_in_ms| {
+            let pixels: heapless::Vec<LinearSrgb, 2> =
+                <Replay<LinearSrgb> as Pattern<Dim1d, Layout>>::tick(&replay, time_in_ms).collect();
+            pixels
+        };
+        for (index, frame) in frames.iter().enumerate() {
+            assert_eq!(replayed(index as u64 * 10 + 5).as_slice(), frame);
+        }
+        // After the last frame, the stream loops back to the first.
+        assert_eq!(replayed(30).as_slice(), &frames[0]);
+    }
+}
@@ -0,0 +1,395 @@
+//! # Stairs Pattern
+//!
+//! The stairs pattern lights a staircase step by step, in the direction someone walks: motion
+//! at the bottom lights the steps from the bottom up, motion at the top lights them from the
+//! top down. After a timeout, the steps fade out again in the same order.
+//!
+//! Each step is a [`Segment`] of the LEDs, ordered from the bottom up, e.g. the segments of a
+//! [`DeviceConfig`](crate::config::DeviceConfig). The pattern is triggered through its params,
+//! from two [presence sensors](crate::presence).
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::stairs::{Stairs, StairsParams},
+//!     presence::PresencePin,
+//! };
+//!
+//! layout1d!(Layout, 14 * 30);
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Stairs>(StairsParams::new(config.segments.clone()))
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//!
+//! let mut bottom = PresencePin::new(bottom_pir_pin);
+//! let mut top = PresencePin::new(top_pir_pin);
+//!
+//! loop {
+//!     let time_in_ms = elapsed().as_millis();
+//!     if let Some(params) = control.pattern_params_mut() {
+//!         params.update_triggers(&mut bottom, &mut top, time_in_ms);
+//!     }
+//!     control.tick(time_in_ms).unwrap();
+//! }
+//! ```
+//!
+//! The pattern uses the time given to the pattern, so if the control's
+//! [time scale](crate::control::Control::set_time_scale) is changed, trigger with the same
+//! warped time.
+
+use heapless::Vec;
+
+use crate::{
+    capacities::MAX_SEGMENTS, color::LinearSrgb, compositor::Segment, layout::Layout1d,
+    markers::Dim1d, pattern::Pattern, presence::PresenceSensor,
+};
+
+/// The end of the staircase where motion was detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StairsEnd {
+    /// The bottom, lighting the steps upwards
+    Bottom,
+    /// The top, lighting the steps downwards
+    Top,
+}
+
+/// A run of the steps lighting up, from one end.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Trigger<const SEGMENT_COUNT: usize> {
+    /// When the first motion of this run was detected
+    start_in_ms: u64,
+    /// When the last motion of this run was detected
+    last_in_ms: u64,
+    /// The steps which were fading out when motion was detected again, by position from the end
+    resumes: Vec<Option<Resume>, SEGMENT_COUNT>,
+}
+
+/// A step fading in again, from where its fade out was.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Resume {
+    /// When motion was detected again
+    at_in_ms: u64,
+    /// The level of the step then
+    level: f32,
+}
+
+impl<const SEGMENT_COUNT: usize> Trigger<SEGMENT_COUNT> {
+    fn new(time_in_ms: u64) -> Self {
+        Self {
+            start_in_ms: time_in_ms,
+            last_in_ms: time_in_ms,
+            resumes: Vec::new(),
+        }
+    }
+}
+
+/// Configuration parameters for the Stairs pattern.
+///
+/// # Type Parameters
+///
+/// - `SEGMENT_COUNT` - The most steps (default: [`MAX_SEGMENTS`])
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StairsParams<const SEGMENT_COUNT: usize = MAX_SEGMENTS> {
+    /// The steps, ordered from the bottom up (the opacity of each is its full level)
+    pub segments: Vec<Segment, SEGMENT_COUNT>,
+    /// The color of the lit steps
+    pub color: LinearSrgb,
+    /// Delay from one step to the next, in milliseconds (default: 150)
+    pub step_delay_in_ms: u64,
+    /// How long each step stays on after the last motion, in milliseconds (default: 20000)
+    pub hold_in_ms: u64,
+    /// Duration of each step's fade in, in milliseconds (default: 300)
+    pub fade_in_in_ms: u64,
+    /// Duration of each step's fade out, in milliseconds (default: 1500)
+    pub fade_out_in_ms: u64,
+    bottom: Option<Trigger<SEGMENT_COUNT>>,
+    top: Option<Trigger<SEGMENT_COUNT>>,
+}
+
+impl<const SEGMENT_COUNT: usize> StairsParams<SEGMENT_COUNT> {
+    /// Creates the params with the default timings, in white.
+    ///
+    /// # Arguments
+    ///
+    /// - `segments` - The steps, ordered from the bottom up
+    pub fn new(segments: Vec<Segment, SEGMENT_COUNT>) -> Self {
+        Self {
+            segments,
+            color: LinearSrgb::new(1., 1., 1.),
+            step_delay_in_ms: 150,
+            hold_in_ms: 20_000,
+            fade_in_in_ms: 300,
+            fade_out_in_ms: 1500,
+            bottom: None,
+            top: None,
+        }
+    }
+
+    /// Lights the steps from an end, as motion was detected there.
+    ///
+    /// If the steps are still lit from this end, they stay lit for longer, and any steps
+    /// already fading out fade in again from their current level.
+    ///
+    /// # Arguments
+    ///
+    /// - `end` - Where motion was detected
+    /// - `time_in_ms` - The time given to the pattern
+    pub fn trigger(&mut self, end: StairsEnd, time_in_ms: u64) {
+        let previous = match end {
+            StairsEnd::Bottom => self.bottom.take(),
+            StairsEnd::Top => self.top.take(),
+        };
+        let trigger = match previous {
+            Some(mut trigger) if time_in_ms <= trigger.last_in_ms + self.run_in_ms() => {
+                self.resume_fading_steps(&mut trigger, time_in_ms);
+                trigger.last_in_ms = time_in_ms;
+                trigger
+            }
+            _ => Trigger::new(time_in_ms),
+        };
+        match end {
+            StairsEnd::Bottom => self.bottom = Some(trigger),
+            StairsEnd::Top => self.top = Some(trigger),
+        }
+    }
+
+    /// Marks the steps of a run which are fading out to fade in again from their current level.
+    fn resume_fading_steps(&self, trigger: &mut Trigger<SEGMENT_COUNT>, time_in_ms: u64) {
+        for position in 0..self.segments.len() {
+            let off_in_ms =
+                trigger.last_in_ms + position as u64 * self.step_delay_in_ms + self.hold_in_ms;
+            if time_in_ms > off_in_ms {
+                let level = self.level(trigger, position, time_in_ms);
+                trigger.resumes.resize(self.segments.len(), None).ok();
+                trigger.resumes[position] = Some(Resume {
+                    at_in_ms: time_in_ms,
+                    level,
+                });
+            }
+        }
+    }
+
+    /// Reads the presence sensors at both ends, triggering the steps from any with presence.
+    ///
+    /// # Arguments
+    ///
+    /// - `bottom` - The presence sensor at the bottom
+    /// - `top` - The presence sensor at the top
+    /// - `time_in_ms` - The time given to the pattern
+    pub fn update_triggers<Bottom, Top>(
+        &mut self,
+        bottom: &mut Bottom,
+        top: &mut Top,
+        time_in_ms: u64,
+    ) where
+        Bottom: PresenceSensor,
+        Top: PresenceSensor,
+    {
+        if bottom.is_present() {
+            self.trigger(StairsEnd::Bottom, time_in_ms);
+        }
+        if top.is_present() {
+            self.trigger(StairsEnd::Top, time_in_ms);
+        }
+    }
+
+    /// Returns how long after the last motion until all steps are dark again.
+    fn run_in_ms(&self) -> u64 {
+        let last_step_delay_in_ms =
+            self.segments.len().saturating_sub(1) as u64 * self.step_delay_in_ms;
+        last_step_delay_in_ms + self.hold_in_ms + self.fade_out_in_ms
+    }
+
+    /// Returns the level of a step, from a run of the steps lighting up.
+    ///
+    /// # Arguments
+    ///
+    /// - `trigger` - The run
+    /// - `position` - How many steps away from the end of the run this step is
+    /// - `time_in_ms` - The time given to the pattern
+    fn level(&self, trigger: &Trigger<SEGMENT_COUNT>, position: usize, time_in_ms: u64) -> f32 {
+        let delay_in_ms = position as u64 * self.step_delay_in_ms;
+        let off_in_ms = trigger.last_in_ms + delay_in_ms + self.hold_in_ms;
+        let fade_in = match trigger.resumes.get(position).copied().flatten() {
+            Some(resume) => {
+                let elapsed_in_ms = time_in_ms.saturating_sub(resume.at_in_ms);
+                (resume.level + fade(elapsed_in_ms, self.fade_in_in_ms)).min(1.)
+            }
+            None => {
+                let on_in_ms = trigger.start_in_ms + delay_in_ms;
+                if time_in_ms < on_in_ms {
+                    return 0.;
+                }
+                fade(time_in_ms - on_in_ms, self.fade_in_in_ms)
+            }
+        };
+        if time_in_ms < off_in_ms {
+            return fade_in;
+        }
+        fade_in * (1. - fade(time_in_ms - off_in_ms, self.fade_out_in_ms))
+    }
+
+    /// Returns the level of the step at an index, from both ends.
+    fn step_level(&self, index: usize, time_in_ms: u64) -> f32 {
+        let count = self.segments.len();
+        let bottom = self
+            .bottom
+            .as_ref()
+            .map_or(0., |trigger| self.level(trigger, index, time_in_ms));
+        let top = self.top.as_ref().map_or(0., |trigger| {
+            self.level(trigger, count - 1 - index, time_in_ms)
+        });
+        bottom.max(top)
+    }
+}
+
+/// Returns the progress of a fade, from 0.0 to 1.0.
+fn fade(elapsed_in_ms: u64, duration_in_ms: u64) -> f32 {
+    if duration_in_ms == 0 {
+        return 1.;
+    }
+    (elapsed_in_ms as f32 / duration_in_ms as f32).min(1.)
+}
+
+/// Stairs pattern implementation.
+///
+/// Lights the steps of a staircase in the direction of travel.
+///
+/// # Type Parameters
+///
+/// - `SEGMENT_COUNT` - The most steps (default: [`MAX_SEGMENTS`])
+#[derive(Debug)]
+pub struct Stairs<const SEGMENT_COUNT: usize = MAX_SEGMENTS> {
+    /// Configuration parameters
+    params: StairsParams<SEGMENT_COUNT>,
+}
+
+impl<Layout, const SEGMENT_COUNT: usize> Pattern<Dim1d, Layout> for Stairs<SEGMENT_COUNT>
+where
+    Layout: Layout1d,
+{
+    type Params = StairsParams<SEGMENT_COUNT>;
+    type Color = LinearSrgb;
+
+    /// Creates a new Stairs pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
+    /// Generates colors for a 1D layout.
+    ///
+    /// Pixels outside of every step are off.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let params = &self.params;
+        let mut levels = [0.; SEGMENT_COUNT];
+        for (index, step_level) in levels.iter_mut().enumerate().take(params.segments.len()) {
+            *step_level = params.step_level(index, time_in_ms);
+        }
+        let LinearSrgb { red, green, blue } = params.color;
+
+        Layout::points().enumerate().map(move |(pixel, _)| {
+            let level = params
+                .segments
+                .iter()
+                .zip(levels)
+                .find(|(segment, _)| segment.range.contains(&pixel))
+                .map_or(0., |(segment, level)| level * segment.opacity);
+            LinearSrgb::new(red * level, green * level, blue * level)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compositor::BlendMode, layout1d};
+
+    layout1d!(Layout, 2);
+
+    fn stairs() -> Stairs<2> {
+        let segments = [0..1, 1..2]
+            .into_iter()
+            .map(|range| Segment::new(range, BlendMode::AlphaOver, 1.))
+            .collect();
+        let mut params = StairsParams::new(segments);
+        params.step_delay_in_ms = 100;
+        params.hold_in_ms = 1000;
+        params.fade_in_in_ms = 100;
+        params.fade_out_in_ms = 200;
+        <Stairs<2> as Pattern<Dim1d, Layout>>::new(params)
+    }
+
+    fn levels(stairs: &Stairs<2>, time_in_ms: u64) -> [f32; 2] {
+        let mut levels =
+            <Stairs<2> as Pattern<Dim1d, Layout>>::tick(stairs, time_in_ms).map(|color| color.red);
+        [levels.next().unwrap(), levels.next().unwrap()]
+    }
+
+    #[test]
+    fn test_trigger_hold_and_fade() {
+        let mut stairs = stairs();
+        assert_eq!(levels(&stairs, 0), [0., 0.]);
+
+        stairs.params.trigger(StairsEnd::Bottom, 0);
+        assert_eq!(levels(&stairs, 50), [0.5, 0.]);
+        assert_eq!(levels(&stairs, 150), [1., 0.5]);
+        assert_eq!(levels(&stairs, 1000), [1., 1.]);
+        assert_eq!(levels(&stairs, 1100), [0.5, 1.]);
+        assert_eq!(levels(&stairs, 1200), [0., 0.5]);
+        assert_eq!(levels(&stairs, 1300), [0., 0.]);
+    }
+
+    #[test]
+    fn test_trigger_from_the_top() {
+        let mut stairs = stairs();
+        stairs.params.trigger(StairsEnd::Top, 0);
+        assert_eq!(levels(&stairs, 150), [0.5, 1.]);
+    }
+
+    #[test]
+    fn test_retrigger_while_fading_out_fades_in_from_the_current_level() {
+        let mut stairs = stairs();
+        stairs.params.trigger(StairsEnd::Bottom, 0);
+        assert_eq!(levels(&stairs, 1100), [0.5, 1.]);
+
+        // The fading step doesn't jump back to full
+        stairs.params.trigger(StairsEnd::Bottom, 1100);
+        assert_eq!(levels(&stairs, 1100), [0.5, 1.]);
+        assert_eq!(levels(&stairs, 1125), [0.75, 1.]);
+        assert_eq!(levels(&stairs, 1200), [1., 1.]);
+
+        // And the run holds from the last motion
+        assert_eq!(levels(&stairs, 2100), [1., 1.]);
+        assert_eq!(levels(&stairs, 2200), [0.5, 1.]);
+        assert_eq!(levels(&stairs, 2400), [0., 0.]);
+    }
+
+    #[test]
+    fn test_trigger_after_the_run_starts_again() {
+        let mut stairs = stairs();
+        stairs.params.trigger(StairsEnd::Bottom, 0);
+        stairs.params.trigger(StairsEnd::Bottom, 5000);
+        assert_eq!(levels(&stairs, 5000), [0., 0.]);
+        assert_eq!(levels(&stairs, 5150), [1., 0.5]);
+    }
+}
@@ -0,0 +1,261 @@
+//! # White Pattern
+//!
+//! The white pattern shows a solid white at a chosen color temperature, for using LEDs as
+//! lighting.
+//!
+//! On LEDs with a white channel (like the [`SK6812`](crate::leds::Sk6812)), the white channel
+//! is driven at full, mixed with only as much RGB as needed to reach the color temperature. So
+//! at the white channel's own temperature, only the white channel is on. (Otherwise, a white
+//! mixed from RGB is dimmer and less even.) On RGB LEDs, the white is mixed from RGB.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::{ColorCorrection, LedRgbw, LinearSrgb},
+//!     driver::ColorCapability,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     markers::Dim1d,
+//!     pattern::Pattern,
+//!     patterns::white::{White, WhiteParams, DEFAULT_WHITE_CHANNEL_TEMPERATURE_IN_KELVIN},
+//! };
+//!
+//! layout1d!(Layout, 1);
+//!
+//! let mut white = <White as Pattern<Dim1d, Layout>>::new(WhiteParams {
+//!     temperature_in_kelvin: DEFAULT_WHITE_CHANNEL_TEMPERATURE_IN_KELVIN,
+//!     ..Default::default()
+//! });
+//! <White as Pattern<Dim1d, Layout>>::set_color_capability(&mut white, ColorCapability::RGBW8);
+//!
+//! let color = <White as Pattern<Dim1d, Layout>>::tick(&white, 0).next().unwrap();
+//! let rgbw = LedRgbw::<u8>::from_linear_srgb(color, 1., ColorCorrection::default());
+//! assert_eq!(rgbw.as_ref(), &[0, 0, 0, 255]);
+//! ```
+
+use crate::{
+    color::{ColorCorrection, LinearSrgb},
+    driver::ColorCapability,
+    layout::{Layout1d, Layout2d, Layout3d},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::{check_param, ParamError, ParamInfo, Pattern, PatternParams},
+};
+
+/// The default color temperature of the white channel, in Kelvin: a "natural white" SK6812.
+pub const DEFAULT_WHITE_CHANNEL_TEMPERATURE_IN_KELVIN: f32 = 4500.;
+
+/// Configuration parameters for the White pattern.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhiteParams {
+    /// Color temperature of the white, in Kelvin (e.g. 2700 for warm, 6500 for cool)
+    pub temperature_in_kelvin: f32,
+    /// Level of the white, from 0.0 (off) to 1.0 (full)
+    pub level: f32,
+    /// Color temperature of the LEDs' white channel, in Kelvin (see your LEDs' datasheet)
+    pub white_channel_temperature_in_kelvin: f32,
+}
+
+impl Default for WhiteParams {
+    fn default() -> Self {
+        Self {
+            temperature_in_kelvin: 4000.,
+            level: 1.,
+            white_channel_temperature_in_kelvin: DEFAULT_WHITE_CHANNEL_TEMPERATURE_IN_KELVIN,
+        }
+    }
+}
+
+impl PatternParams for WhiteParams {
+    const PARAMS: &'static [ParamInfo] = &[
+        ParamInfo {
+            name: "temperature_in_kelvin",
+            min: 1000.,
+            max: 10000.,
+        },
+        ParamInfo {
+            name: "level",
+            min: 0.,
+            max: 1.,
+        },
+        ParamInfo {
+            name: "white_channel_temperature_in_kelvin",
+            min: 1000.,
+            max: 10000.,
+        },
+    ];
+
+    fn get_param(&self, index: usize) -> Option<f32> {
+        match index {
+            0 => Some(self.temperature_in_kelvin),
+            1 => Some(self.level),
+            2 => Some(self.white_channel_temperature_in_kelvin),
+            _ => None,
+        }
+    }
+
+    fn set_param(&mut self, index: usize, value: f32) -> Result<(), ParamError> {
+        check_param::<Self>(index, value)?;
+        match index {
+            0 => self.temperature_in_kelvin = value,
+            1 => self.level = value,
+            2 => self.white_channel_temperature_in_kelvin = value,
+            _ => return Err(ParamError::UnknownIndex),
+        }
+        Ok(())
+    }
+}
+
+/// White pattern implementation.
+///
+/// Shows the same white on every LED, using the white channel if the LEDs have one.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct White {
+    /// Configuration parameters
+    params: WhiteParams,
+    /// Whether the LEDs have a white channel
+    has_white: bool,
+}
+
+impl White {
+    fn new(params: WhiteParams) -> Self {
+        Self {
+            params,
+            has_white: false,
+        }
+    }
+
+    /// Returns the color of the white.
+    ///
+    /// LEDs with a white channel take the minimum of red, green, and blue as white, so on
+    /// those, the color is the white channel at full (as 1.0 in each component), plus the RGB
+    /// to add to the white channel's own color to reach the color temperature.
+    fn color(&self) -> LinearSrgb {
+        let WhiteParams {
+            temperature_in_kelvin,
+            level,
+            white_channel_temperature_in_kelvin,
+        } = self.params;
+        let level = level.clamp(0., 1.);
+        let target = temperature_rgb(temperature_in_kelvin);
+        if !self.has_white {
+            return LinearSrgb::new(target[0] * level, target[1] * level, target[2] * level);
+        }
+
+        // Scale the target until the white channel alone fits inside it.
+        let native = temperature_rgb(white_channel_temperature_in_kelvin);
+        let scale = (0..3)
+            .map(|index| native[index] / target[index])
+            .fold(0., f32::max);
+        if !scale.is_finite() {
+            // The white channel has color the target doesn't, so it can't be used.
+            return LinearSrgb::new(target[0] * level, target[1] * level, target[2] * level);
+        }
+        let rgb = [0, 1, 2].map(|index| scale * target[index] - native[index]);
+
+        // Dim the white and RGB together until the RGB fits.
+        let white = 1. / rgb.iter().copied().fold(1., f32::max);
+        let [red, green, blue] = rgb.map(|component| (white + white * component) * level);
+        LinearSrgb::new(red, green, blue)
+    }
+}
+
+/// Returns the RGB of a color temperature, with the brightest component at 1.0.
+fn temperature_rgb(temperature_in_kelvin: f32) -> [f32; 3] {
+    let ColorCorrection { red, green, blue } =
+        ColorCorrection::from_temperature(temperature_in_kelvin as u32);
+    [red, green, blue]
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for White
+where
+    Layout: Layout1d,
+{
+    type Params = WhiteParams;
+    type Color = LinearSrgb;
+
+    /// Creates a new White pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        White::new(params)
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
+    fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let color = self.color();
+        Layout::points().map(move |_| color)
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.has_white = capability.has_white;
+    }
+}
+
+impl<Layout> Pattern<Dim2d, Layout> for White
+where
+    Layout: Layout2d,
+{
+    type Params = WhiteParams;
+    type Color = LinearSrgb;
+
+    /// Creates a new White pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        White::new(params)
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
+    fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let color = self.color();
+        Layout::points().map(move |_| color)
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.has_white = capability.has_white;
+    }
+}
+
+impl<Layout> Pattern<Dim3d, Layout> for White
+where
+    Layout: Layout3d,
+{
+    type Params = WhiteParams;
+    type Color = LinearSrgb;
+
+    /// Creates a new White pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        White::new(params)
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        Some(&self.params)
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        Some(&mut self.params)
+    }
+
+    fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let color = self.color();
+        Layout::points().map(move |_| color)
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.has_white = capability.has_white;
+    }
+}
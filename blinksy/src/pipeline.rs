@@ -0,0 +1,260 @@
+//! # Render Pipeline
+//!
+//! With one async task, each frame is rendered, then transmitted, then the next frame is
+//! rendered: the CPU waits while the LEDs are written, and the LEDs wait while the CPU
+//! renders. With the `embassy` feature, the pipeline splits this into two
+//! [Embassy](https://embassy.dev) tasks, connected by a [`FrameChannel`]:
+//!
+//! - The render task ticks a [`Control`](crate::control::Control) whose driver is a
+//!   [`FrameSender`], which sends each frame of pixels into the channel
+//! - The transmit task runs a [`FrameTransmitter`], which receives each frame and shows it with
+//!   the real driver
+//!
+//! So the next frame is rendered while the last frame is transmitted. When the channel is full,
+//! the render task waits for the transmit task (backpressure), so frames are never dropped, and
+//! rendering never runs ahead of the LEDs.
+//!
+//! The channel carries pixels, not encoded frames, so the real driver is only used by the
+//! transmit task. The brightness, color correction, and
+//! [global brightness](crate::driver::GlobalBrightness) of the control travel with each
+//! [`Frame`].
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     pipeline::{FrameChannel, FrameSender, FrameTransmitter},
+//!     ControlBuilder,
+//! };
+//! use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+//!
+//! layout1d!(Layout, 300);
+//!
+//! // Double-buffered: one frame transmitting, one frame ready
+//! static FRAMES: FrameChannel<CriticalSectionRawMutex, { Layout::PIXEL_COUNT }, 2> =
+//!     FrameChannel::new();
+//!
+//! #[embassy_executor::task]
+//! async fn transmit_task(driver: /* LED driver */) {
+//!     let mut transmitter = FrameTransmitter::<_, _, { Layout::PIXEL_COUNT }, /* LED frame buffer size */, 2>::new(
+//!         FRAMES.receiver(),
+//!         driver,
+//!     );
+//!     transmitter.run().await.unwrap();
+//! }
+//!
+//! // In the render task
+//! spawner.spawn(transmit_task(/* LED driver */)).unwrap();
+//!
+//! let mut control = ControlBuilder::new_1d_async()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::</* Pattern type */>(/* Pattern params */)
+//!     .with_driver(FrameSender::new(FRAMES.sender()))
+//!     .with_frame_buffer_size::<{ Layout::PIXEL_COUNT }>()
+//!     .build();
+//!
+//! loop {
+//!     control.tick(/* current time in milliseconds */).await.unwrap();
+//! }
+//! ```
+
+use core::convert::Infallible;
+
+use embassy_sync::{
+    blocking_mutex::raw::RawMutex,
+    channel::{Channel, Receiver, Sender},
+};
+use heapless::Vec;
+
+use crate::{
+    color::{ColorCorrection, FromColor, LinearSrgb},
+    driver::{DriverAsync, Frame, GlobalBrightness},
+};
+
+/// A channel of frames, from the render task to the transmit task.
+///
+/// Make it a `static`, to share it between tasks.
+///
+/// # Type Parameters
+///
+/// - `M` - The mutex for the channel, e.g. `CriticalSectionRawMutex`
+/// - `PIXEL_COUNT` - The number of pixels in each frame
+/// - `DEPTH` - How many frames can wait in the channel, e.g. 2 to render one frame ahead
+pub type FrameChannel<M, const PIXEL_COUNT: usize, const DEPTH: usize> =
+    Channel<M, Frame<PIXEL_COUNT>, DEPTH>;
+
+/// A driver which sends each frame into a [`FrameChannel`], for the driver of the render task.
+///
+/// Writing a frame waits while the channel is full. Use a frame buffer size of `PIXEL_COUNT`,
+/// as each word of the frame buffer is a pixel.
+///
+/// The pattern is told the default [color capability](crate::driver::capability), not the
+/// capability of the real driver.
+///
+/// # Type Parameters
+///
+/// - `M` - The mutex for the channel
+/// - `PIXEL_COUNT` - The number of pixels in each frame
+/// - `DEPTH` - How many frames can wait in the channel
+pub struct FrameSender<'a, M, const PIXEL_COUNT: usize, const DEPTH: usize>
+where
+    M: RawMutex,
+{
+    sender: Sender<'a, M, Frame<PIXEL_COUNT>, DEPTH>,
+    brightness: f32,
+    correction: ColorCorrection,
+    global_brightness: GlobalBrightness,
+}
+
+impl<'a, M, const PIXEL_COUNT: usize, const DEPTH: usize> FrameSender<'a, M, PIXEL_COUNT, DEPTH>
+where
+    M: RawMutex,
+{
+    /// Creates a driver sending into a channel.
+    ///
+    /// # Arguments
+    ///
+    /// - `sender` - The sending end of the [`FrameChannel`]
+    pub fn new(sender: Sender<'a, M, Frame<PIXEL_COUNT>, DEPTH>) -> Self {
+        Self {
+            sender,
+            brightness: 1.,
+            correction: ColorCorrection::default(),
+            global_brightness: GlobalBrightness::default(),
+        }
+    }
+}
+
+impl<M, const PIXEL_COUNT: usize, const DEPTH: usize> DriverAsync
+    for FrameSender<'_, M, PIXEL_COUNT, DEPTH>
+where
+    M: RawMutex,
+{
+    type Error = Infallible;
+    type Color = LinearSrgb;
+    type Word = LinearSrgb;
+
+    fn encode<const LAYOUT_PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        // The frame is shown by the transmit task, so keep these for the write
+        self.brightness = brightness;
+        self.correction = correction;
+        pixels.into_iter().map(LinearSrgb::from_color).collect()
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        let frame = Frame::new(
+            frame,
+            self.brightness,
+            self.correction,
+            self.global_brightness,
+        );
+        self.sender.send(frame).await;
+        Ok(())
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.global_brightness = global_brightness;
+    }
+}
+
+/// Shows each frame from a [`FrameChannel`] with a driver, for the transmit task.
+///
+/// # Type Parameters
+///
+/// - `M` - The mutex for the channel
+/// - `Driver` - The LED driver
+/// - `PIXEL_COUNT` - The number of pixels in each frame
+/// - `FRAME_BUFFER_SIZE` - The frame buffer size of the driver
+/// - `DEPTH` - How many frames can wait in the channel
+pub struct FrameTransmitter<
+    'a,
+    M,
+    Driver,
+    const PIXEL_COUNT: usize,
+    const FRAME_BUFFER_SIZE: usize,
+    const DEPTH: usize,
+> where
+    M: RawMutex,
+{
+    receiver: Receiver<'a, M, Frame<PIXEL_COUNT>, DEPTH>,
+    driver: Driver,
+    global_brightness: GlobalBrightness,
+}
+
+impl<
+        'a,
+        M,
+        Driver,
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        const DEPTH: usize,
+    > FrameTransmitter<'a, M, Driver, PIXEL_COUNT, FRAME_BUFFER_SIZE, DEPTH>
+where
+    M: RawMutex,
+    Driver: DriverAsync,
+    Driver::Color: FromColor<LinearSrgb>,
+{
+    /// Creates a transmitter receiving from a channel.
+    ///
+    /// # Arguments
+    ///
+    /// - `receiver` - The receiving end of the [`FrameChannel`]
+    /// - `driver` - The LED driver
+    pub fn new(receiver: Receiver<'a, M, Frame<PIXEL_COUNT>, DEPTH>, driver: Driver) -> Self {
+        Self {
+            receiver,
+            driver,
+            global_brightness: GlobalBrightness::default(),
+        }
+    }
+
+    /// Waits for the next frame, and shows it.
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error from the driver
+    pub async fn transmit_next(&mut self) -> Result<(), Driver::Error> {
+        let frame = self.receiver.receive().await;
+        frame
+            .show_async::<_, FRAME_BUFFER_SIZE>(&mut self.driver, &mut self.global_brightness)
+            .await
+    }
+
+    /// Shows every frame, until the driver fails.
+    ///
+    /// # Returns
+    ///
+    /// The first error from the driver
+    pub async fn run(&mut self) -> Result<Infallible, Driver::Error> {
+        loop {
+            self.transmit_next().await?;
+        }
+    }
+
+    /// Returns a reference to the driver.
+    pub fn driver(&self) -> &Driver {
+        &self.driver
+    }
+
+    /// Returns a mutable reference to the driver.
+    pub fn driver_mut(&mut self) -> &mut Driver {
+        &mut self.driver
+    }
+
+    /// Unwraps the driver.
+    pub fn into_inner(self) -> Driver {
+        self.driver
+    }
+}
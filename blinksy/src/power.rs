@@ -0,0 +1,223 @@
+//! # Power Model
+//!
+//! A [`PowerModel`] estimates how much current a frame of LEDs draws, so you can size power
+//! supplies (and wiring) while designing patterns.
+//!
+//! LEDs are driven with PWM, so each color channel draws current in proportion to its linear
+//! output level, after global brightness and color correction. On top of that, each LED's
+//! controller chip draws a small current even when all channels are off.
+//!
+//! For RGBW chipsets (e.g. SK6812), the white level is extracted from the RGB levels the same
+//! way the LEDs are driven (see [`LedRgbw`](crate::color::LedRgbw)), and drawn at the white
+//! channel's own current.
+//!
+//! The default model is the common rule of thumb for 5V RGB LEDs (e.g. WS2812B): 20 mA per
+//! channel at full, so 60 mA per LED at full white, plus 1 mA per LED at idle.
+//!
+//! Each LED chipset describes its own [`CurrentDraw`] (see
+//! [`ClocklessLed::CURRENT_DRAW`](crate::driver::ClocklessLed::CURRENT_DRAW) and
+//! [`ClockedLed::CURRENT_DRAW`](crate::driver::ClockedLed::CURRENT_DRAW)), so you can model your
+//! chipset with [`PowerModel::new`]:
+//!
+//! ```rust
+//! use blinksy::{driver::ClocklessLed, leds::Ws2815, power::PowerModel};
+//!
+//! let model = PowerModel::new(Ws2815::CURRENT_DRAW, 12.);
+//! ```
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::{ColorCorrection, LinearSrgb},
+//!     power::PowerModel,
+//! };
+//!
+//! let model = PowerModel::default();
+//! let pixels = [LinearSrgb::new(1., 1., 1.); 10];
+//!
+//! let estimate = model.estimate(pixels, 0.5, ColorCorrection::default());
+//!
+//! // 10 LEDs at half-brightness white: 10 × (30 mA + 1 mA idle)
+//! assert!((estimate.milliamps - 310.).abs() < 1e-3);
+//! assert!((estimate.watts() - 1.55).abs() < 1e-3);
+//! ```
+
+use crate::color::{apply_output_pipeline, ColorCorrection, FromColor, LinearSrgb};
+
+/// The current drawn by one LED of a chipset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurrentDraw {
+    /// Current drawn by one color channel at full output, in milliamps
+    pub milliamps_per_channel: f32,
+    /// Current drawn by one LED with all channels off, in milliamps
+    pub idle_milliamps: f32,
+    /// Current drawn by the white channel at full output, in milliamps, for RGBW chipsets
+    pub white_milliamps: Option<f32>,
+}
+
+impl CurrentDraw {
+    /// The common rule of thumb for 5V RGB LEDs: 20 mA per channel, 1 mA at idle.
+    pub const DEFAULT: CurrentDraw = CurrentDraw::new(20., 1.);
+
+    /// Creates a current draw.
+    ///
+    /// # Arguments
+    ///
+    /// - `milliamps_per_channel` - Current drawn by one color channel at full output
+    /// - `idle_milliamps` - Current drawn by one LED with all channels off
+    pub const fn new(milliamps_per_channel: f32, idle_milliamps: f32) -> Self {
+        Self {
+            milliamps_per_channel,
+            idle_milliamps,
+            white_milliamps: None,
+        }
+    }
+
+    /// Adds a white channel, for RGBW chipsets.
+    ///
+    /// # Arguments
+    ///
+    /// - `white_milliamps` - Current drawn by the white channel at full output
+    pub const fn with_white(self, white_milliamps: f32) -> Self {
+        Self {
+            white_milliamps: Some(white_milliamps),
+            ..self
+        }
+    }
+}
+
+impl Default for CurrentDraw {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A model of the current drawn by LEDs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerModel {
+    /// Current drawn by one color channel at full output, in milliamps (default: 20)
+    pub milliamps_per_channel: f32,
+    /// Current drawn by one LED with all channels off, in milliamps (default: 1)
+    pub idle_milliamps_per_led: f32,
+    /// Current drawn by the white channel at full output, in milliamps, for RGBW chipsets
+    /// (default: none)
+    pub white_milliamps: Option<f32>,
+    /// Supply voltage, in volts (default: 5)
+    pub voltage: f32,
+}
+
+impl Default for PowerModel {
+    fn default() -> Self {
+        Self::new(CurrentDraw::DEFAULT, 5.)
+    }
+}
+
+impl PowerModel {
+    /// Creates a model from the current drawn by a chipset.
+    ///
+    /// # Arguments
+    ///
+    /// - `current_draw` - The current drawn by one LED of the chipset
+    /// - `voltage` - Supply voltage, in volts
+    pub const fn new(current_draw: CurrentDraw, voltage: f32) -> Self {
+        Self {
+            milliamps_per_channel: current_draw.milliamps_per_channel,
+            idle_milliamps_per_led: current_draw.idle_milliamps,
+            white_milliamps: current_draw.white_milliamps,
+            voltage,
+        }
+    }
+
+    /// Estimates the power drawn by a frame of LEDs.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels` - The colors of the LEDs
+    /// - `brightness` - Global brightness, from 0.0 to 1.0
+    /// - `correction` - Global color correction
+    ///
+    /// # Returns
+    ///
+    /// The estimated current and voltage
+    pub fn estimate<Pixels, Color>(
+        &self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> PowerEstimate
+    where
+        Pixels: IntoIterator<Item = Color>,
+        LinearSrgb: FromColor<Color>,
+    {
+        let mut led_count = 0;
+        let mut channel_levels = 0.;
+        let mut white_levels = 0.;
+        for color in pixels {
+            let mut color = LinearSrgb::from_color(color);
+            if self.white_milliamps.is_some() {
+                // Extract white the same way as `LedRgbw::from_linear_srgb`
+                let white = color.red.min(color.green).min(color.blue);
+                color = LinearSrgb::new(color.red - white, color.green - white, color.blue - white);
+                white_levels += (white * brightness).clamp(0., 1.);
+            }
+            let LinearSrgb { red, green, blue } =
+                apply_output_pipeline(color, brightness, correction);
+            led_count += 1;
+            channel_levels += red + green + blue;
+        }
+
+        PowerEstimate {
+            milliamps: channel_levels * self.milliamps_per_channel
+                + white_levels * self.white_milliamps.unwrap_or(0.)
+                + led_count as f32 * self.idle_milliamps_per_led,
+            voltage: self.voltage,
+        }
+    }
+}
+
+/// The estimated power drawn by a frame of LEDs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerEstimate {
+    /// Current, in milliamps
+    pub milliamps: f32,
+    /// Supply voltage, in volts
+    pub voltage: f32,
+}
+
+impl PowerEstimate {
+    /// Returns the current, in amps.
+    pub fn amps(&self) -> f32 {
+        self.milliamps / 1000.
+    }
+
+    /// Returns the power, in watts.
+    pub fn watts(&self) -> f32 {
+        self.amps() * self.voltage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgbw_white_is_drawn_by_the_white_channel() {
+        let model = PowerModel::new(CurrentDraw::new(18., 1.).with_white(20.), 5.);
+        let correction = ColorCorrection::default();
+
+        // Full white is all white channel
+        let white = model.estimate([LinearSrgb::new(1., 1., 1.)], 1., correction);
+        assert!((white.milliamps - 21.).abs() < 1e-3);
+
+        // Only the common level is extracted as white
+        let pink = model.estimate([LinearSrgb::new(1., 0.5, 0.5)], 1., correction);
+        assert!((pink.milliamps - (0.5 * 18. + 0.5 * 20. + 1.)).abs() < 1e-3);
+    }
+}
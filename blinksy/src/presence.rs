@@ -0,0 +1,173 @@
+//! # Presence Activation
+//!
+//! Stair and hallway lights only need to be on while someone is there. A [`PresenceSensor`],
+//! e.g. a PIR or radar motion sensor, tells [`Control`](crate::control::Control) when someone
+//! is present: the LEDs fade in, stay on for a while after the last motion, then fade out.
+//!
+//! The sensor is anything implementing [`PresenceSensor`], including any closure returning
+//! whether someone is present, or a digital input pin with [`PresencePin`].
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::presence::{PresencePin, PresenceTimeout};
+//!
+//! let mut sensor = PresencePin::new(pir_pin);
+//! control.set_presence_timeout(Some(PresenceTimeout {
+//!     hold_in_ms: 60_000,
+//!     ..Default::default()
+//! }));
+//!
+//! loop {
+//!     let time_in_ms = elapsed().as_millis();
+//!     control.update_presence(&mut sensor, time_in_ms);
+//!     control.tick(time_in_ms).unwrap();
+//! }
+//! ```
+
+use embedded_hal::digital::InputPin;
+
+/// A motion or presence sensor.
+pub trait PresenceSensor {
+    /// Returns whether someone is present (or moving) now.
+    fn is_present(&mut self) -> bool;
+}
+
+impl<F> PresenceSensor for F
+where
+    F: FnMut() -> bool,
+{
+    fn is_present(&mut self) -> bool {
+        self()
+    }
+}
+
+/// A presence sensor with a digital output, like most PIR and radar modules.
+///
+/// # Type Parameters
+///
+/// - `Pin` - The input pin connected to the sensor output
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PresencePin<Pin> {
+    pin: Pin,
+    is_active_low: bool,
+}
+
+impl<Pin> PresencePin<Pin>
+where
+    Pin: InputPin,
+{
+    /// Wraps an input pin, which is high while someone is present.
+    ///
+    /// # Arguments
+    ///
+    /// - `pin` - The input pin connected to the sensor output
+    pub fn new(pin: Pin) -> Self {
+        Self {
+            pin,
+            is_active_low: false,
+        }
+    }
+
+    /// Inverts the pin, for a sensor which is low while someone is present.
+    pub fn active_low(mut self) -> Self {
+        self.is_active_low = true;
+        self
+    }
+
+    /// Unwraps the input pin.
+    pub fn into_inner(self) -> Pin {
+        self.pin
+    }
+}
+
+impl<Pin> PresenceSensor for PresencePin<Pin>
+where
+    Pin: InputPin,
+{
+    /// Reads the pin, where a failed read counts as no presence.
+    fn is_present(&mut self) -> bool {
+        let is_high = self.pin.is_high().unwrap_or(self.is_active_low);
+        is_high != self.is_active_low
+    }
+}
+
+/// How long the LEDs stay on after presence, and how they fade in and out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PresenceTimeout {
+    /// How long to stay on after the last presence, in milliseconds (default: 30000)
+    pub hold_in_ms: u64,
+    /// Duration of the fade in on presence, in milliseconds (default: 500)
+    pub fade_in_in_ms: u64,
+    /// Duration of the fade out after the hold, in milliseconds (default: 3000)
+    pub fade_out_in_ms: u64,
+}
+
+impl Default for PresenceTimeout {
+    fn default() -> Self {
+        Self {
+            hold_in_ms: 30_000,
+            fade_in_in_ms: 500,
+            fade_out_in_ms: 3000,
+        }
+    }
+}
+
+/// Brightness fade from the presence timeout, for the control system.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub(crate) struct PresenceFade {
+    pub(crate) timeout: PresenceTimeout,
+    last_presence_in_ms: Option<u64>,
+    last_time_in_ms: Option<u64>,
+    pub(crate) scale: f32,
+}
+
+impl PresenceFade {
+    pub(crate) fn new(timeout: PresenceTimeout) -> Self {
+        Self {
+            timeout,
+            last_presence_in_ms: None,
+            last_time_in_ms: None,
+            scale: 0.,
+        }
+    }
+
+    /// Records presence at this time.
+    pub(crate) fn detect(&mut self, time_in_ms: u64) {
+        self.last_presence_in_ms = Some(time_in_ms);
+    }
+
+    /// Returns whether the LEDs are held on, from recent presence.
+    pub(crate) fn is_held(&self, time_in_ms: u64) -> bool {
+        self.last_presence_in_ms.is_some_and(|last_presence_in_ms| {
+            time_in_ms.saturating_sub(last_presence_in_ms) <= self.timeout.hold_in_ms
+        })
+    }
+
+    /// Updates and returns the brightness scale for the current time.
+    pub(crate) fn update(&mut self, time_in_ms: u64) -> f32 {
+        let elapsed_in_ms = time_in_ms.saturating_sub(self.last_time_in_ms.unwrap_or(time_in_ms));
+        self.last_time_in_ms = Some(time_in_ms);
+
+        let (target, duration_in_ms) = if self.is_held(time_in_ms) {
+            (1., self.timeout.fade_in_in_ms)
+        } else {
+            (0., self.timeout.fade_out_in_ms)
+        };
+        self.scale = if duration_in_ms == 0 {
+            target
+        } else {
+            let step = elapsed_in_ms as f32 / duration_in_ms as f32;
+            if target > self.scale {
+                (self.scale + step).min(target)
+            } else {
+                (self.scale - step).max(target)
+            }
+        };
+        self.scale
+    }
+}
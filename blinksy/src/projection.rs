@@ -0,0 +1,287 @@
+//! # 1D Projections
+//!
+//! [`Projected`] runs any 1D pattern on a 2D or 3D layout, by projecting each LED's position
+//! onto a line: the pattern sees a 1D layout where each pixel is at its projected position.
+//!
+//! The built-in projections are:
+//!
+//! - [`AxisX`], [`AxisY`], [`AxisZ`]: Position along an axis, for sweeps across the layout.
+//! - [`Radial`]: Distance from the center, for rings expanding outwards.
+//! - [`Angular`]: Angle around the center, for sweeps like a radar.
+//!
+//! Or implement [`Projection`] for your own.
+//!
+//! Projected positions are not evenly spaced and may be outside of -1.0 to 1.0 (e.g. the
+//! corners of a square layout are further than 1.0 from the center), so a 1D pattern should
+//! use positions from `Layout::points()`, as the built-in patterns do.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     layout2d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     projection::Radial,
+//!     ControlBuilder,
+//! };
+//! # use blinksy::driver::NullDriver;
+//!
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 16,
+//!         vertical_pixel_count: 16,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! // The 1D rainbow, as rings from the center
+//! let mut control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_projected_pattern::<Rainbow, Radial>(RainbowParams::default())
+//! #   .with_driver(NullDriver)
+//! #   .with_frame_buffer_size::<0>()
+//!     // ...
+//!     .build();
+//! ```
+
+use core::{f32::consts::PI, marker::PhantomData};
+
+use glam::{Vec2, Vec3};
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{
+    driver::ColorCapability,
+    layout::{Layout1d, Layout2d, Layout3d},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern,
+};
+
+/// Trait for projecting a point onto a line.
+///
+/// # Type Parameters
+///
+/// - `Point` - The point type ([`Vec2`] or [`Vec3`])
+pub trait Projection<Point> {
+    /// Projects a point onto a line.
+    ///
+    /// # Returns
+    ///
+    /// The 1D position, mostly between -1.0 and 1.0
+    fn project(point: Point) -> f32;
+}
+
+/// Projects onto the X axis: from -1.0 at the left to 1.0 at the right.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AxisX;
+
+impl Projection<Vec2> for AxisX {
+    fn project(point: Vec2) -> f32 {
+        point.x
+    }
+}
+
+impl Projection<Vec3> for AxisX {
+    fn project(point: Vec3) -> f32 {
+        point.x
+    }
+}
+
+/// Projects onto the Y axis: from -1.0 at the bottom to 1.0 at the top.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AxisY;
+
+impl Projection<Vec2> for AxisY {
+    fn project(point: Vec2) -> f32 {
+        point.y
+    }
+}
+
+impl Projection<Vec3> for AxisY {
+    fn project(point: Vec3) -> f32 {
+        point.y
+    }
+}
+
+/// Projects onto the Z axis: from -1.0 at the back to 1.0 at the front.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AxisZ;
+
+impl Projection<Vec3> for AxisZ {
+    fn project(point: Vec3) -> f32 {
+        point.z
+    }
+}
+
+/// Projects onto the distance from the center: from -1.0 at the center to 1.0 at a distance
+/// of 1.0.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Radial;
+
+impl Projection<Vec2> for Radial {
+    fn project(point: Vec2) -> f32 {
+        2. * point.length() - 1.
+    }
+}
+
+impl Projection<Vec3> for Radial {
+    fn project(point: Vec3) -> f32 {
+        2. * point.length() - 1.
+    }
+}
+
+/// Projects onto the angle around the center: from -1.0 to 1.0 counter-clockwise, starting
+/// and ending at the left.
+///
+/// In 3D, the angle is around the vertical (Y) axis.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Angular;
+
+impl Projection<Vec2> for Angular {
+    fn project(point: Vec2) -> f32 {
+        point.y.atan2(point.x) / PI
+    }
+}
+
+impl Projection<Vec3> for Angular {
+    fn project(point: Vec3) -> f32 {
+        point.z.atan2(point.x) / PI
+    }
+}
+
+/// The 1D layout seen by a [`Projected`] pattern: the projected positions of a 2D or 3D
+/// layout.
+///
+/// # Type Parameters
+///
+/// - `Dim` - The dimension marker of the layout ([`Dim2d`] or [`Dim3d`])
+/// - `Layout` - The 2D or 3D layout type
+/// - `Proj` - The projection type
+#[derive(Debug)]
+pub struct ProjectedLayout<Dim, Layout, Proj> {
+    dim: PhantomData<Dim>,
+    layout: PhantomData<Layout>,
+    projection: PhantomData<Proj>,
+}
+
+impl<Layout, Proj> Layout1d for ProjectedLayout<Dim2d, Layout, Proj>
+where
+    Layout: Layout2d,
+    Proj: Projection<Vec2>,
+{
+    const PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+    fn points() -> impl Iterator<Item = f32> {
+        Layout::points().map(Proj::project)
+    }
+}
+
+impl<Layout, Proj> Layout1d for ProjectedLayout<Dim3d, Layout, Proj>
+where
+    Layout: Layout3d,
+    Proj: Projection<Vec3>,
+{
+    const PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+    fn points() -> impl Iterator<Item = f32> {
+        Layout::points().map(Proj::project)
+    }
+}
+
+/// A 1D pattern run on a 2D or 3D layout, through a projection.
+///
+/// The pattern's parameters are passed through unchanged.
+///
+/// # Type Parameters
+///
+/// - `Inner` - The 1D pattern type
+/// - `Proj` - The projection type
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Projected<Inner, Proj> {
+    pattern: Inner,
+    projection: PhantomData<Proj>,
+}
+
+impl<Inner, Proj> Projected<Inner, Proj> {
+    /// Returns the inner 1D pattern.
+    pub fn inner(&self) -> &Inner {
+        &self.pattern
+    }
+}
+
+impl<Inner, Proj, Layout> Pattern<Dim2d, Layout> for Projected<Inner, Proj>
+where
+    Layout: Layout2d,
+    Proj: Projection<Vec2>,
+    Inner: Pattern<Dim1d, ProjectedLayout<Dim2d, Layout, Proj>>,
+{
+    type Params = Inner::Params;
+    type Color = Inner::Color;
+
+    fn new(params: Self::Params) -> Self {
+        Self {
+            pattern: Inner::new(params),
+            projection: PhantomData,
+        }
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.pattern.tick(time_in_ms)
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        self.pattern.params()
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        self.pattern.params_mut()
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.pattern.set_color_capability(capability);
+    }
+}
+
+impl<Inner, Proj, Layout> Pattern<Dim3d, Layout> for Projected<Inner, Proj>
+where
+    Layout: Layout3d,
+    Proj: Projection<Vec3>,
+    Inner: Pattern<Dim1d, ProjectedLayout<Dim3d, Layout, Proj>>,
+{
+    type Params = Inner::Params;
+    type Color = Inner::Color;
+
+    fn new(params: Self::Params) -> Self {
+        Self {
+            pattern: Inner::new(params),
+            projection: PhantomData,
+        }
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.pattern.tick(time_in_ms)
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        self.pattern.params()
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        self.pattern.params_mut()
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.pattern.set_color_capability(capability);
+    }
+}
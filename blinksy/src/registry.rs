@@ -0,0 +1,364 @@
+//! # Pattern Registry
+//!
+//! A [`PatternRegistry`] maps string names to pattern factories, so a pattern can be selected
+//! by name at runtime: from a network request, a button press, or a config file.
+//!
+//! As [`Control`](crate::Control) holds a single pattern type, the registered patterns are
+//! first combined into one enum with [`pattern_set!`](crate::pattern_set), which implements
+//! [`Pattern`](crate::pattern::Pattern) by dispatching to the selected variant. Each variant is
+//! constructed from its default parameters.
+//!
+//! For the built-in patterns, use [`builtin_patterns!`](crate::builtin_patterns).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     layout1d,
+//!     markers::Dim1d,
+//!     pattern_set,
+//!     patterns::{
+//!         noise::{noise_fns, Noise1d},
+//!         rainbow::Rainbow,
+//!     },
+//! };
+//!
+//! layout1d!(Layout, 60);
+//!
+//! pattern_set! {
+//!     /// The patterns to choose from.
+//!     pub enum Patterns: Dim1d {
+//!         Rainbow(Rainbow) = "rainbow",
+//!         Noise(Noise1d<noise_fns::Perlin>) = "noise",
+//!     }
+//! }
+//!
+//! let registry = Patterns::registry::<Layout>();
+//! assert_eq!(registry.names().collect::<heapless::Vec<_, 2>>(), ["rainbow", "noise"]);
+//!
+//! let pattern = registry.create("noise").unwrap();
+//! assert!(matches!(pattern, Patterns::Noise(_)));
+//! assert!(registry.create("sparkle").is_none());
+//!
+//! // Then give the pattern to your control system:
+//! // control.set_pattern(pattern);
+//! ```
+
+use heapless::Vec;
+
+/// A named pattern factory.
+///
+/// # Type Parameters
+///
+/// - `Pattern` - The pattern type constructed
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PatternEntry<Pattern> {
+    /// Name of the pattern
+    pub name: &'static str,
+    /// Constructs the pattern
+    pub factory: fn() -> Pattern,
+}
+
+/// Errors when registering a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RegistryError {
+    /// Raised if the registry is already at capacity
+    Full,
+    /// Raised if a pattern with the same name is already registered
+    DuplicateName,
+}
+
+/// A fixed-capacity registry of named pattern factories.
+///
+/// Patterns keep the order they were registered in, so they can also be selected by index
+/// (e.g. to cycle through patterns with a button).
+///
+/// # Type Parameters
+///
+/// - `Pattern` - The pattern type constructed, usually from [`pattern_set!`](crate::pattern_set)
+/// - `CAPACITY` - The maximum number of patterns
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PatternRegistry<Pattern, const CAPACITY: usize> {
+    entries: Vec<PatternEntry<Pattern>, CAPACITY>,
+}
+
+impl<Pattern, const CAPACITY: usize> Default for PatternRegistry<Pattern, CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Pattern, const CAPACITY: usize> PatternRegistry<Pattern, CAPACITY> {
+    /// Creates an empty registry.
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Registers a pattern factory under a name.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - Name of the pattern
+    /// - `factory` - Constructs the pattern
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success, or an error if the registry is full or the name is taken
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        factory: fn() -> Pattern,
+    ) -> Result<(), RegistryError> {
+        if self.index_of(name).is_some() {
+            return Err(RegistryError::DuplicateName);
+        }
+        self.entries
+            .push(PatternEntry { name, factory })
+            .map_err(|_| RegistryError::Full)
+    }
+
+    /// Returns the number of registered patterns.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no patterns are registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the names of the registered patterns, in order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|entry| entry.name)
+    }
+
+    /// Finds the index of a pattern by name.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.name == name)
+    }
+
+    /// Returns the entry at the index.
+    pub fn get(&self, index: usize) -> Option<&PatternEntry<Pattern>> {
+        self.entries.get(index)
+    }
+
+    /// Constructs the pattern registered under a name.
+    ///
+    /// # Returns
+    ///
+    /// The pattern, or `None` if no pattern is registered under the name
+    pub fn create(&self, name: &str) -> Option<Pattern> {
+        self.index_of(name)
+            .and_then(|index| self.create_index(index))
+    }
+
+    /// Constructs the pattern at the index.
+    ///
+    /// # Returns
+    ///
+    /// The pattern, or `None` if no pattern is registered at the index
+    pub fn create_index(&self, index: usize) -> Option<Pattern> {
+        self.entries.get(index).map(|entry| (entry.factory)())
+    }
+}
+
+/// Combines patterns into one enum, selectable at runtime.
+///
+/// The enum implements [`Pattern`](crate::pattern::Pattern) for the given dimension, for any
+/// layout all variants support, producing [`LinearSrgb`](crate::color::LinearSrgb) colors. Its
+/// params are the enum itself, so `with_pattern::<Patterns>(pattern)` takes an already
+/// constructed pattern.
+///
+/// Also generated:
+///
+/// - `NAMES`: The names of the patterns, in order
+/// - `registry::<Layout>()`: A [`PatternRegistry`] of all patterns, each constructed from
+///   their default params
+///
+/// See [the module docs](crate::registry) for an example.
+#[macro_export]
+macro_rules! pattern_set {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident: $dim:ty {
+            $($variant:ident($pattern:ty) = $pattern_name:literal),+ $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis enum $name {
+            $(
+                #[doc = concat!("The `", $pattern_name, "` pattern")]
+                $variant($pattern),
+            )+
+        }
+
+        impl $name {
+            /// The names of the patterns, in order.
+            pub const NAMES: &'static [&'static str] = &[$($pattern_name),+];
+
+            /// Returns a registry of all patterns, each constructed from their default params.
+            pub fn registry<Layout>(
+            ) -> $crate::registry::PatternRegistry<Self, { $name::NAMES.len() }>
+            where
+                Layout: $crate::layout::LayoutForDim<$dim>,
+                $(
+                    $pattern: $crate::pattern::Pattern<$dim, Layout>,
+                    <$pattern as $crate::pattern::Pattern<$dim, Layout>>::Params: Default,
+                )+
+            {
+                let mut registry = $crate::registry::PatternRegistry::new();
+                $(
+                    registry
+                        .register($pattern_name, || {
+                            $name::$variant(<$pattern as $crate::pattern::Pattern<
+                                $dim,
+                                Layout,
+                            >>::new(Default::default()))
+                        })
+                        .expect("pattern names are unique");
+                )+
+                registry
+            }
+        }
+
+        impl<Layout> $crate::pattern::Pattern<$dim, Layout> for $name
+        where
+            Layout: $crate::layout::LayoutForDim<$dim>,
+            $(
+                $pattern: $crate::pattern::Pattern<$dim, Layout>,
+                $crate::color::LinearSrgb: $crate::color::FromColor<
+                    <$pattern as $crate::pattern::Pattern<$dim, Layout>>::Color,
+                >,
+            )+
+        {
+            type Params = Self;
+            type Color = $crate::color::LinearSrgb;
+
+            fn new(params: Self::Params) -> Self {
+                params
+            }
+
+            fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+                #[allow(non_camel_case_types)]
+                enum Pixels<$($variant),+> {
+                    $($variant($variant)),+
+                }
+
+                impl<$($variant),+> Iterator for Pixels<$($variant),+>
+                where
+                    $($variant: Iterator<Item = $crate::color::LinearSrgb>),+
+                {
+                    type Item = $crate::color::LinearSrgb;
+
+                    fn next(&mut self) -> Option<Self::Item> {
+                        match self {
+                            $(Self::$variant(pixels) => pixels.next()),+
+                        }
+                    }
+                }
+
+                match self {
+                    $(
+                        Self::$variant(pattern) => Pixels::$variant(
+                            <$pattern as $crate::pattern::Pattern<$dim, Layout>>::tick(
+                                pattern,
+                                time_in_ms,
+                            )
+                            .map(<$crate::color::LinearSrgb as $crate::color::FromColor<_>>::from_color),
+                        ),
+                    )+
+                }
+            }
+
+            fn set_color_capability(&mut self, capability: $crate::driver::ColorCapability) {
+                match self {
+                    $(
+                        Self::$variant(pattern) => {
+                            <$pattern as $crate::pattern::Pattern<$dim, Layout>>::set_color_capability(
+                                pattern,
+                                capability,
+                            )
+                        }
+                    )+
+                }
+            }
+        }
+    };
+}
+
+/// Combines the built-in patterns into one enum, with [`pattern_set!`](crate::pattern_set).
+///
+/// The patterns are:
+///
+/// - `"rainbow"`: [`Rainbow`](crate::patterns::rainbow::Rainbow)
+/// - `"noise-perlin"`, `"noise-simplex"`, `"noise-open-simplex"`: the
+///   [noise](crate::patterns::noise) patterns for the dimension (feature `noise`)
+///
+/// ```rust
+/// use blinksy::{builtin_patterns, layout2d, layout::{Shape2d, Vec2}};
+///
+/// layout2d!(
+///     Layout,
+///     [Shape2d::Point(Vec2::new(0., 0.))]
+/// );
+///
+/// builtin_patterns!(pub enum Patterns: Dim2d);
+///
+/// let registry = Patterns::registry::<Layout>();
+/// assert!(registry.create("rainbow").is_some());
+/// ```
+#[cfg(feature = "noise")]
+#[macro_export]
+macro_rules! builtin_patterns {
+    ($(#[$attr:meta])* $vis:vis enum $name:ident: Dim1d) => {
+        $crate::builtin_patterns!(@noise $(#[$attr])* $vis $name, Dim1d, Noise1d);
+    };
+    ($(#[$attr:meta])* $vis:vis enum $name:ident: Dim2d) => {
+        $crate::builtin_patterns!(@noise $(#[$attr])* $vis $name, Dim2d, Noise2d);
+    };
+    ($(#[$attr:meta])* $vis:vis enum $name:ident: Dim3d) => {
+        $crate::builtin_patterns!(@noise $(#[$attr])* $vis $name, Dim3d, Noise3d);
+    };
+    (@noise $(#[$attr:meta])* $vis:vis $name:ident, $dim:ident, $noise:ident) => {
+        $crate::pattern_set! {
+            $(#[$attr])*
+            $vis enum $name: $crate::markers::$dim {
+                Rainbow($crate::patterns::rainbow::Rainbow) = "rainbow",
+                NoisePerlin(
+                    $crate::patterns::noise::$noise<$crate::patterns::noise::noise_fns::Perlin>
+                ) = "noise-perlin",
+                NoiseSimplex(
+                    $crate::patterns::noise::$noise<$crate::patterns::noise::noise_fns::Simplex>
+                ) = "noise-simplex",
+                NoiseOpenSimplex(
+                    $crate::patterns::noise::$noise<
+                        $crate::patterns::noise::noise_fns::OpenSimplex2,
+                    >
+                ) = "noise-open-simplex",
+            }
+        }
+    };
+}
+
+/// Combines the built-in patterns into one enum, with [`pattern_set!`](crate::pattern_set).
+///
+/// The patterns are:
+///
+/// - `"rainbow"`: [`Rainbow`](crate::patterns::rainbow::Rainbow)
+#[cfg(not(feature = "noise"))]
+#[macro_export]
+macro_rules! builtin_patterns {
+    ($(#[$attr:meta])* $vis:vis enum $name:ident: $dim:ident) => {
+        $crate::pattern_set! {
+            $(#[$attr])*
+            $vis enum $name: $crate::markers::$dim {
+                Rainbow($crate::patterns::rainbow::Rainbow) = "rainbow",
+            }
+        }
+    };
+}
@@ -0,0 +1,536 @@
+//! # Remapped Layouts
+//!
+//! Patterns compute their colors in the order of their layout's points, which is usually the
+//! wiring order. [`Remapped`] decouples the two: the pattern computes on a logical layout, in
+//! layout-space, then a [`PixelMap`] picks which logical pixel each LED shows, in wiring order.
+//!
+//! This is for:
+//!
+//! - Irregular installations, mapped at runtime (e.g. with a camera): the pattern computes on
+//!   a regular logical layout (e.g. a grid), and the map, loaded at runtime, has the logical
+//!   pixel nearest to each LED.
+//! - Drivers which support addressing (e.g. network protocols), sent the pixels in another
+//!   order: every Nth pixel with [`PixelMap::strided`], or spatially sorted with
+//!   [`PixelMap::sorted_by_key`].
+//!
+//! With the `serde` feature, a [`PixelMap`] can be (de)serialized, e.g. to store it in flash.
+//!
+//! ## Output transforms
+//!
+//! A map also fixes a mounting, without a new layout: reverse a strip with
+//! [`PixelMap::reversed`], mirror its halves with [`PixelMap::mirrored`], or rotate a grid with
+//! [`PixelMap::rotated_grid`]. To choose at runtime (e.g. from a setting), describe it as an
+//! [`OutputTransform`], and [`Remapped::set_map`] its [`pixel_map`](OutputTransform::pixel_map).
+//!
+//! ## Logical pixel count
+//!
+//! The computed colors are buffered, so until [the `generic_const_exprs` feature] is stable,
+//! you must explicitly provide the logical pixel count, as `Logical::PIXEL_COUNT`.
+//!
+//! [the `generic_const_exprs` feature]: https://doc.rust-lang.org/beta/unstable-book/language-features/generic-const-exprs.html
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     layout2d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     remap::PixelMap,
+//!     ControlBuilder,
+//! };
+//! # use blinksy::driver::NullDriver;
+//!
+//! // The pattern computes on a 4x4 grid
+//! layout2d!(
+//!     Grid,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 4,
+//!         vertical_pixel_count: 4,
+//!         serpentine: false,
+//!     }]
+//! );
+//!
+//! // The 6 LEDs, wired in an irregular path
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Line {
+//!         start: Vec2::new(-1., 0.),
+//!         end: Vec2::new(1., 0.),
+//!         pixel_count: 6,
+//!     }]
+//! );
+//!
+//! // The grid cell of each LED, e.g. loaded from flash
+//! let map = PixelMap::from_indices(&[0, 5, 10, 15, 9, 6], Grid::PIXEL_COUNT).unwrap();
+//!
+//! let mut control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_remapped_pattern::<Rainbow, Grid, { Grid::PIXEL_COUNT }>(
+//!         RainbowParams::default(),
+//!         map,
+//!     )
+//! #   .with_driver(NullDriver)
+//! #   .with_frame_buffer_size::<0>()
+//!     // ...
+//!     .build();
+//! ```
+
+use core::{cmp::Ordering, marker::PhantomData};
+
+use heapless::Vec;
+
+use crate::{driver::ColorCapability, layout::LayoutForDim, pattern::Pattern};
+
+/// All types of errors that can happen while creating a [`PixelMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PixelMapError {
+    /// Raised if there are more indices than the map holds
+    TooLong,
+    /// Raised if an index isn't a pixel of the logical layout
+    OutOfRange(u16),
+}
+
+/// A rotation of a grid, by quarter turns clockwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GridRotation {
+    /// Not rotated
+    #[default]
+    None,
+    /// Rotated a quarter turn clockwise
+    Clockwise90,
+    /// Rotated a half turn
+    Clockwise180,
+    /// Rotated three quarter turns clockwise (a quarter turn counterclockwise)
+    Clockwise270,
+}
+
+/// A transform of the output, to fix how the LEDs are mounted, as a [`PixelMap`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputTransform {
+    /// Shown as computed
+    #[default]
+    Identity,
+    /// Shown in reverse order, see [`PixelMap::reversed`]
+    Reverse,
+    /// The first half shown mirrored on the second half, see [`PixelMap::mirrored`]
+    Mirror,
+    /// A grid shown rotated, see [`PixelMap::rotated_grid`]
+    RotateGrid {
+        /// The number of LEDs in each row
+        width: u16,
+        /// The number of rows
+        height: u16,
+        /// Whether every other row is reversed
+        serpentine: bool,
+        /// The rotation
+        rotation: GridRotation,
+    },
+}
+
+impl OutputTransform {
+    /// Returns the map for this transform.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `PIXEL_COUNT` - The number of LEDs
+    pub fn pixel_map<const PIXEL_COUNT: usize>(&self) -> PixelMap<PIXEL_COUNT> {
+        match *self {
+            OutputTransform::Identity => PixelMap::identity(PIXEL_COUNT),
+            OutputTransform::Reverse => PixelMap::reversed(),
+            OutputTransform::Mirror => PixelMap::mirrored(),
+            OutputTransform::RotateGrid {
+                width,
+                height,
+                serpentine,
+                rotation,
+            } => PixelMap::rotated_grid(width as usize, height as usize, serpentine, rotation),
+        }
+    }
+}
+
+/// Which logical pixel each LED shows, in wiring order.
+///
+/// # Type Parameters
+///
+/// - `PIXEL_COUNT` - The maximum number of LEDs
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PixelMap<const PIXEL_COUNT: usize> {
+    indices: Vec<u16, PIXEL_COUNT>,
+}
+
+impl<const PIXEL_COUNT: usize> PixelMap<PIXEL_COUNT> {
+    /// Creates a map where each LED shows the logical pixel of the same index.
+    ///
+    /// # Arguments
+    ///
+    /// - `logical_pixel_count` - The number of pixels in the logical layout
+    pub fn identity(logical_pixel_count: usize) -> Self {
+        Self {
+            indices: (0..logical_pixel_count.min(PIXEL_COUNT) as u16).collect(),
+        }
+    }
+
+    /// Creates a map where the LEDs show the logical pixels in reverse order, e.g. for a strip
+    /// wired from the other end.
+    pub fn reversed() -> Self {
+        Self {
+            indices: (0..PIXEL_COUNT as u16).rev().collect(),
+        }
+    }
+
+    /// Creates a map where the LEDs of the second half show the logical pixels of the first
+    /// half, mirrored: e.g. for a strip folded in the middle.
+    pub fn mirrored() -> Self {
+        Self {
+            indices: (0..PIXEL_COUNT)
+                .map(|index| index.min(PIXEL_COUNT - 1 - index) as u16)
+                .collect(),
+        }
+    }
+
+    /// Creates a map for a grid mounted rotated: the LEDs show the logical grid, rotated.
+    ///
+    /// The LEDs and the logical pixels are both in rows, as from a
+    /// [`Shape2d::Grid`](crate::layout::Shape2d::Grid), wired the same way. For a quarter turn,
+    /// the logical grid is `height` pixels wide and `width` pixels tall.
+    ///
+    /// # Arguments
+    ///
+    /// - `width` - The number of LEDs in each row
+    /// - `height` - The number of rows
+    /// - `serpentine` - Whether every other row is reversed
+    /// - `rotation` - How to rotate the logical grid
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blinksy::remap::{GridRotation, PixelMap};
+    ///
+    /// // A 2x2 grid, rotated a quarter turn: the top-left logical pixel is shown top-right,
+    /// // and the bottom-right logical pixel is shown bottom-left
+    /// let map = PixelMap::<4>::rotated_grid(2, 2, false, GridRotation::Clockwise90);
+    /// assert_eq!(map.indices(), [1, 3, 0, 2]);
+    /// ```
+    pub fn rotated_grid(
+        width: usize,
+        height: usize,
+        serpentine: bool,
+        rotation: GridRotation,
+    ) -> Self {
+        let index = |x: usize, y: usize, width: usize| {
+            let x = if serpentine && y % 2 == 1 {
+                width - 1 - x
+            } else {
+                x
+            };
+            (y * width + x) as u16
+        };
+        let indices = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                // Undo the serpentine wiring of the LED
+                let x = if serpentine && y % 2 == 1 {
+                    width - 1 - x
+                } else {
+                    x
+                };
+                match rotation {
+                    GridRotation::None => index(x, y, width),
+                    GridRotation::Clockwise90 => index(height - 1 - y, x, height),
+                    GridRotation::Clockwise180 => index(width - 1 - x, height - 1 - y, width),
+                    GridRotation::Clockwise270 => index(y, width - 1 - x, height),
+                }
+            })
+            .take(PIXEL_COUNT)
+            .collect();
+        Self { indices }
+    }
+
+    /// Creates a map of every Nth pixel: the LEDs show logical pixels `0, N, 2N, ..`, then
+    /// `1, N + 1, 2N + 1, ..`, and so on.
+    ///
+    /// # Arguments
+    ///
+    /// - `stride` - The step between pixels, `N` (0 is the same as 1)
+    /// - `logical_pixel_count` - The number of pixels in the logical layout
+    pub fn strided(stride: usize, logical_pixel_count: usize) -> Self {
+        let stride = stride.max(1);
+        Self {
+            indices: (0..stride)
+                .flat_map(|offset| (offset..logical_pixel_count).step_by(stride))
+                .map(|index| index as u16)
+                .take(PIXEL_COUNT)
+                .collect(),
+        }
+    }
+
+    /// Creates a map sorted by a key of each pixel: the LEDs show the logical pixels in
+    /// ascending order of their key, e.g. their position along an axis.
+    ///
+    /// # Arguments
+    ///
+    /// - `points` - The position of each logical pixel, e.g. `Layout::points()`
+    /// - `key` - The key of each position
+    pub fn sorted_by_key<Point>(
+        points: impl IntoIterator<Item = Point>,
+        key: impl Fn(&Point) -> f32,
+    ) -> Self {
+        let mut keyed: Vec<(f32, u16), PIXEL_COUNT> = points
+            .into_iter()
+            .take(PIXEL_COUNT)
+            .enumerate()
+            .map(|(index, point)| (key(&point), index as u16))
+            .collect();
+        keyed.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        Self {
+            indices: keyed.into_iter().map(|(_, index)| index).collect(),
+        }
+    }
+
+    /// Creates a map from the logical pixel of each LED.
+    ///
+    /// # Arguments
+    ///
+    /// - `indices` - The index of the logical pixel of each LED, in wiring order
+    /// - `logical_pixel_count` - The number of pixels in the logical layout
+    ///
+    /// # Returns
+    ///
+    /// The map, or an error if there are too many indices or any is out of range
+    pub fn from_indices(
+        indices: &[u16],
+        logical_pixel_count: usize,
+    ) -> Result<Self, PixelMapError> {
+        if let Some(&index) = indices
+            .iter()
+            .find(|&&index| index as usize >= logical_pixel_count)
+        {
+            return Err(PixelMapError::OutOfRange(index));
+        }
+        Ok(Self {
+            indices: Vec::from_slice(indices).map_err(|_| PixelMapError::TooLong)?,
+        })
+    }
+
+    /// Returns the index of the logical pixel of each LED, in wiring order.
+    pub fn indices(&self) -> &[u16] {
+        &self.indices
+    }
+}
+
+impl<const PIXEL_COUNT: usize> Default for PixelMap<PIXEL_COUNT> {
+    /// The identity map, for a logical layout of `PIXEL_COUNT` pixels.
+    fn default() -> Self {
+        Self::identity(PIXEL_COUNT)
+    }
+}
+
+/// A pattern computed on a logical layout, with its colors shown in the order of a
+/// [`PixelMap`].
+///
+/// The pattern's parameters are passed through unchanged. LEDs mapped to a missing logical
+/// pixel are black, so every other LED still shows its own pixel.
+///
+/// # Type Parameters
+///
+/// - `Inner` - The pattern type, for the logical layout
+/// - `Logical` - The logical layout type
+/// - `LOGICAL_PIXEL_COUNT` - The number of pixels in the logical layout
+/// - `PIXEL_COUNT` - The number of LEDs
+#[derive(Debug)]
+pub struct Remapped<Inner, Logical, const LOGICAL_PIXEL_COUNT: usize, const PIXEL_COUNT: usize> {
+    pattern: Inner,
+    map: PixelMap<PIXEL_COUNT>,
+    logical: PhantomData<Logical>,
+}
+
+impl<Inner, Logical, const LOGICAL_PIXEL_COUNT: usize, const PIXEL_COUNT: usize>
+    Remapped<Inner, Logical, LOGICAL_PIXEL_COUNT, PIXEL_COUNT>
+{
+    /// Creates a remapped pattern from an inner pattern and its map.
+    ///
+    /// # Arguments
+    ///
+    /// - `pattern` - The pattern, for the logical layout
+    /// - `map` - The logical pixel of each LED
+    pub fn with_map(pattern: Inner, map: PixelMap<PIXEL_COUNT>) -> Self {
+        Self {
+            pattern,
+            map,
+            logical: PhantomData,
+        }
+    }
+
+    /// Returns the inner pattern.
+    pub fn inner(&self) -> &Inner {
+        &self.pattern
+    }
+
+    /// Returns the map.
+    pub fn map(&self) -> &PixelMap<PIXEL_COUNT> {
+        &self.map
+    }
+
+    /// Replaces the map, e.g. with one loaded at runtime.
+    ///
+    /// # Arguments
+    ///
+    /// - `map` - The logical pixel of each LED
+    pub fn set_map(&mut self, map: PixelMap<PIXEL_COUNT>) {
+        self.map = map;
+    }
+}
+
+impl<Dim, Layout, Inner, Logical, const LOGICAL_PIXEL_COUNT: usize, const PIXEL_COUNT: usize>
+    Pattern<Dim, Layout> for Remapped<Inner, Logical, LOGICAL_PIXEL_COUNT, PIXEL_COUNT>
+where
+    Layout: LayoutForDim<Dim>,
+    Logical: LayoutForDim<Dim>,
+    Inner: Pattern<Dim, Logical>,
+    Inner::Color: Clone + Default,
+{
+    type Params = Inner::Params;
+    type Color = Inner::Color;
+
+    /// Creates the pattern with the identity map.
+    fn new(params: Self::Params) -> Self {
+        Self::with_map(Inner::new(params), PixelMap::identity(LOGICAL_PIXEL_COUNT))
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let colors: Vec<Self::Color, LOGICAL_PIXEL_COUNT> = self
+            .pattern
+            .tick(time_in_ms)
+            .take(LOGICAL_PIXEL_COUNT)
+            .collect();
+
+        self.map
+            .indices()
+            .iter()
+            .map(move |&index| colors.get(index as usize).cloned().unwrap_or_default())
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        self.pattern.params()
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        self.pattern.params_mut()
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.pattern.set_color_capability(capability);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color::LinearSrgb, layout1d, markers::Dim1d};
+
+    #[test]
+    fn test_orders() {
+        assert_eq!(PixelMap::<5>::identity(5).indices(), [0, 1, 2, 3, 4]);
+        assert_eq!(PixelMap::<5>::strided(2, 5).indices(), [0, 2, 4, 1, 3]);
+        assert_eq!(PixelMap::<4>::reversed().indices(), [3, 2, 1, 0]);
+        assert_eq!(PixelMap::<5>::mirrored().indices(), [0, 1, 2, 1, 0]);
+        assert_eq!(
+            PixelMap::<4>::sorted_by_key([0.5, -1., 1., 0.], |x| *x).indices(),
+            [1, 3, 0, 2]
+        );
+        assert_eq!(
+            PixelMap::<4>::from_indices(&[3, 4], 4),
+            Err(PixelMapError::OutOfRange(4))
+        );
+        assert_eq!(
+            PixelMap::<1>::from_indices(&[0, 0], 4),
+            Err(PixelMapError::TooLong)
+        );
+    }
+
+    #[test]
+    fn test_rotated_grid() {
+        // A grid 3 LEDs wide, in 2 rows
+        let rotated =
+            |serpentine, rotation| PixelMap::<6>::rotated_grid(3, 2, serpentine, rotation);
+        assert_eq!(
+            rotated(false, GridRotation::None).indices(),
+            [0, 1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            rotated(false, GridRotation::Clockwise180).indices(),
+            [5, 4, 3, 2, 1, 0]
+        );
+        // The logical grid is 2 wide and 3 tall
+        assert_eq!(
+            rotated(false, GridRotation::Clockwise90).indices(),
+            [1, 3, 5, 0, 2, 4]
+        );
+        assert_eq!(
+            rotated(false, GridRotation::Clockwise270).indices(),
+            [4, 2, 0, 5, 3, 1]
+        );
+
+        // Both grids are serpentine
+        assert_eq!(
+            rotated(true, GridRotation::None).indices(),
+            [0, 1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            rotated(true, GridRotation::Clockwise180).indices(),
+            [3, 4, 5, 0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_logical_pixel_count() {
+        // Fewer logical pixels than LEDs
+        assert_eq!(PixelMap::<5>::identity(3).indices(), [0, 1, 2]);
+        assert_eq!(PixelMap::<5>::strided(2, 3).indices(), [0, 2, 1]);
+        // More logical pixels than LEDs
+        assert_eq!(PixelMap::<3>::identity(5).indices(), [0, 1, 2]);
+        assert_eq!(PixelMap::<3>::strided(2, 5).indices(), [0, 2, 4]);
+    }
+
+    layout1d!(Logical, 3);
+    layout1d!(Layout, 4);
+
+    /// A pattern with colors for only the first two pixels.
+    struct Short;
+
+    impl Pattern<Dim1d, Logical> for Short {
+        type Params = ();
+        type Color = LinearSrgb;
+
+        fn new(_params: Self::Params) -> Self {
+            Self
+        }
+
+        fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+            [LinearSrgb::new(1., 0., 0.), LinearSrgb::new(0., 1., 0.)].into_iter()
+        }
+    }
+
+    #[test]
+    fn test_missing_colors_are_black() {
+        type Remap = Remapped<Short, Logical, 3, 4>;
+        let map = PixelMap::from_indices(&[2, 1, 0, 1], 3).unwrap();
+        let pattern = Remap::with_map(Short, map);
+        let colors: Vec<LinearSrgb, 4> =
+            <Remap as Pattern<Dim1d, Layout>>::tick(&pattern, 0).collect();
+        let black = LinearSrgb::default();
+        let red = LinearSrgb::new(1., 0., 0.);
+        let green = LinearSrgb::new(0., 1., 0.);
+        assert_eq!(colors, [black, green, red, green]);
+    }
+}
@@ -0,0 +1,345 @@
+//! # Replicated Layouts
+//!
+//! For symmetric installations, a pattern only needs to compute part of the layout: the rest
+//! are copies. [`Mirrored`] and [`Tiled`] present a smaller logical layout to the pattern, of
+//! the first part of a physical layout, then [`Replicated`] copies the computed colors across
+//! the whole physical layout.
+//!
+//! - [`Mirrored<Layout>`]: The first half, mirrored onto the second half. The pixels of the
+//!   second half are in reverse order, so a strip folded in the middle (or a strip of two
+//!   halves wired from the center outwards) is symmetric.
+//! - [`Tiled<Layout, N>`]: The first of `N` tiles, repeated onto each tile in order.
+//!
+//! The logical layout has the positions of the first part of the physical layout.
+//!
+//! ## Logical pixel count
+//!
+//! The computed colors are buffered, so until [the `generic_const_exprs` feature] is stable,
+//! you must explicitly provide the logical pixel count, as `Logical::PIXEL_COUNT`.
+//!
+//! [the `generic_const_exprs` feature]: https://doc.rust-lang.org/beta/unstable-book/language-features/generic-const-exprs.html
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     replicate::Mirrored,
+//!     ControlBuilder,
+//! };
+//! # use blinksy::driver::NullDriver;
+//!
+//! layout1d!(Layout, 60);
+//!
+//! // The rainbow on the first 30 pixels, mirrored onto the last 30 pixels
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_replicated_pattern::<Rainbow, Mirrored<Layout>, { Mirrored::<Layout>::PIXEL_COUNT }>(
+//!         RainbowParams::default(),
+//!     )
+//! #   .with_driver(NullDriver)
+//! #   .with_frame_buffer_size::<0>()
+//!     // ...
+//!     .build();
+//! ```
+
+use core::marker::PhantomData;
+
+use heapless::Vec;
+
+use crate::{
+    driver::ColorCapability,
+    layout::{Layout1d, Layout2d, Layout3d, LayoutForDim, Shape2d, Shape3d, Vec2, Vec3},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern,
+};
+
+/// Trait for logical layouts replicated across a physical layout.
+///
+/// # Type Parameters
+///
+/// - `Dim` - The dimension marker (Dim1d, Dim2d, or Dim3d)
+pub trait Replica<Dim> {
+    /// The physical layout type.
+    type Physical: LayoutForDim<Dim>;
+
+    /// The number of pixels in the physical layout.
+    const PHYSICAL_PIXEL_COUNT: usize;
+
+    /// Maps a physical pixel to the logical pixel it copies.
+    ///
+    /// # Arguments
+    ///
+    /// - `physical_index` - Index of the pixel in the physical layout
+    ///
+    /// # Returns
+    ///
+    /// Index of the pixel in the logical layout
+    fn logical_index(physical_index: usize) -> usize;
+}
+
+/// The first half of a physical layout, mirrored onto the second half.
+///
+/// With an odd pixel count, the middle pixel is in the first half.
+///
+/// # Type Parameters
+///
+/// - `Layout` - The physical layout type
+#[derive(Debug)]
+pub struct Mirrored<Layout> {
+    layout: PhantomData<Layout>,
+}
+
+impl<Layout> Mirrored<Layout> {
+    const fn logical_pixel_count(physical_pixel_count: usize) -> usize {
+        physical_pixel_count.div_ceil(2)
+    }
+
+    const fn logical_index(physical_pixel_count: usize, physical_index: usize) -> usize {
+        if physical_index < Self::logical_pixel_count(physical_pixel_count) {
+            physical_index
+        } else {
+            physical_pixel_count - 1 - physical_index
+        }
+    }
+}
+
+/// The first of `N` tiles of a physical layout, repeated onto each tile.
+///
+/// If the pixel count isn't divisible by `N`, the last tile is partial.
+///
+/// # Type Parameters
+///
+/// - `Layout` - The physical layout type
+/// - `N` - The number of tiles
+#[derive(Debug)]
+pub struct Tiled<Layout, const N: usize> {
+    layout: PhantomData<Layout>,
+}
+
+impl<Layout, const N: usize> Tiled<Layout, N> {
+    const fn logical_pixel_count(physical_pixel_count: usize) -> usize {
+        physical_pixel_count.div_ceil(if N > 0 { N } else { 1 })
+    }
+
+    const fn logical_index(physical_pixel_count: usize, physical_index: usize) -> usize {
+        match Self::logical_pixel_count(physical_pixel_count) {
+            0 => 0,
+            logical_pixel_count => physical_index % logical_pixel_count,
+        }
+    }
+}
+
+macro_rules! impl_replica {
+    ($replica:ident $(, $n:ident)?) => {
+        impl<Layout $(, const $n: usize)?> Layout1d for $replica<Layout $(, $n)?>
+        where
+            Layout: Layout1d,
+        {
+            const PIXEL_COUNT: usize = Self::logical_pixel_count(Layout::PIXEL_COUNT);
+
+            fn points() -> impl Iterator<Item = f32> {
+                Layout::points().take(<Self as Layout1d>::PIXEL_COUNT)
+            }
+        }
+
+        impl<Layout $(, const $n: usize)?> Replica<Dim1d> for $replica<Layout $(, $n)?>
+        where
+            Layout: Layout1d,
+        {
+            type Physical = Layout;
+            const PHYSICAL_PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+            fn logical_index(physical_index: usize) -> usize {
+                Self::logical_index(Layout::PIXEL_COUNT, physical_index)
+            }
+        }
+
+        impl<Layout $(, const $n: usize)?> Layout2d for $replica<Layout $(, $n)?>
+        where
+            Layout: Layout2d,
+        {
+            const PIXEL_COUNT: usize = Self::logical_pixel_count(Layout::PIXEL_COUNT);
+
+            /// Returns the shapes of the whole physical layout.
+            fn shapes() -> impl Iterator<Item = Shape2d> {
+                Layout::shapes()
+            }
+
+            fn points() -> impl Iterator<Item = Vec2> {
+                Layout::points().take(<Self as Layout2d>::PIXEL_COUNT)
+            }
+        }
+
+        impl<Layout $(, const $n: usize)?> Replica<Dim2d> for $replica<Layout $(, $n)?>
+        where
+            Layout: Layout2d,
+        {
+            type Physical = Layout;
+            const PHYSICAL_PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+            fn logical_index(physical_index: usize) -> usize {
+                Self::logical_index(Layout::PIXEL_COUNT, physical_index)
+            }
+        }
+
+        impl<Layout $(, const $n: usize)?> Layout3d for $replica<Layout $(, $n)?>
+        where
+            Layout: Layout3d,
+        {
+            const PIXEL_COUNT: usize = Self::logical_pixel_count(Layout::PIXEL_COUNT);
+
+            /// Returns the shapes of the whole physical layout.
+            fn shapes() -> impl Iterator<Item = Shape3d> {
+                Layout::shapes()
+            }
+
+            fn points() -> impl Iterator<Item = Vec3> {
+                Layout::points().take(<Self as Layout3d>::PIXEL_COUNT)
+            }
+        }
+
+        impl<Layout $(, const $n: usize)?> Replica<Dim3d> for $replica<Layout $(, $n)?>
+        where
+            Layout: Layout3d,
+        {
+            type Physical = Layout;
+            const PHYSICAL_PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+            fn logical_index(physical_index: usize) -> usize {
+                Self::logical_index(Layout::PIXEL_COUNT, physical_index)
+            }
+        }
+    };
+}
+
+impl_replica!(Mirrored);
+impl_replica!(Tiled, N);
+
+/// A pattern computed on a logical layout, with its colors replicated across the physical
+/// layout.
+///
+/// The pattern's parameters are passed through unchanged.
+///
+/// # Type Parameters
+///
+/// - `Inner` - The pattern type, for the logical layout
+/// - `Logical` - The logical layout type, [`Mirrored`] or [`Tiled`]
+/// - `LOGICAL_PIXEL_COUNT` - The number of pixels in the logical layout
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Replicated<Inner, Logical, const LOGICAL_PIXEL_COUNT: usize> {
+    pattern: Inner,
+    logical: PhantomData<Logical>,
+}
+
+impl<Inner, Logical, const LOGICAL_PIXEL_COUNT: usize>
+    Replicated<Inner, Logical, LOGICAL_PIXEL_COUNT>
+{
+    /// Returns the inner pattern.
+    pub fn inner(&self) -> &Inner {
+        &self.pattern
+    }
+}
+
+impl<Dim, Layout, Inner, Logical, const LOGICAL_PIXEL_COUNT: usize> Pattern<Dim, Layout>
+    for Replicated<Inner, Logical, LOGICAL_PIXEL_COUNT>
+where
+    Layout: LayoutForDim<Dim>,
+    Logical: Replica<Dim, Physical = Layout> + LayoutForDim<Dim>,
+    Inner: Pattern<Dim, Logical>,
+    Inner::Color: Clone + Default,
+{
+    type Params = Inner::Params;
+    type Color = Inner::Color;
+
+    fn new(params: Self::Params) -> Self {
+        Self {
+            pattern: Inner::new(params),
+            logical: PhantomData,
+        }
+    }
+
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let colors: Vec<Self::Color, LOGICAL_PIXEL_COUNT> = self
+            .pattern
+            .tick(time_in_ms)
+            .take(LOGICAL_PIXEL_COUNT)
+            .collect();
+
+        // Pixels the inner pattern has no color for are black, so each color stays at its LED.
+        (0..Logical::PHYSICAL_PIXEL_COUNT).map(move |index| {
+            colors
+                .get(Logical::logical_index(index))
+                .cloned()
+                .unwrap_or_default()
+        })
+    }
+
+    fn params(&self) -> Option<&Self::Params> {
+        self.pattern.params()
+    }
+
+    fn params_mut(&mut self) -> Option<&mut Self::Params> {
+        self.pattern.params_mut()
+    }
+
+    fn set_color_capability(&mut self, capability: ColorCapability) {
+        self.pattern.set_color_capability(capability);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color::LinearSrgb, layout1d};
+
+    layout1d!(Layout, 5);
+
+    #[test]
+    fn test_logical_index() {
+        type Mirror = Mirrored<Layout>;
+        assert_eq!(<Mirror as Layout1d>::PIXEL_COUNT, 3);
+        let mirrored: Vec<usize, 5> = (0..5)
+            .map(<Mirror as Replica<Dim1d>>::logical_index)
+            .collect();
+        assert_eq!(mirrored, [0, 1, 2, 1, 0]);
+
+        type Tile = Tiled<Layout, 2>;
+        assert_eq!(<Tile as Layout1d>::PIXEL_COUNT, 3);
+        let tiled: Vec<usize, 5> = (0..5)
+            .map(<Tile as Replica<Dim1d>>::logical_index)
+            .collect();
+        assert_eq!(tiled, [0, 1, 2, 0, 1]);
+    }
+
+    /// A pattern with colors for only the first two pixels.
+    struct Short;
+
+    impl Pattern<Dim1d, Mirrored<Layout>> for Short {
+        type Params = ();
+        type Color = LinearSrgb;
+
+        fn new(_params: Self::Params) -> Self {
+            Self
+        }
+
+        fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+            [LinearSrgb::new(1., 0., 0.), LinearSrgb::new(0., 1., 0.)].into_iter()
+        }
+    }
+
+    #[test]
+    fn test_missing_colors_are_black() {
+        let pattern = <Replicated<Short, Mirrored<Layout>, 3> as Pattern<Dim1d, Layout>>::new(());
+        let colors: Vec<LinearSrgb, 5> =
+            <Replicated<Short, Mirrored<Layout>, 3> as Pattern<Dim1d, Layout>>::tick(&pattern, 0)
+                .collect();
+        let black = LinearSrgb::default();
+        let red = LinearSrgb::new(1., 0., 0.);
+        let green = LinearSrgb::new(0., 1., 0.);
+        assert_eq!(colors, [red, green, black, green, red]);
+    }
+}
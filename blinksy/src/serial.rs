@@ -0,0 +1,394 @@
+//! # Serial Control Protocol
+//!
+//! A simple protocol to control a device over a byte stream, usually USB serial (CDC ACM), for
+//! boards without a radio (e.g. RP2040 or STM32): set the brightness, select a pattern, or
+//! stream frames of pixels from a host.
+//!
+//! Each message is a header, then a payload:
+//!
+//! | Byte   | Content                                             |
+//! |--------|-----------------------------------------------------|
+//! | 0      | [`MAGIC`]                                           |
+//! | 1      | The [`CommandCode`]                                 |
+//! | 2 to 3 | The length of the payload, as a little-endian `u16` |
+//! | 4...   | The payload                                         |
+//!
+//! With the payloads:
+//!
+//! - [`CommandCode::SetBrightness`]: The brightness, as a little-endian `f32`, from 0.0 to 1.0
+//! - [`CommandCode::SetPattern`]: The name of the pattern, in UTF-8, e.g. for a
+//!   [`PatternRegistry`](crate::registry::PatternRegistry)
+//! - [`CommandCode::Pixels`]: The color of each pixel in LED order, as 8-bit sRGB: red, green,
+//!   then blue
+//!
+//! A device feeds each byte it receives to a [`Decoder`], which returns each [`Command`]. Bytes
+//! before the magic byte are skipped, so a device can start listening in the middle of a
+//! stream. For a host-side client, see `blinksy_desktop::serial`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::Srgb,
+//!     serial::{header, Command, CommandCode, Decoder},
+//! };
+//!
+//! // From the host
+//! let mut bytes = Vec::new();
+//! bytes.extend(header(CommandCode::SetBrightness, 4));
+//! bytes.extend(0.5_f32.to_le_bytes());
+//! bytes.extend(header(CommandCode::Pixels, 6));
+//! bytes.extend([255, 0, 0, 0, 0, 255]);
+//!
+//! // On the device
+//! let mut decoder = Decoder::<64>::new();
+//! for byte in bytes {
+//!     match decoder.push(byte) {
+//!         Some(Ok(Command::SetBrightness(brightness))) => {
+//!             assert_eq!(brightness, 0.5);
+//!             // control.set_brightness(brightness);
+//!         }
+//!         Some(Ok(Command::Pixels(pixels))) => {
+//!             assert_eq!(pixels.len(), 2);
+//!             assert_eq!(pixels.get(1), Some(Srgb::from_u8(0, 0, 255)));
+//!             // control.show_pixels(pixels, time_in_ms)?;
+//!         }
+//!         Some(Ok(_)) | None => {}
+//!         Some(Err(error)) => panic!("{error:?}"),
+//!     }
+//! }
+//! ```
+
+use core::str;
+
+use heapless::Vec;
+
+use crate::color::Srgb;
+
+/// The first byte of every message.
+pub const MAGIC: u8 = 0xb5;
+
+/// The length of a message header, in bytes.
+pub const HEADER_LEN: usize = 4;
+
+/// The kind of a command, as sent in a message header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum CommandCode {
+    /// Sets the brightness
+    SetBrightness = 1,
+    /// Selects a pattern by name
+    SetPattern = 2,
+    /// Shows a frame of pixels
+    Pixels = 3,
+}
+
+impl CommandCode {
+    /// Returns the command code of a byte, if any.
+    pub fn from_u8(byte: u8) -> Option<Self> {
+        match byte {
+            1 => Some(CommandCode::SetBrightness),
+            2 => Some(CommandCode::SetPattern),
+            3 => Some(CommandCode::Pixels),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the header of a message.
+///
+/// # Arguments
+///
+/// - `code` - The kind of command
+/// - `payload_len` - The length of the payload which follows, in bytes
+pub const fn header(code: CommandCode, payload_len: u16) -> [u8; HEADER_LEN] {
+    let [low, high] = payload_len.to_le_bytes();
+    [MAGIC, code as u8, low, high]
+}
+
+/// A command received from a host.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Command<'a> {
+    /// Sets the brightness, from 0.0 to 1.0
+    SetBrightness(f32),
+    /// Selects a pattern by name
+    SetPattern(&'a str),
+    /// Shows a frame of pixels
+    Pixels(Pixels<'a>),
+}
+
+/// A frame of pixels received from a host, as 8-bit sRGB.
+///
+/// Iterates over [`Srgb`] colors, so it can be given to
+/// [`Control::show_pixels`](crate::control::Control::show_pixels).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Pixels<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Pixels<'a> {
+    /// Returns the number of pixels.
+    pub fn len(&self) -> usize {
+        self.bytes.len() / 3
+    }
+
+    /// Returns whether there are no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the color of a pixel, if in range.
+    pub fn get(&self, index: usize) -> Option<Srgb> {
+        let bytes = self.bytes.get(index * 3..index * 3 + 3)?;
+        Some(Srgb::from_u8(bytes[0], bytes[1], bytes[2]))
+    }
+
+    /// Returns the raw bytes: red, green, then blue, for each pixel.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns an iterator over the colors of the pixels.
+    pub fn iter(&self) -> impl Iterator<Item = Srgb> + 'a {
+        self.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for Pixels<'a> {
+    type Item = Srgb;
+    type IntoIter = core::iter::Map<core::slice::ChunksExact<'a, u8>, fn(&[u8]) -> Srgb>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.bytes
+            .chunks_exact(3)
+            .map(|bytes| Srgb::from_u8(bytes[0], bytes[1], bytes[2]))
+    }
+}
+
+/// All types of errors that can happen while decoding a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DecodeError {
+    /// Raised if the command code is unknown
+    UnknownCommand(u8),
+    /// Raised if the payload is longer than the decoder's capacity, in which case it is skipped
+    TooLong(u16),
+    /// Raised if the payload is invalid for the command
+    InvalidPayload(CommandCode),
+}
+
+/// Where the decoder is in a message.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum State {
+    Magic,
+    Code,
+    LengthLow { code: u8 },
+    LengthHigh { code: u8, low: u8 },
+    Payload { code: u8, len: u16 },
+    Skip { remaining: u16 },
+}
+
+/// Decodes messages from a stream of bytes, one byte at a time.
+///
+/// # Type Parameters
+///
+/// - `CAPACITY` - The longest payload, in bytes: at least 3 times the number of pixels to
+///   stream
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Decoder<const CAPACITY: usize> {
+    state: State,
+    payload: Vec<u8, CAPACITY>,
+}
+
+impl<const CAPACITY: usize> Default for Decoder<CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAPACITY: usize> Decoder<CAPACITY> {
+    /// Creates a decoder, waiting for a message.
+    pub const fn new() -> Self {
+        Self {
+            state: State::Magic,
+            payload: Vec::new(),
+        }
+    }
+
+    /// Forgets any partial message, e.g. after the host reconnects.
+    pub fn reset(&mut self) {
+        self.state = State::Magic;
+        self.payload.clear();
+    }
+
+    /// Decodes the next byte of the stream.
+    ///
+    /// # Arguments
+    ///
+    /// - `byte` - The next byte received
+    ///
+    /// # Returns
+    ///
+    /// - `None` while the message is incomplete
+    /// - `Some(Ok(command))` once a message is complete
+    /// - `Some(Err(error))` if a message is invalid, after which the decoder waits for the
+    ///   next message
+    pub fn push(&mut self, byte: u8) -> Option<Result<Command<'_>, DecodeError>> {
+        match self.state {
+            State::Magic => {
+                if byte == MAGIC {
+                    self.state = State::Code;
+                }
+                None
+            }
+            State::Code => {
+                self.state = State::LengthLow { code: byte };
+                None
+            }
+            State::LengthLow { code } => {
+                self.state = State::LengthHigh { code, low: byte };
+                None
+            }
+            State::LengthHigh { code, low } => {
+                let len = u16::from_le_bytes([low, byte]);
+                self.payload.clear();
+                if usize::from(len) > CAPACITY {
+                    self.state = State::Skip { remaining: len };
+                    return Some(Err(DecodeError::TooLong(len)));
+                }
+                if len == 0 {
+                    self.state = State::Magic;
+                    return Some(decode(code, &self.payload));
+                }
+                self.state = State::Payload { code, len };
+                None
+            }
+            State::Payload { code, len } => {
+                // The capacity was checked against the length in the header.
+                let _ = self.payload.push(byte);
+                if self.payload.len() < usize::from(len) {
+                    return None;
+                }
+                self.state = State::Magic;
+                Some(decode(code, &self.payload))
+            }
+            State::Skip { remaining } => {
+                self.state = if remaining > 1 {
+                    State::Skip {
+                        remaining: remaining - 1,
+                    }
+                } else {
+                    State::Magic
+                };
+                None
+            }
+        }
+    }
+}
+
+/// Decodes the payload of a command.
+fn decode(code: u8, payload: &[u8]) -> Result<Command<'_>, DecodeError> {
+    let code = CommandCode::from_u8(code).ok_or(DecodeError::UnknownCommand(code))?;
+    let invalid = DecodeError::InvalidPayload(code);
+    match code {
+        CommandCode::SetBrightness => {
+            let bytes: [u8; 4] = payload.try_into().map_err(|_| invalid)?;
+            let brightness = f32::from_le_bytes(bytes);
+            if !brightness.is_finite() {
+                return Err(invalid);
+            }
+            Ok(Command::SetBrightness(brightness.clamp(0., 1.)))
+        }
+        CommandCode::SetPattern => str::from_utf8(payload)
+            .map(Command::SetPattern)
+            .map_err(|_| invalid),
+        CommandCode::Pixels => {
+            if !payload.len().is_multiple_of(3) {
+                return Err(invalid);
+            }
+            Ok(Command::Pixels(Pixels { bytes: payload }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all<const CAPACITY: usize>(
+        decoder: &mut Decoder<CAPACITY>,
+        bytes: &[u8],
+        mut on_result: impl FnMut(Result<Command<'_>, DecodeError>),
+    ) {
+        for &byte in bytes {
+            if let Some(result) = decoder.push(byte) {
+                on_result(result);
+            }
+        }
+    }
+
+    #[test]
+    fn test_skips_until_magic() {
+        let mut bytes = [0u8; 11];
+        bytes[..3].copy_from_slice(&[0x00, 0x42, 0xff]);
+        bytes[3..7].copy_from_slice(&header(CommandCode::SetBrightness, 4));
+        bytes[7..].copy_from_slice(&1.5_f32.to_le_bytes());
+
+        let mut decoder = Decoder::<8>::new();
+        let mut results = 0;
+        decode_all(&mut decoder, &bytes, |result| {
+            assert_eq!(result, Ok(Command::SetBrightness(1.)));
+            results += 1;
+        });
+        assert_eq!(results, 1);
+    }
+
+    #[test]
+    fn test_skips_too_long_payload() {
+        let mut bytes = [0u8; 21];
+        bytes[..4].copy_from_slice(&header(CommandCode::Pixels, 9));
+        bytes[4..13].copy_from_slice(&[MAGIC; 9]);
+        bytes[13..17].copy_from_slice(&header(CommandCode::SetPattern, 4));
+        bytes[17..].copy_from_slice(b"fire");
+
+        let mut decoder = Decoder::<6>::new();
+        let mut results = 0;
+        decode_all(&mut decoder, &bytes, |result| {
+            match results {
+                0 => assert_eq!(result, Err(DecodeError::TooLong(9))),
+                _ => assert_eq!(result, Ok(Command::SetPattern("fire"))),
+            }
+            results += 1;
+        });
+        assert_eq!(results, 2);
+    }
+
+    #[test]
+    fn test_invalid_payloads() {
+        let mut decoder = Decoder::<8>::new();
+        let mut results = 0;
+        let mut bytes = [0u8; 14];
+        bytes[..4].copy_from_slice(&[MAGIC, 9, 0, 0]);
+        bytes[4..8].copy_from_slice(&header(CommandCode::Pixels, 2));
+        bytes[8..10].copy_from_slice(&[1, 2]);
+        bytes[10..].copy_from_slice(&header(CommandCode::Pixels, 0));
+        decode_all(&mut decoder, &bytes, |result| {
+            match results {
+                0 => assert_eq!(result, Err(DecodeError::UnknownCommand(9))),
+                1 => assert_eq!(
+                    result,
+                    Err(DecodeError::InvalidPayload(CommandCode::Pixels))
+                ),
+                _ => assert!(matches!(result, Ok(Command::Pixels(pixels)) if pixels.is_empty())),
+            }
+            results += 1;
+        });
+        assert_eq!(results, 3);
+    }
+}
@@ -0,0 +1,242 @@
+//! # Switch Outputs
+//!
+//! Not every load is addressable: white strips behind MOSFETs, relays for mains lights, or a
+//! fan. A [`SwitchBank`] drives a set of digital output pins as on/off channels, so these loads
+//! can be automated from the same device as the LEDs.
+//!
+//! Each channel has its own on/off state, and the bank follows the power of the control system:
+//! while the power is off, every channel is off, and when the power comes back on, each channel
+//! returns to its state. A channel may also be switched on for a time, e.g. a relay for the
+//! hallway lights after motion.
+//!
+//! Like [`Control`](crate::control::Control), changes apply on the next
+//! [`update`](SwitchBank::update), so call it in your control loop.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # use core::{cell::Cell, convert::Infallible};
+//! # use embedded_hal::digital::{ErrorType, OutputPin};
+//! use blinksy::switch::SwitchBank;
+//!
+//! # struct Pin<'a>(&'a Cell<bool>);
+//! # impl ErrorType for Pin<'_> {
+//! #     type Error = Infallible;
+//! # }
+//! # impl OutputPin for Pin<'_> {
+//! #     fn set_low(&mut self) -> Result<(), Infallible> {
+//! #         self.0.set(false);
+//! #         Ok(())
+//! #     }
+//! #     fn set_high(&mut self) -> Result<(), Infallible> {
+//! #         self.0.set(true);
+//! #         Ok(())
+//! #     }
+//! # }
+//! let (strip, relay) = (Cell::new(false), Cell::new(false));
+//! // Your output pins, e.g. to MOSFET gates or relay drivers
+//! let mut switches = SwitchBank::new([Pin(&strip), Pin(&relay)]);
+//!
+//! switches.set(0, true);
+//! switches.switch_on_for(1, 60_000, 0);
+//! switches.update(0).unwrap();
+//! assert!(strip.get() && relay.get());
+//!
+//! // The control is turned off
+//! switches.set_power(false);
+//! switches.update(1000).unwrap();
+//! assert!(!strip.get() && !relay.get());
+//!
+//! // The control is turned on again, after the relay's time is up
+//! switches.set_power(true);
+//! switches.update(61_000).unwrap();
+//! assert!(strip.get() && !relay.get());
+//! ```
+//!
+//! Then, in your control loop:
+//!
+//! ```rust,ignore
+//! switches.set_power(control.is_on());
+//! switches.update(time_in_ms)?;
+//! ```
+
+use embedded_hal::digital::OutputPin;
+
+use crate::input::ControlEvent;
+
+/// A bank of on/off output channels.
+///
+/// # Type Parameters
+///
+/// - `Pin` - The output pin type of every channel
+/// - `CHANNEL_COUNT` - The number of channels
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SwitchBank<Pin, const CHANNEL_COUNT: usize> {
+    pins: [Pin; CHANNEL_COUNT],
+    is_on: [bool; CHANNEL_COUNT],
+    /// When each channel switches off, if on for a time
+    off_in_ms: [Option<u64>; CHANNEL_COUNT],
+    is_powered: bool,
+    is_active_low: bool,
+}
+
+impl<Pin, const CHANNEL_COUNT: usize> SwitchBank<Pin, CHANNEL_COUNT>
+where
+    Pin: OutputPin,
+{
+    /// Wraps output pins, which are high while their channel is on.
+    ///
+    /// Every channel starts off, with the power on.
+    ///
+    /// # Arguments
+    ///
+    /// - `pins` - The output pin of each channel
+    pub fn new(pins: [Pin; CHANNEL_COUNT]) -> Self {
+        Self {
+            pins,
+            is_on: [false; CHANNEL_COUNT],
+            off_in_ms: [None; CHANNEL_COUNT],
+            is_powered: true,
+            is_active_low: false,
+        }
+    }
+
+    /// Inverts the pins, for loads which are on while their pin is low.
+    pub fn active_low(mut self) -> Self {
+        self.is_active_low = true;
+        self
+    }
+
+    /// Returns the number of channels.
+    pub fn channel_count(&self) -> usize {
+        CHANNEL_COUNT
+    }
+
+    /// Switches a channel on or off, cancelling any time it was switched on for.
+    ///
+    /// An out-of-range channel is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// - `channel` - The index of the channel
+    /// - `is_on` - Whether the channel is on
+    pub fn set(&mut self, channel: usize, is_on: bool) {
+        if let Some(state) = self.is_on.get_mut(channel) {
+            *state = is_on;
+            self.off_in_ms[channel] = None;
+        }
+    }
+
+    /// Toggles a channel on or off.
+    ///
+    /// An out-of-range channel is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// - `channel` - The index of the channel
+    pub fn toggle(&mut self, channel: usize) {
+        if let Some(is_on) = self.is_on(channel) {
+            self.set(channel, !is_on);
+        }
+    }
+
+    /// Switches a channel on for a time, after which it switches off.
+    ///
+    /// If the channel is already on for a longer time, it stays on for that longer time.
+    ///
+    /// An out-of-range channel is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// - `channel` - The index of the channel
+    /// - `duration_in_ms` - How long the channel stays on, in milliseconds
+    /// - `time_in_ms` - The current time in milliseconds
+    pub fn switch_on_for(&mut self, channel: usize, duration_in_ms: u64, time_in_ms: u64) {
+        let Some(is_on) = self.is_on.get_mut(channel) else {
+            return;
+        };
+        let off_in_ms = time_in_ms + duration_in_ms;
+        self.off_in_ms[channel] = match self.off_in_ms[channel] {
+            Some(current_off_in_ms) if *is_on => Some(current_off_in_ms.max(off_in_ms)),
+            // Already on without a time, so stays on
+            None if *is_on => None,
+            _ => Some(off_in_ms),
+        };
+        *is_on = true;
+    }
+
+    /// Returns whether a channel is on, ignoring the power.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(is_on)` for a channel in range
+    /// - `None` otherwise
+    pub fn is_on(&self, channel: usize) -> Option<bool> {
+        self.is_on.get(channel).copied()
+    }
+
+    /// Turns the power of every channel on or off, e.g. to follow
+    /// [`Control::is_on`](crate::control::Control::is_on).
+    ///
+    /// While the power is off, every channel is off. Each channel keeps its state for when the
+    /// power is on again.
+    ///
+    /// # Arguments
+    ///
+    /// - `is_powered` - Whether the power is on
+    pub fn set_power(&mut self, is_powered: bool) {
+        self.is_powered = is_powered;
+    }
+
+    /// Returns whether the power is on.
+    pub fn is_powered(&self) -> bool {
+        self.is_powered
+    }
+
+    /// Follows the power of a control event, e.g. from
+    /// [`DeviceControls`](crate::input::DeviceControls) or a remote.
+    ///
+    /// Other events are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// - `event` - The control event
+    pub fn handle(&mut self, event: ControlEvent) {
+        if let ControlEvent::PowerChanged(is_powered) = event {
+            self.set_power(is_powered);
+        }
+    }
+
+    /// Switches off channels whose time is up, and writes every channel to its pin.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The current time in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// The error of the first pin which failed to write, if any
+    pub fn update(&mut self, time_in_ms: u64) -> Result<(), Pin::Error> {
+        for (is_on, off_in_ms) in self.is_on.iter_mut().zip(self.off_in_ms.iter_mut()) {
+            if off_in_ms.is_some_and(|off_in_ms| time_in_ms >= off_in_ms) {
+                *is_on = false;
+                *off_in_ms = None;
+            }
+        }
+        for (pin, is_on) in self.pins.iter_mut().zip(self.is_on) {
+            let is_high = (self.is_powered && is_on) != self.is_active_low;
+            if is_high {
+                pin.set_high()?;
+            } else {
+                pin.set_low()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Unwraps the output pins.
+    pub fn into_inner(self) -> [Pin; CHANNEL_COUNT] {
+        self.pins
+    }
+}
@@ -0,0 +1,292 @@
+//! # Testing
+//!
+//! Helpers to unit test the logic of a [`Pattern`] on your host: without a driver, a
+//! [`Control`](crate::control::Control), or the desktop simulator.
+//!
+//! - Tiny fixed layouts, small enough to check every pixel by hand: [`Strip4`], [`Grid3x3`],
+//!   and [`Cube2`]
+//! - A [`PatternHarness`], which collects the output of a pattern at given times into arrays,
+//!   as [`LinearSrgb`] colors
+//! - Approximate color comparisons, across color spaces: [`assert_color_near!`](crate::assert_color_near)
+//!   and [`is_color_near`]
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::LinearSrgb,
+//!     markers::{Dim1d, Dim2d},
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     testing::{Grid3x3, PatternHarness, Strip4},
+//! };
+//!
+//! let harness = PatternHarness::<Dim1d, Strip4, Rainbow>::new(RainbowParams::default());
+//!
+//! // The rainbow moves over time
+//! let [start, later]: [[LinearSrgb; 4]; 2] = harness.frames([0, 1000]);
+//! assert_ne!(start, later);
+//!
+//! // In 2d, the hue depends on x + y, so each anti-diagonal has one color
+//! let harness = PatternHarness::<Dim2d, Grid3x3, Rainbow>::new(RainbowParams::default());
+//! let frame: [LinearSrgb; 9] = harness.frame(0);
+//! assert_eq!(frame[2], frame[4]);
+//! assert_eq!(frame[4], frame[6]);
+//! assert_ne!(frame[0], frame[1]);
+//! ```
+//!
+//! ## Example (Colors)
+//!
+//! ```rust
+//! use blinksy::{
+//!     assert_color_near,
+//!     color::{LinearSrgb, Okhsv, Srgb},
+//! };
+//!
+//! // The same red, in different color spaces
+//! assert_color_near!(Okhsv::new(0.0812, 1., 1.), Srgb::new(1., 0., 0.), 0.05);
+//! assert_color_near!(Srgb::new(1., 0., 0.), LinearSrgb::new(1., 0., 0.));
+//! ```
+
+use core::marker::PhantomData;
+
+use crate::{
+    color::{FromColor, LinearSrgb},
+    layout::{LayoutForDim, Shape2d, Shape3d, Vec2, Vec3},
+    layout1d, layout2d, layout3d,
+    pattern::Pattern,
+};
+
+layout1d!(
+    /// A strip of 4 LEDs, at x = -1, -1/3, 1/3, and 1.
+    pub Strip4,
+    4
+);
+
+layout2d!(
+    /// A grid of 3 by 3 LEDs, from (-1, -1) to (1, 1).
+    ///
+    /// The LEDs are in rows from the bottom, each row from left to right (not serpentine): so
+    /// the LED at (x, y), where x and y are 0 to 2, is at index `3 * y + x`.
+    pub Grid3x3,
+    [Shape2d::Grid {
+        start: Vec2::new(-1., -1.),
+        horizontal_end: Vec2::new(1., -1.),
+        vertical_end: Vec2::new(-1., 1.),
+        horizontal_pixel_count: 3,
+        vertical_pixel_count: 3,
+        serpentine: false,
+    }]
+);
+
+/// The corners of [`Cube2`], in order.
+const CUBE2_POINTS: [Vec3; 8] = [
+    Vec3::new(-1., -1., -1.),
+    Vec3::new(1., -1., -1.),
+    Vec3::new(-1., 1., -1.),
+    Vec3::new(1., 1., -1.),
+    Vec3::new(-1., -1., 1.),
+    Vec3::new(1., -1., 1.),
+    Vec3::new(-1., 1., 1.),
+    Vec3::new(1., 1., 1.),
+];
+
+layout3d!(
+    /// A cube of 2 by 2 by 2 LEDs, one at each corner from (-1, -1, -1) to (1, 1, 1).
+    ///
+    /// The LED at (x, y, z), where x, y, and z are 0 or 1, is at index `4 * z + 2 * y + x`.
+    pub Cube2,
+    [Shape3d::Points(&CUBE2_POINTS)]
+);
+
+/// The default tolerance of [`assert_color_near!`](crate::assert_color_near), as the largest
+/// difference of a linear sRGB channel.
+pub const DEFAULT_COLOR_TOLERANCE: f32 = 1e-3;
+
+/// Returns the difference between two colors, in any color spaces.
+///
+/// Both colors are converted to linear sRGB, so a hue which wraps around (e.g. 0 and 1) is the
+/// same.
+///
+/// # Returns
+///
+/// The largest difference of a linear sRGB channel
+pub fn color_difference<A, B>(a: A, b: B) -> f32
+where
+    LinearSrgb: FromColor<A> + FromColor<B>,
+{
+    let a = LinearSrgb::from_color(a);
+    let b = LinearSrgb::from_color(b);
+    (a.red - b.red)
+        .abs()
+        .max((a.green - b.green).abs())
+        .max((a.blue - b.blue).abs())
+}
+
+/// Returns whether two colors, in any color spaces, are within a tolerance.
+///
+/// # Arguments
+///
+/// - `a` - The first color
+/// - `b` - The second color
+/// - `tolerance` - The largest difference of a linear sRGB channel, see [`color_difference`]
+pub fn is_color_near<A, B>(a: A, b: B, tolerance: f32) -> bool
+where
+    LinearSrgb: FromColor<A> + FromColor<B>,
+{
+    color_difference(a, b) <= tolerance
+}
+
+/// Asserts that two colors, in any color spaces, are within a tolerance.
+///
+/// The tolerance is the largest difference of a linear sRGB channel, see
+/// [`color_difference`](crate::testing::color_difference), and defaults to
+/// [`DEFAULT_COLOR_TOLERANCE`](crate::testing::DEFAULT_COLOR_TOLERANCE).
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{assert_color_near, color::LinearSrgb};
+///
+/// let gray = LinearSrgb::new(0.5, 0.5, 0.5);
+/// assert_color_near!(gray, LinearSrgb::new(0.5004, 0.5, 0.4998));
+/// assert_color_near!(gray, LinearSrgb::new(0.51, 0.5, 0.5), 0.01);
+/// ```
+///
+/// ```rust,should_panic
+/// use blinksy::{assert_color_near, color::LinearSrgb};
+///
+/// assert_color_near!(LinearSrgb::new(1., 0., 0.), LinearSrgb::new(0., 0., 1.));
+/// ```
+#[macro_export]
+macro_rules! assert_color_near {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_color_near!($left, $right, $crate::testing::DEFAULT_COLOR_TOLERANCE)
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {{
+        let left: $crate::color::LinearSrgb = $crate::color::IntoColor::into_color($left);
+        let right: $crate::color::LinearSrgb = $crate::color::IntoColor::into_color($right);
+        let tolerance: f32 = $tolerance;
+        let difference = $crate::testing::color_difference(left, right);
+        ::core::assert!(
+            difference <= tolerance,
+            "assertion `left ~= right` failed (difference {} > tolerance {})\n  left: {:?}\n right: {:?}",
+            difference,
+            tolerance,
+            left,
+            right,
+        );
+    }};
+}
+
+/// Collects the output of a pattern, for tests.
+///
+/// # Type Parameters
+///
+/// - `Dim` - The dimension marker (Dim1d, Dim2d, or Dim3d)
+/// - `Layout` - The layout, e.g. [`Strip4`]
+/// - `P` - The pattern
+pub struct PatternHarness<Dim, Layout, P>
+where
+    Layout: LayoutForDim<Dim>,
+    P: Pattern<Dim, Layout>,
+{
+    dim: PhantomData<Dim>,
+    layout: PhantomData<Layout>,
+    pattern: P,
+}
+
+impl<Dim, Layout, P> PatternHarness<Dim, Layout, P>
+where
+    Layout: LayoutForDim<Dim>,
+    P: Pattern<Dim, Layout>,
+    LinearSrgb: FromColor<P::Color>,
+{
+    /// Creates a harness with a new pattern.
+    ///
+    /// # Arguments
+    ///
+    /// - `params` - The pattern parameters
+    pub fn new(params: P::Params) -> Self {
+        Self::from_pattern(P::new(params))
+    }
+
+    /// Creates a harness with an existing pattern.
+    ///
+    /// # Arguments
+    ///
+    /// - `pattern` - The pattern
+    pub fn from_pattern(pattern: P) -> Self {
+        Self {
+            dim: PhantomData,
+            layout: PhantomData,
+            pattern,
+        }
+    }
+
+    /// Returns a reference to the pattern.
+    pub fn pattern(&self) -> &P {
+        &self.pattern
+    }
+
+    /// Returns a mutable reference to the pattern, e.g. to change its parameters between
+    /// frames.
+    pub fn pattern_mut(&mut self) -> &mut P {
+        &mut self.pattern
+    }
+
+    /// Collects the pattern's output at a time.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `PIXEL_COUNT` - The number of pixels in the layout
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The time in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// The color of each pixel
+    ///
+    /// # Panics
+    ///
+    /// If the pattern outputs a number of pixels other than `PIXEL_COUNT`.
+    pub fn frame<const PIXEL_COUNT: usize>(&self, time_in_ms: u64) -> [LinearSrgb; PIXEL_COUNT] {
+        let mut frame = [LinearSrgb::new(0., 0., 0.); PIXEL_COUNT];
+        let mut count = 0;
+        for color in self.pattern.tick(time_in_ms) {
+            assert!(
+                count < PIXEL_COUNT,
+                "pattern output more than {PIXEL_COUNT} pixels"
+            );
+            frame[count] = LinearSrgb::from_color(color);
+            count += 1;
+        }
+        assert_eq!(
+            count, PIXEL_COUNT,
+            "pattern output {count} pixels, expected {PIXEL_COUNT}"
+        );
+        frame
+    }
+
+    /// Collects the pattern's output at each of a list of times.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `PIXEL_COUNT` - The number of pixels in the layout
+    /// - `FRAME_COUNT` - The number of times
+    ///
+    /// # Arguments
+    ///
+    /// - `times_in_ms` - The times in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// A frame for each time, as from [`frame`](Self::frame)
+    pub fn frames<const PIXEL_COUNT: usize, const FRAME_COUNT: usize>(
+        &self,
+        times_in_ms: [u64; FRAME_COUNT],
+    ) -> [[LinearSrgb; PIXEL_COUNT]; FRAME_COUNT] {
+        times_in_ms.map(|time_in_ms| self.frame(time_in_ms))
+    }
+}
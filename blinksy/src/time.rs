@@ -6,6 +6,9 @@
 //! - [`Nanoseconds`]: For specifying timing durations in nanoseconds
 //!
 //! [`fugit`]: https://docs.rs/fugit
+//!
+//! The current time, to [`tick`](crate::control::Control::tick) your control, comes from a
+//! [`TimeSource`].
 
 /// Represents a frequency in megahertz (MHz).
 ///
@@ -16,3 +19,35 @@ pub use fugit::MegahertzU32 as Megahertz;
 ///
 /// Used for precise timing control in LED driver protocols and animations.
 pub use fugit::NanosDurationU32 as Nanoseconds;
+
+/// Trait for sources of the current time.
+///
+/// Each platform has its own clock (e.g. `esp-hal`, `embassy-time`, or `std`), so this lets
+/// code that needs the time stay independent of the platform.
+///
+/// Any function or closure returning the time in milliseconds is a time source.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::time::TimeSource;
+///
+/// fn ticks_at<Time: TimeSource>(time: &Time) -> u64 {
+///     time.now_in_ms()
+/// }
+///
+/// assert_eq!(ticks_at(&|| 1000), 1000);
+/// ```
+pub trait TimeSource {
+    /// Returns the current time in milliseconds, from an arbitrary start (e.g. boot).
+    fn now_in_ms(&self) -> u64;
+}
+
+impl<F> TimeSource for F
+where
+    F: Fn() -> u64,
+{
+    fn now_in_ms(&self) -> u64 {
+        self()
+    }
+}
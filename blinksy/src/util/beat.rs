@@ -0,0 +1,262 @@
+//! # Beats
+//!
+//! Oscillators in the style of [FastLED's `beatsin8`][beatsin8], in time with a tempo in beats
+//! per minute (BPM), for music-synced effects and faithful ports of FastLED demos.
+//!
+//! - [`beat8`], [`beat16`]: A sawtooth, from 0 up to the maximum once per beat
+//! - [`beatsin8`], [`beatsin16`]: A sine wave, between a low and a high value once per beat
+//!
+//! The global [`BPM`] clock holds the tempo for all patterns. Set it from anywhere: a
+//! network message, a tap-tempo button, or beat detection, then [`sync`](BpmClock::sync) it on
+//! a beat to line the phase up with the music.
+//!
+//! With [beat detection](crate::audio), the clock also has the time since the last detected
+//! beat, to pulse with the music, and the confidence of the detected tempo.
+//!
+//! [beatsin8]: https://fastled.io/docs/group___beat_generators.html
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::util::beat::{beat8, beatsin8, BPM};
+//!
+//! // At 60 BPM, one beat per second
+//! assert_eq!(beat8(60., 0), 0);
+//! assert_eq!(beat8(60., 500), 128);
+//!
+//! // Between 100 and 200, centered at the start of a beat
+//! assert_eq!(beatsin8(60., 0, 100, 200), 150);
+//!
+//! // In a pattern, follow the global tempo
+//! BPM.set_bpm(128.);
+//! let level = BPM.beatsin8(1000, 0, 255);
+//! ```
+
+use core::{
+    f32::consts::TAU,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use super::math8::scale8;
+
+/// Milliseconds times BPM (in 256ths) in one beat: the period of every beat, at any tempo.
+const BEAT_PERIOD: u64 = 60_000 * 256;
+
+/// The default tempo of the [`BPM`] clock.
+pub const DEFAULT_BPM: f32 = 120.;
+
+/// Returns the phase of a beat, from 0 to 65535.
+fn beat_phase(bpm: f32, elapsed_in_ms: u64) -> u16 {
+    let bpm88 = (bpm * 256.) as u64 % BEAT_PERIOD;
+    let beats = (elapsed_in_ms % BEAT_PERIOD) * bpm88 % BEAT_PERIOD;
+    (beats * 65536 / BEAT_PERIOD) as u16
+}
+
+/// Returns a sine wave of a phase, from 0 to 65535, starting at the middle.
+fn sin16(phase: u16) -> u16 {
+    let sin = (phase as f32 / 65536. * TAU).sin();
+    (32767.5 + sin * 32767.5) as u16
+}
+
+/// A sawtooth, from 0 up to 255 once per beat.
+///
+/// # Arguments
+///
+/// - `bpm` - The tempo in beats per minute
+/// - `time_in_ms` - The current time in milliseconds
+pub fn beat8(bpm: f32, time_in_ms: u64) -> u8 {
+    (beat16(bpm, time_in_ms) >> 8) as u8
+}
+
+/// A sawtooth, from 0 up to 65535 once per beat.
+///
+/// # Arguments
+///
+/// - `bpm` - The tempo in beats per minute
+/// - `time_in_ms` - The current time in milliseconds
+pub fn beat16(bpm: f32, time_in_ms: u64) -> u16 {
+    beat_phase(bpm, time_in_ms)
+}
+
+/// A sine wave, between a low and a high value once per beat.
+///
+/// The wave starts in the middle, rising.
+///
+/// # Arguments
+///
+/// - `bpm` - The tempo in beats per minute
+/// - `time_in_ms` - The current time in milliseconds
+/// - `low` - The lowest value
+/// - `high` - The highest value
+pub fn beatsin8(bpm: f32, time_in_ms: u64, low: u8, high: u8) -> u8 {
+    let sin = (sin16(beat16(bpm, time_in_ms)) >> 8) as u8;
+    low.saturating_add(scale8(sin, high.saturating_sub(low)))
+}
+
+/// A sine wave, between a low and a high value once per beat.
+///
+/// The wave starts in the middle, rising.
+///
+/// # Arguments
+///
+/// - `bpm` - The tempo in beats per minute
+/// - `time_in_ms` - The current time in milliseconds
+/// - `low` - The lowest value
+/// - `high` - The highest value
+pub fn beatsin16(bpm: f32, time_in_ms: u64, low: u16, high: u16) -> u16 {
+    let sin = sin16(beat16(bpm, time_in_ms));
+    let range = high.saturating_sub(low) as u32;
+    low.saturating_add(((sin as u32 * (range + 1)) >> 16) as u16)
+}
+
+/// A tempo, shared between whoever sets it and the patterns following it.
+///
+/// The tempo and phase are atomics, so they can be set from another task or an interrupt.
+#[derive(Debug)]
+pub struct BpmClock {
+    /// The tempo, as the bits of an `f32`.
+    bpm: AtomicU32,
+    /// The time of a beat, modulo [`BEAT_PERIOD`].
+    timebase: AtomicU32,
+    /// The time of the last recorded beat, wrapping.
+    last_beat: AtomicU32,
+    /// The confidence of the tempo, as the bits of an `f32`.
+    confidence: AtomicU32,
+}
+
+impl BpmClock {
+    /// Creates a clock at a tempo.
+    ///
+    /// # Arguments
+    ///
+    /// - `bpm` - The tempo in beats per minute
+    pub const fn new(bpm: f32) -> Self {
+        Self {
+            bpm: AtomicU32::new(bpm.to_bits()),
+            timebase: AtomicU32::new(0),
+            last_beat: AtomicU32::new(0),
+            confidence: AtomicU32::new(0),
+        }
+    }
+
+    /// Returns the tempo, in beats per minute.
+    pub fn bpm(&self) -> f32 {
+        f32::from_bits(self.bpm.load(Ordering::Relaxed))
+    }
+
+    /// Sets the tempo.
+    ///
+    /// # Arguments
+    ///
+    /// - `bpm` - The tempo in beats per minute
+    pub fn set_bpm(&self, bpm: f32) {
+        self.bpm.store(bpm.max(0.).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Lines the phase up with a beat: each beat now starts at this time.
+    ///
+    /// Patterns see pattern time, so with a time scale or pausing set on the control, sync with
+    /// the pattern time rather than the real time.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The time of the beat in milliseconds
+    pub fn sync(&self, time_in_ms: u64) {
+        self.timebase
+            .store((time_in_ms % BEAT_PERIOD) as u32, Ordering::Relaxed);
+    }
+
+    /// Records a detected beat: syncs the phase to it, and sets the confidence of the tempo.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The time of the beat in milliseconds
+    /// - `confidence` - How sure the detector is of the tempo, from 0.0 to 1.0
+    pub fn record_beat(&self, time_in_ms: u64, confidence: f32) {
+        self.sync(time_in_ms);
+        self.last_beat.store(time_in_ms as u32, Ordering::Relaxed);
+        self.confidence
+            .store(confidence.clamp(0., 1.).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Returns how sure the beat detection is of the tempo, from 0.0 to 1.0.
+    ///
+    /// Without beat detection, this is 0.0.
+    pub fn confidence(&self) -> f32 {
+        f32::from_bits(self.confidence.load(Ordering::Relaxed))
+    }
+
+    /// Returns the time since the last recorded beat, in milliseconds, e.g. to flash on each
+    /// beat and fade until the next.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The current time in milliseconds
+    pub fn since_beat_in_ms(&self, time_in_ms: u64) -> u32 {
+        (time_in_ms as u32).wrapping_sub(self.last_beat.load(Ordering::Relaxed))
+    }
+
+    /// Returns the time since the last synced beat, modulo [`BEAT_PERIOD`].
+    fn elapsed_in_ms(&self, time_in_ms: u64) -> u64 {
+        let timebase = self.timebase.load(Ordering::Relaxed) as u64;
+        (time_in_ms % BEAT_PERIOD + BEAT_PERIOD - timebase) % BEAT_PERIOD
+    }
+
+    /// A sawtooth, from 0 up to 255 once per beat. See [`beat8`].
+    pub fn beat8(&self, time_in_ms: u64) -> u8 {
+        beat8(self.bpm(), self.elapsed_in_ms(time_in_ms))
+    }
+
+    /// A sawtooth, from 0 up to 65535 once per beat. See [`beat16`].
+    pub fn beat16(&self, time_in_ms: u64) -> u16 {
+        beat16(self.bpm(), self.elapsed_in_ms(time_in_ms))
+    }
+
+    /// A sine wave, between a low and a high value once per beat. See [`beatsin8`].
+    pub fn beatsin8(&self, time_in_ms: u64, low: u8, high: u8) -> u8 {
+        beatsin8(self.bpm(), self.elapsed_in_ms(time_in_ms), low, high)
+    }
+
+    /// A sine wave, between a low and a high value once per beat. See [`beatsin16`].
+    pub fn beatsin16(&self, time_in_ms: u64, low: u16, high: u16) -> u16 {
+        beatsin16(self.bpm(), self.elapsed_in_ms(time_in_ms), low, high)
+    }
+}
+
+impl Default for BpmClock {
+    fn default() -> Self {
+        Self::new(DEFAULT_BPM)
+    }
+}
+
+/// The global tempo, for all patterns.
+pub static BPM: BpmClock = BpmClock::new(DEFAULT_BPM);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_beats() {
+        assert_eq!(beat16(60., 250), 16384);
+        assert_eq!(beat16(60., 1000), 0);
+        assert_eq!(beat16(120., 250), 32768);
+        assert_eq!(beat16(0., 250), 0);
+        // Far from boot, the phase is still exact
+        assert_eq!(beat16(60., 1_000_000_000_250), 16384);
+
+        assert_eq!(beatsin8(60., 250, 0, 255), 255);
+        assert_eq!(beatsin8(60., 750, 0, 255), 0);
+        assert_eq!(beatsin16(60., 250, 1000, 2000), 2000);
+        assert_eq!(beatsin16(60., 750, 1000, 2000), 1000);
+
+        let clock = BpmClock::new(60.);
+        clock.sync(10_100);
+        assert_eq!(clock.beat8(10_100), 0);
+        assert_eq!(clock.beat8(10_600), 128);
+        assert_eq!(clock.beat8(100), 0);
+    }
+}
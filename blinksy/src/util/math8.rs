@@ -0,0 +1,220 @@
+//! # 8-bit Math
+//!
+//! Fixed-point helpers in the style of [FastLED's `lib8tion`][lib8tion], for patterns ported
+//! from FastLED, which do their math in 8 bits: a [`Fract8`] is a fraction in 256ths.
+//!
+//! - Scale: [`scale8`], [`scale8_video`], [`scale16`]
+//! - Saturate: [`qadd8`], [`qsub8`]
+//! - Blend: [`blend8`]
+//! - Ease: [`ease8_in_out_quad`], [`ease8_in_out_cubic`]
+//! - Waves: [`triwave8`], [`quadwave8`], [`cubicwave8`]
+//!
+//! Each gives the same results as its FastLED counterpart, so ported patterns look the same.
+//!
+//! [lib8tion]: https://fastled.io/docs/group__lib8tion.html
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::util::math8::{blend8, scale8, triwave8};
+//!
+//! // Half of 200
+//! assert_eq!(scale8(200, 128), 100);
+//!
+//! // A quarter of the way from 0 to 200
+//! assert_eq!(blend8(0, 200, 64), 50);
+//!
+//! // Up, then down
+//! assert_eq!(triwave8(0), 0);
+//! assert_eq!(triwave8(64), 128);
+//! assert_eq!(triwave8(128), 254);
+//! ```
+
+/// A fraction in 256ths: 0 is 0.0, 128 is 0.5, and 255 is almost 1.0.
+pub type Fract8 = u8;
+
+/// Scales a value by a fraction.
+///
+/// A scale of 255 keeps the value, so `scale8(255, 255) == 255`.
+///
+/// # Arguments
+///
+/// - `value` - The value to scale
+/// - `scale` - The fraction to scale by
+#[inline]
+pub const fn scale8(value: u8, scale: Fract8) -> u8 {
+    ((value as u16 * (1 + scale as u16)) >> 8) as u8
+}
+
+/// Scales a value by a non-zero fraction, never scaling a non-zero value to zero.
+///
+/// For brightness: a dimmed pixel stays lit, rather than turning off.
+///
+/// # Arguments
+///
+/// - `value` - The value to scale
+/// - `scale` - The fraction to scale by
+#[inline]
+pub const fn scale8_video(value: u8, scale: Fract8) -> u8 {
+    let scaled = ((value as u16 * scale as u16) >> 8) as u8;
+    if value != 0 && scale != 0 {
+        scaled + 1
+    } else {
+        scaled
+    }
+}
+
+/// Scales a 16-bit value by a fraction in 65536ths.
+///
+/// # Arguments
+///
+/// - `value` - The value to scale
+/// - `scale` - The fraction to scale by, in 65536ths
+#[inline]
+pub const fn scale16(value: u16, scale: u16) -> u16 {
+    ((value as u32 * (1 + scale as u32)) >> 16) as u16
+}
+
+/// Adds two values, saturating at 255.
+#[inline]
+pub const fn qadd8(a: u8, b: u8) -> u8 {
+    a.saturating_add(b)
+}
+
+/// Subtracts a value from another, saturating at 0.
+#[inline]
+pub const fn qsub8(a: u8, b: u8) -> u8 {
+    a.saturating_sub(b)
+}
+
+/// Blends between two values.
+///
+/// # Arguments
+///
+/// - `a` - The value at an amount of 0
+/// - `b` - The value at an amount of 255
+/// - `amount` - How far to blend from `a` to `b`
+#[inline]
+pub const fn blend8(a: u8, b: u8, amount: Fract8) -> u8 {
+    let partial = ((a as u32) << 8 | b as u32) + b as u32 * amount as u32;
+    ((partial - a as u32 * amount as u32) >> 8) as u8
+}
+
+/// Eases in and out, with a quadratic curve: slow at the ends, fast in the middle.
+///
+/// # Arguments
+///
+/// - `value` - The fraction to ease
+#[inline]
+pub const fn ease8_in_out_quad(value: Fract8) -> Fract8 {
+    let half = if value & 0x80 != 0 {
+        255 - value
+    } else {
+        value
+    };
+    let eased = scale8(half, half) << 1;
+    if value & 0x80 != 0 {
+        255 - eased
+    } else {
+        eased
+    }
+}
+
+/// Eases in and out, with a cubic curve: slower at the ends than
+/// [`ease8_in_out_quad`].
+///
+/// # Arguments
+///
+/// - `value` - The fraction to ease
+#[inline]
+pub const fn ease8_in_out_cubic(value: Fract8) -> Fract8 {
+    let squared = scale8(value, value);
+    let cubed = scale8(squared, value);
+    let eased = 3 * squared as u16 - 2 * cubed as u16;
+    if eased & 0x100 != 0 {
+        255
+    } else {
+        eased as u8
+    }
+}
+
+/// A triangle wave: from 0 up to 254 and back down, over a period of 256.
+///
+/// # Arguments
+///
+/// - `phase` - The position in the wave
+#[inline]
+pub const fn triwave8(phase: u8) -> u8 {
+    let half = if phase & 0x80 != 0 {
+        255 - phase
+    } else {
+        phase
+    };
+    half << 1
+}
+
+/// A wave with quadratic easing: like a sine wave, from [`triwave8`] and
+/// [`ease8_in_out_quad`].
+///
+/// # Arguments
+///
+/// - `phase` - The position in the wave
+#[inline]
+pub const fn quadwave8(phase: u8) -> u8 {
+    ease8_in_out_quad(triwave8(phase))
+}
+
+/// A wave with cubic easing: spends more time at the peaks than [`quadwave8`].
+///
+/// # Arguments
+///
+/// - `phase` - The position in the wave
+#[inline]
+pub const fn cubicwave8(phase: u8) -> u8 {
+    ease8_in_out_cubic(triwave8(phase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale() {
+        assert_eq!(scale8(255, 255), 255);
+        assert_eq!(scale8(255, 0), 0);
+        assert_eq!(scale8(100, 127), 50);
+        assert_eq!(scale8_video(1, 1), 1);
+        assert_eq!(scale8_video(0, 255), 0);
+        assert_eq!(scale8_video(255, 0), 0);
+        assert_eq!(scale8_video(255, 255), 255);
+        assert_eq!(scale16(65535, 65535), 65535);
+        assert_eq!(scale16(1000, 32767), 500);
+        assert_eq!(qadd8(200, 100), 255);
+        assert_eq!(qsub8(100, 200), 0);
+    }
+
+    #[test]
+    fn test_blend() {
+        for (a, b) in [(0, 255), (255, 0), (17, 17), (90, 10)] {
+            assert_eq!(blend8(a, b, 0), a);
+            assert_eq!(blend8(a, b, 255), b);
+        }
+        assert_eq!(blend8(0, 255, 128), 128);
+    }
+
+    #[test]
+    fn test_ease_and_waves() {
+        for ease in [ease8_in_out_quad, ease8_in_out_cubic] {
+            assert_eq!(ease(0), 0);
+            assert_eq!(ease(255), 255);
+        }
+        // As in FastLED, only the quadratic curve never steps back
+        assert!((0..=255).is_sorted_by_key(ease8_in_out_quad));
+        assert_eq!(ease8_in_out_quad(64), 32);
+        assert_eq!(triwave8(255), 0);
+        assert_eq!(quadwave8(0), 0);
+        assert_eq!(quadwave8(128), 255);
+        assert_eq!(cubicwave8(0), 0);
+        assert!(cubicwave8(128) >= 254);
+    }
+}
@@ -1,5 +1,9 @@
+pub mod beat;
 pub mod bits;
 pub mod component;
 mod map_range;
+pub mod math8;
+pub mod rng;
+pub mod subpixel;
 
 pub use self::map_range::*;
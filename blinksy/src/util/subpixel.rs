@@ -0,0 +1,164 @@
+//! # Sub-pixel Positions
+//!
+//! Patterns which draw objects at continuous positions (meteors, scanners, particles, ...)
+//! look like they step from pixel to pixel if each object lights only its nearest pixel.
+//! Instead, these helpers split an object's brightness between the two nearest pixels, in
+//! proportion to how near each is, so motion looks smooth.
+//!
+//! There are two ways to use them:
+//!
+//! - For patterns which compute each pixel from its position (as with `Layout::points()`),
+//!   [`coverage`] is the share of an object's brightness on a pixel.
+//! - For patterns which draw objects into a frame buffer, [`split`] returns the two nearest
+//!   pixels and their weights, and [`draw`] adds an object's color to them.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     layout::Layout1d,
+//!     layout1d,
+//!     util::subpixel::{coverage, pixel_spacing},
+//! };
+//!
+//! layout1d!(Layout, 5);
+//!
+//! // A scanner, a quarter of the way between the 2nd and 3rd pixel
+//! let position = -0.375;
+//! let spacing = pixel_spacing(Layout::PIXEL_COUNT);
+//!
+//! let levels: Vec<f32> = Layout::points()
+//!     .map(|x| coverage(x, position, spacing))
+//!     .collect();
+//!
+//! assert_eq!(levels, [0., 0.75, 0.25, 0., 0.]);
+//! ```
+
+#[allow(unused_imports)]
+use num_traits::float::FloatCore;
+
+use crate::color::LinearSrgb;
+
+/// A pixel and its share of an object's brightness.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SubPixel {
+    /// Index of the pixel
+    pub index: usize,
+    /// Share of the brightness, from 0.0 to 1.0
+    pub weight: f32,
+}
+
+/// Returns the distance between neighbouring pixels of a 1D layout.
+///
+/// For our 1D space, the first LED pixel is at -1.0 and the last LED pixel is at 1.0.
+///
+/// # Arguments
+///
+/// - `pixel_count` - The number of pixels in the layout
+pub fn pixel_spacing(pixel_count: usize) -> f32 {
+    if pixel_count > 1 {
+        2.0 / (pixel_count as f32 - 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Returns the share of an object's brightness on a pixel.
+///
+/// The share falls linearly from 1.0, with the object on the pixel, to 0.0, with the object
+/// a pixel spacing (or more) away.
+///
+/// # Arguments
+///
+/// - `pixel_position` - The position of the pixel
+/// - `position` - The position of the object
+/// - `pixel_spacing` - The distance between neighbouring pixels, see [`pixel_spacing`]
+///
+/// # Returns
+///
+/// The share of brightness, from 0.0 to 1.0
+pub fn coverage(pixel_position: f32, position: f32, pixel_spacing: f32) -> f32 {
+    if pixel_spacing <= 0. {
+        return if pixel_position == position { 1. } else { 0. };
+    }
+    (1. - (pixel_position - position).abs() / pixel_spacing).max(0.)
+}
+
+/// Splits a continuous position between the two nearest pixels.
+///
+/// # Arguments
+///
+/// - `position_in_pixels` - The position of the object, in pixels from the first pixel
+///   (e.g. 2.25 is a quarter of the way from the 3rd pixel to the 4th pixel)
+///
+/// # Returns
+///
+/// The pixel at or before the position, then the pixel after it, with weights summing to 1.0.
+/// Negative positions are clamped to the first pixel, but the pixel after may be past the end
+/// of the layout, so check each index before use.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::util::subpixel::{split, SubPixel};
+///
+/// let [lower, upper] = split(2.25);
+/// assert_eq!(lower, SubPixel { index: 2, weight: 0.75 });
+/// assert_eq!(upper, SubPixel { index: 3, weight: 0.25 });
+/// ```
+pub fn split(position_in_pixels: f32) -> [SubPixel; 2] {
+    let position_in_pixels = position_in_pixels.max(0.);
+    let index = position_in_pixels.floor();
+    let fraction = position_in_pixels - index;
+    let index = index as usize;
+    [
+        SubPixel {
+            index,
+            weight: 1. - fraction,
+        },
+        SubPixel {
+            index: index + 1,
+            weight: fraction,
+        },
+    ]
+}
+
+/// Adds an object's color to a frame, split between the two nearest pixels.
+///
+/// Colors are added, so overlapping objects blend, with each channel saturating at 1.0.
+/// Pixels outside of the frame are skipped, so objects can move on and off the ends.
+///
+/// # Arguments
+///
+/// - `pixels` - The frame of pixels
+/// - `position_in_pixels` - The position of the object, in pixels from the first pixel
+/// - `color` - The color of the object
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{color::LinearSrgb, util::subpixel::draw};
+///
+/// let mut pixels = [LinearSrgb::new(0., 0., 0.); 4];
+/// draw(&mut pixels, 1.5, LinearSrgb::new(1., 0., 0.));
+///
+/// assert_eq!(pixels[1].red, 0.5);
+/// assert_eq!(pixels[2].red, 0.5);
+/// ```
+pub fn draw(pixels: &mut [LinearSrgb], position_in_pixels: f32, color: LinearSrgb) {
+    let lower = position_in_pixels.floor();
+    let fraction = position_in_pixels - lower;
+
+    for (index, weight) in [(lower, 1. - fraction), (lower + 1., fraction)] {
+        if index < 0. {
+            continue;
+        }
+        let Some(pixel) = pixels.get_mut(index as usize) else {
+            continue;
+        };
+        pixel.red = (pixel.red + color.red * weight).min(1.);
+        pixel.green = (pixel.green + color.green * weight).min(1.);
+        pixel.blue = (pixel.blue + color.blue * weight).min(1.);
+    }
+}
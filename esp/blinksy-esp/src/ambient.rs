@@ -0,0 +1,100 @@
+//! # Ambient Light Utilities
+//!
+//! This module reads a light-dependent resistor (LDR) on an ADC pin, for use with
+//! [`AutoBrightness`](blinksy::ambient::AutoBrightness) to scale brightness with the ambient
+//! light.
+//!
+//! The LDR is expected in a voltage divider, with the LDR to 3.3V and a resistor to ground, so
+//! the voltage rises with the light. If your board wires it the other way around, use
+//! [`AdcLightSensor::inverted`].
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::ambient::{AutoBrightness, AutoBrightnessLimits};
+//! use blinksy_esp::ambient::AdcLightSensor;
+//! use esp_hal::analog::adc::{Adc, AdcConfig, Attenuation};
+//!
+//! let mut config = AdcConfig::new();
+//! let pin = config.enable_pin(p.GPIO34, Attenuation::_11dB);
+//! let adc = Adc::new(p.ADC1, config);
+//!
+//! let mut auto_brightness =
+//!     AutoBrightness::new(AdcLightSensor::new(adc, pin), AutoBrightnessLimits::default());
+//! ```
+
+use blinksy::ambient::AmbientLightSensor;
+use esp_hal::{
+    analog::adc::{Adc, AdcChannel, AdcPin, RegisterAccess},
+    Blocking,
+};
+
+/// The highest ADC reading, at the default resolution.
+#[cfg(feature = "esp32s2")]
+const MAX_READING: u16 = (1 << 13) - 1;
+#[cfg(not(feature = "esp32s2"))]
+const MAX_READING: u16 = (1 << 12) - 1;
+
+/// A light-dependent resistor, read with the ADC.
+///
+/// # Type Parameters
+///
+/// - `ADCI` - The ADC instance
+/// - `PIN` - The ADC pin
+pub struct AdcLightSensor<'d, ADCI, PIN> {
+    adc: Adc<'d, ADCI, Blocking>,
+    pin: AdcPin<PIN, ADCI>,
+    is_inverted: bool,
+}
+
+impl<'d, ADCI, PIN> AdcLightSensor<'d, ADCI, PIN>
+where
+    ADCI: RegisterAccess + 'd,
+    PIN: AdcChannel,
+{
+    /// Wraps an ADC and its pin.
+    ///
+    /// # Arguments
+    ///
+    /// - `adc` - The ADC driver
+    /// - `pin` - The pin with the LDR, enabled in the ADC config
+    pub fn new(adc: Adc<'d, ADCI, Blocking>, pin: AdcPin<PIN, ADCI>) -> Self {
+        Self {
+            adc,
+            pin,
+            is_inverted: false,
+        }
+    }
+
+    /// Inverts the readings, for an LDR where the voltage falls with the light.
+    pub fn inverted(mut self) -> Self {
+        self.is_inverted = !self.is_inverted;
+        self
+    }
+
+    /// Unwraps the ADC driver and its pin.
+    pub fn into_inner(self) -> (Adc<'d, ADCI, Blocking>, AdcPin<PIN, ADCI>) {
+        (self.adc, self.pin)
+    }
+}
+
+impl<'d, ADCI, PIN> AmbientLightSensor for AdcLightSensor<'d, ADCI, PIN>
+where
+    ADCI: RegisterAccess + 'd,
+    PIN: AdcChannel,
+{
+    fn light_level(&mut self) -> f32 {
+        // A oneshot read only ever waits for the conversion to finish.
+        let reading = loop {
+            if let Ok(reading) = self.adc.read_oneshot(&mut self.pin) {
+                break reading;
+            }
+        };
+        let level = (reading.min(MAX_READING) as f32) / (MAX_READING as f32);
+        if self.is_inverted {
+            1. - level
+        } else {
+            level
+        }
+    }
+}
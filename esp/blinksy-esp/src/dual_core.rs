@@ -0,0 +1,218 @@
+//! # Dual-Core Rendering
+//!
+//! On dual-core chips (ESP32, ESP32-S3), split each frame across the two cores: the render
+//! core runs the pattern, while the transmit core encodes and sends the previous frame to the
+//! LEDs, with RMT or SPI. For heavy patterns (e.g. 3D noise) on big layouts, this raises the
+//! frame rate up to the slower of the two, rather than the sum of both.
+//!
+//! The cores hand off frames through a [`FrameQueue`], split into:
+//!
+//! - A [`RenderDriver`], the driver of the [`Control`](blinksy::control::Control) on the render
+//!   core, which queues each frame of pixels
+//! - A [`Transmitter`], which owns the real LED driver on the transmit core, and shows each
+//!   queued frame
+//!
+//! If the transmitter falls behind, the render driver waits for a free slot, so no frame is
+//! dropped.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{layout::Layout1d, layout1d, leds::Ws2812, ControlBuilder};
+//! use blinksy_esp::{dual_core::FrameQueue, time::elapsed};
+//! use esp_hal::system::{CpuControl, Stack};
+//! use static_cell::StaticCell;
+//!
+//! layout1d!(Layout, 60 * 20);
+//!
+//! static QUEUE: StaticCell<FrameQueue<{ Layout::PIXEL_COUNT }>> = StaticCell::new();
+//! static STACK: StaticCell<Stack<8192>> = StaticCell::new();
+//!
+//! let (render_driver, transmitter) = QUEUE.init(FrameQueue::new()).split();
+//!
+//! let ws2812_driver = /* RMT driver, as usual */;
+//! let mut cpu_control = CpuControl::new(p.CPU_CTRL);
+//! let _guard = cpu_control
+//!     .start_app_core(STACK.init(Stack::new()), move || {
+//!         transmitter
+//!             .run::<_, { Ws2812::frame_buffer_size(Layout::PIXEL_COUNT) }>(ws2812_driver)
+//!             .unwrap();
+//!     })
+//!     .unwrap();
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Noise1d<noise_fns::Perlin>>(Default::default())
+//!     .with_driver(render_driver)
+//!     .with_frame_buffer_size::<{ Layout::PIXEL_COUNT }>()
+//!     .build();
+//!
+//! loop {
+//!     control.tick(elapsed().as_millis()).unwrap();
+//! }
+//! ```
+
+use core::{convert::Infallible, hint};
+
+pub use blinksy::capacities::FRAME_QUEUE_SIZE;
+use blinksy::{
+    color::{ColorCorrection, FromColor, LinearSrgb},
+    driver::{Driver, Frame, GlobalBrightness},
+};
+use heapless::{
+    spsc::{Consumer, Producer, Queue},
+    Vec,
+};
+
+/// A queue of frames, from the render core to the transmit core.
+///
+/// Place it in a `static` (e.g. with `static_cell`), then [`split`](FrameQueue::split) it.
+///
+/// # Type Parameters
+///
+/// - `PIXEL_COUNT` - Number of pixels in each frame
+pub struct FrameQueue<const PIXEL_COUNT: usize> {
+    // Holds one less frame than its size.
+    queue: Queue<Frame<PIXEL_COUNT>, { FRAME_QUEUE_SIZE + 1 }>,
+}
+
+impl<const PIXEL_COUNT: usize> FrameQueue<PIXEL_COUNT> {
+    /// Creates an empty queue.
+    pub const fn new() -> Self {
+        Self {
+            queue: Queue::new(),
+        }
+    }
+
+    /// Splits the queue into the driver for the render core, and the transmitter for the transmit
+    /// core.
+    pub fn split(&mut self) -> (RenderDriver<'_, PIXEL_COUNT>, Transmitter<'_, PIXEL_COUNT>) {
+        let (producer, consumer) = self.queue.split();
+        (
+            RenderDriver {
+                producer,
+                global_brightness: GlobalBrightness::default(),
+            },
+            Transmitter {
+                consumer,
+                global_brightness: GlobalBrightness::default(),
+            },
+        )
+    }
+}
+
+impl<const PIXEL_COUNT: usize> Default for FrameQueue<PIXEL_COUNT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The driver on the render core: queues each frame for the [`Transmitter`].
+///
+/// Use a frame buffer size of `PIXEL_COUNT`: each word of the frame buffer is a pixel.
+pub struct RenderDriver<'a, const PIXEL_COUNT: usize> {
+    producer: Producer<'a, Frame<PIXEL_COUNT>>,
+    global_brightness: GlobalBrightness,
+}
+
+impl<const PIXEL_COUNT: usize> Driver for RenderDriver<'_, PIXEL_COUNT> {
+    type Error = Infallible;
+    type Color = LinearSrgb;
+    type Word = LinearSrgb;
+
+    fn encode<const P: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        pixels.into_iter().map(LinearSrgb::from_color).collect()
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        let mut frame = Frame::new(frame, brightness, correction, self.global_brightness);
+        // Wait for the transmitter to free a slot.
+        while let Err(unsent) = self.producer.enqueue(frame) {
+            frame = unsent;
+            hint::spin_loop();
+        }
+        Ok(())
+    }
+
+    fn set_global_brightness(&mut self, global_brightness: GlobalBrightness) {
+        self.global_brightness = global_brightness;
+    }
+}
+
+/// The transmitter on the transmit core: shows each frame queued by the [`RenderDriver`].
+pub struct Transmitter<'a, const PIXEL_COUNT: usize> {
+    consumer: Consumer<'a, Frame<PIXEL_COUNT>>,
+    global_brightness: GlobalBrightness,
+}
+
+impl<const PIXEL_COUNT: usize> Transmitter<'_, PIXEL_COUNT> {
+    /// Shows the next queued frame, if any.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `FRAME_BUFFER_SIZE` - Length of the encoded frame buffer of the LED driver, in words
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The LED driver
+    ///
+    /// # Returns
+    ///
+    /// Whether a frame was shown, or an error from the driver
+    pub fn transmit<D, const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        driver: &mut D,
+    ) -> Result<bool, D::Error>
+    where
+        D: Driver,
+        D::Color: FromColor<LinearSrgb>,
+    {
+        let Some(frame) = self.consumer.dequeue() else {
+            return Ok(false);
+        };
+        frame.show::<_, FRAME_BUFFER_SIZE>(driver, &mut self.global_brightness)?;
+        Ok(true)
+    }
+
+    /// Shows each queued frame, forever: the entry point of the transmit core.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `FRAME_BUFFER_SIZE` - Length of the encoded frame buffer of the LED driver, in words
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The LED driver
+    ///
+    /// # Returns
+    ///
+    /// Only returns on an error from the driver
+    pub fn run<D, const FRAME_BUFFER_SIZE: usize>(
+        mut self,
+        mut driver: D,
+    ) -> Result<Infallible, D::Error>
+    where
+        D: Driver,
+        D::Color: FromColor<LinearSrgb>,
+    {
+        loop {
+            if !self.transmit::<D, FRAME_BUFFER_SIZE>(&mut driver)? {
+                hint::spin_loop();
+            }
+        }
+    }
+}
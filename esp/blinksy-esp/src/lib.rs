@@ -7,7 +7,14 @@
 //! ## Features
 //!
 //! - ESP-specific driver for clockless (e.g. WS2812) LEDs, using [RMT (Remote Control Module)][RMT] peripheral
+//!   (on all chips except the ESP32-C2, which has no RMT peripheral)
 //! - ESP-specific elapsed time helper
+//! - Panic-safe LED blanking hook
+//! - Dual-core rendering, with the pattern on one core and transmission on the other (ESP32 and
+//!   ESP32-S3)
+//! - Internal temperature sensor, for thermal throttling (ESP32-C3 and ESP32-C6)
+//! - Light-dependent resistor on an ADC pin, for automatic brightness
+//! - Over-the-air firmware updates, with progress shown on the LEDs (with the `ota` feature)
 //!
 //! [RMT]: https://docs.espressif.com/projects/esp-idf/en/latest/esp32/api-reference/peripherals/rmt.html
 //!
@@ -56,7 +63,8 @@
 //!                 .with_led::<Ws2812>()
 //!                 .with_channel(rmt_channel)
 //!                 .with_pin(data_pin)
-//!                 .build())
+//!                 .build()
+//!                 .unwrap())
 //!     };
 //!
 //!     // Build the Blinky controller
@@ -89,8 +97,21 @@
 //! [blinksy-quickstart-gledopto]: https://github.com/ahdinosaur/blinksy-quickstart-gledopto
 //! [gledopto]: https://docs.rs/gledopto/0.10/gledopto
 
+pub mod ambient;
+#[cfg(any(feature = "esp32", feature = "esp32s3"))]
+pub mod dual_core;
+#[cfg(feature = "ota")]
+pub mod ota;
+pub mod panic;
+#[cfg(not(feature = "esp32c2"))]
 pub mod rmt;
+#[cfg(any(feature = "esp32c3", feature = "esp32c6"))]
+pub mod thermal;
 pub mod time;
+#[cfg(not(feature = "esp32c2"))]
 pub(crate) mod util;
 
-pub use crate::rmt::{ClocklessRmt, ClocklessRmtBuilder, ClocklessRmtError};
+#[cfg(not(feature = "esp32c2"))]
+pub use crate::rmt::{
+    ClocklessRmt, ClocklessRmtBuilder, ClocklessRmtError, FrameEnd, RmtChannels, RmtTxChannel,
+};
@@ -0,0 +1,220 @@
+//! # Over-The-Air Updates
+//!
+//! Update the firmware of a deployed controller, from an image received over the network
+//! (WiFi, Ethernet, ...): write the new image in chunks with [`OtaUpdate`], then reboot.
+//!
+//! This uses the ESP-IDF OTA partition scheme, via [`esp-bootloader-esp-idf`][bootloader]: the
+//! partition table needs an `otadata` partition and at least two OTA app partitions (`ota_0`,
+//! `ota_1`). The new image is written to the OTA app partition which isn't running, then the
+//! bootloader boots it on the next reset. Once the new firmware works, call [`mark_valid`] so
+//! the bootloader keeps it (otherwise, with rollback enabled, it goes back to the old firmware).
+//!
+//! Each write returns the [`OtaProgress`], which converts into a progress bar
+//! [`NotificationStyle`] for [`Control::notify`](blinksy::control::Control::notify), so the LEDs
+//! show the update in progress.
+//!
+//! [bootloader]: https://docs.rs/esp-bootloader-esp-idf
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::color::LinearSrgb;
+//! use blinksy_esp::ota::{OtaUpdate, PARTITION_TABLE_MAX_LEN};
+//! use esp_storage::FlashStorage;
+//!
+//! let mut flash = FlashStorage::new();
+//! let mut buffer = [0; PARTITION_TABLE_MAX_LEN];
+//! let mut update = OtaUpdate::begin(&mut flash, &mut buffer, image_size).unwrap();
+//!
+//! while let Some(chunk) = /* next chunk of the image, from the network */ {
+//!     let progress = update.write(chunk).unwrap();
+//!     control.notify(LinearSrgb::new(0., 0., 1.), 5000, progress.into());
+//!     control.tick(elapsed().as_millis()).unwrap();
+//! }
+//!
+//! update.finish().unwrap();
+//! esp_hal::system::software_reset();
+//! ```
+
+use blinksy::compositor::NotificationStyle;
+use embedded_storage::Storage;
+use esp_bootloader_esp_idf::{
+    ota::OtaImageState,
+    ota_updater::OtaUpdater,
+    partitions::{self, FlashRegion},
+};
+
+pub use esp_bootloader_esp_idf::partitions::PARTITION_TABLE_MAX_LEN;
+
+/// All types of errors that can happen during an OTA update.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OtaError {
+    /// Raised if the partitions can't be read or written, or the partition table doesn't have
+    /// the OTA partitions
+    Partition(partitions::Error),
+    /// Raised if the image is larger than the OTA app partition
+    TooLarge,
+    /// Raised if more bytes are written than the image size
+    Overflow,
+    /// Raised if the update is finished before the whole image is written
+    Incomplete,
+}
+
+impl From<partitions::Error> for OtaError {
+    fn from(error: partitions::Error) -> Self {
+        OtaError::Partition(error)
+    }
+}
+
+/// How much of an image has been written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OtaProgress {
+    /// Bytes written so far
+    pub written: u32,
+    /// Size of the image in bytes
+    pub image_size: u32,
+}
+
+impl OtaProgress {
+    /// Returns the fraction written, from 0.0 to 1.0.
+    pub fn fraction(&self) -> f32 {
+        if self.image_size == 0 {
+            1.
+        } else {
+            self.written as f32 / self.image_size as f32
+        }
+    }
+}
+
+impl From<OtaProgress> for NotificationStyle {
+    fn from(progress: OtaProgress) -> Self {
+        NotificationStyle::Progress {
+            progress: progress.fraction(),
+        }
+    }
+}
+
+/// An OTA update in progress.
+///
+/// # Type Parameters
+///
+/// - `F` - The flash storage type, e.g. `esp_storage::FlashStorage`
+pub struct OtaUpdate<'a, F>
+where
+    F: Storage,
+{
+    updater: OtaUpdater<'a, F>,
+    progress: OtaProgress,
+}
+
+impl<'a, F> OtaUpdate<'a, F>
+where
+    F: Storage,
+{
+    /// Begins an update, to the OTA app partition which isn't running.
+    ///
+    /// # Arguments
+    ///
+    /// - `flash` - The flash storage
+    /// - `buffer` - A buffer to read the partition table into
+    /// - `image_size` - Size of the new image in bytes
+    ///
+    /// # Returns
+    ///
+    /// The update, or an error if the partition table doesn't have the OTA partitions or the
+    /// image doesn't fit
+    pub fn begin(
+        flash: &'a mut F,
+        buffer: &'a mut [u8; PARTITION_TABLE_MAX_LEN],
+        image_size: u32,
+    ) -> Result<Self, OtaError> {
+        let mut updater = OtaUpdater::new(flash, buffer)?;
+        let partition_size = updater.with_next_partition(|region, _| region.partition_size())?;
+        if image_size as usize > partition_size {
+            return Err(OtaError::TooLarge);
+        }
+        Ok(Self {
+            updater,
+            progress: OtaProgress {
+                written: 0,
+                image_size,
+            },
+        })
+    }
+
+    /// Writes the next chunk of the image.
+    ///
+    /// # Arguments
+    ///
+    /// - `chunk` - The next bytes of the image
+    ///
+    /// # Returns
+    ///
+    /// The progress of the update, or an error if the chunk can't be written
+    pub fn write(&mut self, chunk: &[u8]) -> Result<OtaProgress, OtaError> {
+        let offset = self.progress.written;
+        let written = offset
+            .checked_add(chunk.len() as u32)
+            .filter(|written| *written <= self.progress.image_size)
+            .ok_or(OtaError::Overflow)?;
+        self.updater
+            .with_next_partition(|mut region: FlashRegion<'_, F>, _| {
+                region.write(offset, chunk)
+            })??;
+        self.progress.written = written;
+        Ok(self.progress)
+    }
+
+    /// Returns the progress of the update.
+    pub fn progress(&self) -> OtaProgress {
+        self.progress
+    }
+
+    /// Finishes the update, so the bootloader boots the new image on the next reset.
+    ///
+    /// # Returns
+    ///
+    /// An error if the whole image hasn't been written, or the partition can't be activated
+    pub fn finish(mut self) -> Result<(), OtaError> {
+        if self.progress.written != self.progress.image_size {
+            return Err(OtaError::Incomplete);
+        }
+        self.updater.activate_next_partition()?;
+        self.updater.set_current_ota_state(OtaImageState::New)?;
+        Ok(())
+    }
+}
+
+/// Marks the running firmware as working, so the bootloader doesn't roll back to the previous
+/// firmware.
+///
+/// Call this once the firmware of an update has started up fine.
+///
+/// # Arguments
+///
+/// - `flash` - The flash storage
+/// - `buffer` - A buffer to read the partition table into
+///
+/// # Returns
+///
+/// An error if the partition table doesn't have the OTA partitions, or they can't be written
+pub fn mark_valid<F>(
+    flash: &mut F,
+    buffer: &mut [u8; PARTITION_TABLE_MAX_LEN],
+) -> Result<(), OtaError>
+where
+    F: Storage,
+{
+    let mut updater = OtaUpdater::new(flash, buffer)?;
+    match updater.current_ota_state() {
+        Ok(OtaImageState::New | OtaImageState::PendingVerify) => {
+            updater.set_current_ota_state(OtaImageState::Valid)?;
+        }
+        // Not booted from an update, e.g. from the factory partition
+        Ok(_) | Err(partitions::Error::InvalidState) => {}
+        Err(error) => return Err(error.into()),
+    }
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//! # Panic-Safe LED Blanking
+//!
+//! LEDs latch the last frame they received. If the controller panics (or is reset by a
+//! watchdog) mid-show, the strip keeps showing that frame, which may be a blinding full
+//! white.
+//!
+//! This module holds a single "blank" hook: a function which writes a zero frame to the LEDs.
+//!
+//! - Register it with [`set_blank_hook`], once the LED pins are known.
+//! - Call [`blank`] from your panic handler. (With `esp-backtrace`, enable its
+//!   `custom-pre-backtrace` feature and call [`blank`] from `custom_pre_backtrace`. The
+//!   `gledopto` crate does this for you with its `panic-blank` feature.)
+//! - A watchdog reset doesn't run any code beforehand, so also call [`blank`] early at boot,
+//!   to clear a frame latched before the reset.
+//!
+//! The hook runs after the LED driver may have panicked, so it shouldn't rely on the driver:
+//! instead, steal the peripherals and write a zero frame with a new blocking driver. For
+//! clockless LEDs, use a [`ClocklessRmt`](crate::ClocklessRmt), not a bit-banged writer: with
+//! the sub-µs timing of a busy-wait delay, "0" bits may stretch into "1" bits, so the LEDs
+//! could latch full white instead.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{driver::{ClocklessDriver, Driver}, leds::Ws2812};
+//! use blinksy_esp::{rmt::DEFAULT_RMT_FREQUENCY, ClocklessRmtBuilder, RmtChannels};
+//! use esp_hal::{peripherals::{GPIO16, RMT}, rmt::Rmt};
+//!
+//! fn blank_leds() {
+//!     let rmt = unsafe { RMT::steal() };
+//!     let pin = unsafe { GPIO16::steal() };
+//!     let Ok(rmt) = Rmt::new(rmt, DEFAULT_RMT_FREQUENCY) else {
+//!         return;
+//!     };
+//!     let mut rmt = RmtChannels::new(rmt);
+//!     let Ok(writer) = ClocklessRmtBuilder::default()
+//!         .with_led::<Ws2812>()
+//!         .with_rmt(&mut rmt)
+//!         .and_then(|builder| builder.with_pin(pin).build())
+//!     else {
+//!         return;
+//!     };
+//!     let mut driver = ClocklessDriver::default()
+//!         .with_led::<Ws2812>()
+//!         .with_writer(writer);
+//!     let _ = driver.blank::<60, { Ws2812::frame_buffer_size(60) }>();
+//! }
+//!
+//! blinksy_esp::panic::set_blank_hook(blank_leds);
+//! blinksy_esp::panic::blank();
+//! ```
+
+use core::{
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// The registered blank hook, as a pointer to a `fn()`, or null if none.
+static BLANK_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Registers the function which writes a zero frame to the LEDs.
+///
+/// Replaces any previously registered hook.
+///
+/// # Arguments
+///
+/// - `hook` - Writes a zero frame to the LEDs
+pub fn set_blank_hook(hook: fn()) {
+    BLANK_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Unregisters the blank hook.
+pub fn clear_blank_hook() {
+    BLANK_HOOK.store(ptr::null_mut(), Ordering::Release);
+}
+
+/// Blanks the LEDs, by calling the registered hook.
+///
+/// Does nothing if no hook is registered. The hook is unregistered while it runs, so a panic
+/// within the hook doesn't call it again.
+pub fn blank() {
+    let hook = BLANK_HOOK.load(Ordering::Acquire);
+    if hook.is_null() {
+        return;
+    }
+    clear_blank_hook();
+
+    // SAFETY: The only non-null values stored are `fn()` pointers, from `set_blank_hook`.
+    let hook = unsafe { core::mem::transmute::<*mut (), fn()>(hook) };
+    hook();
+
+    BLANK_HOOK.store(hook as *mut (), Ordering::Release);
+}
@@ -10,6 +10,8 @@
 //! - Hardware-accelerated LED control
 //! - Precise timing for WS2812 and similar protocols
 //! - Blocking and async (feature "async") APIs with equivalent behavior
+//! - No panics: configuration and transmission failures are returned as
+//!   [`ClocklessRmtError`]s, so firmware can degrade gracefully
 //!
 //! ## Technical Details
 //!
@@ -17,6 +19,43 @@
 //! pulses that match the protocol requirements. This implementation converts
 //! each bit of color data into the corresponding high/low pulse durations
 //! required by the specific LED protocol.
+//!
+//! ## Frames Larger Than Channel RAM
+//!
+//! Each RMT channel has only [`CHANNEL_RAM_SIZE`] pulses of RAM. A frame is sent in
+//! transmissions of up to `RMT_BUFFER_SIZE` pulses (see
+//! [`ClocklessRmtBuilder::with_rmt_buffer_size`]):
+//!
+//! - With an RMT buffer for the whole frame, from [`rmt_buffer_size`], each frame is one
+//!   transmission. `esp-hal` streams it through the channel RAM in wrap mode, refilling each
+//!   half of the RAM at the threshold event while the other half is sent, so the frame goes
+//!   out without gaps.
+//! - With a smaller RMT buffer (the default is one channel RAM), the frame is split into
+//!   chunks, each a separate transmission. Between chunks, the data line idles low for a few
+//!   microseconds. LEDs tolerate short gaps, but a long one (e.g. from an interrupt) latches
+//!   a partial frame.
+//!
+//! Streaming a frame through the channel RAM straight from the frame buffer, without an RMT
+//! buffer for the whole frame, needs access to the channel RAM and threshold registers,
+//! which `esp-hal` keeps private: its transmissions only refill from a buffer of pulses.
+//!
+//! ## End of Frame
+//!
+//! LEDs latch a frame once the data line stays low for the reset time of their chipset
+//! ([`ClocklessLed::T_RESET`]). Set how the end of each frame is handled with
+//! [`ClocklessRmtBuilder::with_frame_end`]:
+//!
+//! - [`FrameEnd::ResetPulse`] (default): A low reset pulse is sent after the data, within the
+//!   frame, so writes return once the LEDs latched. With an RMT buffer for the whole frame, add
+//!   a pulse for each 819 µs of reset time (at 80 MHz) to [`rmt_buffer_size`], to send it in the
+//!   same transmission.
+//! - [`FrameEnd::Idle`]: The line idles low after the frame, and the next frame is held back
+//!   until the reset time has passed since. Writes return as soon as the data is sent. The next
+//!   write busy-waits for the rest of the reset time, or with the `embassy-time` feature, async
+//!   writes wait with an embassy timer, so need an embassy time driver, e.g. from `esp-rtos`.
+//!
+//! Some clone chipsets need a longer latch time than their datasheet, or than genuine
+//! WS2812Bs: extend the reset time with [`ClocklessRmtBuilder::with_inter_frame_gap`].
 
 #[cfg(feature = "async")]
 use blinksy::driver::ClocklessWriterAsync;
@@ -28,20 +67,52 @@ use core::{fmt::Debug, marker::PhantomData};
 #[cfg(feature = "async")]
 use esp_hal::Async;
 use esp_hal::{
-    clock::Clocks,
     gpio::{interconnect::PeripheralOutput, Level},
     rmt::{
-        Channel, Error as RmtError, PulseCode, Tx, TxChannelConfig, TxChannelCreator,
-        CHANNEL_RAM_SIZE,
+        Channel, ChannelCreator, Error as RmtError, PulseCode, Rmt, Tx, TxChannelConfig,
+        TxChannelCreator, CHANNEL_RAM_SIZE,
     },
+    time::{Duration, Instant, Rate},
     Blocking, DriverMode,
 };
 use heapless::Vec;
 
 use crate::util::chunked;
 
+/// The default frequency of the RMT peripheral, as used with [`Rmt::new`].
+pub const DEFAULT_RMT_FREQUENCY: Rate = Rate::from_mhz(80);
+
+/// Returns the RMT buffer size to send a whole frame as one transmission: a pulse for each
+/// bit of each channel's word, and the end marker.
+///
+/// So a [`Ws2812`](blinksy::leds::Ws2812) has 8 pulses per channel, and a
+/// [`Ws2816`](blinksy::leds::Ws2816), with 16-bit words, has 16:
+///
+/// ```rust,ignore
+/// // 60 pixels, 3 channels, 16 bits each, and the end marker
+/// assert_eq!(rmt_buffer_size::<Ws2816>(60), 60 * 3 * 16 + 1);
+/// ```
 pub const fn rmt_buffer_size<Led: ClocklessLed>(pixel_count: usize) -> usize {
-    pixel_count * Led::LED_CHANNELS.channel_count() * 8 + 1
+    pixel_count * Led::LED_CHANNELS.channel_count() * size_of::<Led::Word>() * 8 + 1
+}
+
+// Each channel's word is sent as a pulse per bit, so wider words need more pulses.
+const _: () = {
+    assert!(rmt_buffer_size::<blinksy::leds::Ws2812>(1) == 3 * 8 + 1);
+    assert!(rmt_buffer_size::<blinksy::leds::Ws2816>(1) == 3 * 16 + 1);
+};
+
+/// How the end of each frame latches the LEDs.
+///
+/// See [End of Frame](crate::rmt#end-of-frame).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FrameEnd {
+    /// Send a low reset pulse after the data, within the frame
+    #[default]
+    ResetPulse,
+    /// Idle low after the frame, holding back the next frame until the reset time has passed
+    Idle,
 }
 
 /// All types of errors that can happen during the conversion and transmission
@@ -53,12 +124,188 @@ pub enum ClocklessRmtError {
     BufferSizeExceeded,
     /// Raised if something goes wrong in the transmission
     TransmissionError(RmtError),
+    /// Raised if every RMT transmit channel has already been allocated
+    ChannelsExhausted,
+    /// Raised if the RMT transmit channel can't be configured, e.g. with an invalid pin
+    ConfigurationError(RmtError),
+    /// Raised if the RMT transmit channel was lost to an earlier failed transmission
+    ChannelUnavailable,
+}
+
+/// A free RMT transmit channel, allocated by [`RmtChannels`].
+///
+/// Wraps the typed channel creators of the [`Rmt`] peripheral, so channels
+/// can be handed out at runtime instead of by hand-picked channel number.
+pub enum RmtTxChannel<'ch, Dm>
+where
+    Dm: DriverMode,
+{
+    Channel0(ChannelCreator<'ch, Dm, 0>),
+    Channel1(ChannelCreator<'ch, Dm, 1>),
+    #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+    Channel2(ChannelCreator<'ch, Dm, 2>),
+    #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+    Channel3(ChannelCreator<'ch, Dm, 3>),
+    #[cfg(feature = "esp32")]
+    Channel4(ChannelCreator<'ch, Dm, 4>),
+    #[cfg(feature = "esp32")]
+    Channel5(ChannelCreator<'ch, Dm, 5>),
+    #[cfg(feature = "esp32")]
+    Channel6(ChannelCreator<'ch, Dm, 6>),
+    #[cfg(feature = "esp32")]
+    Channel7(ChannelCreator<'ch, Dm, 7>),
+}
+
+impl<'ch, Dm> TxChannelCreator<'ch, Dm> for RmtTxChannel<'ch, Dm>
+where
+    Dm: DriverMode,
+{
+    fn configure_tx(
+        self,
+        pin: impl PeripheralOutput<'ch>,
+        config: TxChannelConfig,
+    ) -> Result<Channel<'ch, Dm, Tx>, RmtError>
+    where
+        Self: Sized,
+    {
+        match self {
+            RmtTxChannel::Channel0(channel) => channel.configure_tx(pin, config),
+            RmtTxChannel::Channel1(channel) => channel.configure_tx(pin, config),
+            #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+            RmtTxChannel::Channel2(channel) => channel.configure_tx(pin, config),
+            #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+            RmtTxChannel::Channel3(channel) => channel.configure_tx(pin, config),
+            #[cfg(feature = "esp32")]
+            RmtTxChannel::Channel4(channel) => channel.configure_tx(pin, config),
+            #[cfg(feature = "esp32")]
+            RmtTxChannel::Channel5(channel) => channel.configure_tx(pin, config),
+            #[cfg(feature = "esp32")]
+            RmtTxChannel::Channel6(channel) => channel.configure_tx(pin, config),
+            #[cfg(feature = "esp32")]
+            RmtTxChannel::Channel7(channel) => channel.configure_tx(pin, config),
+        }
+    }
+}
+
+/// Allocator for the transmit channels of an [`Rmt`] peripheral.
+///
+/// Each call to [`RmtChannels::next_tx`] hands out a channel that hasn't been
+/// allocated yet, so multiple clockless drivers can share one RMT peripheral.
+///
+/// ```rust,ignore
+/// let mut rmt = RmtChannels::new(Rmt::new(p.RMT, Rate::from_mhz(80)).unwrap());
+///
+/// let strip_a = ClocklessRmtBuilder::default()
+///     .with_led::<Ws2812>()
+///     .with_rmt(&mut rmt)?
+///     .with_pin(p.GPIO16)
+///     .build()?;
+/// let strip_b = ClocklessRmtBuilder::default()
+///     .with_led::<Ws2812>()
+///     .with_rmt(&mut rmt)?
+///     .with_pin(p.GPIO17)
+///     .build()?;
+/// ```
+pub struct RmtChannels<'rmt, Dm>
+where
+    Dm: DriverMode,
+{
+    channel0: Option<ChannelCreator<'rmt, Dm, 0>>,
+    channel1: Option<ChannelCreator<'rmt, Dm, 1>>,
+    #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+    channel2: Option<ChannelCreator<'rmt, Dm, 2>>,
+    #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+    channel3: Option<ChannelCreator<'rmt, Dm, 3>>,
+    #[cfg(feature = "esp32")]
+    channel4: Option<ChannelCreator<'rmt, Dm, 4>>,
+    #[cfg(feature = "esp32")]
+    channel5: Option<ChannelCreator<'rmt, Dm, 5>>,
+    #[cfg(feature = "esp32")]
+    channel6: Option<ChannelCreator<'rmt, Dm, 6>>,
+    #[cfg(feature = "esp32")]
+    channel7: Option<ChannelCreator<'rmt, Dm, 7>>,
+}
+
+impl<'rmt, Dm> RmtChannels<'rmt, Dm>
+where
+    Dm: DriverMode,
+{
+    /// Take ownership of the transmit channels of an RMT peripheral.
+    pub fn new(rmt: Rmt<'rmt, Dm>) -> Self {
+        Self {
+            channel0: Some(rmt.channel0),
+            channel1: Some(rmt.channel1),
+            #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+            channel2: Some(rmt.channel2),
+            #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+            channel3: Some(rmt.channel3),
+            #[cfg(feature = "esp32")]
+            channel4: Some(rmt.channel4),
+            #[cfg(feature = "esp32")]
+            channel5: Some(rmt.channel5),
+            #[cfg(feature = "esp32")]
+            channel6: Some(rmt.channel6),
+            #[cfg(feature = "esp32")]
+            channel7: Some(rmt.channel7),
+        }
+    }
+
+    /// Allocate the next free transmit channel.
+    ///
+    /// # Returns
+    ///
+    /// A free channel, or `None` if every transmit channel is already allocated
+    pub fn next_tx(&mut self) -> Option<RmtTxChannel<'rmt, Dm>> {
+        if let Some(channel) = self.channel0.take() {
+            return Some(RmtTxChannel::Channel0(channel));
+        }
+        if let Some(channel) = self.channel1.take() {
+            return Some(RmtTxChannel::Channel1(channel));
+        }
+        #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+        if let Some(channel) = self.channel2.take() {
+            return Some(RmtTxChannel::Channel2(channel));
+        }
+        #[cfg(any(feature = "esp32", feature = "esp32s2", feature = "esp32s3"))]
+        if let Some(channel) = self.channel3.take() {
+            return Some(RmtTxChannel::Channel3(channel));
+        }
+        #[cfg(feature = "esp32")]
+        if let Some(channel) = self.channel4.take() {
+            return Some(RmtTxChannel::Channel4(channel));
+        }
+        #[cfg(feature = "esp32")]
+        if let Some(channel) = self.channel5.take() {
+            return Some(RmtTxChannel::Channel5(channel));
+        }
+        #[cfg(feature = "esp32")]
+        if let Some(channel) = self.channel6.take() {
+            return Some(RmtTxChannel::Channel6(channel));
+        }
+        #[cfg(feature = "esp32")]
+        if let Some(channel) = self.channel7.take() {
+            return Some(RmtTxChannel::Channel7(channel));
+        }
+        None
+    }
+}
+
+impl<'rmt, Dm> From<Rmt<'rmt, Dm>> for RmtChannels<'rmt, Dm>
+where
+    Dm: DriverMode,
+{
+    fn from(rmt: Rmt<'rmt, Dm>) -> Self {
+        Self::new(rmt)
+    }
 }
 
 pub struct ClocklessRmtBuilder<const RMT_BUFFER_SIZE: usize, Led, Chan, Pin> {
     led: PhantomData<Led>,
     channel: Chan,
     pin: Pin,
+    frequency: Rate,
+    frame_end: FrameEnd,
+    inter_frame_gap: Duration,
 }
 
 impl Default for ClocklessRmtBuilder<CHANNEL_RAM_SIZE, (), (), ()> {
@@ -67,11 +314,18 @@ impl Default for ClocklessRmtBuilder<CHANNEL_RAM_SIZE, (), (), ()> {
             led: PhantomData,
             channel: (),
             pin: (),
+            frequency: DEFAULT_RMT_FREQUENCY,
+            frame_end: FrameEnd::default(),
+            inter_frame_gap: Duration::ZERO,
         }
     }
 }
 
 impl<Led, Chan, Pin> ClocklessRmtBuilder<CHANNEL_RAM_SIZE, Led, Chan, Pin> {
+    /// Set the number of pulses in each transmission, including the end marker.
+    ///
+    /// Use [`rmt_buffer_size`] to send each frame as one transmission, without gaps. See
+    /// [Frames Larger Than Channel RAM](self#frames-larger-than-channel-ram).
     pub fn with_rmt_buffer_size<const RMT_BUFFER_SIZE: usize>(
         self,
     ) -> ClocklessRmtBuilder<RMT_BUFFER_SIZE, Led, Chan, Pin> {
@@ -79,6 +333,9 @@ impl<Led, Chan, Pin> ClocklessRmtBuilder<CHANNEL_RAM_SIZE, Led, Chan, Pin> {
             led: self.led,
             channel: self.channel,
             pin: self.pin,
+            frequency: self.frequency,
+            frame_end: self.frame_end,
+            inter_frame_gap: self.inter_frame_gap,
         }
     }
 }
@@ -89,6 +346,9 @@ impl<const RMT_BUFFER_SIZE: usize, Chan, Pin> ClocklessRmtBuilder<RMT_BUFFER_SIZ
             led: PhantomData,
             channel: self.channel,
             pin: self.pin,
+            frequency: self.frequency,
+            frame_end: self.frame_end,
+            inter_frame_gap: self.inter_frame_gap,
         }
     }
 }
@@ -102,8 +362,31 @@ impl<const RMT_BUFFER_SIZE: usize, Led, Pin> ClocklessRmtBuilder<RMT_BUFFER_SIZE
             led: self.led,
             channel,
             pin: self.pin,
+            frequency: self.frequency,
+            frame_end: self.frame_end,
+            inter_frame_gap: self.inter_frame_gap,
         }
     }
+
+    /// Allocate the next free transmit channel from the RMT peripheral.
+    ///
+    /// # Returns
+    ///
+    /// The builder with the channel set, or
+    /// [`ClocklessRmtError::ChannelsExhausted`] if no channel is free
+    pub fn with_rmt<'ch, Dm>(
+        self,
+        rmt: &mut RmtChannels<'ch, Dm>,
+    ) -> Result<
+        ClocklessRmtBuilder<RMT_BUFFER_SIZE, Led, RmtTxChannel<'ch, Dm>, Pin>,
+        ClocklessRmtError,
+    >
+    where
+        Dm: DriverMode,
+    {
+        let channel = rmt.next_tx().ok_or(ClocklessRmtError::ChannelsExhausted)?;
+        Ok(self.with_channel(channel))
+    }
 }
 
 impl<const RMT_BUFFER_SIZE: usize, Led, Chan> ClocklessRmtBuilder<RMT_BUFFER_SIZE, Led, Chan, ()> {
@@ -112,23 +395,67 @@ impl<const RMT_BUFFER_SIZE: usize, Led, Chan> ClocklessRmtBuilder<RMT_BUFFER_SIZ
             led: self.led,
             channel: self.channel,
             pin,
+            frequency: self.frequency,
+            frame_end: self.frame_end,
+            inter_frame_gap: self.inter_frame_gap,
         }
     }
 }
 
+impl<const RMT_BUFFER_SIZE: usize, Led, Chan, Pin>
+    ClocklessRmtBuilder<RMT_BUFFER_SIZE, Led, Chan, Pin>
+{
+    /// Set the frequency the RMT peripheral was created with.
+    ///
+    /// Defaults to 80 MHz. Must match the frequency given to [`Rmt::new`], as the LED
+    /// timings are converted into ticks of this frequency.
+    pub fn with_rmt_frequency(mut self, frequency: Rate) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Set how the end of each frame latches the LEDs.
+    ///
+    /// Defaults to [`FrameEnd::ResetPulse`]. See [End of Frame](crate::rmt#end-of-frame).
+    pub fn with_frame_end(mut self, frame_end: FrameEnd) -> Self {
+        self.frame_end = frame_end;
+        self
+    }
+
+    /// Extend the reset time between frames, for LEDs which need a longer latch time than
+    /// their chipset.
+    ///
+    /// Defaults to no extension. See [End of Frame](crate::rmt#end-of-frame).
+    pub fn with_inter_frame_gap(mut self, inter_frame_gap: Duration) -> Self {
+        self.inter_frame_gap = inter_frame_gap;
+        self
+    }
+}
+
 impl<const RMT_BUFFER_SIZE: usize, Led, Chan, Pin>
     ClocklessRmtBuilder<RMT_BUFFER_SIZE, Led, Chan, Pin>
 where
     Led: ClocklessLed,
     Led::Word: Word,
 {
-    pub fn build<'ch, Dm>(self) -> ClocklessRmt<RMT_BUFFER_SIZE, Led, Channel<'ch, Dm, Tx>>
+    /// Build the RMT writer, configuring the transmit channel.
+    ///
+    /// # Returns
+    ///
+    /// The writer, or [`ClocklessRmtError::ConfigurationError`] if the channel can't be
+    /// configured
+    pub fn build<'ch, Dm>(
+        self,
+    ) -> Result<ClocklessRmt<RMT_BUFFER_SIZE, Led, Channel<'ch, Dm, Tx>>, ClocklessRmtError>
     where
         Chan: TxChannelCreator<'ch, Dm>,
         Pin: PeripheralOutput<'ch>,
         Dm: DriverMode,
     {
-        ClocklessRmt::new(self.channel, self.pin)
+        let mut writer = ClocklessRmt::new_with_frequency(self.channel, self.pin, self.frequency)?;
+        writer.set_frame_end(self.frame_end);
+        writer.set_inter_frame_gap(self.inter_frame_gap);
+        Ok(writer)
     }
 }
 
@@ -148,7 +475,11 @@ where
 {
     led: PhantomData<Led>,
     channel: Option<TxChannel>,
-    pulses: (PulseCode, PulseCode, PulseCode),
+    pulses: (PulseCode, PulseCode),
+    ticks_per_second: u64,
+    frame_end: FrameEnd,
+    reset: Duration,
+    last_frame_end: Option<Instant>,
 }
 
 impl<const RMT_BUFFER_SIZE: usize, Led, TxChannel> ClocklessRmt<RMT_BUFFER_SIZE, Led, TxChannel>
@@ -160,35 +491,113 @@ where
         1
     }
 
-    fn setup_pulses() -> (PulseCode, PulseCode, PulseCode) {
-        let clocks = Clocks::get();
-        let freq_hz = clocks.apb_clock.as_hz() / Self::clock_divider() as u32;
-        let freq_mhz = freq_hz / 1_000_000;
+    fn setup_pulses(frequency: Rate) -> (PulseCode, PulseCode) {
+        // The RMT source clock is the frequency given to `Rmt::new`: on the ESP32 and
+        // ESP32-S2 this must be the APB clock, while on other chips the source clock is
+        // divided down to this frequency.
+        let ticks_per_second = Self::ticks_per_second(frequency);
+        // In nanoseconds, so a clock which isn't a whole number of MHz isn't truncated
+        let ticks = |nanos: u32| (nanos as u64 * ticks_per_second / 1_000_000_000) as u16;
 
-        let t_0h = ((Led::T_0H.to_nanos() * freq_mhz) / 1_000) as u16;
-        let t_0l = ((Led::T_0L.to_nanos() * freq_mhz) / 1_000) as u16;
-        let t_1h = ((Led::T_1H.to_nanos() * freq_mhz) / 1_000) as u16;
-        let t_1l = ((Led::T_1L.to_nanos() * freq_mhz) / 1_000) as u16;
-        let t_reset = ((Led::T_RESET.to_nanos() * freq_mhz) / 1_000) as u16;
+        let t_0h = ticks(Led::T_0H.to_nanos());
+        let t_0l = ticks(Led::T_0L.to_nanos());
+        let t_1h = ticks(Led::T_1H.to_nanos());
+        let t_1l = ticks(Led::T_1L.to_nanos());
 
         (
             PulseCode::new(Level::High, t_0h, Level::Low, t_0l),
             PulseCode::new(Level::High, t_1h, Level::Low, t_1l),
-            PulseCode::new(Level::Low, t_reset, Level::Low, 0),
         )
     }
 
+    fn ticks_per_second(frequency: Rate) -> u64 {
+        frequency.as_hz() as u64 / Self::clock_divider() as u64
+    }
+
+    fn reset_time(inter_frame_gap: Duration) -> Duration {
+        Duration::from_micros(Led::T_RESET.to_nanos().div_ceil(1_000) as u64)
+            .saturating_add(inter_frame_gap)
+    }
+
+    /// Set how the end of each frame latches the LEDs.
+    ///
+    /// See [End of Frame](crate::rmt#end-of-frame).
+    pub fn set_frame_end(&mut self, frame_end: FrameEnd) {
+        self.frame_end = frame_end;
+    }
+
+    /// Extend the reset time between frames, for LEDs which need a longer latch time than
+    /// their chipset.
+    ///
+    /// See [End of Frame](crate::rmt#end-of-frame).
+    pub fn set_inter_frame_gap(&mut self, inter_frame_gap: Duration) {
+        self.reset = Self::reset_time(inter_frame_gap);
+    }
+
+    /// Returns the low pulses of the reset time, if sent within the frame.
+    fn reset_pulses(&self) -> impl Iterator<Item = PulseCode> {
+        let mut remaining = match self.frame_end {
+            FrameEnd::Idle => 0,
+            FrameEnd::ResetPulse => {
+                (self.reset.as_micros() * self.ticks_per_second / 1_000_000) as u32
+            }
+        };
+        core::iter::from_fn(move || {
+            if remaining == 0 {
+                return None;
+            }
+            let max = PulseCode::MAX_LEN as u32;
+            let first = remaining.min(max);
+            let second = (remaining - first).min(max);
+            remaining -= first + second;
+            // The last pulse may have an empty second half, which ends the transmission.
+            Some(PulseCode::new_clamped(
+                Level::Low,
+                first as u16,
+                Level::Low,
+                second as u16,
+            ))
+        })
+    }
+
+    /// Returns how much of the reset time is left since the last frame, unless it has passed
+    /// or was sent within the frame.
+    fn remaining_reset(&self) -> Option<Duration> {
+        let elapsed = self.last_frame_end?.elapsed();
+        (elapsed < self.reset).then(|| self.reset - elapsed)
+    }
+
+    /// Holds back the next frame until the reset time has passed since the last frame.
+    fn wait_for_reset(&self) {
+        if let Some(remaining) = self.remaining_reset() {
+            let start = Instant::now();
+            while start.elapsed() < remaining {}
+        }
+    }
+
+    /// Holds back the next frame until the reset time has passed since the last frame, without
+    /// blocking the executor.
+    #[cfg(feature = "embassy-time")]
+    async fn wait_for_reset_async(&self) {
+        if let Some(remaining) = self.remaining_reset() {
+            embassy_time::Timer::after_micros(remaining.as_micros()).await;
+        }
+    }
+
     fn frame_pulses<const FRAME_BUFFER_SIZE: usize>(
         &self,
         frame: Vec<Led::Word, FRAME_BUFFER_SIZE>,
     ) -> impl Iterator<Item = PulseCode> {
         let pulses = self.pulses;
-        frame.into_iter().flat_map(move |word| {
-            word_to_bits_msb(word).map(move |bit| match bit {
-                false => pulses.0,
-                true => pulses.1,
+        frame
+            .into_iter()
+            .flat_map(move |word| {
+                word_to_bits_msb(word).map(move |bit| match bit {
+                    false => pulses.0,
+                    true => pulses.1,
+                })
             })
-        })
+            .chain(self.reset_pulses())
     }
 }
 
@@ -199,17 +608,44 @@ where
     Led::Word: Word,
     Dm: DriverMode,
 {
+    /// Create a new adapter object that drives the pin using the RMT channel.
+    ///
+    /// Assumes the RMT peripheral was created with a frequency of 80 MHz.
+    ///
+    /// # Arguments
+    ///
+    /// - `channel` - RMT transmit channel creator
+    /// - `pin` - GPIO pin connected to the LED data line
+    ///
+    /// # Returns
+    ///
+    /// A configured ClocklessRmt instance, or [`ClocklessRmtError::ConfigurationError`] if
+    /// the channel can't be configured
+    pub fn new<C, O>(channel: C, pin: O) -> Result<Self, ClocklessRmtError>
+    where
+        C: TxChannelCreator<'ch, Dm>,
+        O: PeripheralOutput<'ch>,
+    {
+        Self::new_with_frequency(channel, pin, DEFAULT_RMT_FREQUENCY)
+    }
+
     /// Create a new adapter object that drives the pin using the RMT channel.
     ///
     /// # Arguments
     ///
     /// - `channel` - RMT transmit channel creator
     /// - `pin` - GPIO pin connected to the LED data line
+    /// - `frequency` - The frequency the RMT peripheral was created with
     ///
     /// # Returns
     ///
-    /// A configured ClocklessRmt instance
-    pub fn new<C, O>(channel: C, pin: O) -> Self
+    /// A configured ClocklessRmt instance, or [`ClocklessRmtError::ConfigurationError`] if
+    /// the channel can't be configured
+    pub fn new_with_frequency<C, O>(
+        channel: C,
+        pin: O,
+        frequency: Rate,
+    ) -> Result<Self, ClocklessRmtError>
     where
         C: TxChannelCreator<'ch, Dm>,
         O: PeripheralOutput<'ch>,
@@ -218,14 +654,20 @@ where
             .with_clk_divider(Self::clock_divider())
             .with_idle_output_level(Level::Low)
             .with_idle_output(true);
-        let channel = channel.configure_tx(pin, config).unwrap();
-        let pulses = Self::setup_pulses();
+        let channel = channel
+            .configure_tx(pin, config)
+            .map_err(ClocklessRmtError::ConfigurationError)?;
+        let pulses = Self::setup_pulses(frequency);
 
-        Self {
+        Ok(Self {
             led: PhantomData,
             channel: Some(channel),
             pulses,
-        }
+            ticks_per_second: Self::ticks_per_second(frequency),
+            frame_end: FrameEnd::default(),
+            reset: Self::reset_time(Duration::ZERO),
+            last_frame_end: None,
+        })
     }
 }
 
@@ -244,8 +686,15 @@ where
     ///
     /// Result indicating success or an error
     fn transmit_blocking(&mut self, buffer: &[PulseCode]) -> Result<(), ClocklessRmtError> {
-        let channel = self.channel.take().unwrap();
-        match channel.transmit(buffer).unwrap().wait() {
+        let channel = self
+            .channel
+            .take()
+            .ok_or(ClocklessRmtError::ChannelUnavailable)?;
+        // If the transmission can't start, the channel is lost
+        let transaction = channel
+            .transmit(buffer)
+            .map_err(ClocklessRmtError::TransmissionError)?;
+        match transaction.wait() {
             Ok(chan) => {
                 self.channel = Some(chan);
                 Ok(())
@@ -274,7 +723,10 @@ where
     ///
     /// Result indicating success or an error
     async fn transmit_async(&mut self, buffer: &[PulseCode]) -> Result<(), ClocklessRmtError> {
-        let channel = self.channel.as_mut().unwrap();
+        let channel = self
+            .channel
+            .as_mut()
+            .ok_or(ClocklessRmtError::ChannelUnavailable)?;
         channel
             .transmit(buffer)
             .await
@@ -294,12 +746,19 @@ where
         &mut self,
         frame: Vec<Led::Word, FRAME_BUFFER_SIZE>,
     ) -> Result<(), Self::Error> {
+        self.wait_for_reset();
         let rmt_pulses = self.frame_pulses(frame);
         for mut rmt_buffer in chunked::<_, RMT_BUFFER_SIZE>(rmt_pulses, RMT_BUFFER_SIZE - 1) {
             // RMT buffer must end with 0.
-            rmt_buffer.push(PulseCode::end_marker()).unwrap();
+            rmt_buffer
+                .push(PulseCode::end_marker())
+                .map_err(|_| ClocklessRmtError::BufferSizeExceeded)?;
             self.transmit_blocking(&rmt_buffer)?;
         }
+        self.last_frame_end = match self.frame_end {
+            FrameEnd::Idle => Some(Instant::now()),
+            FrameEnd::ResetPulse => None,
+        };
 
         Ok(())
     }
@@ -318,12 +777,22 @@ where
         &mut self,
         frame: Vec<Led::Word, FRAME_BUFFER_SIZE>,
     ) -> Result<(), Self::Error> {
+        #[cfg(feature = "embassy-time")]
+        self.wait_for_reset_async().await;
+        #[cfg(not(feature = "embassy-time"))]
+        self.wait_for_reset();
         let rmt_pulses = self.frame_pulses(frame);
         for mut rmt_buffer in chunked::<_, RMT_BUFFER_SIZE>(rmt_pulses, RMT_BUFFER_SIZE - 1) {
             // RMT buffer must end with 0.
-            rmt_buffer.push(PulseCode::end_marker()).unwrap();
+            rmt_buffer
+                .push(PulseCode::end_marker())
+                .map_err(|_| ClocklessRmtError::BufferSizeExceeded)?;
             self.transmit_async(&rmt_buffer).await?;
         }
+        self.last_frame_end = match self.frame_end {
+            FrameEnd::Idle => Some(Instant::now()),
+            FrameEnd::ResetPulse => None,
+        };
 
         Ok(())
     }
@@ -0,0 +1,56 @@
+//! # Thermal Utilities
+//!
+//! This module reads the chip's internal temperature sensor, for use with
+//! [`ThermalThrottle`](blinksy::driver::ThermalThrottle) to scale brightness down when the
+//! controller overheats.
+//!
+//! Only available on chips with an internal temperature sensor (ESP32-C3 and ESP32-C6).
+//!
+//! Note: the chip's internal temperature is generally higher than the ambient temperature, so
+//! you may want higher [`ThermalLimits`](blinksy::driver::ThermalLimits) than the defaults.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::driver::{ThermalLimits, ThermalThrottle};
+//! use blinksy_esp::thermal::ChipTemperature;
+//! use esp_hal::tsens::{Config, TemperatureSensor};
+//!
+//! let sensor = TemperatureSensor::new(p.TSENS, Config::default()).unwrap();
+//! let driver = ThermalThrottle::new(
+//!     driver,
+//!     ChipTemperature::new(sensor),
+//!     ThermalLimits::default(),
+//! );
+//! ```
+
+use blinksy::driver::TemperatureSensor;
+use esp_hal::tsens;
+
+/// The chip's internal temperature sensor.
+#[derive(Debug)]
+pub struct ChipTemperature<'d> {
+    sensor: tsens::TemperatureSensor<'d>,
+}
+
+impl<'d> ChipTemperature<'d> {
+    /// Wraps the internal temperature sensor.
+    ///
+    /// # Arguments
+    ///
+    /// - `sensor` - The temperature sensor driver
+    pub fn new(sensor: tsens::TemperatureSensor<'d>) -> Self {
+        Self { sensor }
+    }
+
+    /// Unwraps the temperature sensor driver.
+    pub fn into_inner(self) -> tsens::TemperatureSensor<'d> {
+        self.sensor
+    }
+}
+
+impl TemperatureSensor for ChipTemperature<'_> {
+    fn temperature_in_celsius(&mut self) -> f32 {
+        self.sensor.get_temperature().to_celsius()
+    }
+}
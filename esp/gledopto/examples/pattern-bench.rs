@@ -0,0 +1,169 @@
+//! Measures the frames per second of each built-in pattern, at several pixel counts, and prints
+//! a table over defmt.
+//!
+//! The frames aren't written to any LEDs, so this is the time to render every pixel of a frame
+//! and convert it to linear sRGB, not including output. To find the highest frame rate for your LEDs, take the lower of this
+//! and the output rate of your LED chipset.
+
+#![no_std]
+#![no_main]
+
+use core::{cell::Cell, convert::Infallible, hint::black_box};
+
+use blinksy::{
+    color::{ColorCorrection, FromColor, LinearSrgb, Palette, Srgb},
+    driver::Driver,
+    layout1d,
+    markers::Dim1d,
+    pattern::Pattern,
+    patterns::{
+        noise::{noise_fns, Noise1d, NoiseParams},
+        rainbow::{Rainbow, RainbowParams},
+        white::{White, WhiteParams},
+    },
+    ControlBuilder,
+};
+use defmt::{assert_eq, info};
+use gledopto::{board, bootloader, elapsed, main};
+use heapless::Vec;
+
+bootloader!();
+
+/// How long to run each pattern at each pixel count, in milliseconds.
+const RUN_IN_MS: u64 = 2000;
+
+layout1d!(Layout<const PIXEL_COUNT: usize>, PIXEL_COUNT);
+
+/// A driver which converts every pixel and counts them, without any output.
+///
+/// Unlike [`NullDriver`](blinksy::driver::NullDriver), this checks that each frame has every
+/// pixel.
+struct BenchSink<'a> {
+    pixel_count: &'a Cell<u64>,
+}
+
+impl Driver for BenchSink<'_> {
+    type Error = Infallible;
+    type Color = LinearSrgb;
+    type Word = ();
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        let mut pixel_count = 0;
+        for pixel in pixels {
+            black_box(LinearSrgb::from_color(pixel));
+            pixel_count += 1;
+        }
+        self.pixel_count.set(self.pixel_count.get() + pixel_count);
+        Vec::new()
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        _frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Returns the frames per second of a pattern, at a pixel count.
+fn frames_per_second<P, const PIXEL_COUNT: usize>(params: P::Params) -> u32
+where
+    P: Pattern<Dim1d, Layout<PIXEL_COUNT>>,
+    LinearSrgb: FromColor<P::Color>,
+{
+    let pixel_count = Cell::new(0);
+    let mut control = ControlBuilder::new_1d()
+        .with_layout::<Layout<PIXEL_COUNT>, PIXEL_COUNT>()
+        .with_pattern::<P>(params)
+        .with_driver(BenchSink {
+            pixel_count: &pixel_count,
+        })
+        .with_frame_buffer_size::<0>()
+        .build();
+
+    let start_in_ms = elapsed().as_millis();
+    let mut frame_count: u64 = 0;
+    loop {
+        let elapsed_in_ms = elapsed().as_millis();
+        let run_in_ms = elapsed_in_ms - start_in_ms;
+        if run_in_ms >= RUN_IN_MS {
+            assert_eq!(pixel_count.get(), frame_count * PIXEL_COUNT as u64);
+            return (frame_count * 1000 / run_in_ms) as u32;
+        }
+        control.tick(elapsed_in_ms).unwrap();
+        frame_count += 1;
+    }
+}
+
+/// Prints a row of the table, for a pattern at each pixel count.
+macro_rules! bench {
+    ($name:expr, $pattern:ty, $params:expr) => {
+        info!(
+            "| {=str} | {=u32} | {=u32} | {=u32} | {=u32} |",
+            $name,
+            frames_per_second::<$pattern, 50>($params),
+            frames_per_second::<$pattern, 150>($params),
+            frames_per_second::<$pattern, 300>($params),
+            frames_per_second::<$pattern, 600>($params),
+        )
+    };
+}
+
+#[main]
+fn main() -> ! {
+    let _p = board!();
+
+    info!("Frames per second, by pixel count:");
+    info!("| Pattern | 50 | 150 | 300 | 600 |");
+    info!("|---------|----|-----|-----|-----|");
+    bench!("Rainbow", Rainbow, RainbowParams::default());
+    bench!("White", White, WhiteParams::default());
+    bench!(
+        "Noise (Perlin)",
+        Noise1d<noise_fns::Perlin>,
+        NoiseParams::default()
+    );
+    bench!(
+        "Noise (Simplex)",
+        Noise1d<noise_fns::Simplex>,
+        NoiseParams::default()
+    );
+    bench!(
+        "Noise (OpenSimplex2)",
+        Noise1d<noise_fns::OpenSimplex2>,
+        NoiseParams::default()
+    );
+    bench!(
+        "Noise (Perlin, 4 octaves)",
+        Noise1d<noise_fns::Perlin, 4>,
+        NoiseParams::default()
+    );
+    bench!(
+        "Noise (Perlin, palette)",
+        Noise1d<noise_fns::Perlin>,
+        NoiseParams {
+            palette: Some(Palette::new([
+                Srgb::new(0.5, 0., 0.),
+                Srgb::new(1., 0.3, 0.),
+                Srgb::new(1., 0.8, 0.2),
+            ])),
+            ..Default::default()
+        }
+    );
+    info!("Done");
+
+    loop {
+        core::hint::spin_loop();
+    }
+}
@@ -0,0 +1,76 @@
+#![no_std]
+#![no_main]
+#![feature(impl_trait_in_assoc_type)]
+
+use blinksy::{
+    control::Control,
+    layout::Layout1d,
+    layout1d,
+    leds::Ws2812,
+    markers::{Async, Dim1d},
+    patterns::rainbow::{Rainbow, RainbowParams},
+    pipeline::{FrameChannel, FrameSender, FrameTransmitter},
+    ControlBuilder,
+};
+use embassy_executor::Spawner;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use gledopto::{board, bootloader, elapsed, init_embassy, main_embassy, ws2812_async};
+
+bootloader!();
+
+layout1d!(Layout, 60 * 5);
+
+const PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+/// How many frames can wait for the transmit task.
+const FRAME_DEPTH: usize = 2;
+
+static FRAMES: FrameChannel<CriticalSectionRawMutex, PIXEL_COUNT, FRAME_DEPTH> =
+    FrameChannel::new();
+
+type RenderControl = Control<
+    PIXEL_COUNT,
+    PIXEL_COUNT,
+    Dim1d,
+    Async,
+    Layout,
+    Rainbow,
+    FrameSender<'static, CriticalSectionRawMutex, PIXEL_COUNT, FRAME_DEPTH>,
+>;
+
+/// Renders each frame into the channel, while the last frame is transmitted.
+#[embassy_executor::task]
+async fn render_task(mut control: RenderControl) {
+    loop {
+        let elapsed_in_ms = elapsed().as_millis();
+        control.tick(elapsed_in_ms).await.unwrap();
+    }
+}
+
+#[main_embassy]
+async fn main(spawner: Spawner) {
+    let p = board!();
+
+    init_embassy!(p);
+
+    let mut control = ControlBuilder::new_1d_async()
+        .with_layout::<Layout, PIXEL_COUNT>()
+        .with_pattern::<Rainbow>(RainbowParams::default())
+        .with_driver(FrameSender::new(FRAMES.sender()))
+        .with_frame_buffer_size::<PIXEL_COUNT>()
+        .build();
+
+    control.set_brightness(0.2);
+
+    spawner.spawn(render_task(control)).unwrap();
+
+    // This task transmits each frame to the LEDs
+    let mut transmitter = FrameTransmitter::<
+        _,
+        _,
+        PIXEL_COUNT,
+        { Ws2812::frame_buffer_size(PIXEL_COUNT) },
+        FRAME_DEPTH,
+    >::new(FRAMES.receiver(), ws2812_async!(p, PIXEL_COUNT));
+    transmitter.run().await.unwrap();
+}
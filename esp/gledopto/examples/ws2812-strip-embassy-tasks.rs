@@ -0,0 +1,27 @@
+#![no_std]
+#![no_main]
+#![feature(impl_trait_in_assoc_type)]
+
+use blinksy::{builtin_patterns, input::ControlEvent, layout::Layout1d, layout1d, leds::Ws2812};
+use embassy_time::Timer;
+use gledopto::{embassy::ControlEvents, led_app_embassy, ws2812_async};
+
+layout1d!(Layout, 60 * 5);
+
+builtin_patterns!(enum Patterns: Dim1d);
+
+/// A stand-in for a network task: dims the LEDs after a minute, as if asked to remotely.
+#[embassy_executor::task]
+async fn network_task(events: &'static ControlEvents) {
+    Timer::after_secs(60).await;
+    events.send(ControlEvent::BrightnessChanged(0.1)).await;
+}
+
+led_app_embassy! {
+    dim: Dim1d,
+    layout: Layout,
+    patterns: Patterns,
+    driver: |p| ws2812_async!(p, Layout::PIXEL_COUNT),
+    frame_buffer_size: Ws2812::frame_buffer_size(Layout::PIXEL_COUNT),
+    spawn: |spawner, events| spawner.spawn(network_task(events)).unwrap(),
+}
@@ -51,6 +51,7 @@ fn main() -> ! {
         .with_pattern::<Noise3d<noise_fns::Perlin>>(NoiseParams {
             time_scalar: 0.25 / 1e3,
             position_scalar: 0.25,
+            ..Default::default()
         })
         .with_driver(ws2812!(p, VolumeCubeLayout::PIXEL_COUNT))
         .with_frame_buffer_size::<{ Ws2812::frame_buffer_size(VolumeCubeLayout::PIXEL_COUNT) }>()
@@ -0,0 +1,310 @@
+//! # App Module
+//!
+//! This module turns a set of patterns into a complete, WLED-like firmware with
+//! [`led_app!`](crate::led_app): everything between a quickstart and a usable device.
+//!
+//! The app:
+//!
+//! - Shows one pattern at a time, from a [`pattern_set!`](crate::blinksy::pattern_set)
+//! - Handles the "Function" button with [`DeviceControls`]:
+//!   - Click: next pattern
+//!   - Double click: cycle through [`BRIGHTNESS_STEPS`]
+//!   - Triple click: previous pattern
+//! - Saves the selected pattern and brightness to flash, with [`SettingsStore`]
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! #![no_std]
+//! #![no_main]
+//!
+//! use blinksy::{builtin_patterns, layout::Layout1d, layout1d, leds::Ws2812};
+//! use gledopto::{led_app, ws2812};
+//!
+//! layout1d!(Layout, 60 * 5);
+//!
+//! builtin_patterns!(enum Patterns: Dim1d);
+//!
+//! led_app! {
+//!     dim: Dim1d,
+//!     layout: Layout,
+//!     patterns: Patterns,
+//!     driver: |p| ws2812!(p, Layout::PIXEL_COUNT),
+//!     frame_buffer_size: Ws2812::frame_buffer_size(Layout::PIXEL_COUNT),
+//! }
+//! ```
+
+use blinksy::input::{ButtonEvent, ControlEvent, DeviceControls};
+
+use crate::{
+    button::FunctionButton,
+    settings::{Settings, SettingsStore},
+};
+
+pub use blinksy::input::BRIGHTNESS_STEPS;
+
+/// The state of an LED app: the device controls, driven by the "Function" button.
+pub struct App<'a> {
+    button: FunctionButton<'a>,
+    store: Option<SettingsStore>,
+    controls: DeviceControls,
+}
+
+impl<'a> App<'a> {
+    /// Creates the app state, loading any saved settings.
+    ///
+    /// If the settings can't be stored, the app still works, but forgets its settings on
+    /// power off.
+    ///
+    /// # Arguments
+    ///
+    /// - `button` - The "Function" button
+    /// - `pattern_count` - The number of patterns to cycle through
+    pub fn new(button: FunctionButton<'a>, pattern_count: usize) -> Self {
+        let mut store = SettingsStore::new().ok();
+        let mut controls = DeviceControls::new(pattern_count);
+
+        if let Some(settings) = store.as_mut().and_then(|store| store.load()) {
+            controls.set_pattern_index(settings.pattern_index as usize);
+            controls.set_brightness_index(settings.brightness_index as usize);
+        }
+
+        Self {
+            button,
+            store,
+            controls,
+        }
+    }
+
+    /// Returns the index of the selected pattern.
+    pub fn pattern_index(&self) -> usize {
+        self.controls.pattern_index()
+    }
+
+    /// Returns the selected brightness level.
+    pub fn brightness(&self) -> f32 {
+        self.controls.brightness()
+    }
+
+    /// Checks the button for a requested change.
+    ///
+    /// Call this every loop. Any change is saved straight away.
+    ///
+    /// # Returns
+    ///
+    /// The requested change, if any
+    pub fn tick(&mut self) -> Option<ControlEvent> {
+        self.button.tick();
+
+        let button_event = if self.button.is_clicked() {
+            Some(ButtonEvent::Click)
+        } else if self.button.is_double_clicked() {
+            Some(ButtonEvent::DoubleClick)
+        } else if self.button.is_triple_clicked() {
+            Some(ButtonEvent::TripleClick)
+        } else {
+            None
+        };
+
+        self.button.reset();
+
+        let event = button_event.and_then(|event| self.controls.handle(event));
+
+        if event.is_some() {
+            if let Some(store) = self.store.as_mut() {
+                let settings = Settings {
+                    pattern_index: self.controls.pattern_index() as u8,
+                    brightness_index: self.controls.brightness_index() as u8,
+                };
+                let _ = store.save(&settings);
+            }
+        }
+
+        event
+    }
+}
+
+/// Expands to a complete firmware: a main loop showing a set of patterns, cycled with the
+/// "Function" button, with brightness steps and persisted settings.
+///
+/// # Arguments
+///
+/// - `dim` - The dimension: `Dim1d`, `Dim2d`, or `Dim3d`
+/// - `layout` - The layout type
+/// - `patterns` - The patterns, as an enum from [`pattern_set!`](crate::blinksy::pattern_set)
+///   or [`builtin_patterns!`](crate::blinksy::builtin_patterns)
+/// - `driver` - A closure-like `|p| ...`, creating the LED driver from the peripherals `p`
+/// - `frame_buffer_size` - The frame buffer size for the driver and LEDs
+///
+/// See [the module docs](crate::app) for an example.
+#[macro_export]
+macro_rules! led_app {
+    (dim: Dim1d, $($rest:tt)*) => {
+        $crate::led_app!(@app new_1d, Layout1d, $($rest)*);
+    };
+    (dim: Dim2d, $($rest:tt)*) => {
+        $crate::led_app!(@app new_2d, Layout2d, $($rest)*);
+    };
+    (dim: Dim3d, $($rest:tt)*) => {
+        $crate::led_app!(@app new_3d, Layout3d, $($rest)*);
+    };
+    (
+        @app $new:ident, $layout_trait:ident,
+        layout: $layout:ty,
+        patterns: $patterns:ty,
+        driver: |$peripherals:ident| $driver:expr,
+        frame_buffer_size: $frame_buffer_size:expr $(,)?
+    ) => {
+        $crate::bootloader!();
+
+        #[$crate::main]
+        fn main() -> ! {
+            let $peripherals = $crate::board!();
+
+            let registry = <$patterns>::registry::<$layout>();
+            let mut app =
+                $crate::app::App::new($crate::function_button!($peripherals), registry.len());
+
+            let pattern = registry
+                .create_index(app.pattern_index())
+                .expect("at least one pattern");
+            let mut control = $crate::blinksy::ControlBuilder::$new()
+                .with_layout::<$layout, { <$layout as $crate::blinksy::layout::$layout_trait>::PIXEL_COUNT }>()
+                .with_pattern::<$patterns>(pattern)
+                .with_driver($driver)
+                .with_frame_buffer_size::<{ $frame_buffer_size }>()
+                .build();
+
+            control.set_brightness(app.brightness());
+
+            loop {
+                match app.tick() {
+                    Some($crate::blinksy::input::ControlEvent::PatternChanged(index)) => {
+                        if let Some(pattern) = registry.create_index(index) {
+                            control.set_pattern(pattern);
+                        }
+                    }
+                    Some($crate::blinksy::input::ControlEvent::BrightnessChanged(brightness)) => {
+                        control.set_brightness(brightness);
+                    }
+                    Some($crate::blinksy::input::ControlEvent::PowerChanged(is_on)) => {
+                        control.set_on(is_on);
+                    }
+                    None => {}
+                }
+
+                let elapsed_in_ms = $crate::elapsed().as_millis();
+                control.tick(elapsed_in_ms).unwrap();
+            }
+        }
+    };
+}
+
+/// Expands to a complete async firmware on Embassy: the same app as [`led_app!`], as tasks.
+///
+/// This is the template of a [`main_embassy`](crate::main_embassy) firmware:
+///
+/// - The main task renders: applies [`ControlEvent`]s, then ticks the async control
+/// - The [`app_task`](crate::embassy::app_task) handles the "Function" button
+///
+/// Any other tasks (e.g. network) are spawned with `spawn: |spawner, events| ...`, sending their
+/// own [`ControlEvent`]s into `events`.
+///
+/// # Arguments
+///
+/// - `dim` - The dimension: `Dim1d`, `Dim2d`, or `Dim3d`
+/// - `layout` - The layout type
+/// - `patterns` - The patterns, as an enum from [`pattern_set!`](crate::blinksy::pattern_set)
+///   or [`builtin_patterns!`](crate::blinksy::builtin_patterns)
+/// - `driver` - A closure-like `|p| ...`, creating the async LED driver from the peripherals `p`
+/// - `frame_buffer_size` - The frame buffer size for the driver and LEDs
+/// - `spawn` (optional) - A closure-like `|spawner, events| ...`, spawning any other tasks
+///
+/// ```rust,ignore
+/// led_app_embassy! {
+///     dim: Dim1d,
+///     layout: Layout,
+///     patterns: Patterns,
+///     driver: |p| ws2812_async!(p, Layout::PIXEL_COUNT),
+///     frame_buffer_size: Ws2812::frame_buffer_size(Layout::PIXEL_COUNT),
+///     spawn: |spawner, events| spawner.spawn(network_task(events)).unwrap(),
+/// }
+/// ```
+#[cfg(feature = "embassy")]
+#[macro_export]
+macro_rules! led_app_embassy {
+    (dim: Dim1d, $($rest:tt)*) => {
+        $crate::led_app_embassy!(@app new_1d_async, Layout1d, $($rest)*);
+    };
+    (dim: Dim2d, $($rest:tt)*) => {
+        $crate::led_app_embassy!(@app new_2d_async, Layout2d, $($rest)*);
+    };
+    (dim: Dim3d, $($rest:tt)*) => {
+        $crate::led_app_embassy!(@app new_3d_async, Layout3d, $($rest)*);
+    };
+    (
+        @app $new:ident, $layout_trait:ident,
+        layout: $layout:ty,
+        patterns: $patterns:ty,
+        driver: |$peripherals:ident| $driver:expr,
+        frame_buffer_size: $frame_buffer_size:expr
+        $(, spawn: |$spawner:ident, $events:ident| $spawn:expr)? $(,)?
+    ) => {
+        $crate::bootloader!();
+
+        static EVENTS: $crate::embassy::ControlEvents = $crate::embassy::ControlEvents::new();
+
+        #[$crate::main_embassy]
+        async fn main(spawner: $crate::embassy::Spawner) {
+            use $crate::blinksy::time::TimeSource;
+
+            let $peripherals = $crate::board!();
+            $crate::init_embassy!($peripherals);
+
+            let registry = <$patterns>::registry::<$layout>();
+            let app =
+                $crate::app::App::new($crate::function_button!($peripherals), registry.len());
+
+            let pattern = registry
+                .create_index(app.pattern_index())
+                .expect("at least one pattern");
+            let mut control = $crate::blinksy::ControlBuilder::$new()
+                .with_layout::<$layout, { <$layout as $crate::blinksy::layout::$layout_trait>::PIXEL_COUNT }>()
+                .with_pattern::<$patterns>(pattern)
+                .with_driver($driver)
+                .with_frame_buffer_size::<{ $frame_buffer_size }>()
+                .build();
+
+            control.set_brightness(app.brightness());
+
+            spawner
+                .spawn($crate::embassy::app_task(app, &EVENTS))
+                .expect("app task to spawn");
+            $({
+                let $spawner = &spawner;
+                let $events: &'static $crate::embassy::ControlEvents = &EVENTS;
+                $spawn;
+            })?
+
+            loop {
+                match EVENTS.try_receive() {
+                    Ok($crate::blinksy::input::ControlEvent::PatternChanged(index)) => {
+                        if let Some(pattern) = registry.create_index(index) {
+                            control.set_pattern(pattern);
+                        }
+                    }
+                    Ok($crate::blinksy::input::ControlEvent::BrightnessChanged(brightness)) => {
+                        control.set_brightness(brightness);
+                    }
+                    Ok($crate::blinksy::input::ControlEvent::PowerChanged(is_on)) => {
+                        control.set_on(is_on);
+                    }
+                    Err(_) => {}
+                }
+
+                let elapsed_in_ms = $crate::embassy::EmbassyTime.now_in_ms();
+                control.tick(elapsed_in_ms).await.unwrap();
+            }
+        }
+    };
+}
@@ -0,0 +1,108 @@
+//! # Embassy Module
+//!
+//! Building blocks for async firmware on [Embassy](https://embassy.dev), with the `embassy`
+//! feature:
+//!
+//! - [`EmbassyTime`]: A [`TimeSource`] backed by `embassy-time`
+//! - [`ControlEvents`]: A channel of [`ControlEvent`]s, from input tasks to the render task
+//! - [`app_task`]: A task handling the "Function" button, as in [`led_app!`](crate::led_app)
+//!
+//! A firmware is split into tasks, which all wait without blocking each other:
+//!
+//! - The render task ticks the control, applying any [`ControlEvent`]s (usually the main task)
+//! - The button task sends [`ControlEvent`]s from the "Function" button, see [`app_task`]
+//! - A network task (WiFi, Ethernet, ...) can send [`ControlEvent`]s too
+//!
+//! For a complete firmware from this structure, see [`led_app_embassy!`](crate::led_app_embassy).
+//!
+//! To render the next frame while the last frame is transmitted, split the render task in two
+//! with [`blinksy::pipeline`]: see the `ws2812-strip-embassy-pipeline` example.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! #![no_std]
+//! #![no_main]
+//!
+//! use blinksy::{input::ControlEvent, time::TimeSource};
+//! use embassy_executor::Spawner;
+//! use gledopto::{
+//!     app::App,
+//!     board, bootloader, function_button, init_embassy, main_embassy,
+//!     embassy::{app_task, ControlEvents, EmbassyTime},
+//! };
+//!
+//! bootloader!();
+//!
+//! static EVENTS: ControlEvents = ControlEvents::new();
+//!
+//! #[main_embassy]
+//! async fn main(spawner: Spawner) {
+//!     let p = board!();
+//!     init_embassy!(p);
+//!
+//!     let app = App::new(function_button!(p), /* pattern count */);
+//!     spawner.spawn(app_task(app, &EVENTS)).unwrap();
+//!
+//!     let mut control = /* build your async control */;
+//!
+//!     loop {
+//!         if let Ok(ControlEvent::BrightnessChanged(brightness)) = EVENTS.try_receive() {
+//!             control.set_brightness(brightness);
+//!         }
+//!         control.tick(EmbassyTime.now_in_ms()).await.unwrap();
+//!     }
+//! }
+//! ```
+
+use blinksy::{input::ControlEvent, time::TimeSource};
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, channel::Channel};
+use embassy_time::{Instant, Timer};
+
+use crate::app::App;
+
+/// Re-export of the Embassy task spawner
+pub use embassy_executor::Spawner;
+
+/// How many [`ControlEvent`]s can wait in [`ControlEvents`].
+pub const CONTROL_EVENTS_CAPACITY: usize = 4;
+
+/// How often [`app_task`] checks the "Function" button, in milliseconds.
+pub const BUTTON_POLL_INTERVAL_IN_MS: u64 = 5;
+
+/// A channel of [`ControlEvent`]s, from input tasks to the render task.
+///
+/// Make it a `static`, to share it between tasks.
+pub type ControlEvents = Channel<CriticalSectionRawMutex, ControlEvent, CONTROL_EVENTS_CAPACITY>;
+
+/// A [`TimeSource`] backed by `embassy-time`.
+///
+/// Embassy must be started first, with [`init_embassy!`](crate::init_embassy).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmbassyTime;
+
+impl TimeSource for EmbassyTime {
+    fn now_in_ms(&self) -> u64 {
+        Instant::now().as_millis()
+    }
+}
+
+/// A task handling the "Function" button: sends each requested change of pattern or
+/// brightness to the render task.
+///
+/// The button is checked every [`BUTTON_POLL_INTERVAL_IN_MS`], and settings are saved as in
+/// [`App::tick`].
+///
+/// # Arguments
+///
+/// - `app` - The app state, with the "Function" button
+/// - `events` - Where to send the requested changes
+#[embassy_executor::task]
+pub async fn app_task(mut app: App<'static>, events: &'static ControlEvents) {
+    loop {
+        if let Some(event) = app.tick() {
+            events.send(event).await;
+        }
+        Timer::after_millis(BUTTON_POLL_INTERVAL_IN_MS).await;
+    }
+}
@@ -17,6 +17,11 @@
 //! - [x] Built-in "Function" button
 //! - [ ] Alternative "IO33" button
 //! - [ ] Built-in microphone
+//! - [ ] Ethernet (on Ethernet variants)
+//! - [x] Blank the LEDs on panic (feature `panic-blank`)
+//! - [x] Settings persisted to flash
+//! - [x] Complete LED app, with [`led_app!`]
+//! - [x] Async tasks on Embassy, with [`led_app_embassy!`] (feature `embassy`)
 //!
 //! ## Getting started
 //!
@@ -117,6 +122,20 @@
 
 #![no_std]
 
+#[cfg(not(any(feature = "gl_c_016wl_d", feature = "gl_c_017wl_d", feature = "mock")))]
+compile_error!("No board selected: enable one board feature, `gl_c_016wl_d` or `gl_c_017wl_d`");
+
+#[cfg(any(
+    all(feature = "gl_c_016wl_d", feature = "gl_c_017wl_d"),
+    all(
+        feature = "mock",
+        any(feature = "gl_c_016wl_d", feature = "gl_c_017wl_d")
+    ),
+))]
+compile_error!(
+    "Conflicting boards selected: enable only one of `gl_c_016wl_d`, `gl_c_017wl_d`, or `mock`"
+);
+
 /// Re-export of the core Blinksy library
 pub use blinksy;
 
@@ -149,9 +168,26 @@ pub use esp_backtrace as backtrace;
 #[cfg(any(feature = "println", feature = "defmt"))]
 pub use esp_println as println;
 
+/// Complete LED app, cycling patterns with the button
+pub mod app;
+
 /// Button handling functionality
 pub mod button;
 
+/// Embassy tasks and time source
+#[cfg(feature = "embassy")]
+pub mod embassy;
+
+/// Settings persisted to flash
+pub mod settings;
+
+/// Called by the `esp-backtrace` panic handler, before printing the backtrace.
+#[cfg(feature = "panic-blank")]
+#[no_mangle]
+fn custom_pre_backtrace() {
+    blinksy_esp::panic::blank();
+}
+
 /// Initializes the heap allocator with a 72KB heap.
 ///
 /// This is required for ESP32 targets that need dynamic memory allocation.
@@ -185,16 +221,193 @@ macro_rules! board {
     }};
 }
 
+/// Maps a signal on the selected board to its GPIO pin.
+///
+/// - `$peripherals, $signal` - The pin, moved out of the peripherals
+/// - `steal $signal` - The pin, stolen (unsafe)
+///
+/// Signals are `led_data` (clockless data), `spi_clock`, `spi_data`, and `button`.
+///
+/// Each board has its own table, so a board wired differently must add its own table here
+/// rather than silently getting another board's pins.
+// Gledopto GL-C-016WL-D
+#[cfg(feature = "gl_c_016wl_d")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! board_pin {
+    ($peripherals:ident, led_data) => {
+        $peripherals.GPIO16
+    };
+    ($peripherals:ident, spi_clock) => {
+        $peripherals.GPIO16
+    };
+    ($peripherals:ident, spi_data) => {
+        $peripherals.GPIO2
+    };
+    ($peripherals:ident, button) => {
+        $peripherals.GPIO0
+    };
+    (steal led_data) => {
+        $crate::hal::peripherals::GPIO16::steal()
+    };
+    (steal spi_clock) => {
+        $crate::hal::peripherals::GPIO16::steal()
+    };
+    (steal spi_data) => {
+        $crate::hal::peripherals::GPIO2::steal()
+    };
+}
+
+// Gledopto GL-C-017WL-D
+#[cfg(feature = "gl_c_017wl_d")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! board_pin {
+    ($peripherals:ident, led_data) => {
+        $peripherals.GPIO16
+    };
+    ($peripherals:ident, spi_clock) => {
+        $peripherals.GPIO16
+    };
+    ($peripherals:ident, spi_data) => {
+        $peripherals.GPIO2
+    };
+    ($peripherals:ident, button) => {
+        $peripherals.GPIO0
+    };
+    (steal led_data) => {
+        $crate::hal::peripherals::GPIO16::steal()
+    };
+    (steal spi_clock) => {
+        $crate::hal::peripherals::GPIO16::steal()
+    };
+    (steal spi_data) => {
+        $crate::hal::peripherals::GPIO2::steal()
+    };
+}
+
+// Mock, with the pins of the GL-C-016WL-D
+#[cfg(feature = "mock")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! board_pin {
+    ($peripherals:ident, led_data) => {
+        $peripherals.GPIO16
+    };
+    ($peripherals:ident, spi_clock) => {
+        $peripherals.GPIO16
+    };
+    ($peripherals:ident, spi_data) => {
+        $peripherals.GPIO2
+    };
+    ($peripherals:ident, button) => {
+        $peripherals.GPIO0
+    };
+    (steal led_data) => {
+        $crate::hal::peripherals::GPIO16::steal()
+    };
+    (steal spi_clock) => {
+        $crate::hal::peripherals::GPIO16::steal()
+    };
+    (steal spi_data) => {
+        $crate::hal::peripherals::GPIO2::steal()
+    };
+}
+
 /// Creates a function button instance connected to GPIO0.
 ///
 /// The function button can be used for mode selection, brightness control, etc.
 #[macro_export]
 macro_rules! function_button {
     ($peripherals:ident) => {
-        $crate::button::FunctionButton::new($peripherals.GPIO0)
+        $crate::button::FunctionButton::new($crate::board_pin!($peripherals, button))
     };
 }
 
+/// Registers a hook to blank the LEDs on panic.
+///
+/// The hook writes a zero frame with a new driver on the stolen peripherals, so it works even
+/// if the LED driver itself panicked: the RMT for clockless LEDs, or bit-banged pins for
+/// clocked LEDs. Enable the `panic-blank` feature to call it from the panic handler.
+///
+/// A watchdog reset doesn't run the hook, so this also blanks the LEDs straight away, to
+/// clear any frame latched before a reset.
+///
+/// # Arguments
+///
+/// - `clockless: $led` or `clocked: $led` - The type of LED, or `ws2812` or `apa102`
+/// - `$pixel_count` - The number of LEDs
+///
+/// ```rust,ignore
+/// let p = board!();
+/// blank_on_panic!(ws2812: Layout::PIXEL_COUNT);
+/// ```
+#[macro_export]
+macro_rules! blank_on_panic {
+    (ws2812: $pixel_count:expr) => {{
+        $crate::blank_on_panic!(clockless: $crate::blinksy::leds::Ws2812, $pixel_count)
+    }};
+    (apa102: $pixel_count:expr) => {{
+        $crate::blank_on_panic!(clocked: $crate::blinksy::leds::Apa102, $pixel_count)
+    }};
+    (clockless: $led:ty, $pixel_count:expr) => {{
+        fn blank_leds() {
+            // SAFETY: Only used while panicking or before the LED driver is created.
+            let rmt = unsafe { $crate::hal::peripherals::RMT::steal() };
+            let data_pin = unsafe { $crate::board_pin!(steal led_data) };
+            // A bit-banged frame may stretch "0" bits into "1" bits, so send it with the RMT
+            let Ok(rmt) =
+                $crate::hal::rmt::Rmt::new(rmt, $crate::blinksy_esp::rmt::DEFAULT_RMT_FREQUENCY)
+            else {
+                return;
+            };
+            let mut rmt = $crate::blinksy_esp::RmtChannels::new(rmt);
+            let Ok(writer) = $crate::blinksy_esp::ClocklessRmtBuilder::default()
+                .with_led::<$led>()
+                .with_rmt(&mut rmt)
+                .and_then(|builder| builder.with_pin(data_pin).build())
+            else {
+                return;
+            };
+            let driver = $crate::blinksy::driver::ClocklessDriver::default()
+                .with_led::<$led>()
+                .with_writer(writer);
+            $crate::blank_on_panic!(@show driver, $led, $pixel_count);
+        }
+        $crate::blinksy_esp::panic::set_blank_hook(blank_leds);
+        $crate::blinksy_esp::panic::blank();
+    }};
+    (clocked: $led:ty, $pixel_count:expr) => {{
+        fn blank_leds() {
+            use $crate::hal::gpio::{Level, Output, OutputConfig};
+
+            // SAFETY: Only used while panicking or before the LED driver is created.
+            let clock_pin = unsafe { $crate::board_pin!(steal spi_clock) };
+            let data_pin = unsafe { $crate::board_pin!(steal spi_data) };
+            let clock = Output::new(clock_pin, Level::Low, OutputConfig::default());
+            let data = Output::new(data_pin, Level::Low, OutputConfig::default());
+            let writer = $crate::blinksy::driver::ClockedDelayBuilder::default()
+                .with_data(data)
+                .with_clock(clock)
+                .with_delay($crate::hal::delay::Delay::new())
+                .with_data_rate($crate::blinksy::time::Megahertz::MHz(1))
+                .build();
+            let driver = $crate::blinksy::driver::ClockedDriver::default()
+                .with_led::<$led>()
+                .with_writer(writer);
+            $crate::blank_on_panic!(@show driver, $led, $pixel_count);
+        }
+        $crate::blinksy_esp::panic::set_blank_hook(blank_leds);
+        $crate::blinksy_esp::panic::blank();
+    }};
+    (@show $driver:ident, $led:ty, $pixel_count:expr) => {{
+        use $crate::blinksy::driver::Driver;
+
+        let mut driver = $driver;
+        let _ = driver.blank::<{ $pixel_count }, { <$led>::frame_buffer_size($pixel_count) }>();
+    }};
+}
+
 #[cfg(feature = "embassy")]
 #[macro_export]
 macro_rules! init_embassy {
@@ -204,12 +417,53 @@ macro_rules! init_embassy {
     }};
 }
 
+/// Creates an SPI interface for clocked LEDs.
+///
+/// # Arguments
+///
+/// - `$peripherals` - The ESP32 peripherals instance
+/// - `clock: $clock, data: $data` (Optional) - The clock and data pins, instead of the
+///   board's GPIO16 and GPIO2
+/// - `frequency: $frequency` (Optional) - The SPI clock
+///   [`Rate`](crate::hal::time::Rate), instead of 4 MHz. Long cables may need a slower clock.
+///
+/// ```rust,ignore
+/// let spi = spi!(p, clock: p.GPIO5, data: p.GPIO4, frequency: Rate::from_khz(500));
+/// ```
+///
+/// # Returns
+///
+/// The SPI interface
 #[macro_export]
 macro_rules! spi {
     ($peripherals:ident) => {{
-        let clock_pin = $peripherals.GPIO16;
-        let data_pin = $peripherals.GPIO2;
-        let data_rate = $crate::hal::time::Rate::from_mhz(4);
+        $crate::spi!(
+            $peripherals,
+            clock: $crate::board_pin!($peripherals, spi_clock),
+            data: $crate::board_pin!($peripherals, spi_data),
+            frequency: $crate::hal::time::Rate::from_mhz(4)
+        )
+    }};
+    ($peripherals:ident, frequency: $frequency:expr) => {{
+        $crate::spi!(
+            $peripherals,
+            clock: $crate::board_pin!($peripherals, spi_clock),
+            data: $crate::board_pin!($peripherals, spi_data),
+            frequency: $frequency
+        )
+    }};
+    ($peripherals:ident, clock: $clock:expr, data: $data:expr) => {{
+        $crate::spi!(
+            $peripherals,
+            clock: $clock,
+            data: $data,
+            frequency: $crate::hal::time::Rate::from_mhz(4)
+        )
+    }};
+    ($peripherals:ident, clock: $clock:expr, data: $data:expr, frequency: $frequency:expr) => {{
+        let clock_pin = $clock;
+        let data_pin = $data;
+        let data_rate = $frequency;
 
         $crate::hal::spi::master::Spi::new(
             $peripherals.SPI2,
@@ -229,14 +483,15 @@ macro_rules! spi {
 ///
 /// - `$peripherals` - The ESP32 peripherals instance
 /// - `$led` - The type of LED
+/// - (Optional) The pins and frequency, as with [`spi!`]
 ///
 /// # Returns
 ///
 /// A clocked driver configured for the Gledopto board
 #[macro_export]
 macro_rules! clocked {
-    ($peripherals:ident, $led:ty) => {{
-        let spi = $crate::spi!($peripherals);
+    ($peripherals:ident, $led:ty $(, $($spi_args:tt)+)?) => {{
+        let spi = $crate::spi!($peripherals $(, $($spi_args)+)?);
         $crate::blinksy::driver::ClockedDriver::default()
             .with_led::<$led>()
             .with_writer(spi)
@@ -248,14 +503,16 @@ macro_rules! clocked {
 /// # Arguments
 ///
 /// - `$peripherals` - The ESP32 peripherals instance
+/// - (Optional) The pins and frequency, as with [`spi!`], e.g.
+///   `apa102!(p, frequency: Rate::from_mhz(1))`
 ///
 /// # Returns
 ///
 /// An APA102 driver configured for the Gledopto board
 #[macro_export]
 macro_rules! apa102 {
-    ($peripherals:ident) => {{
-        $crate::clocked!($peripherals, $crate::blinksy::leds::Apa102)
+    ($peripherals:ident $(, $($spi_args:tt)+)?) => {{
+        $crate::clocked!($peripherals, $crate::blinksy::leds::Apa102 $(, $($spi_args)+)?)
     }};
 }
 
@@ -265,6 +522,7 @@ macro_rules! apa102 {
 ///
 /// - `$peripherals` - The ESP32 peripherals instance
 /// - `$led` - The type of LED
+/// - (Optional) The pins and frequency, as with [`spi!`]
 ///
 /// # Returns
 ///
@@ -272,8 +530,8 @@ macro_rules! apa102 {
 #[cfg(feature = "async")]
 #[macro_export]
 macro_rules! clocked_async {
-    ($peripherals:ident, $led:ty) => {{
-        let spi = $crate::spi!($peripherals).into_async();
+    ($peripherals:ident, $led:ty $(, $($spi_args:tt)+)?) => {{
+        let spi = $crate::spi!($peripherals $(, $($spi_args)+)?).into_async();
         $crate::blinksy::driver::ClockedDriver::default()
             .with_led::<$led>()
             .with_writer(spi)
@@ -285,6 +543,8 @@ macro_rules! clocked_async {
 /// # Arguments
 ///
 /// - `$peripherals` - The ESP32 peripherals instance
+/// - (Optional) The pins and frequency, as with [`spi!`], e.g.
+///   `apa102!(p, frequency: Rate::from_mhz(1))`
 ///
 /// # Returns
 ///
@@ -292,8 +552,8 @@ macro_rules! clocked_async {
 #[cfg(feature = "async")]
 #[macro_export]
 macro_rules! apa102_async {
-    ($peripherals:ident) => {{
-        $crate::clocked_async!($peripherals, $crate::blinksy::leds::Apa102)
+    ($peripherals:ident $(, $($spi_args:tt)+)?) => {{
+        $crate::clocked_async!($peripherals, $crate::blinksy::leds::Apa102 $(, $($spi_args)+)?)
     }};
 }
 
@@ -305,6 +565,43 @@ macro_rules! rmt {
     }};
 }
 
+/// Creates an allocator for the RMT transmit channels.
+///
+/// Pass the allocator to [`clockless!`], [`ws2812!`], or their async
+/// equivalents to create more than one clockless driver per program, each on
+/// the next free channel.
+///
+/// # Arguments
+///
+/// - `$peripherals` - The ESP32 peripherals instance
+///
+/// # Returns
+///
+/// An allocator for the RMT transmit channels
+#[macro_export]
+macro_rules! rmt_channels {
+    ($peripherals:ident) => {{
+        $crate::blinksy_esp::RmtChannels::new($crate::rmt!($peripherals))
+    }};
+}
+
+/// Creates an async allocator for the RMT transmit channels.
+///
+/// # Arguments
+///
+/// - `$peripherals` - The ESP32 peripherals instance
+///
+/// # Returns
+///
+/// An allocator for the async RMT transmit channels
+#[cfg(feature = "async")]
+#[macro_export]
+macro_rules! rmt_channels_async {
+    ($peripherals:ident) => {{
+        $crate::blinksy_esp::RmtChannels::new($crate::rmt!($peripherals).into_async())
+    }};
+}
+
 /// Creates a clockless LED driver using the RMT peripheral.
 ///
 /// # Arguments
@@ -314,31 +611,55 @@ macro_rules! rmt {
 /// - `$led` - The type of LED
 /// - `$rmt_buffer_size` (Optional) - The length of the RMT buffer
 ///
+/// To create more than one driver, pass an allocator from
+/// [`rmt_channels!`] and the data pin instead of the peripherals:
+///
+/// ```rust,ignore
+/// let mut rmt = rmt_channels!(p);
+/// let a = clockless!(rmt: rmt, pin: p.GPIO16, 60, Ws2812);
+/// let b = clockless!(rmt: rmt, pin: p.GPIO2, 60, Ws2812);
+/// ```
+///
 /// # Returns
 ///
 /// A clockless driver configured for the LED type on the Gledopto board
+///
+/// # Panics
+///
+/// If every RMT transmit channel has already been allocated, or the channel can't be
+/// configured. To handle these errors, build a [`ClocklessRmt`](blinksy_esp::ClocklessRmt)
+/// with its builder instead.
 #[macro_export]
 macro_rules! clockless {
-    ($peripherals:ident, $pixel_count:expr, $led:ty) => {{
-        $crate::clockless!($peripherals, $pixel_count, $led, {
+    (rmt: $rmt:ident, pin: $pin:expr, $pixel_count:expr, $led:ty) => {{
+        $crate::clockless!(rmt: $rmt, pin: $pin, $pixel_count, $led, {
             $crate::blinksy_esp::rmt::rmt_buffer_size::<$led>($pixel_count)
         })
     }};
-    ($peripherals:ident, $pixel_count:expr, $led:ty, $rmt_buffer_size:expr) => {{
-        let led_pin = $peripherals.GPIO16;
-        let rmt = $crate::rmt!($peripherals);
-
+    (rmt: $rmt:ident, pin: $pin:expr, $pixel_count:expr, $led:ty, $rmt_buffer_size:expr) => {{
         $crate::blinksy::driver::ClocklessDriver::default()
             .with_led::<$led>()
             .with_writer(
                 $crate::blinksy_esp::ClocklessRmtBuilder::default()
                     .with_rmt_buffer_size::<$rmt_buffer_size>()
                     .with_led::<$led>()
-                    .with_channel(rmt.channel0)
-                    .with_pin(led_pin)
-                    .build(),
+                    .with_rmt(&mut $rmt)
+                    .unwrap()
+                    .with_pin($pin)
+                    .build()
+                    .unwrap(),
             )
     }};
+    ($peripherals:ident, $pixel_count:expr, $led:ty) => {{
+        $crate::clockless!($peripherals, $pixel_count, $led, {
+            $crate::blinksy_esp::rmt::rmt_buffer_size::<$led>($pixel_count)
+        })
+    }};
+    ($peripherals:ident, $pixel_count:expr, $led:ty, $rmt_buffer_size:expr) => {{
+        let led_pin = $crate::board_pin!($peripherals, led_data);
+        let mut rmt = $crate::rmt_channels!($peripherals);
+        $crate::clockless!(rmt: rmt, pin: led_pin, $pixel_count, $led, $rmt_buffer_size)
+    }};
 }
 
 /// Creates a WS2812 LED driver using the RMT peripheral.
@@ -349,11 +670,31 @@ macro_rules! clockless {
 /// - `$pixel_count` - The number of LEDs in the strip
 /// - `$rmt_buffer_size` (Optional) - The length of the RMT buffer
 ///
+/// As with [`clockless!`], pass `rmt: <allocator>, pin: <pin>` instead of the
+/// peripherals to create more than one driver.
+///
 /// # Returns
 ///
 /// A WS2812 driver configured for the Gledopto board
 #[macro_export]
 macro_rules! ws2812 {
+    (rmt: $rmt:ident, pin: $pin:expr, $pixel_count:expr) => {{
+        $crate::clockless!(
+            rmt: $rmt,
+            pin: $pin,
+            $pixel_count,
+            $crate::blinksy::leds::Ws2812
+        )
+    }};
+    (rmt: $rmt:ident, pin: $pin:expr, $pixel_count:expr, $rmt_buffer_size:expr) => {{
+        $crate::clockless!(
+            rmt: $rmt,
+            pin: $pin,
+            $pixel_count,
+            $crate::blinksy::leds::Ws2812,
+            $rmt_buffer_size
+        )
+    }};
     ($peripherals:ident, $pixel_count:expr) => {{
         $crate::clockless!($peripherals, $pixel_count, $crate::blinksy::leds::Ws2812)
     }};
@@ -376,32 +717,56 @@ macro_rules! ws2812 {
 /// - `$led` - The type of LED
 /// - `$rmt_buffer_size` (Optional) - The length of the RMT buffer
 ///
+/// To create more than one driver, pass an allocator from
+/// [`rmt_channels_async!`] and the data pin instead of the peripherals:
+///
+/// ```rust,ignore
+/// let mut rmt = rmt_channels_async!(p);
+/// let a = clockless_async!(rmt: rmt, pin: p.GPIO16, 60, Ws2812);
+/// let b = clockless_async!(rmt: rmt, pin: p.GPIO2, 60, Ws2812);
+/// ```
+///
 /// # Returns
 ///
 /// A clockless driver configured for the LED type on the Gledopto board
+///
+/// # Panics
+///
+/// If every RMT transmit channel has already been allocated, or the channel can't be
+/// configured. To handle these errors, build a [`ClocklessRmt`](blinksy_esp::ClocklessRmt)
+/// with its builder instead.
 #[cfg(feature = "async")]
 #[macro_export]
 macro_rules! clockless_async {
-    ($peripherals:ident, $pixel_count:expr, $led:ty) => {{
-        $crate::clockless_async!($peripherals, $pixel_count, $led, {
+    (rmt: $rmt:ident, pin: $pin:expr, $pixel_count:expr, $led:ty) => {{
+        $crate::clockless_async!(rmt: $rmt, pin: $pin, $pixel_count, $led, {
             $crate::blinksy_esp::rmt::rmt_buffer_size::<$led>($pixel_count)
         })
     }};
-    ($peripherals:ident, $pixel_count:expr, $led:ty, $rmt_buffer_size:expr) => {{
-        let led_pin = $peripherals.GPIO16;
-        let rmt = $crate::rmt!($peripherals).into_async();
-
+    (rmt: $rmt:ident, pin: $pin:expr, $pixel_count:expr, $led:ty, $rmt_buffer_size:expr) => {{
         $crate::blinksy::driver::ClocklessDriver::default()
             .with_led::<$led>()
             .with_writer(
                 $crate::blinksy_esp::ClocklessRmtBuilder::default()
                     .with_rmt_buffer_size::<$rmt_buffer_size>()
                     .with_led::<$led>()
-                    .with_channel(rmt.channel0)
-                    .with_pin(led_pin)
-                    .build(),
+                    .with_rmt(&mut $rmt)
+                    .unwrap()
+                    .with_pin($pin)
+                    .build()
+                    .unwrap(),
             )
     }};
+    ($peripherals:ident, $pixel_count:expr, $led:ty) => {{
+        $crate::clockless_async!($peripherals, $pixel_count, $led, {
+            $crate::blinksy_esp::rmt::rmt_buffer_size::<$led>($pixel_count)
+        })
+    }};
+    ($peripherals:ident, $pixel_count:expr, $led:ty, $rmt_buffer_size:expr) => {{
+        let led_pin = $crate::board_pin!($peripherals, led_data);
+        let mut rmt = $crate::rmt_channels_async!($peripherals);
+        $crate::clockless_async!(rmt: rmt, pin: led_pin, $pixel_count, $led, $rmt_buffer_size)
+    }};
 }
 
 /// Creates an async WS2812 LED driver using the RMT peripheral.
@@ -412,12 +777,32 @@ macro_rules! clockless_async {
 /// - `$pixel_count` - The number of LEDs in the strip
 /// - `$rmt_buffer_size` (Optional) - The length of the RMT buffer
 ///
+/// As with [`clockless_async!`], pass `rmt: <allocator>, pin: <pin>` instead of the
+/// peripherals to create more than one driver.
+///
 /// # Returns
 ///
 /// A WS2812 driver configured for the Gledopto board
 #[cfg(feature = "async")]
 #[macro_export]
 macro_rules! ws2812_async {
+    (rmt: $rmt:ident, pin: $pin:expr, $pixel_count:expr) => {{
+        $crate::clockless_async!(
+            rmt: $rmt,
+            pin: $pin,
+            $pixel_count,
+            $crate::blinksy::leds::Ws2812
+        )
+    }};
+    (rmt: $rmt:ident, pin: $pin:expr, $pixel_count:expr, $rmt_buffer_size:expr) => {{
+        $crate::clockless_async!(
+            rmt: $rmt,
+            pin: $pin,
+            $pixel_count,
+            $crate::blinksy::leds::Ws2812,
+            $rmt_buffer_size
+        )
+    }};
     ($peripherals:ident, $pixel_count:expr) => {{
         $crate::clockless_async!($peripherals, $pixel_count, $crate::blinksy::leds::Ws2812)
     }};
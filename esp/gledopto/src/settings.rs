@@ -0,0 +1,149 @@
+//! # Settings Module
+//!
+//! This module persists a small set of [`Settings`] to flash, so the controller comes back
+//! after a power cycle with the same pattern and brightness.
+//!
+//! The settings are stored at the start of the NVS data partition, found from the partition
+//! table. (Rust firmware doesn't otherwise use NVS, but note this will overwrite anything
+//! stored there by other firmware, such as WLED.)
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use gledopto::settings::SettingsStore;
+//!
+//! let mut store = SettingsStore::new().unwrap();
+//! let mut settings = store.load().unwrap_or_default();
+//!
+//! settings.pattern_index += 1;
+//! store.save(&settings).unwrap();
+//! ```
+//!
+//! With [`Control::cycle_brightness`](blinksy::control::Control::cycle_brightness), save the
+//! selected brightness step:
+//!
+//! ```rust,ignore
+//! const BRIGHTNESS_STEPS: [f32; 3] = [0.25, 0.5, 1.0];
+//!
+//! control.set_brightness_step(&BRIGHTNESS_STEPS, settings.brightness_index as usize);
+//!
+//! // On button press
+//! control.cycle_brightness(&BRIGHTNESS_STEPS);
+//! settings.brightness_index = control.brightness_step().unwrap_or(0) as u8;
+//! store.save(&settings).unwrap();
+//! ```
+
+use embedded_storage::{ReadStorage, Storage};
+use esp_bootloader_esp_idf::partitions::{
+    self, read_partition_table, DataPartitionSubType, PartitionType, PARTITION_TABLE_MAX_LEN,
+};
+use esp_storage::{FlashStorage, FlashStorageError};
+
+/// Marks the start of stored settings.
+const MAGIC: [u8; 4] = *b"BLKY";
+
+/// Version of the stored settings layout.
+const VERSION: u8 = 1;
+
+/// Length of the stored settings, in bytes.
+const RECORD_SIZE: usize = 8;
+
+/// The persisted settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Settings {
+    /// Index of the selected pattern
+    pub pattern_index: u8,
+    /// Index of the selected brightness step
+    pub brightness_index: u8,
+}
+
+impl Settings {
+    fn to_bytes(self) -> [u8; RECORD_SIZE] {
+        let mut bytes = [0; RECORD_SIZE];
+        bytes[0..4].copy_from_slice(&MAGIC);
+        bytes[4] = VERSION;
+        bytes[5] = self.pattern_index;
+        bytes[6] = self.brightness_index;
+        bytes[7] = checksum(&bytes[..7]);
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; RECORD_SIZE]) -> Option<Self> {
+        if bytes[0..4] != MAGIC || bytes[4] != VERSION || bytes[7] != checksum(&bytes[..7]) {
+            return None;
+        }
+        Some(Self {
+            pattern_index: bytes[5],
+            brightness_index: bytes[6],
+        })
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0xA5, |sum, byte| sum ^ byte)
+}
+
+/// All types of errors that can happen while loading or saving settings.
+#[derive(Debug)]
+pub enum SettingsError {
+    /// Raised if the partition table can't be read
+    PartitionTable(partitions::Error),
+    /// Raised if the partition table has no NVS partition
+    NoPartition,
+    /// Raised if the flash can't be read or written
+    Flash(FlashStorageError),
+}
+
+/// Flash storage for [`Settings`].
+pub struct SettingsStore {
+    flash: FlashStorage,
+    offset: u32,
+}
+
+impl SettingsStore {
+    /// Finds where to store the settings, from the partition table.
+    ///
+    /// # Returns
+    ///
+    /// The settings store, or an error if the partition table has no NVS partition
+    pub fn new() -> Result<Self, SettingsError> {
+        let mut flash = FlashStorage::new();
+        let mut buffer = [0; PARTITION_TABLE_MAX_LEN];
+        let table =
+            read_partition_table(&mut flash, &mut buffer).map_err(SettingsError::PartitionTable)?;
+        let partition = table
+            .find_partition(PartitionType::Data(DataPartitionSubType::Nvs))
+            .map_err(SettingsError::PartitionTable)?
+            .ok_or(SettingsError::NoPartition)?;
+        let offset = partition.offset();
+        Ok(Self { flash, offset })
+    }
+
+    /// Loads the settings.
+    ///
+    /// # Returns
+    ///
+    /// The settings, or `None` if none were saved (or they can't be read)
+    pub fn load(&mut self) -> Option<Settings> {
+        let mut bytes = [0; RECORD_SIZE];
+        self.flash.read(self.offset, &mut bytes).ok()?;
+        Settings::from_bytes(&bytes)
+    }
+
+    /// Saves the settings.
+    ///
+    /// Skips writing if the stored settings are already the same, to save flash wear.
+    ///
+    /// # Arguments
+    ///
+    /// - `settings` - The settings to save
+    pub fn save(&mut self, settings: &Settings) -> Result<(), SettingsError> {
+        if self.load().as_ref() == Some(settings) {
+            return Ok(());
+        }
+        self.flash
+            .write(self.offset, &settings.to_bytes())
+            .map_err(SettingsError::Flash)
+    }
+}
@@ -0,0 +1,32 @@
+//! The Perlin noise pattern, on a strip of 60 LEDs.
+
+use blinksy::{
+    layout::Layout1d,
+    patterns::noise::{noise_fns, Noise1d, NoiseParams},
+    ControlBuilder,
+};
+use blinksy_desktop::{
+    driver::{Desktop, DesktopError},
+    time::elapsed_in_ms,
+};
+use blinksy_examples::layouts::StripLayout;
+use std::{thread::sleep, time::Duration};
+
+fn main() {
+    Desktop::new_1d::<StripLayout>().start(|driver| {
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<StripLayout, { StripLayout::PIXEL_COUNT }>()
+            .with_pattern::<Noise1d<noise_fns::Perlin>>(NoiseParams::default())
+            .with_driver(driver)
+            .with_frame_buffer_size::<{ StripLayout::PIXEL_COUNT }>()
+            .build();
+
+        loop {
+            if let Err(DesktopError::WindowClosed) = control.tick(elapsed_in_ms()) {
+                break;
+            }
+
+            sleep(Duration::from_millis(16));
+        }
+    });
+}
@@ -0,0 +1,32 @@
+//! The Perlin noise pattern, on an 8x8x8 cube.
+
+use blinksy::{
+    layout::Layout3d,
+    patterns::noise::{noise_fns, Noise3d, NoiseParams},
+    ControlBuilder,
+};
+use blinksy_desktop::{
+    driver::{Desktop, DesktopError},
+    time::elapsed_in_ms,
+};
+use blinksy_examples::layouts::CubeLayout;
+use std::{thread::sleep, time::Duration};
+
+fn main() {
+    Desktop::new_3d::<CubeLayout>().start(|driver| {
+        let mut control = ControlBuilder::new_3d()
+            .with_layout::<CubeLayout, { CubeLayout::PIXEL_COUNT }>()
+            .with_pattern::<Noise3d<noise_fns::Perlin>>(NoiseParams::default())
+            .with_driver(driver)
+            .with_frame_buffer_size::<{ CubeLayout::PIXEL_COUNT }>()
+            .build();
+
+        loop {
+            if let Err(DesktopError::WindowClosed) = control.tick(elapsed_in_ms()) {
+                break;
+            }
+
+            sleep(Duration::from_millis(16));
+        }
+    });
+}
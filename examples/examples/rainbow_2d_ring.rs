@@ -0,0 +1,32 @@
+//! The rainbow pattern, on a ring of 24 LEDs.
+
+use blinksy::{
+    layout::Layout2d,
+    patterns::rainbow::{Rainbow, RainbowParams},
+    ControlBuilder,
+};
+use blinksy_desktop::{
+    driver::{Desktop, DesktopError},
+    time::elapsed_in_ms,
+};
+use blinksy_examples::layouts::RingLayout;
+use std::{thread::sleep, time::Duration};
+
+fn main() {
+    Desktop::new_2d::<RingLayout>().start(|driver| {
+        let mut control = ControlBuilder::new_2d()
+            .with_layout::<RingLayout, { RingLayout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(RainbowParams::default())
+            .with_driver(driver)
+            .with_frame_buffer_size::<{ RingLayout::PIXEL_COUNT }>()
+            .build();
+
+        loop {
+            if let Err(DesktopError::WindowClosed) = control.tick(elapsed_in_ms()) {
+                break;
+            }
+
+            sleep(Duration::from_millis(16));
+        }
+    });
+}
@@ -0,0 +1,81 @@
+//! # Blinksy Examples
+//!
+//! Runnable examples of every built-in pattern, on each type of layout, in the
+//! [desktop simulator](blinksy_desktop).
+//!
+//! The examples share the layouts in [`layouts`]. Run any example by name, from anywhere in
+//! the workspace:
+//!
+//! ```sh
+//! cargo run --release --example noise_3d_cube
+//! ```
+
+pub mod layouts {
+    //! The layouts of the examples.
+
+    use blinksy::{
+        layout::{Layout3d, Shape2d, Shape3d, Vec2, Vec3},
+        layout1d, layout2d,
+        util::map_range,
+    };
+    use core::f32::consts::TAU;
+
+    /// The number of LEDs in [`RingLayout`].
+    const RING_PIXEL_COUNT: usize = 24;
+
+    /// The number of LEDs along each edge of [`CubeLayout`].
+    const CUBE_EDGE_PIXEL_COUNT: usize = 8;
+
+    layout1d!(
+        /// A strip of 60 LEDs.
+        pub StripLayout,
+        60
+    );
+
+    layout2d!(
+        /// A panel of 16x16 LEDs, wired in a serpentine.
+        pub PanelLayout,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 16,
+            vertical_pixel_count: 16,
+            serpentine: true,
+        }]
+    );
+
+    layout2d!(
+        /// A ring of 24 LEDs.
+        pub RingLayout,
+        [Shape2d::Arc {
+            center: Vec2::ZERO,
+            axis_u: Vec2::new(1., 0.),
+            axis_v: Vec2::new(0., 1.),
+            start_angle_in_radians: 0.,
+            // Stop one LED short of a full turn, so the first and last LEDs don't overlap
+            end_angle_in_radians: TAU - TAU / RING_PIXEL_COUNT as f32,
+            pixel_count: RING_PIXEL_COUNT,
+        }]
+    );
+
+    /// A cube of 8x8x8 LEDs, filling its volume.
+    pub struct CubeLayout;
+
+    impl Layout3d for CubeLayout {
+        const PIXEL_COUNT: usize =
+            CUBE_EDGE_PIXEL_COUNT * CUBE_EDGE_PIXEL_COUNT * CUBE_EDGE_PIXEL_COUNT;
+
+        fn shapes() -> impl Iterator<Item = Shape3d> {
+            let edge = CUBE_EDGE_PIXEL_COUNT;
+            let map = move |n: usize| map_range(n as f32, 0., (edge - 1) as f32, -1., 1.);
+
+            (0..Self::PIXEL_COUNT).map(move |index| {
+                let x = map(index % edge);
+                let z = map(index / edge % edge);
+                let y = map(index / edge / edge);
+                Shape3d::Point(Vec3::new(x, y, z))
+            })
+        }
+    }
+}